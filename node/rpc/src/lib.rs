@@ -43,6 +43,8 @@ use bifrost_salp_rpc::{SalpRpc, SalpRpcApiServer};
 use bifrost_salp_rpc_runtime_api::SalpRuntimeApi;
 use bifrost_stable_pool_rpc::{StablePoolRpc, StablePoolRpcApiServer};
 use bifrost_stable_pool_rpc_runtime_api::StablePoolRuntimeApi;
+use bifrost_swap_router_rpc::{SwapRouterRpc, SwapRouterRpcApiServer};
+use bifrost_swap_router_rpc_runtime_api::SwapRouterRuntimeApi;
 use bifrost_ve_minting_rpc::{VeMintingRpc, VeMintingRpcApiServer};
 use bifrost_ve_minting_rpc_runtime_api::VeMintingRuntimeApi;
 use bifrost_vtoken_minting_rpc::{VtokenMintingRpc, VtokenMintingRpcApiServer};
@@ -109,6 +111,7 @@ where
 	C::Api: FeeRuntimeApi<Block, AccountId>,
 	C::Api: SalpRuntimeApi<Block, ParaId, AccountId>,
 	C::Api: StablePoolRuntimeApi<Block>,
+	C::Api: SwapRouterRuntimeApi<Block>,
 	C::Api: LendMarketApi<Block, AccountId, Balance>,
 	C::Api: VtokenMintingRuntimeApi<Block, CurrencyId>,
 	C::Api: ZenlinkProtocolRuntimeApi<Block, AccountId, AssetId>,
@@ -129,6 +132,7 @@ where
 	module.merge(ZenlinkProtocol::new(client.clone()).into_rpc())?;
 	module.merge(StableAmm::new(client.clone()).into_rpc())?;
 	module.merge(StablePoolRpc::new(client.clone()).into_rpc())?;
+	module.merge(SwapRouterRpc::new(client.clone()).into_rpc())?;
 	module.merge(LendMarket::new(client.clone()).into_rpc())?;
 	module.merge(VtokenMintingRpc::new(client).into_rpc())?;
 
@@ -157,6 +161,7 @@ where
 	C::Api: VtokenMintingRuntimeApi<Block, CurrencyId>,
 	C::Api: ZenlinkProtocolRuntimeApi<Block, AccountId, AssetId>,
 	C::Api: StablePoolRuntimeApi<Block>,
+	C::Api: SwapRouterRuntimeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 {
@@ -172,6 +177,7 @@ where
 	module.merge(VeMintingRpc::new(client.clone()).into_rpc())?;
 	module.merge(ZenlinkProtocol::new(client.clone()).into_rpc())?;
 	module.merge(StablePoolRpc::new(client.clone()).into_rpc())?;
+	module.merge(SwapRouterRpc::new(client.clone()).into_rpc())?;
 	module.merge(LendMarket::new(client.clone()).into_rpc())?;
 	module.merge(VtokenMintingRpc::new(client).into_rpc())?;
 
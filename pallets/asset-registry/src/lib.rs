@@ -884,6 +884,15 @@ impl<T: Config> CurrencyIdRegister<CurrencyId> for AssetIdMaps<T> {
 			},
 		)
 	}
+
+	fn check_currency_registered(currency_id: CurrencyId) -> bool {
+		CurrencyMetadatas::<T>::get(currency_id).is_some()
+	}
+
+	fn remove_blp_metadata(pool_id: PoolId) -> DispatchResult {
+		CurrencyMetadatas::<T>::remove(CurrencyId::BLP(pool_id));
+		Ok(())
+	}
 }
 
 /// Simple fee calculator that requires payment in a single fungible at a fixed rate.
@@ -57,7 +57,7 @@ use frame_support::{
 	ensure,
 	pallet_prelude::{DispatchResult, Get},
 };
-use pallet_traits::evm::InspectEvmAccounts;
+use pallet_traits::evm::{InspectEvmAccounts, OnEvmAddressBound};
 use sp_core::{
 	crypto::{AccountId32, ByteArray},
 	H160, U256,
@@ -106,6 +106,9 @@ pub mod pallet {
 		/// Origin that can whitelist addresses for smart contract deployment.
 		type ControllerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+		/// Carries the state of the truncated account over to the account an address is bound to.
+		type OnAddressBound: OnEvmAddressBound<Self::AccountId>;
+
 		/// Weight information for extrinsic in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -168,6 +171,8 @@ pub mod pallet {
 		/// address, which doesn't correspond to the origin address.
 		///
 		/// Binding an address is not necessary for interacting with the EVM.
+		/// Whatever `OnAddressBound` keeps of the truncated address, like its fee currency, is
+		/// carried over to the Substrate address.
 		///
 		/// Parameters:
 		/// - `origin`: Substrate account binding an address
@@ -196,6 +201,7 @@ pub mod pallet {
 			last_12_bytes.copy_from_slice(&who.as_ref()[20..32]);
 
 			<AccountExtension<T>>::insert(evm_address, last_12_bytes);
+			T::OnAddressBound::on_evm_address_bound(&Self::truncated_account_id(evm_address), &who);
 
 			Self::deposit_event(Event::Bound { account: who, address: evm_address });
 
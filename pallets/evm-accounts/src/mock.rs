@@ -60,6 +60,7 @@ impl Config for Test {
 	type FeeMultiplier = sp_core::ConstU32<10>;
 	type EvmNonceProvider = EvmNonceProviderMock;
 	type ControllerOrigin = EnsureRoot<AccountId>;
+	type OnAddressBound = ();
 	type WeightInfo = ();
 }
 
@@ -0,0 +1,169 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::{Pallet as FarmingVault, *};
+use bifrost_primitives::{DOT, KSM};
+use frame_benchmarking::v1::{account, benchmarks, whitelisted_caller, BenchmarkError};
+use frame_support::{
+	assert_ok,
+	traits::{EnsureOrigin, Hooks},
+};
+use frame_system::{Pallet as System, RawOrigin};
+use sp_runtime::Perbill;
+
+const UNIT: Balance = 1_000_000_000_000;
+const VAULT_SHARE: CurrencyId = CurrencyId::Token2(100);
+
+// Creates a DOT farming pool rewarding KSM, and a vault over it.
+fn init_vault<T>() -> Result<VaultId, BenchmarkError>
+where
+	T: Config + bifrost_farming::Config<CurrencyId = CurrencyId>,
+	<T as bifrost_farming::Config>::MultiCurrency: MultiCurrency<T::AccountId, Balance = Balance>,
+{
+	let farming_origin = <T as bifrost_farming::Config>::ControlOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	assert_ok!(bifrost_farming::Pallet::<T>::create_farming_pool(
+		farming_origin,
+		vec![(DOT, Perbill::from_percent(100))],
+		vec![(KSM, UNIT)],
+		None,
+		0,
+		0u32.into(),
+		10u32.into(),
+		0u32.into(),
+		5,
+	));
+	let charger: T::AccountId = account("charger", 0, 0);
+	<T as Config>::MultiCurrency::deposit(KSM, &charger, 1_000 * UNIT)?;
+	assert_ok!(bifrost_farming::Pallet::<T>::charge(
+		RawOrigin::Signed(charger).into(),
+		0,
+		vec![(KSM, 1_000 * UNIT)],
+		false,
+	));
+
+	let origin = <T as Config>::ControlOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	assert_ok!(FarmingVault::<T>::create_vault(
+		origin,
+		0,
+		VAULT_SHARE,
+		10u32.into(),
+		Permill::from_percent(10),
+		Permill::from_percent(1),
+	));
+	Ok(NextVaultId::<T>::get() - 1)
+}
+
+// Deposits into the vault for a funded caller, and starts the pool.
+fn deposit_and_start<T>(vault_id: VaultId) -> Result<T::AccountId, BenchmarkError>
+where
+	T: Config + bifrost_farming::Config<CurrencyId = CurrencyId>,
+{
+	let caller: T::AccountId = whitelisted_caller();
+	<T as Config>::MultiCurrency::deposit(DOT, &caller, 1_000 * UNIT)?;
+	assert_ok!(FarmingVault::<T>::deposit(
+		RawOrigin::Signed(caller.clone()).into(),
+		vault_id,
+		100 * UNIT
+	));
+	let now = System::<T>::block_number();
+	bifrost_farming::Pallet::<T>::on_initialize(now);
+	Ok(caller)
+}
+
+benchmarks! {
+	where_clause {
+		where
+			T: bifrost_farming::Config<CurrencyId = CurrencyId>,
+			<T as bifrost_farming::Config>::MultiCurrency: MultiCurrency<T::AccountId, Balance = Balance>,
+	}
+
+	create_vault {
+		init_vault::<T>()?;
+		let origin = <T as Config>::ControlOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(
+		origin,
+		0,
+		CurrencyId::Token2(101),
+		10u32.into(),
+		Permill::from_percent(10),
+		Permill::from_percent(1)
+	)
+
+	edit_vault {
+		let vault_id = init_vault::<T>()?;
+		let origin = <T as Config>::ControlOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(
+		origin,
+		vault_id,
+		Some(20u32.into()),
+		Some(Permill::from_percent(5)),
+		Some(Permill::from_percent(2))
+	)
+
+	deposit {
+		let vault_id = init_vault::<T>()?;
+		let caller: T::AccountId = whitelisted_caller();
+		<T as Config>::MultiCurrency::deposit(DOT, &caller, 1_000 * UNIT)?;
+	}: _(RawOrigin::Signed(caller), vault_id, 100 * UNIT)
+
+	redeem {
+		let vault_id = init_vault::<T>()?;
+		let caller = deposit_and_start::<T>(vault_id)?;
+		// Redeeming harvests the rewards accrued since the last harvest first.
+		let now = System::<T>::block_number() + 10u32.into();
+		bifrost_farming::Pallet::<T>::on_initialize(now);
+		System::<T>::set_block_number(now);
+	}: _(RawOrigin::Signed(caller), vault_id, 50 * UNIT)
+
+	claim_unlocked {
+		let vault_id = init_vault::<T>()?;
+		let caller = deposit_and_start::<T>(vault_id)?;
+		assert_ok!(FarmingVault::<T>::redeem(
+			RawOrigin::Signed(caller.clone()).into(),
+			vault_id,
+			50 * UNIT
+		));
+		// The harvest withdraws the batch, which unlocks `withdraw_limit_time` later.
+		let now = System::<T>::block_number() + 10u32.into();
+		System::<T>::set_block_number(now);
+		assert_ok!(FarmingVault::<T>::harvest(RawOrigin::Signed(caller.clone()).into(), vault_id));
+		System::<T>::set_block_number(now + 10u32.into());
+	}: _(RawOrigin::Signed(caller), vault_id)
+
+	harvest {
+		let vault_id = init_vault::<T>()?;
+		let caller = deposit_and_start::<T>(vault_id)?;
+		// The harvest withdraws the pending batch too.
+		assert_ok!(FarmingVault::<T>::redeem(
+			RawOrigin::Signed(caller.clone()).into(),
+			vault_id,
+			50 * UNIT
+		));
+		let now = System::<T>::block_number() + 10u32.into();
+		bifrost_farming::Pallet::<T>::on_initialize(now);
+		System::<T>::set_block_number(now);
+	}: _(RawOrigin::Signed(caller), vault_id)
+
+	impl_benchmark_test_suite!(FarmingVault, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
+}
@@ -0,0 +1,786 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Farming vaults.
+//!
+//! A vault farms a farming pool on behalf of its depositors, who hold a transferable vault share
+//! currency instead of farming shares. Every `harvest_interval` blocks the vault claims the pool
+//! rewards, pays the performance fee to the treasury, turns the rest into the pool tokens by
+//! minting vTokens or swapping through the swap router, and deposits them back into the pool, so
+//! that each vault share is backed by more farming shares over time. Redeemed vault shares join
+//! a batch that the next harvest withdraws from the pool in one go, so that the redeemers of a
+//! vault share a single entry of its withdraw list in the pool, and their tokens can be claimed
+//! once the pool's `withdraw_limit_time` is over after that. The first deposit of a vault mints
+//! `DeadShares` of its vault shares to the vault account, so that its depositor cannot inflate
+//! the share price.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+use bifrost_primitives::{
+	Balance, CurrencyId, FarmingOperator, PoolId, SwapRouter, SwapType, VtokenMintingInterface,
+};
+use frame_support::{
+	pallet_prelude::*, storage::with_storage_layer, weights::WeightMeter, PalletId,
+};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+pub use pallet::*;
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{AccountIdConversion, Saturating, Zero},
+	ArithmeticError, Permill, Rounding,
+};
+use sp_std::{vec, vec::Vec};
+pub use weights::WeightInfo;
+
+pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+
+pub type VaultId = u32;
+
+pub type BatchId = u32;
+
+/// A vault farming a farming pool.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct VaultInfo<AccountId, BlockNumber> {
+	/// The farming pool the vault deposits into
+	pub pid: PoolId,
+	/// The currency of the vault shares
+	pub share_currency_id: CurrencyId,
+	/// The account holding the farming shares and the tokens of the vault
+	pub account: AccountId,
+	/// The blocks between two harvests
+	pub harvest_interval: BlockNumber,
+	pub last_harvest: BlockNumber,
+	/// The part of the claimed rewards paid to the treasury
+	pub performance_fee: Permill,
+	/// The highest price impact a reward swap may have; a reward that would swap worse waits for
+	/// a later harvest
+	pub max_price_impact: Permill,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		type WeightInfo: WeightInfo;
+
+		type ControlOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		type MultiCurrency: MultiCurrency<
+			AccountIdOf<Self>,
+			CurrencyId = CurrencyId,
+			Balance = Balance,
+		>;
+
+		type Farming: FarmingOperator<AccountIdOf<Self>, Balance, CurrencyId, BlockNumberFor<Self>>;
+
+		type SwapRouter: SwapRouter<AccountIdOf<Self>, Balance>;
+
+		type VtokenMinting: VtokenMintingInterface<AccountIdOf<Self>, CurrencyId, Balance>;
+
+		/// The account the performance fees are paid to.
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The vault accounts are sub-accounts of this pallet id.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The maximum number of redemptions of an account waiting to be claimed in a vault.
+		#[pallet::constant]
+		type MaxUnlockings: Get<u32>;
+
+		/// The vault shares the first deposit of a vault locks in the vault account for good.
+		#[pallet::constant]
+		type DeadShares: Get<Balance>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		VaultCreated {
+			vault_id: VaultId,
+			pid: PoolId,
+			share_currency_id: CurrencyId,
+		},
+		VaultEdited {
+			vault_id: VaultId,
+		},
+		/// `who` deposited the tokens of `shares` farming shares for `vault_shares`.
+		Deposited {
+			who: AccountIdOf<T>,
+			vault_id: VaultId,
+			shares: Balance,
+			vault_shares: Balance,
+		},
+		/// `who` redeemed `vault_shares` for `shares` farming shares, which the next harvest
+		/// withdraws from the pool with `batch`.
+		Redeemed {
+			who: AccountIdOf<T>,
+			vault_id: VaultId,
+			vault_shares: Balance,
+			shares: Balance,
+			batch: BatchId,
+		},
+		UnlockedClaimed {
+			who: AccountIdOf<T>,
+			vault_id: VaultId,
+			tokens: Vec<(CurrencyId, Balance)>,
+		},
+		/// The rewards of the vault were compounded into `shares` farming shares.
+		Harvested {
+			vault_id: VaultId,
+			shares: Balance,
+		},
+		HarvestFailed {
+			vault_id: VaultId,
+			error: DispatchError,
+		},
+		/// The `shares` redeemed in `batch` were withdrawn from the pool, claimable from
+		/// `unlock_at`.
+		BatchWithdrawn {
+			vault_id: VaultId,
+			batch: BatchId,
+			shares: Balance,
+			unlock_at: BlockNumberFor<T>,
+		},
+		/// The pending batch could not be withdrawn from the pool, and waits for the next
+		/// harvest.
+		BatchWithdrawFailed {
+			vault_id: VaultId,
+			error: DispatchError,
+		},
+		PerformanceFeePaid {
+			vault_id: VaultId,
+			currency_id: CurrencyId,
+			amount: Balance,
+		},
+		/// A reward could not be turned into a pool token, and stays in the vault until the next
+		/// harvest.
+		ConvertFailed {
+			vault_id: VaultId,
+			currency_in: CurrencyId,
+			currency_out: CurrencyId,
+			amount: Balance,
+			error: DispatchError,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The vault does not exist.
+		VaultNotExist,
+		/// The share currency already has an issuance.
+		ShareCurrencyInUse,
+		/// The harvest interval is zero, or shorter than the claim limit time of the pool.
+		InvalidHarvestInterval,
+		/// The amount is too small to be worth a share.
+		ZeroShares,
+		/// The vault was harvested less than `harvest_interval` blocks ago.
+		HarvestNotDue,
+		/// The account has too many redemptions waiting to be claimed.
+		TooManyUnlockings,
+		/// No redemption of the account is unlocked yet.
+		NothingToClaim,
+		/// The swap would have a price impact above the vault maximum.
+		PriceImpactTooHigh,
+		/// The first deposit of a vault is not worth more than `DeadShares` vault shares.
+		InitialDepositTooSmall,
+	}
+
+	#[pallet::storage]
+	pub type NextVaultId<T: Config> = StorageValue<_, VaultId, ValueQuery>;
+
+	#[pallet::storage]
+	pub type Vaults<T: Config> =
+		StorageMap<_, Twox64Concat, VaultId, VaultInfo<AccountIdOf<T>, BlockNumberFor<T>>>;
+
+	/// Tokens withdrawn from the pool into the vault account and owed to redeemers.
+	///
+	/// double_map (VaultId, CurrencyId) => Balance
+	#[pallet::storage]
+	pub type Unclaimed<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, VaultId, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// The redeemed farming shares of an account, and the batches they are withdrawn with.
+	///
+	/// double_map (VaultId, AccountId) => [(batch, shares)]
+	#[pallet::storage]
+	pub type Unlockings<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		VaultId,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		BoundedVec<(BatchId, Balance), T::MaxUnlockings>,
+		ValueQuery,
+	>;
+
+	/// The batch the redemptions of a vault join, and the farming shares redeemed in it so far.
+	/// They stay in the pool until the next harvest withdraws them.
+	///
+	/// map VaultId => (batch, shares)
+	#[pallet::storage]
+	pub type PendingBatch<T: Config> =
+		StorageMap<_, Twox64Concat, VaultId, (BatchId, Balance), ValueQuery>;
+
+	/// The batches withdrawn from the pool, the block they unlock at, and their farming shares
+	/// that are not claimed yet.
+	///
+	/// double_map (VaultId, BatchId) => (unlock_at, shares)
+	#[pallet::storage]
+	pub type WithdrawnBatches<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		VaultId,
+		Twox64Concat,
+		BatchId,
+		(BlockNumberFor<T>, Balance),
+	>;
+
+	/// The raw `Vaults` key after which `on_idle` resumes looking for due harvests.
+	#[pallet::storage]
+	pub type HarvestScanCursor<T: Config> = StorageValue<_, Vec<u8>>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let mut meter = WeightMeter::with_limit(remaining_weight);
+			if meter.try_consume(T::DbWeight::get().reads_writes(1, 1)).is_err() {
+				return meter.consumed();
+			}
+			let harvest_weight = T::WeightInfo::harvest();
+
+			let mut cursor = HarvestScanCursor::<T>::get();
+			let mut iter = match cursor.clone() {
+				Some(raw_key) => Vaults::<T>::iter_from(raw_key),
+				None => Vaults::<T>::iter(),
+			};
+			while meter.can_consume(T::DbWeight::get().reads(1).saturating_add(harvest_weight)) {
+				meter.consume(T::DbWeight::get().reads(1));
+				let (vault_id, mut vault) = match iter.next() {
+					Some(item) => item,
+					None => {
+						// Every vault has been scanned, start over in the next block.
+						cursor = None;
+						break;
+					},
+				};
+
+				if n >= vault.last_harvest.saturating_add(vault.harvest_interval) {
+					meter.consume(harvest_weight);
+					if let Err(error) = Self::do_harvest(vault_id, &mut vault) {
+						Self::deposit_event(Event::HarvestFailed { vault_id, error });
+					}
+				}
+				cursor = Some(Vaults::<T>::hashed_key_for(vault_id));
+			}
+
+			match cursor {
+				Some(raw_key) => HarvestScanCursor::<T>::put(raw_key),
+				None => HarvestScanCursor::<T>::kill(),
+			}
+			meter.consumed()
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Creates a vault over the farming pool `pid`, whose shares are `share_currency_id`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::create_vault())]
+		pub fn create_vault(
+			origin: OriginFor<T>,
+			pid: PoolId,
+			share_currency_id: CurrencyId,
+			harvest_interval: BlockNumberFor<T>,
+			performance_fee: Permill,
+			max_price_impact: Permill,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			Self::ensure_harvest_interval(pid, harvest_interval)?;
+			ensure!(
+				T::MultiCurrency::total_issuance(share_currency_id).is_zero() &&
+					!Vaults::<T>::iter_values()
+						.any(|vault| vault.share_currency_id == share_currency_id),
+				Error::<T>::ShareCurrencyInUse
+			);
+
+			let vault_id = NextVaultId::<T>::get();
+			let vault = VaultInfo {
+				pid,
+				share_currency_id,
+				account: T::PalletId::get().into_sub_account_truncating(vault_id),
+				harvest_interval,
+				last_harvest: frame_system::Pallet::<T>::block_number(),
+				performance_fee,
+				max_price_impact,
+			};
+			Vaults::<T>::insert(vault_id, vault);
+			NextVaultId::<T>::put(vault_id.checked_add(1).ok_or(ArithmeticError::Overflow)?);
+
+			Self::deposit_event(Event::VaultCreated { vault_id, pid, share_currency_id });
+			Ok(())
+		}
+
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::edit_vault())]
+		pub fn edit_vault(
+			origin: OriginFor<T>,
+			vault_id: VaultId,
+			harvest_interval: Option<BlockNumberFor<T>>,
+			performance_fee: Option<Permill>,
+			max_price_impact: Option<Permill>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			Vaults::<T>::try_mutate(vault_id, |maybe_vault| -> DispatchResult {
+				let vault = maybe_vault.as_mut().ok_or(Error::<T>::VaultNotExist)?;
+				if let Some(harvest_interval) = harvest_interval {
+					Self::ensure_harvest_interval(vault.pid, harvest_interval)?;
+					vault.harvest_interval = harvest_interval;
+				}
+				if let Some(performance_fee) = performance_fee {
+					vault.performance_fee = performance_fee;
+				}
+				if let Some(max_price_impact) = max_price_impact {
+					vault.max_price_impact = max_price_impact;
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::VaultEdited { vault_id });
+			Ok(())
+		}
+
+		/// Deposits the tokens of `shares` farming shares into the vault, for vault shares.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::deposit())]
+		pub fn deposit(
+			origin: OriginFor<T>,
+			vault_id: VaultId,
+			#[pallet::compact] shares: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let vault = Vaults::<T>::get(vault_id).ok_or(Error::<T>::VaultNotExist)?;
+
+			let total_shares = Self::total_shares(vault_id, &vault);
+			let total_vault_shares = T::MultiCurrency::total_issuance(vault.share_currency_id);
+			let (vault_shares, dead_shares) =
+				if total_shares.is_zero() || total_vault_shares.is_zero() {
+					ensure!(shares > T::DeadShares::get(), Error::<T>::InitialDepositTooSmall);
+					(shares.saturating_sub(T::DeadShares::get()), T::DeadShares::get())
+				} else {
+					let vault_shares = multiply_by_rational_with_rounding(
+						shares,
+						total_vault_shares,
+						total_shares,
+						Rounding::Down,
+					)
+					.ok_or(ArithmeticError::Overflow)?;
+					(vault_shares, Zero::zero())
+				};
+			ensure!(!vault_shares.is_zero(), Error::<T>::ZeroShares);
+
+			for (token, amount) in T::Farming::get_share_tokens(vault.pid, shares)? {
+				T::MultiCurrency::transfer(token, &who, &vault.account, amount)?;
+			}
+			T::Farming::deposit(&vault.account, vault.pid, shares)?;
+			T::MultiCurrency::deposit(vault.share_currency_id, &who, vault_shares)?;
+			if !dead_shares.is_zero() {
+				T::MultiCurrency::deposit(vault.share_currency_id, &vault.account, dead_shares)?;
+			}
+
+			Self::deposit_event(Event::Deposited { who, vault_id, shares, vault_shares });
+			Ok(())
+		}
+
+		/// Redeems `vault_shares` for their farming shares. The next harvest withdraws them from
+		/// the pool, and their tokens can be claimed once the pool's `withdraw_limit_time` is over
+		/// after that.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::redeem())]
+		pub fn redeem(
+			origin: OriginFor<T>,
+			vault_id: VaultId,
+			#[pallet::compact] vault_shares: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let vault = Vaults::<T>::get(vault_id).ok_or(Error::<T>::VaultNotExist)?;
+
+			// Compound the rewards accrued since the last harvest first, so that the redeemed
+			// vault shares get their part of them.
+			if let Err(error) = with_storage_layer(|| Self::compound(vault_id, &vault)) {
+				Self::deposit_event(Event::HarvestFailed { vault_id, error });
+			}
+
+			let total_shares = Self::total_shares(vault_id, &vault);
+			let total_vault_shares = T::MultiCurrency::total_issuance(vault.share_currency_id);
+			let shares = multiply_by_rational_with_rounding(
+				vault_shares,
+				total_shares,
+				total_vault_shares,
+				Rounding::Down,
+			)
+			.unwrap_or_default();
+			ensure!(!shares.is_zero(), Error::<T>::ZeroShares);
+
+			let batch = PendingBatch::<T>::mutate(vault_id, |(batch, pending_shares)| {
+				*pending_shares = pending_shares.saturating_add(shares);
+				*batch
+			});
+			Unlockings::<T>::try_mutate(vault_id, &who, |unlockings| {
+				unlockings.try_push((batch, shares)).map_err(|_| Error::<T>::TooManyUnlockings)
+			})?;
+
+			T::MultiCurrency::withdraw(vault.share_currency_id, &who, vault_shares)?;
+
+			Self::deposit_event(Event::Redeemed { who, vault_id, vault_shares, shares, batch });
+			Ok(())
+		}
+
+		/// Pays out the tokens of the redemptions of the caller that are unlocked.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::claim_unlocked())]
+		pub fn claim_unlocked(origin: OriginFor<T>, vault_id: VaultId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let vault = Vaults::<T>::get(vault_id).ok_or(Error::<T>::VaultNotExist)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut unlocked_shares: Balance = Zero::zero();
+			Unlockings::<T>::mutate(vault_id, &who, |unlockings| {
+				unlockings.retain(|(batch, shares)| {
+					let unlocked =
+						WithdrawnBatches::<T>::mutate_exists(vault_id, batch, |maybe_batch| {
+							match *maybe_batch {
+								Some((unlock_at, batch_shares)) if unlock_at <= now => {
+									let batch_shares = batch_shares.saturating_sub(*shares);
+									*maybe_batch = if batch_shares.is_zero() {
+										None
+									} else {
+										Some((unlock_at, batch_shares))
+									};
+									true
+								},
+								_ => false,
+							}
+						});
+					if unlocked {
+						unlocked_shares = unlocked_shares.saturating_add(*shares);
+					}
+					!unlocked
+				})
+			});
+			ensure!(!unlocked_shares.is_zero(), Error::<T>::NothingToClaim);
+
+			Self::claim_withdrawn(vault_id, &vault)?;
+
+			let mut tokens = vec![];
+			for (token, amount) in T::Farming::get_share_tokens(vault.pid, unlocked_shares)? {
+				let amount = Unclaimed::<T>::mutate(vault_id, token, |unclaimed| {
+					let amount = amount.min(*unclaimed);
+					*unclaimed = unclaimed.saturating_sub(amount);
+					amount
+				});
+				T::MultiCurrency::transfer(token, &vault.account, &who, amount)?;
+				tokens.push((token, amount));
+			}
+
+			Self::deposit_event(Event::UnlockedClaimed { who, vault_id, tokens });
+			Ok(())
+		}
+
+		/// Withdraws the pending batch and compounds the rewards of a vault whose harvest is due.
+		/// Callable by anyone.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::harvest())]
+		pub fn harvest(origin: OriginFor<T>, vault_id: VaultId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let mut vault = Vaults::<T>::get(vault_id).ok_or(Error::<T>::VaultNotExist)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >=
+					vault.last_harvest.saturating_add(vault.harvest_interval),
+				Error::<T>::HarvestNotDue
+			);
+
+			Self::do_harvest(vault_id, &mut vault)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Withdraws the pending batch of the vault from the pool and compounds its rewards.
+	pub(crate) fn do_harvest(
+		vault_id: VaultId,
+		vault: &mut VaultInfo<AccountIdOf<T>, BlockNumberFor<T>>,
+	) -> DispatchResult {
+		// The redeemers don't wait on the rewards, nor the rewards on a batch the pool can't take
+		// yet.
+		if let Err(error) = with_storage_layer(|| Self::withdraw_batch(vault_id, vault)) {
+			Self::deposit_event(Event::BatchWithdrawFailed { vault_id, error });
+		}
+		let result = with_storage_layer(|| Self::compound(vault_id, vault));
+
+		// Wait a whole interval before trying again if the compounding failed.
+		vault.last_harvest = frame_system::Pallet::<T>::block_number();
+		Vaults::<T>::insert(vault_id, vault.clone());
+		result
+	}
+
+	/// Claims the rewards of the vault, turns them into the pool tokens and deposits all the
+	/// pool tokens the vault holds.
+	///
+	/// Leaves `last_harvest` alone, so that redemptions can't put off the withdrawal of the
+	/// pending batch.
+	fn compound(
+		vault_id: VaultId,
+		vault: &VaultInfo<AccountIdOf<T>, BlockNumberFor<T>>,
+	) -> DispatchResult {
+		if !T::Farming::get_shares(&vault.account, vault.pid).is_zero() {
+			Self::with_performance_fee(vault_id, vault, || {
+				T::Farming::claim(&vault.account, vault.pid)
+			})?;
+		}
+
+		let tokens_proportion = T::Farming::get_tokens_proportion(vault.pid);
+		let total_proportion = tokens_proportion.iter().fold(0u128, |total, (_, proportion)| {
+			total.saturating_add(proportion.deconstruct().into())
+		});
+		for reward_currency in T::Farming::get_reward_currencies(vault.pid) {
+			if tokens_proportion.iter().any(|(token, _)| *token == reward_currency) {
+				continue;
+			}
+			let reward = Self::spendable_balance(vault_id, vault, reward_currency);
+			if reward.is_zero() {
+				continue;
+			}
+
+			// Split the reward between the pool tokens by their proportions.
+			for (token, proportion) in tokens_proportion.iter() {
+				let amount = multiply_by_rational_with_rounding(
+					reward,
+					proportion.deconstruct().into(),
+					total_proportion,
+					Rounding::Down,
+				)
+				.unwrap_or_default();
+				if amount.is_zero() {
+					continue;
+				}
+				if let Err(error) =
+					with_storage_layer(|| Self::convert(vault, reward_currency, *token, amount))
+				{
+					Self::deposit_event(Event::ConvertFailed {
+						vault_id,
+						currency_in: reward_currency,
+						currency_out: *token,
+						amount,
+						error,
+					});
+				}
+			}
+		}
+
+		let tokens = tokens_proportion
+			.iter()
+			.map(|(token, _)| (*token, Self::spendable_balance(vault_id, vault, *token)))
+			.collect();
+		let shares = T::Farming::get_max_shares(vault.pid, tokens);
+		if !shares.is_zero() {
+			T::Farming::deposit(&vault.account, vault.pid, shares)?;
+		}
+
+		Self::deposit_event(Event::Harvested { vault_id, shares });
+		Ok(())
+	}
+
+	/// Withdraws the farming shares of the pending batch from the pool, after moving the
+	/// unlocked withdrawals out of it to keep its withdraw list short.
+	fn withdraw_batch(
+		vault_id: VaultId,
+		vault: &VaultInfo<AccountIdOf<T>, BlockNumberFor<T>>,
+	) -> DispatchResult {
+		Self::claim_withdrawn(vault_id, vault)?;
+
+		let (batch, shares) = PendingBatch::<T>::get(vault_id);
+		if shares.is_zero() {
+			return Ok(());
+		}
+		// Withdrawing claims the rewards of the vault too.
+		Self::with_performance_fee(vault_id, vault, || {
+			T::Farming::withdraw(&vault.account, vault.pid, shares)
+		})?;
+
+		let unlock_at = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::Farming::get_withdraw_limit_time(vault.pid)?);
+		WithdrawnBatches::<T>::insert(vault_id, batch, (unlock_at, shares));
+		PendingBatch::<T>::insert(
+			vault_id,
+			(batch.checked_add(1).ok_or(ArithmeticError::Overflow)?, Balance::zero()),
+		);
+
+		Self::deposit_event(Event::BatchWithdrawn { vault_id, batch, shares, unlock_at });
+		Ok(())
+	}
+
+	/// Moves every unlocked withdrawal of the vault out of the pool, and owes it to the
+	/// redeemers.
+	fn claim_withdrawn(
+		vault_id: VaultId,
+		vault: &VaultInfo<AccountIdOf<T>, BlockNumberFor<T>>,
+	) -> DispatchResult {
+		let tokens_proportion = T::Farming::get_tokens_proportion(vault.pid);
+		let balances_before: Vec<Balance> = tokens_proportion
+			.iter()
+			.map(|(token, _)| T::MultiCurrency::free_balance(*token, &vault.account))
+			.collect();
+		T::Farming::claim_withdrawn(&vault.account, vault.pid)?;
+		for ((token, _), balance_before) in tokens_proportion.iter().zip(balances_before) {
+			let withdrawn = T::MultiCurrency::free_balance(*token, &vault.account)
+				.saturating_sub(balance_before);
+			Unclaimed::<T>::mutate(vault_id, token, |unclaimed| {
+				*unclaimed = unclaimed.saturating_add(withdrawn)
+			});
+		}
+		Ok(())
+	}
+
+	/// Ensures the vault can claim the rewards of the pool `pid` at every harvest.
+	fn ensure_harvest_interval(pid: PoolId, harvest_interval: BlockNumberFor<T>) -> DispatchResult {
+		// Fails if the pool does not exist.
+		let claim_limit_time = T::Farming::get_claim_limit_time(pid)?;
+		ensure!(
+			!harvest_interval.is_zero() && harvest_interval >= claim_limit_time,
+			Error::<T>::InvalidHarvestInterval
+		);
+		Ok(())
+	}
+
+	/// Turns `amount` of `currency_in` of the vault into `currency_out`, minting it if it is the
+	/// vToken of `currency_in`, swapping otherwise.
+	fn convert(
+		vault: &VaultInfo<AccountIdOf<T>, BlockNumberFor<T>>,
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount: Balance,
+	) -> DispatchResult {
+		if T::VtokenMinting::vtoken_id(currency_in) == Some(currency_out) {
+			T::VtokenMinting::mint(
+				vault.account.clone(),
+				currency_in,
+				amount,
+				BoundedVec::default(),
+				None,
+			)?;
+			return Ok(());
+		}
+
+		let quote =
+			T::SwapRouter::get_best_quote(currency_in, currency_out, amount, SwapType::ExactIn)?;
+		ensure!(quote.price_impact <= vault.max_price_impact, Error::<T>::PriceImpactTooHigh);
+		T::SwapRouter::swap_exact_in(
+			&vault.account,
+			currency_in,
+			currency_out,
+			amount,
+			quote.amount_out,
+		)?;
+		Ok(())
+	}
+
+	/// Runs `f`, and pays the performance fee of the vault on the rewards it claimed.
+	fn with_performance_fee(
+		vault_id: VaultId,
+		vault: &VaultInfo<AccountIdOf<T>, BlockNumberFor<T>>,
+		f: impl FnOnce() -> DispatchResult,
+	) -> DispatchResult {
+		let reward_currencies = T::Farming::get_reward_currencies(vault.pid);
+		let balances_before: Vec<Balance> = reward_currencies
+			.iter()
+			.map(|currency_id| T::MultiCurrency::free_balance(*currency_id, &vault.account))
+			.collect();
+
+		f()?;
+
+		for (currency_id, balance_before) in reward_currencies.into_iter().zip(balances_before) {
+			let claimed = T::MultiCurrency::free_balance(currency_id, &vault.account)
+				.saturating_sub(balance_before);
+			let fee = vault.performance_fee.mul_floor(claimed);
+			if fee.is_zero() {
+				continue;
+			}
+			T::MultiCurrency::transfer(
+				currency_id,
+				&vault.account,
+				&T::TreasuryAccount::get(),
+				fee,
+			)?;
+			Self::deposit_event(Event::PerformanceFeePaid { vault_id, currency_id, amount: fee });
+		}
+		Ok(())
+	}
+
+	/// The balance of the vault account that is not owed to redeemers.
+	fn spendable_balance(
+		vault_id: VaultId,
+		vault: &VaultInfo<AccountIdOf<T>, BlockNumberFor<T>>,
+		currency_id: CurrencyId,
+	) -> Balance {
+		T::MultiCurrency::free_balance(currency_id, &vault.account)
+			.saturating_sub(Unclaimed::<T>::get(vault_id, currency_id))
+	}
+
+	/// The farming shares of the vault backing its vault shares, leaving out the ones redeemed
+	/// in the pending batch.
+	fn total_shares(
+		vault_id: VaultId,
+		vault: &VaultInfo<AccountIdOf<T>, BlockNumberFor<T>>,
+	) -> Balance {
+		T::Farming::get_shares(&vault.account, vault.pid)
+			.saturating_sub(PendingBatch::<T>::get(vault_id).1)
+	}
+
+	/// The farming shares `vault_shares` of the vault are worth.
+	pub fn get_vault_share_value(vault_id: VaultId, vault_shares: Balance) -> Option<Balance> {
+		let vault = Vaults::<T>::get(vault_id)?;
+		multiply_by_rational_with_rounding(
+			vault_shares,
+			Self::total_shares(vault_id, &vault),
+			T::MultiCurrency::total_issuance(vault.share_currency_id),
+			Rounding::Down,
+		)
+	}
+}
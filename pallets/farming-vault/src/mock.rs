@@ -0,0 +1,266 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+#![allow(non_upper_case_globals)]
+
+use bifrost_primitives::SwapRouter;
+pub use bifrost_primitives::{currency::*, CurrencyId, SwapQuote, SwapType, TokenSymbol};
+use frame_support::{derive_impl, ord_parameter_types, parameter_types, traits::Nothing, PalletId};
+use frame_system::EnsureSignedBy;
+use orml_traits::MultiCurrency;
+use sp_core::ConstU32;
+use sp_runtime::{
+	traits::{ConvertInto, IdentityLookup},
+	AccountId32, BuildStorage, DispatchError, Permill,
+};
+
+use crate as bifrost_farming_vault;
+
+pub type BlockNumber = u64;
+pub type Amount = i128;
+pub type Balance = u128;
+
+pub type AccountId = AccountId32;
+pub const ALICE: AccountId = AccountId32::new([0u8; 32]);
+pub const BOB: AccountId = AccountId32::new([1u8; 32]);
+pub const CHARLIE: AccountId = AccountId32::new([3u8; 32]);
+pub const TREASURY_ACCOUNT: AccountId = AccountId32::new([9u8; 32]);
+pub const VAULT_SHARE: CurrencyId = CurrencyId::Token2(100);
+
+frame_support::construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Tokens: orml_tokens,
+		Balances: pallet_balances,
+		Currencies: bifrost_currencies,
+		Farming: bifrost_farming,
+		FarmingVault: bifrost_farming_vault,
+	}
+);
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type AccountId = AccountId;
+	type Block = Block;
+	type Lookup = IdentityLookup<Self::AccountId>;
+}
+
+parameter_types! {
+	pub const GetNativeCurrencyId: CurrencyId = CurrencyId::Native(TokenSymbol::ASG);
+}
+
+pub type AdaptedBasicCurrency =
+	bifrost_currencies::BasicCurrencyAdapter<Runtime, Balances, Amount, BlockNumber>;
+
+impl bifrost_currencies::Config for Runtime {
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type MultiCurrency = Tokens;
+	type NativeCurrency = AdaptedBasicCurrency;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+	pub const MaxLocks: u32 = 100;
+}
+
+impl pallet_balances::Config for Runtime {
+	type AccountStore = frame_system::Pallet<Runtime>;
+	type Balance = Balance;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ExistentialDeposit;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type FreezeIdentifier = ();
+	type MaxFreezes = ConstU32<0>;
+}
+
+orml_traits::parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		0
+	};
+}
+impl orml_tokens::Config for Runtime {
+	type Amount = i128;
+	type Balance = Balance;
+	type CurrencyId = CurrencyId;
+	type DustRemovalWhitelist = Nothing;
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposits = ExistentialDeposits;
+	type MaxLocks = MaxLocks;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+	type CurrencyHooks = ();
+}
+
+parameter_types! {
+	pub const FarmingKeeperPalletId: PalletId = PalletId(*b"bf/fmkpr");
+	pub const FarmingRewardIssuerPalletId: PalletId = PalletId(*b"bf/fmrir");
+	pub const FarmingBoostPalletId: PalletId = PalletId(*b"bf/fmbst");
+	pub const TreasuryAccount: AccountId32 = TREASURY_ACCOUNT;
+	pub const WhitelistMaximumLimit: u32 = 10;
+	pub const MaxEmissionSchedulePoints: u32 = 50;
+	pub const FarmingGaugeRewardIssuerPalletId: PalletId = PalletId(*b"bf/fmgar");
+}
+
+ord_parameter_types! {
+	pub const One: AccountId = ALICE;
+}
+
+impl bifrost_farming::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type CurrencyId = CurrencyId;
+	type MultiCurrency = Currencies;
+	type ControlOrigin = EnsureSignedBy<One, AccountId>;
+	type TreasuryAccount = TreasuryAccount;
+	type Keeper = FarmingKeeperPalletId;
+	type RewardIssuer = FarmingRewardIssuerPalletId;
+	type FarmingBoost = FarmingBoostPalletId;
+	type WeightInfo = ();
+	type VeMinting = ();
+	type BlockNumberToBalance = ConvertInto;
+	type WhitelistMaximumLimit = WhitelistMaximumLimit;
+	type GaugeRewardIssuer = FarmingGaugeRewardIssuerPalletId;
+	type MaxEmissionSchedulePoints = MaxEmissionSchedulePoints;
+}
+
+parameter_types! {
+	pub static MockPriceImpact: Permill = Permill::zero();
+}
+
+/// Swaps any two currencies at par, with the price impact set in `MockPriceImpact`.
+pub struct MockSwapRouter;
+impl SwapRouter<AccountId, Balance> for MockSwapRouter {
+	fn get_best_quote(
+		_asset_in: CurrencyId,
+		_asset_out: CurrencyId,
+		amount: Balance,
+		_swap_type: SwapType,
+	) -> Result<SwapQuote<Balance>, DispatchError> {
+		Ok(SwapQuote {
+			route: vec![],
+			amount_in: amount,
+			amount_out: amount,
+			price_impact: MockPriceImpact::get(),
+		})
+	}
+
+	fn swap_exact_in(
+		who: &AccountId,
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount_in: Balance,
+		_min_amount_out: Balance,
+	) -> Result<Balance, DispatchError> {
+		Currencies::withdraw(asset_in, who, amount_in)?;
+		Currencies::deposit(asset_out, who, amount_in)?;
+		Ok(amount_in)
+	}
+
+	fn swap_exact_out(
+		who: &AccountId,
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount_out: Balance,
+		_max_amount_in: Balance,
+	) -> Result<Balance, DispatchError> {
+		Self::swap_exact_in(who, asset_in, asset_out, amount_out, amount_out)
+	}
+}
+
+parameter_types! {
+	pub const FarmingVaultPalletId: PalletId = PalletId(*b"bf/fmvlt");
+	pub const MaxUnlockings: u32 = 3;
+	pub const DeadShares: Balance = 10;
+}
+
+impl bifrost_farming_vault::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type ControlOrigin = EnsureSignedBy<One, AccountId>;
+	type MultiCurrency = Currencies;
+	type Farming = Farming;
+	type SwapRouter = MockSwapRouter;
+	type VtokenMinting = ();
+	type TreasuryAccount = TreasuryAccount;
+	type PalletId = FarmingVaultPalletId;
+	type MaxUnlockings = MaxUnlockings;
+	type DeadShares = DeadShares;
+}
+
+pub struct ExtBuilder {
+	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self { endowed_accounts: vec![] }
+	}
+}
+
+impl ExtBuilder {
+	pub fn balances(mut self, endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>) -> Self {
+		self.endowed_accounts = endowed_accounts;
+		self
+	}
+
+	pub fn one_thousand_dot_for_alice_n_bob(self) -> Self {
+		self.balances(vec![(ALICE, DOT, 1000), (BOB, DOT, 1000), (CHARLIE, KSM, 1_000_000)])
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: self
+				.endowed_accounts
+				.clone()
+				.into_iter()
+				.filter(|(_, currency_id, _)| *currency_id == BNC)
+				.map(|(account_id, _, initial_balance)| (account_id, initial_balance))
+				.collect::<Vec<_>>(),
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			balances: self
+				.endowed_accounts
+				.clone()
+				.into_iter()
+				.filter(|(_, currency_id, _)| *currency_id != BNC)
+				.collect::<Vec<_>>(),
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::from(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,506 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate::{mock::*, *};
+use bifrost_primitives::FarmingOperator;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use sp_runtime::Perbill;
+
+// Creates a DOT pool rewarding 100 KSM a block and a vault over it, and returns their ids.
+fn init_vault() -> (PoolId, VaultId) {
+	assert_ok!(Farming::create_farming_pool(
+		RuntimeOrigin::signed(ALICE),
+		vec![(DOT, Perbill::from_percent(100))],
+		vec![(KSM, 100)],
+		None,
+		0,
+		0,
+		10,
+		0,
+		1
+	));
+	let pid = 0;
+	assert_ok!(Farming::charge(RuntimeOrigin::signed(CHARLIE), pid, vec![(KSM, 100_000)], false));
+
+	assert_ok!(FarmingVault::create_vault(
+		RuntimeOrigin::signed(ALICE),
+		pid,
+		VAULT_SHARE,
+		10,
+		Permill::from_percent(10),
+		Permill::from_percent(1),
+	));
+	(pid, 0)
+}
+
+fn vault_account(vault_id: VaultId) -> AccountId {
+	Vaults::<Runtime>::get(vault_id).unwrap().account
+}
+
+#[test]
+fn create_vault() {
+	ExtBuilder::default()
+		.one_thousand_dot_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let (pid, vault_id) = init_vault();
+
+			let vault = Vaults::<Runtime>::get(vault_id).unwrap();
+			assert_eq!(vault.pid, pid);
+			assert_eq!(vault.last_harvest, 1);
+			assert_eq!(NextVaultId::<Runtime>::get(), 1);
+
+			assert_noop!(
+				FarmingVault::create_vault(
+					RuntimeOrigin::signed(BOB),
+					pid,
+					VAULT_SHARE,
+					10,
+					Permill::zero(),
+					Permill::zero(),
+				),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				FarmingVault::create_vault(
+					RuntimeOrigin::signed(ALICE),
+					pid,
+					VAULT_SHARE,
+					10,
+					Permill::zero(),
+					Permill::zero(),
+				),
+				Error::<Runtime>::ShareCurrencyInUse
+			);
+			assert_noop!(
+				FarmingVault::create_vault(
+					RuntimeOrigin::signed(ALICE),
+					pid,
+					DOT,
+					10,
+					Permill::zero(),
+					Permill::zero(),
+				),
+				Error::<Runtime>::ShareCurrencyInUse
+			);
+			assert_noop!(
+				FarmingVault::create_vault(
+					RuntimeOrigin::signed(ALICE),
+					pid,
+					VDOT,
+					0,
+					Permill::zero(),
+					Permill::zero(),
+				),
+				Error::<Runtime>::InvalidHarvestInterval
+			);
+
+			assert_ok!(FarmingVault::edit_vault(
+				RuntimeOrigin::signed(ALICE),
+				vault_id,
+				Some(20),
+				None,
+				Some(Permill::from_percent(2)),
+			));
+			let vault = Vaults::<Runtime>::get(vault_id).unwrap();
+			assert_eq!(vault.harvest_interval, 20);
+			assert_eq!(vault.performance_fee, Permill::from_percent(10));
+			assert_eq!(vault.max_price_impact, Permill::from_percent(2));
+
+			// A pool whose rewards can only be claimed every 20 blocks.
+			assert_ok!(Farming::create_farming_pool(
+				RuntimeOrigin::signed(ALICE),
+				vec![(DOT, Perbill::from_percent(100))],
+				vec![(KSM, 100)],
+				None,
+				0,
+				0,
+				10,
+				20,
+				1
+			));
+			assert_noop!(
+				FarmingVault::create_vault(
+					RuntimeOrigin::signed(ALICE),
+					1,
+					VDOT,
+					10,
+					Permill::zero(),
+					Permill::zero(),
+				),
+				Error::<Runtime>::InvalidHarvestInterval
+			);
+			assert_ok!(FarmingVault::create_vault(
+				RuntimeOrigin::signed(ALICE),
+				1,
+				VDOT,
+				20,
+				Permill::zero(),
+				Permill::zero(),
+			));
+			assert_noop!(
+				FarmingVault::edit_vault(RuntimeOrigin::signed(ALICE), 1, Some(19), None, None),
+				Error::<Runtime>::InvalidHarvestInterval
+			);
+		});
+}
+
+#[test]
+fn deposit_and_harvest() {
+	ExtBuilder::default()
+		.one_thousand_dot_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let (pid, vault_id) = init_vault();
+			let account = vault_account(vault_id);
+
+			assert_noop!(
+				FarmingVault::deposit(RuntimeOrigin::signed(ALICE), vault_id, DeadShares::get()),
+				Error::<Runtime>::InitialDepositTooSmall
+			);
+			assert_ok!(FarmingVault::deposit(RuntimeOrigin::signed(ALICE), vault_id, 100));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 900);
+			// The first deposit locks the dead shares in the vault account.
+			assert_eq!(Tokens::free_balance(VAULT_SHARE, &ALICE), 90);
+			assert_eq!(Tokens::free_balance(VAULT_SHARE, &account), DeadShares::get());
+			assert_eq!(Farming::get_shares(&account, pid), 100);
+
+			// The pool starts at block 2 and pays the vault 100 KSM at block 3.
+			Farming::on_initialize(2);
+			Farming::on_initialize(3);
+
+			assert_noop!(
+				FarmingVault::harvest(RuntimeOrigin::signed(BOB), vault_id),
+				Error::<Runtime>::HarvestNotDue
+			);
+			System::set_block_number(11);
+			assert_ok!(FarmingVault::harvest(RuntimeOrigin::signed(BOB), vault_id));
+
+			// 10 KSM of fee, and the other 90 swapped to DOT, all but the rounding unit deposited.
+			assert_eq!(Tokens::free_balance(KSM, &TREASURY_ACCOUNT), 10);
+			assert_eq!(Tokens::free_balance(KSM, &account), 0);
+			assert_eq!(Tokens::free_balance(DOT, &account), 1);
+			assert_eq!(Farming::get_shares(&account, pid), 189);
+			assert_eq!(Vaults::<Runtime>::get(vault_id).unwrap().last_harvest, 11);
+			System::assert_last_event(RuntimeEvent::FarmingVault(Event::Harvested {
+				vault_id,
+				shares: 89,
+			}));
+
+			// Later depositors get fewer vault shares for the same farming shares.
+			assert_ok!(FarmingVault::deposit(RuntimeOrigin::signed(BOB), vault_id, 189));
+			assert_eq!(Tokens::free_balance(VAULT_SHARE, &BOB), 100);
+			assert_eq!(FarmingVault::get_vault_share_value(vault_id, 100), Some(189));
+
+			assert_noop!(
+				FarmingVault::deposit(RuntimeOrigin::signed(BOB), vault_id, 1),
+				Error::<Runtime>::ZeroShares
+			);
+		});
+}
+
+#[test]
+fn harvest_skips_swaps_above_max_price_impact() {
+	ExtBuilder::default()
+		.one_thousand_dot_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let (pid, vault_id) = init_vault();
+			let account = vault_account(vault_id);
+
+			assert_ok!(FarmingVault::deposit(RuntimeOrigin::signed(ALICE), vault_id, 100));
+			Farming::on_initialize(2);
+			Farming::on_initialize(3);
+
+			MockPriceImpact::set(Permill::from_percent(5));
+			System::set_block_number(11);
+			assert_ok!(FarmingVault::harvest(RuntimeOrigin::signed(BOB), vault_id));
+
+			System::assert_has_event(RuntimeEvent::FarmingVault(Event::ConvertFailed {
+				vault_id,
+				currency_in: KSM,
+				currency_out: DOT,
+				amount: 90,
+				error: Error::<Runtime>::PriceImpactTooHigh.into(),
+			}));
+			assert_eq!(Tokens::free_balance(KSM, &account), 90);
+			assert_eq!(Farming::get_shares(&account, pid), 100);
+
+			// The reward is compounded by a later harvest.
+			MockPriceImpact::set(Permill::zero());
+			System::set_block_number(21);
+			assert_ok!(FarmingVault::harvest(RuntimeOrigin::signed(BOB), vault_id));
+			assert_eq!(Tokens::free_balance(KSM, &account), 0);
+			assert_eq!(Farming::get_shares(&account, pid), 189);
+		});
+}
+
+#[test]
+fn redeem_and_claim_unlocked() {
+	ExtBuilder::default()
+		.one_thousand_dot_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let (pid, vault_id) = init_vault();
+			let account = vault_account(vault_id);
+
+			assert_ok!(FarmingVault::deposit(RuntimeOrigin::signed(ALICE), vault_id, 100));
+			Farming::on_initialize(2);
+			Farming::on_initialize(3);
+			System::set_block_number(11);
+			assert_ok!(FarmingVault::harvest(RuntimeOrigin::signed(BOB), vault_id));
+
+			assert_ok!(FarmingVault::redeem(RuntimeOrigin::signed(ALICE), vault_id, 50));
+			assert_eq!(Tokens::free_balance(VAULT_SHARE, &ALICE), 40);
+			assert_eq!(Unlockings::<Runtime>::get(vault_id, &ALICE).into_inner(), vec![(0, 94)]);
+			// The redeemed shares stay in the pool until the next harvest, but no longer back the
+			// vault shares.
+			assert_eq!(Farming::get_shares(&account, pid), 189);
+			assert_eq!(PendingBatch::<Runtime>::get(vault_id), (0, 94));
+			assert_eq!(FarmingVault::get_vault_share_value(vault_id, 50), Some(95));
+
+			System::set_block_number(21);
+			assert_noop!(
+				FarmingVault::claim_unlocked(RuntimeOrigin::signed(ALICE), vault_id),
+				Error::<Runtime>::NothingToClaim
+			);
+			assert_ok!(FarmingVault::harvest(RuntimeOrigin::signed(BOB), vault_id));
+			System::assert_has_event(RuntimeEvent::FarmingVault(Event::BatchWithdrawn {
+				vault_id,
+				batch: 0,
+				shares: 94,
+				unlock_at: 31,
+			}));
+			assert_eq!(Farming::get_shares(&account, pid), 95);
+			assert_eq!(PendingBatch::<Runtime>::get(vault_id), (1, 0));
+			assert_noop!(
+				FarmingVault::claim_unlocked(RuntimeOrigin::signed(ALICE), vault_id),
+				Error::<Runtime>::NothingToClaim
+			);
+
+			System::set_block_number(31);
+			assert_ok!(FarmingVault::claim_unlocked(RuntimeOrigin::signed(ALICE), vault_id));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 994);
+			assert_eq!(Unclaimed::<Runtime>::get(vault_id, DOT), 0);
+			assert!(Unlockings::<Runtime>::get(vault_id, &ALICE).is_empty());
+			assert_eq!(WithdrawnBatches::<Runtime>::get(vault_id, 0), None);
+		});
+}
+
+#[test]
+fn redeem_harvests_first() {
+	ExtBuilder::default()
+		.one_thousand_dot_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let (pid, vault_id) = init_vault();
+			let account = vault_account(vault_id);
+
+			assert_ok!(FarmingVault::deposit(RuntimeOrigin::signed(ALICE), vault_id, 100));
+			Farming::on_initialize(2);
+			Farming::on_initialize(3);
+
+			// The harvest is not due yet, but the 100 KSM paid at block 3 are compounded into
+			// 89 farming shares before Alice's vault shares are valued.
+			System::set_block_number(5);
+			assert_ok!(FarmingVault::redeem(RuntimeOrigin::signed(ALICE), vault_id, 90));
+			System::assert_has_event(RuntimeEvent::FarmingVault(Event::Harvested {
+				vault_id,
+				shares: 89,
+			}));
+			assert_eq!(Unlockings::<Runtime>::get(vault_id, &ALICE).into_inner(), vec![(0, 170)]);
+			assert_eq!(Farming::get_shares(&account, pid), 189);
+			assert_eq!(FarmingVault::get_vault_share_value(vault_id, DeadShares::get()), Some(19));
+			// The scheduled harvest, which withdraws the batch, is not put off.
+			assert_eq!(Vaults::<Runtime>::get(vault_id).unwrap().last_harvest, 1);
+		});
+}
+
+#[test]
+fn unlocked_tokens_are_not_compounded() {
+	ExtBuilder::default()
+		.one_thousand_dot_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let (pid, vault_id) = init_vault();
+			let account = vault_account(vault_id);
+
+			assert_ok!(FarmingVault::deposit(RuntimeOrigin::signed(ALICE), vault_id, 100));
+			assert_ok!(FarmingVault::deposit(RuntimeOrigin::signed(BOB), vault_id, 100));
+			Farming::on_initialize(2);
+
+			assert_ok!(FarmingVault::redeem(RuntimeOrigin::signed(ALICE), vault_id, 90));
+			assert_ok!(FarmingVault::redeem(RuntimeOrigin::signed(BOB), vault_id, 50));
+			System::set_block_number(11);
+			assert_ok!(FarmingVault::harvest(RuntimeOrigin::signed(CHARLIE), vault_id));
+			assert_eq!(Farming::get_shares(&account, pid), 60);
+
+			// Alice's claim moves Bob's unlocked tokens into the vault account too.
+			System::set_block_number(21);
+			assert_ok!(FarmingVault::claim_unlocked(RuntimeOrigin::signed(ALICE), vault_id));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 990);
+			assert_eq!(Tokens::free_balance(DOT, &account), 50);
+			assert_eq!(Unclaimed::<Runtime>::get(vault_id, DOT), 50);
+
+			assert_ok!(FarmingVault::harvest(RuntimeOrigin::signed(CHARLIE), vault_id));
+			assert_eq!(Farming::get_shares(&account, pid), 60);
+
+			assert_ok!(FarmingVault::claim_unlocked(RuntimeOrigin::signed(BOB), vault_id));
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 950);
+			assert_eq!(Tokens::free_balance(DOT, &account), 0);
+		});
+}
+
+#[test]
+fn redeem_limits_unlockings() {
+	ExtBuilder::default()
+		.one_thousand_dot_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let (_pid, vault_id) = init_vault();
+
+			assert_ok!(FarmingVault::deposit(RuntimeOrigin::signed(ALICE), vault_id, 100));
+			Farming::on_initialize(2);
+			for _ in 0..MaxUnlockings::get() {
+				assert_ok!(FarmingVault::redeem(RuntimeOrigin::signed(ALICE), vault_id, 10));
+			}
+			assert_noop!(
+				FarmingVault::redeem(RuntimeOrigin::signed(ALICE), vault_id, 10),
+				Error::<Runtime>::TooManyUnlockings
+			);
+			assert_noop!(
+				FarmingVault::redeem(RuntimeOrigin::signed(ALICE), 1, 10),
+				Error::<Runtime>::VaultNotExist
+			);
+		});
+}
+
+#[test]
+fn on_idle_harvests_due_vaults() {
+	ExtBuilder::default()
+		.one_thousand_dot_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let (pid, vault_id) = init_vault();
+			let account = vault_account(vault_id);
+
+			assert_ok!(FarmingVault::deposit(RuntimeOrigin::signed(ALICE), vault_id, 100));
+			Farming::on_initialize(2);
+			Farming::on_initialize(3);
+
+			FarmingVault::on_idle(10, Weight::MAX);
+			assert_eq!(Farming::get_shares(&account, pid), 100);
+
+			System::set_block_number(11);
+			FarmingVault::on_idle(11, Weight::MAX);
+			assert_eq!(Farming::get_shares(&account, pid), 189);
+
+			// Not enough weight for a harvest.
+			System::set_block_number(21);
+			FarmingVault::on_idle(21, Weight::zero());
+			assert_eq!(Vaults::<Runtime>::get(vault_id).unwrap().last_harvest, 11);
+		});
+}
+
+#[test]
+fn redeems_in_consecutive_blocks_share_a_batch() {
+	let redeemers: Vec<AccountId> = (10..16).map(|i| AccountId::new([i; 32])).collect();
+	let mut balances: Vec<(AccountId, CurrencyId, Balance)> =
+		redeemers.iter().map(|who| (who.clone(), DOT, 100)).collect();
+	balances.push((CHARLIE, KSM, 1_000_000));
+	ExtBuilder::default().balances(balances).build().execute_with(|| {
+		let (pid, vault_id) = init_vault();
+		let account = vault_account(vault_id);
+
+		for who in redeemers.iter() {
+			assert_ok!(FarmingVault::deposit(RuntimeOrigin::signed(who.clone()), vault_id, 100));
+		}
+		Farming::on_initialize(2);
+
+		// More redeemers than the single withdrawal the pool lets the vault have at a time.
+		for (block, who) in (2..).zip(redeemers.iter()) {
+			System::set_block_number(block);
+			assert_ok!(FarmingVault::redeem(RuntimeOrigin::signed(who.clone()), vault_id, 50));
+		}
+		assert_eq!(PendingBatch::<Runtime>::get(vault_id), (0, 300));
+		assert_eq!(Farming::get_shares(&account, pid), 600);
+
+		System::set_block_number(11);
+		FarmingVault::on_idle(11, Weight::MAX);
+		assert_eq!(Farming::get_shares(&account, pid), 300);
+		assert_eq!(WithdrawnBatches::<Runtime>::get(vault_id, 0), Some((21, 300)));
+
+		System::set_block_number(21);
+		for who in redeemers.iter() {
+			assert_ok!(FarmingVault::claim_unlocked(RuntimeOrigin::signed(who.clone()), vault_id));
+			assert_eq!(Tokens::free_balance(DOT, who), 50);
+		}
+		assert_eq!(Unclaimed::<Runtime>::get(vault_id, DOT), 0);
+		assert_eq!(WithdrawnBatches::<Runtime>::get(vault_id, 0), None);
+	});
+}
+
+#[test]
+fn on_idle_resumes_after_the_last_scanned_vault() {
+	ExtBuilder::default()
+		.one_thousand_dot_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let (pid, vault_id) = init_vault();
+			assert_ok!(FarmingVault::create_vault(
+				RuntimeOrigin::signed(ALICE),
+				pid,
+				VDOT,
+				1,
+				Permill::zero(),
+				Permill::zero(),
+			));
+			assert_ok!(FarmingVault::edit_vault(
+				RuntimeOrigin::signed(ALICE),
+				vault_id,
+				Some(1),
+				None,
+				None
+			));
+			let last_harvests = || {
+				let mut last_harvests: Vec<BlockNumber> =
+					Vaults::<Runtime>::iter_values().map(|vault| vault.last_harvest).collect();
+				last_harvests.sort();
+				last_harvests
+			};
+
+			// Room for a single harvest a block, while both vaults are due every block.
+			let weight = <() as WeightInfo>::harvest();
+			System::set_block_number(11);
+			FarmingVault::on_idle(11, weight);
+			assert_eq!(last_harvests(), vec![1, 11]);
+			assert!(HarvestScanCursor::<Runtime>::get().is_some());
+
+			// The other vault is harvested next, rather than the first one of the map again.
+			System::set_block_number(12);
+			FarmingVault::on_idle(12, weight);
+			assert_eq!(last_harvests(), vec![11, 12]);
+
+			// The end of the map is reached, and the scan starts over.
+			System::set_block_number(13);
+			FarmingVault::on_idle(13, weight);
+			assert_eq!(HarvestScanCursor::<Runtime>::get(), None);
+		});
+}
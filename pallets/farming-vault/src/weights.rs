@@ -0,0 +1,197 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Autogenerated weights for `bifrost_farming_vault`
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 32.0.0
+//! DATE: 2024-06-05, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `yml`, CPU: `AMD Ryzen 9 3950X 16-Core Processor`
+//! WASM-EXECUTION: `Compiled`, CHAIN: `Some("bifrost-polkadot-local")`, DB CACHE: 1024
+
+// Executed Command:
+// ./target/release/bifrost
+// benchmark
+// pallet
+// --chain=bifrost-polkadot-local
+// --pallet=bifrost-farming-vault
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --header=./HEADER-GPL3
+// --output=./bifrost-farming-vault.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{constants::RocksDbWeight, Weight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for bifrost_farming_vault.
+pub trait WeightInfo {
+	fn create_vault() -> Weight;
+	fn edit_vault() -> Weight;
+	fn deposit() -> Weight;
+	fn redeem() -> Weight;
+	fn claim_unlocked() -> Weight;
+	fn harvest() -> Weight;
+}
+
+/// Weight functions for `bifrost_farming_vault`.
+impl WeightInfo for () {
+	/// Storage: `Farming::PoolInfos` (r:1 w:0)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::TotalIssuance` (r:1 w:0)
+	/// Proof: `Tokens::TotalIssuance` (`max_values`: None, `max_size`: Some(38), added: 2513, mode: `MaxEncodedLen`)
+	/// Storage: `FarmingVault::Vaults` (r:2 w:1)
+	/// Proof: `FarmingVault::Vaults` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `FarmingVault::NextVaultId` (r:1 w:1)
+	/// Proof: `FarmingVault::NextVaultId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	fn create_vault() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `420`
+		//  Estimated: `6176`
+		// Minimum execution time: 38_412_000 picoseconds.
+		Weight::from_parts(39_105_000, 0)
+			.saturating_add(Weight::from_parts(0, 6176))
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	/// Storage: `FarmingVault::Vaults` (r:1 w:1)
+	/// Proof: `FarmingVault::Vaults` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	fn edit_vault() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `310`
+		//  Estimated: `3550`
+		// Minimum execution time: 19_227_000 picoseconds.
+		Weight::from_parts(19_804_000, 0)
+			.saturating_add(Weight::from_parts(0, 3550))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: `FarmingVault::Vaults` (r:1 w:0)
+	/// Proof: `FarmingVault::Vaults` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `Farming::PoolInfos` (r:1 w:1)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::SharesAndWithdrawnRewards` (r:1 w:1)
+	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::TotalIssuance` (r:1 w:1)
+	/// Proof: `Tokens::TotalIssuance` (`max_values`: None, `max_size`: Some(38), added: 2513, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:5 w:5)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn deposit() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2248`
+		//  Estimated: `13905`
+		// Minimum execution time: 212_560_000 picoseconds.
+		Weight::from_parts(217_032_000, 0)
+			.saturating_add(Weight::from_parts(0, 13905))
+			.saturating_add(RocksDbWeight::get().reads(11))
+			.saturating_add(RocksDbWeight::get().writes(9))
+	}
+	/// Storage: `FarmingVault::Vaults` (r:1 w:0)
+	/// Proof: `FarmingVault::Vaults` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `Farming::PoolInfos` (r:1 w:1)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::SharesAndWithdrawnRewards` (r:1 w:1)
+	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::GaugePoolInfos` (r:1 w:0)
+	/// Proof: `Farming::GaugePoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `FarmingVault::Unclaimed` (r:2 w:0)
+	/// Proof: `FarmingVault::Unclaimed` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::TotalIssuance` (r:1 w:1)
+	/// Proof: `Tokens::TotalIssuance` (`max_values`: None, `max_size`: Some(38), added: 2513, mode: `MaxEncodedLen`)
+	/// Storage: `FarmingVault::PendingBatch` (r:1 w:1)
+	/// Proof: `FarmingVault::PendingBatch` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
+	/// Storage: `FarmingVault::Unlockings` (r:1 w:1)
+	/// Proof: `FarmingVault::Unlockings` (`max_values`: None, `max_size`: Some(870), added: 3345, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:8 w:8)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:3 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn redeem() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3294`
+		//  Estimated: `23621`
+		// Minimum execution time: 498_361_000 picoseconds.
+		Weight::from_parts(509_772_000, 0)
+			.saturating_add(Weight::from_parts(0, 23621))
+			.saturating_add(RocksDbWeight::get().reads(20))
+			.saturating_add(RocksDbWeight::get().writes(15))
+	}
+	/// Storage: `FarmingVault::Vaults` (r:1 w:0)
+	/// Proof: `FarmingVault::Vaults` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `FarmingVault::Unlockings` (r:1 w:1)
+	/// Proof: `FarmingVault::Unlockings` (`max_values`: None, `max_size`: Some(870), added: 3345, mode: `MaxEncodedLen`)
+	/// Storage: `FarmingVault::WithdrawnBatches` (r:1 w:1)
+	/// Proof: `FarmingVault::WithdrawnBatches` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `Farming::PoolInfos` (r:1 w:0)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::SharesAndWithdrawnRewards` (r:1 w:1)
+	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `FarmingVault::Unclaimed` (r:2 w:2)
+	/// Proof: `FarmingVault::Unclaimed` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:4 w:4)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn claim_unlocked() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2581`
+		//  Estimated: `13807`
+		// Minimum execution time: 179_218_000 picoseconds.
+		Weight::from_parts(183_964_000, 0)
+			.saturating_add(Weight::from_parts(0, 13807))
+			.saturating_add(RocksDbWeight::get().reads(12))
+			.saturating_add(RocksDbWeight::get().writes(10))
+	}
+	/// Storage: `FarmingVault::Vaults` (r:1 w:1)
+	/// Proof: `FarmingVault::Vaults` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `FarmingVault::PendingBatch` (r:1 w:1)
+	/// Proof: `FarmingVault::PendingBatch` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
+	/// Storage: `Farming::PoolInfos` (r:1 w:1)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::SharesAndWithdrawnRewards` (r:1 w:1)
+	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::GaugePoolInfos` (r:1 w:0)
+	/// Proof: `Farming::GaugePoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `FarmingVault::Unclaimed` (r:2 w:1)
+	/// Proof: `FarmingVault::Unclaimed` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:10 w:10)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:3 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `FarmingVault::WithdrawnBatches` (r:0 w:1)
+	/// Proof: `FarmingVault::WithdrawnBatches` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	fn harvest() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3437`
+		//  Estimated: `29217`
+		// Minimum execution time: 517_940_000 picoseconds.
+		Weight::from_parts(529_306_000, 0)
+			.saturating_add(Weight::from_parts(0, 29217))
+			.saturating_add(RocksDbWeight::get().reads(20))
+			.saturating_add(RocksDbWeight::get().writes(18))
+	}
+}
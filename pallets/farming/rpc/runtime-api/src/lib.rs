@@ -18,7 +18,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use bifrost_primitives::Balance;
+use bifrost_primitives::{Balance, BlockNumber};
 use parity_scale_codec::Codec;
 use sp_api::decl_runtime_apis;
 use sp_std::vec::Vec;
@@ -38,5 +38,9 @@ decl_runtime_apis! {
 			who: AccountId,
 			pid: PoolId,
 		) -> Vec<(CurrencyId, Balance)>;
+
+		fn get_remaining_funded_blocks(
+			pid: PoolId,
+		) -> Vec<(CurrencyId, Option<BlockNumber>)>;
 	}
 }
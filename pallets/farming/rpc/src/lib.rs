@@ -19,7 +19,7 @@
 use std::{marker::PhantomData, sync::Arc};
 
 pub use bifrost_farming_rpc_runtime_api::{self as runtime_api, FarmingRuntimeApi};
-use bifrost_primitives::Balance;
+use bifrost_primitives::{Balance, BlockNumber};
 use jsonrpsee::{
 	core::{async_trait, RpcResult},
 	proc_macros::rpc,
@@ -50,6 +50,15 @@ pub trait FarmingRpcApi<BlockHash, AccountId, PoolId, CurrencyId> {
 		pid: PoolId,
 		at: Option<BlockHash>,
 	) -> RpcResult<Vec<(CurrencyId, NumberOrHex)>>;
+
+	/// rpc method for getting how many more blocks each reward currency stays funded, `None`
+	/// if it never runs out
+	#[method(name = "farming_getRemainingFundedBlocks")]
+	fn get_remaining_funded_blocks(
+		&self,
+		pid: PoolId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(CurrencyId, Option<BlockNumber>)>>;
 }
 
 #[derive(Clone, Debug)]
@@ -66,7 +75,8 @@ impl<C, Block> FarmingRpc<C, Block> {
 
 #[async_trait]
 impl<C, Block, AccountId, PoolId, CurrencyId>
-	FarmingRpcApiServer<<Block as BlockT>::Hash, AccountId, PoolId, CurrencyId> for FarmingRpc<C, Block>
+	FarmingRpcApiServer<<Block as BlockT>::Hash, AccountId, PoolId, CurrencyId>
+	for FarmingRpc<C, Block>
 where
 	Block: BlockT,
 	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
@@ -123,4 +133,21 @@ where
 			)),
 		}
 	}
+
+	fn get_remaining_funded_blocks(
+		&self,
+		pid: PoolId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(CurrencyId, Option<BlockNumber>)>> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		lm_rpc_api.get_remaining_funded_blocks(at, pid).map_err(|e| {
+			ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get remaining funded blocks.",
+				Some(format!("{:?}", e)),
+			)
+		})
+	}
 }
@@ -369,4 +369,23 @@ benchmarks! {
 		assert_ok!(Farming::<T>::add_boost_pool_whitelist(RawOrigin::Root.into(), vec![0]));
 		assert_ok!(Farming::<T>::start_boost_round(RawOrigin::Root.into(), BlockNumberFor::<T>::from(100000u32)));
 	}: _(RawOrigin::Signed(caller.clone()), charge_list)
+
+	set_emission_schedule {
+		let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+		let default_currency_id = CurrencyIdOf::<T>::default();
+		let tokens_proportion = vec![(default_currency_id, Perbill::from_percent(100))];
+		let basic_rewards = vec![(default_currency_id, token_amount)];
+		assert_ok!(Farming::<T>::create_farming_pool(RawOrigin::Root.into(),
+			tokens_proportion.clone(),
+			basic_rewards.clone(),
+			None,
+			BalanceOf::<T>::unique_saturated_from(0u128),
+			BlockNumberFor::<T>::from(0u32),
+			BlockNumberFor::<T>::from(7u32),
+			BlockNumberFor::<T>::from(6u32),
+			5));
+		let points = (0..T::MaxEmissionSchedulePoints::get())
+			.map(|i| (BlockNumberFor::<T>::from(i * 100), token_amount))
+			.collect();
+	}: _(RawOrigin::Root, 0, default_currency_id, Some(EmissionSchedule::Piecewise(points)))
 }
@@ -34,13 +34,13 @@ pub mod weights;
 pub use weights::WeightInfo;
 
 use crate::boost::*;
-use bifrost_primitives::{FarmingInfo, PoolId};
+use bifrost_primitives::{FarmingInfo, FarmingOperator, PoolId};
 use frame_support::{
 	pallet_prelude::*,
 	sp_runtime::{
 		traits::{
 			AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, CheckedDiv, CheckedSub, Convert,
-			Saturating, Zero,
+			One, Saturating, Zero,
 		},
 		ArithmeticError, Perbill, Percent,
 	},
@@ -123,6 +123,10 @@ pub mod pallet {
 
 		#[pallet::constant]
 		type GaugeRewardIssuer: Get<PalletId>;
+
+		/// The maximum number of `(block, rate)` points in a piecewise emission schedule.
+		#[pallet::constant]
+		type MaxEmissionSchedulePoints: Get<u32>;
 	}
 
 	#[pallet::event]
@@ -207,6 +211,16 @@ pub mod pallet {
 			who: AccountIdOf<T>,
 			rewards: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
 		},
+		EmissionScheduleSet {
+			pid: PoolId,
+			currency_id: CurrencyIdOf<T>,
+			schedule: Option<EmissionSchedule<BalanceOf<T>, BlockNumberFor<T>>>,
+		},
+		/// The charged rewards of a currency have all been emitted.
+		RewardsExhausted {
+			pid: PoolId,
+			currency_id: CurrencyIdOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -233,6 +247,9 @@ pub mod pallet {
 		NotInWhitelist,
 		PercentOverflow,
 		PoolNotCleared,
+		/// The currency is not one of the pool's basic rewards
+		NotRewardCurrency,
+		InvalidEmissionSchedule,
 	}
 
 	#[pallet::storage]
@@ -324,25 +341,56 @@ pub mod pallet {
 	pub type BoostBasicRewards<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, PoolId, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>>;
 
+	/// Emission schedules overriding the fixed per-block amount of a pool's basic reward.
+	///
+	/// double_map (PoolId, CurrencyId) => EmissionSchedule
+	#[pallet::storage]
+	#[pallet::getter(fn emission_schedules)]
+	pub type EmissionSchedules<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PoolId,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		EmissionSchedule<BalanceOf<T>, BlockNumberFor<T>>,
+	>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
 			PoolInfos::<T>::iter().for_each(|(pid, mut pool_info)| match pool_info.state {
 				PoolState::Ongoing => {
-					pool_info.basic_rewards.clone().iter_mut().for_each(
-						|(reward_currency_id, reward_amount)| {
-							if let Some(boost_basic_reward) =
-								Self::boost_basic_rewards(pid, reward_currency_id)
-							{
-								*reward_amount = reward_amount.saturating_add(boost_basic_reward);
+					// The pool first emits in the block after it starts, at elapsed 0.
+					let elapsed = pool_info.block_startup.map_or(Zero::zero(), |block_startup| {
+						n.saturating_sub(block_startup).saturating_sub(One::one())
+					});
+					pool_info.basic_rewards.clone().iter().for_each(
+						|(reward_currency_id, basic_reward)| {
+							let mut reward_amount = Self::emission_rate(
+								pid,
+								reward_currency_id,
+								*basic_reward,
+								elapsed,
+							);
+							// Stop emitting once everything charged has been handed out.
+							let unallocated =
+								Self::unallocated_rewards(&pool_info, reward_currency_id);
+							if reward_amount > unallocated {
+								if !unallocated.is_zero() {
+									Self::deposit_event(Event::RewardsExhausted {
+										pid,
+										currency_id: *reward_currency_id,
+									});
+								}
+								reward_amount = unallocated;
 							}
 							pool_info
 								.rewards
 								.entry(*reward_currency_id)
 								.and_modify(|(total_reward, _)| {
-									*total_reward = total_reward.saturating_add(*reward_amount);
+									*total_reward = total_reward.saturating_add(reward_amount);
 								})
-								.or_insert((*reward_amount, Zero::zero()));
+								.or_insert((reward_amount, Zero::zero()));
 						},
 					);
 					PoolInfos::<T>::insert(pid, &pool_info);
@@ -500,37 +548,7 @@ pub mod pallet {
 			// Check origin
 			let exchanger = ensure_signed(origin)?;
 
-			let mut pool_info = Self::pool_infos(&pid).ok_or(Error::<T>::PoolDoesNotExist)?;
-			ensure!(
-				pool_info.state == PoolState::Ongoing || pool_info.state == PoolState::Charged,
-				Error::<T>::InvalidPoolState
-			);
-
-			if let PoolState::Charged = pool_info.state {
-				let current_block_number: BlockNumberFor<T> =
-					frame_system::Pallet::<T>::block_number();
-				ensure!(
-					current_block_number >= pool_info.after_block_to_start,
-					Error::<T>::CanNotDeposit
-				);
-			}
-
-			let native_amount = pool_info.basic_token.1.saturating_reciprocal_mul(add_value);
-			pool_info.tokens_proportion.iter().try_for_each(
-				|(token, proportion)| -> DispatchResult {
-					T::MultiCurrency::transfer(
-						*token,
-						&exchanger,
-						&pool_info.keeper,
-						*proportion * native_amount,
-					)
-				},
-			)?;
-			Self::add_share(&exchanger, pid, &mut pool_info, add_value);
-			Self::update_reward(&exchanger, pid)?;
-
-			Self::deposit_event(Event::Deposited { who: exchanger, pid, add_value, gauge_info });
-			Ok(())
+			Self::deposit_inner(&exchanger, pid, add_value, gauge_info)
 		}
 
 		#[pallet::call_index(3)]
@@ -570,22 +588,7 @@ pub mod pallet {
 			// Check origin
 			let exchanger = ensure_signed(origin)?;
 
-			let pool_info = Self::pool_infos(&pid).ok_or(Error::<T>::PoolDoesNotExist)?;
-			ensure!(
-				pool_info.state == PoolState::Ongoing || pool_info.state == PoolState::Dead,
-				Error::<T>::InvalidPoolState
-			);
-
-			let current_block_number: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
-			let share_info = Self::shares_and_withdrawn_rewards(&pid, &exchanger)
-				.ok_or(Error::<T>::ShareInfoNotExists)?;
-			ensure!(
-				share_info.claim_last_block.saturating_add(pool_info.claim_limit_time) <=
-					current_block_number,
-				Error::<T>::CanNotClaim
-			);
-
-			Self::claim_rewards(&exchanger, pid)?;
+			let pool_info = Self::claim_inner(&exchanger, pid)?;
 			Self::process_withdraw_list(&exchanger, pid, &pool_info, true)?;
 
 			Self::deposit_event(Event::Claimed { who: exchanger, pid });
@@ -735,6 +738,7 @@ pub mod pallet {
 			);
 			let res = SharesAndWithdrawnRewards::<T>::clear_prefix(pid, u32::max_value(), None);
 			ensure!(res.maybe_cursor.is_none(), Error::<T>::PoolNotCleared);
+			let _ = EmissionSchedules::<T>::clear_prefix(pid, u32::max_value(), None);
 			PoolInfos::<T>::remove(pid);
 
 			Self::deposit_event(Event::FarmingPoolKilled { pid });
@@ -917,6 +921,101 @@ pub mod pallet {
 			Self::deposit_event(Event::BoostCharged { who: exchanger, rewards });
 			Ok(())
 		}
+
+		/// Make `currency_id` follow `schedule` instead of the fixed amount in the pool's
+		/// `basic_rewards`, or go back to the fixed amount if `schedule` is `None`.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::set_emission_schedule())]
+		pub fn set_emission_schedule(
+			origin: OriginFor<T>,
+			pid: PoolId,
+			currency_id: CurrencyIdOf<T>,
+			schedule: Option<EmissionSchedule<BalanceOf<T>, BlockNumberFor<T>>>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			let pool_info = Self::pool_infos(&pid).ok_or(Error::<T>::PoolDoesNotExist)?;
+			ensure!(
+				pool_info.basic_rewards.contains_key(&currency_id),
+				Error::<T>::NotRewardCurrency
+			);
+			if let Some(ref schedule) = schedule {
+				ensure!(
+					schedule.is_valid(T::MaxEmissionSchedulePoints::get()),
+					Error::<T>::InvalidEmissionSchedule
+				);
+			}
+			EmissionSchedules::<T>::set(pid, currency_id, schedule.clone());
+
+			Self::deposit_event(Event::EmissionScheduleSet { pid, currency_id, schedule });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub(crate) fn deposit_inner(
+		who: &AccountIdOf<T>,
+		pid: PoolId,
+		add_value: BalanceOf<T>,
+		gauge_info: Option<(BalanceOf<T>, BlockNumberFor<T>)>,
+	) -> DispatchResult {
+		let mut pool_info = Self::pool_infos(&pid).ok_or(Error::<T>::PoolDoesNotExist)?;
+		ensure!(
+			pool_info.state == PoolState::Ongoing || pool_info.state == PoolState::Charged,
+			Error::<T>::InvalidPoolState
+		);
+
+		if let PoolState::Charged = pool_info.state {
+			let current_block_number: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				current_block_number >= pool_info.after_block_to_start,
+				Error::<T>::CanNotDeposit
+			);
+		}
+
+		let native_amount = pool_info.basic_token.1.saturating_reciprocal_mul(add_value);
+		pool_info.tokens_proportion.iter().try_for_each(
+			|(token, proportion)| -> DispatchResult {
+				T::MultiCurrency::transfer(
+					*token,
+					who,
+					&pool_info.keeper,
+					*proportion * native_amount,
+				)
+			},
+		)?;
+		Self::add_share(who, pid, &mut pool_info, add_value);
+		Self::update_reward(who, pid)?;
+
+		Self::deposit_event(Event::Deposited { who: who.clone(), pid, add_value, gauge_info });
+		Ok(())
+	}
+
+	pub(crate) fn claim_inner(
+		who: &AccountIdOf<T>,
+		pid: PoolId,
+	) -> Result<
+		PoolInfo<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
+		DispatchError,
+	> {
+		let pool_info = Self::pool_infos(&pid).ok_or(Error::<T>::PoolDoesNotExist)?;
+		ensure!(
+			pool_info.state == PoolState::Ongoing || pool_info.state == PoolState::Dead,
+			Error::<T>::InvalidPoolState
+		);
+
+		let current_block_number: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+		let share_info =
+			Self::shares_and_withdrawn_rewards(&pid, who).ok_or(Error::<T>::ShareInfoNotExists)?;
+		ensure!(
+			share_info.claim_last_block.saturating_add(pool_info.claim_limit_time) <=
+				current_block_number,
+			Error::<T>::CanNotClaim
+		);
+
+		Self::claim_rewards(who, pid)?;
+		Ok(pool_info)
 	}
 }
 
@@ -932,3 +1031,116 @@ impl<T: Config> FarmingInfo<BalanceOf<T>, CurrencyIdOf<T>> for Pallet<T> {
 		Zero::zero()
 	}
 }
+
+impl<T: Config> FarmingOperator<AccountIdOf<T>, BalanceOf<T>, CurrencyIdOf<T>, BlockNumberFor<T>>
+	for Pallet<T>
+{
+	fn get_shares(who: &AccountIdOf<T>, pool_id: PoolId) -> BalanceOf<T> {
+		Self::shares_and_withdrawn_rewards(pool_id, who)
+			.map(|share_info| share_info.share)
+			.unwrap_or_default()
+	}
+
+	fn get_share_tokens(
+		pool_id: PoolId,
+		shares: BalanceOf<T>,
+	) -> Result<Vec<(CurrencyIdOf<T>, BalanceOf<T>)>, DispatchError> {
+		let pool_info = Self::pool_infos(&pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+		let native_amount = pool_info.basic_token.1.saturating_reciprocal_mul(shares);
+		Ok(pool_info
+			.tokens_proportion
+			.iter()
+			.map(|(token, proportion)| (*token, *proportion * native_amount))
+			.collect())
+	}
+
+	fn get_tokens_proportion(pool_id: PoolId) -> Vec<(CurrencyIdOf<T>, Perbill)> {
+		Self::pool_infos(&pool_id)
+			.map(|pool_info| pool_info.tokens_proportion.into_iter().collect())
+			.unwrap_or_default()
+	}
+
+	fn get_max_shares(
+		pool_id: PoolId,
+		tokens: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
+	) -> BalanceOf<T> {
+		let tokens: BTreeMap<CurrencyIdOf<T>, BalanceOf<T>> = tokens.into_iter().collect();
+		Self::pool_infos(&pool_id).map_or(Zero::zero(), |pool_info| {
+			let native_amount = pool_info
+				.tokens_proportion
+				.iter()
+				.map(|(token, proportion)| {
+					proportion.saturating_reciprocal_mul_floor(
+						tokens.get(token).copied().unwrap_or_default(),
+					)
+				})
+				.min()
+				.unwrap_or_default();
+			// One native unit less keeps the rounding of a deposit within `tokens`.
+			pool_info.basic_token.1.mul_floor(native_amount.saturating_sub(One::one()))
+		})
+	}
+
+	fn get_reward_currencies(pool_id: PoolId) -> Vec<CurrencyIdOf<T>> {
+		Self::pool_infos(&pool_id)
+			.map(|pool_info| pool_info.basic_rewards.into_keys().collect())
+			.unwrap_or_default()
+	}
+
+	fn get_withdraw_limit_time(pool_id: PoolId) -> Result<BlockNumberFor<T>, DispatchError> {
+		let pool_info = Self::pool_infos(&pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+		Ok(pool_info.withdraw_limit_time)
+	}
+
+	fn get_claim_limit_time(pool_id: PoolId) -> Result<BlockNumberFor<T>, DispatchError> {
+		let pool_info = Self::pool_infos(&pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+		Ok(pool_info.claim_limit_time)
+	}
+
+	fn deposit(who: &AccountIdOf<T>, pool_id: PoolId, shares: BalanceOf<T>) -> DispatchResult {
+		Self::deposit_inner(who, pool_id, shares, None)
+	}
+
+	fn withdraw(who: &AccountIdOf<T>, pool_id: PoolId, shares: BalanceOf<T>) -> DispatchResult {
+		let pool_info = Self::pool_infos(&pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+		ensure!(
+			pool_info.state == PoolState::Ongoing ||
+				pool_info.state == PoolState::Charged ||
+				pool_info.state == PoolState::Dead,
+			Error::<T>::InvalidPoolState
+		);
+		let share_info = Self::shares_and_withdrawn_rewards(&pool_id, who)
+			.ok_or(Error::<T>::ShareInfoNotExists)?;
+		let unlock_at =
+			frame_system::Pallet::<T>::block_number().saturating_add(pool_info.withdraw_limit_time);
+		ensure!(
+			share_info.withdraw_list.len() < pool_info.withdraw_limit_count.into() ||
+				share_info.withdraw_list.iter().any(|(block, _)| *block == unlock_at),
+			Error::<T>::WithdrawLimitCountExceeded
+		);
+
+		Self::remove_share(who, pool_id, Some(shares), pool_info.withdraw_limit_time)?;
+		Self::update_reward(who, pool_id)?;
+
+		Self::deposit_event(Event::Withdrawn {
+			who: who.clone(),
+			pid: pool_id,
+			remove_value: Some(shares),
+		});
+		Ok(())
+	}
+
+	fn claim(who: &AccountIdOf<T>, pool_id: PoolId) -> DispatchResult {
+		Self::claim_inner(who, pool_id)?;
+		Self::deposit_event(Event::Claimed { who: who.clone(), pid: pool_id });
+		Ok(())
+	}
+
+	fn claim_withdrawn(who: &AccountIdOf<T>, pool_id: PoolId) -> DispatchResult {
+		let pool_info = Self::pool_infos(&pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+		Self::process_withdraw_list(who, pool_id, &pool_info, false)?;
+
+		Self::deposit_event(Event::WithdrawClaimed { who: who.clone(), pid: pool_id });
+		Ok(())
+	}
+}
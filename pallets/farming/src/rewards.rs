@@ -22,7 +22,7 @@ use parity_scale_codec::HasCompact;
 use scale_info::TypeInfo;
 use sp_core::U256;
 use sp_runtime::{
-	traits::{CheckedAdd, Saturating, UniqueSaturatedInto, Zero},
+	traits::{AtLeast32BitUnsigned, CheckedAdd, One, Saturating, UniqueSaturatedInto, Zero},
 	RuntimeDebug, SaturatedConversion,
 };
 use sp_std::{borrow::ToOwned, collections::btree_map::BTreeMap, prelude::*};
@@ -124,6 +124,117 @@ pub enum PoolState {
 	Retired,
 }
 
+/// Per-block emission of a reward currency, as a function of the blocks the pool has emitted in
+/// so far. Replaces the fixed amount the pool has in `basic_rewards` for that currency.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum EmissionSchedule<BalanceOf, BlockNumberFor> {
+	/// Starts at `initial` and declines by `decline` every block, never going below `floor`.
+	LinearDecline { initial: BalanceOf, decline: BalanceOf, floor: BalanceOf },
+	/// Starts at `initial` and halves every `epoch` blocks.
+	Halving { initial: BalanceOf, epoch: BlockNumberFor },
+	/// `(block, rate)` points in increasing block order, each rate applying until the next
+	/// point. Nothing is emitted before the first point.
+	Piecewise(Vec<(BlockNumberFor, BalanceOf)>),
+}
+
+impl<BalanceOf, BlockNumberFor> EmissionSchedule<BalanceOf, BlockNumberFor>
+where
+	BalanceOf: AtLeast32BitUnsigned + Copy,
+	BlockNumberFor: AtLeast32BitUnsigned + Copy,
+{
+	pub fn is_valid(&self, max_points: u32) -> bool {
+		match self {
+			Self::LinearDecline { initial, floor, .. } => floor <= initial,
+			Self::Halving { epoch, .. } => !epoch.is_zero(),
+			Self::Piecewise(points) =>
+				!points.is_empty() &&
+					points.len() <= max_points as usize &&
+					points.windows(2).all(|pair| pair[0].0 < pair[1].0),
+		}
+	}
+
+	/// The amount emitted after the pool has emitted in `elapsed` blocks, the first emitting
+	/// block being at elapsed 0.
+	pub fn rate_at(&self, elapsed: BlockNumberFor) -> BalanceOf {
+		let elapsed: u128 = elapsed.saturated_into();
+		let rate: u128 = match *self {
+			Self::LinearDecline { initial, decline, floor } => initial
+				.saturated_into::<u128>()
+				.saturating_sub(decline.saturated_into::<u128>().saturating_mul(elapsed))
+				.max(floor.saturated_into()),
+			Self::Halving { initial, epoch } => {
+				let halvings = elapsed.checked_div(epoch.saturated_into()).unwrap_or_default();
+				initial
+					.saturated_into::<u128>()
+					.checked_shr(halvings.saturated_into())
+					.unwrap_or_default()
+			},
+			Self::Piecewise(ref points) => points
+				.iter()
+				.rev()
+				.find(|(block, _)| (*block).saturated_into::<u128>() <= elapsed)
+				.map(|(_, rate)| (*rate).saturated_into())
+				.unwrap_or_default(),
+		};
+		rate.unique_saturated_into()
+	}
+
+	/// The total amount emitted in the emitting blocks `from..to`.
+	pub fn emitted_between(&self, from: BlockNumberFor, to: BlockNumberFor) -> u128 {
+		self.emitted_before(to.saturated_into())
+			.saturating_sub(self.emitted_before(from.saturated_into()))
+	}
+
+	fn emitted_before(&self, blocks: u128) -> u128 {
+		match *self {
+			Self::LinearDecline { initial, decline, floor } => {
+				let initial: u128 = initial.saturated_into();
+				let decline: u128 = decline.saturated_into();
+				let floor: u128 = floor.saturated_into();
+				// Blocks emitting more than `floor`, summed as an arithmetic series.
+				let declining = initial
+					.saturating_sub(floor)
+					.saturating_add(decline.saturating_sub(1))
+					.checked_div(decline)
+					.unwrap_or(blocks)
+					.min(blocks);
+				declining
+					.saturating_mul(initial)
+					.saturating_sub(
+						decline.saturating_mul(
+							declining.saturating_mul(declining.saturating_sub(1)) / 2,
+						),
+					)
+					.saturating_add(blocks.saturating_sub(declining).saturating_mul(floor))
+			},
+			Self::Halving { initial, epoch } => {
+				let initial: u128 = initial.saturated_into();
+				let epoch: u128 = epoch.saturated_into();
+				let halvings = blocks.checked_div(epoch).unwrap_or_default();
+				let full_epochs = (0..halvings.min(u128::BITS.into())).fold(0u128, |total, i| {
+					total.saturating_add(epoch.saturating_mul(initial >> i))
+				});
+				let last_epoch_rate =
+					initial.checked_shr(halvings.saturated_into()).unwrap_or_default();
+				full_epochs.saturating_add(
+					blocks.checked_rem(epoch).unwrap_or_default().saturating_mul(last_epoch_rate),
+				)
+			},
+			Self::Piecewise(ref points) =>
+				points.iter().enumerate().fold(0u128, |total, (i, (start, rate))| {
+					let end = points
+						.get(i + 1)
+						.map_or(u128::MAX, |(next, _)| (*next).saturated_into())
+						.min(blocks);
+					total.saturating_add(
+						end.saturating_sub((*start).saturated_into())
+							.saturating_mul((*rate).saturated_into()),
+					)
+				}),
+		}
+	}
+}
+
 impl<T: Config> Pallet<T> {
 	pub fn accumulate_reward(
 		pool: PoolId,
@@ -240,9 +351,15 @@ impl<T: Config> Pallet<T> {
 				PoolInfos::<T>::mutate(pool, |maybe_pool_info| -> DispatchResult {
 					let pool_info = maybe_pool_info.as_mut().ok_or(Error::<T>::PoolDoesNotExist)?;
 
-					share_info
+					let dest_block = current_block_number + withdraw_limit_time;
+					match share_info
 						.withdraw_list
-						.push((current_block_number + withdraw_limit_time, remove_amount));
+						.iter_mut()
+						.find(|(block, _)| *block == dest_block)
+					{
+						Some((_, value)) => *value = value.saturating_add(remove_amount),
+						None => share_info.withdraw_list.push((dest_block, remove_amount)),
+					}
 
 					let removing_share = U256::from(remove_amount.saturated_into::<u128>());
 
@@ -446,4 +563,92 @@ impl<T: Config> Pallet<T> {
 			},
 		)
 	}
+
+	/// The amount of `reward_currency` the pool emits after it has emitted in `elapsed` blocks,
+	/// boost included.
+	pub(crate) fn emission_rate(
+		pid: PoolId,
+		reward_currency: &CurrencyIdOf<T>,
+		basic_reward: BalanceOf<T>,
+		elapsed: BlockNumberFor<T>,
+	) -> BalanceOf<T> {
+		Self::emission_schedules(pid, reward_currency)
+			.map_or(basic_reward, |schedule| schedule.rate_at(elapsed))
+			.saturating_add(Self::boost_basic_rewards(pid, reward_currency).unwrap_or_default())
+	}
+
+	/// The part of the reward issuer's balance that is not owed to farmers yet, i.e. what the
+	/// pool can still emit.
+	pub(crate) fn unallocated_rewards(
+		pool_info: &PoolInfo<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
+		reward_currency: &CurrencyIdOf<T>,
+	) -> BalanceOf<T> {
+		let owed = pool_info
+			.rewards
+			.get(reward_currency)
+			.map(|(total_reward, total_withdrawn)| total_reward.saturating_sub(*total_withdrawn))
+			.unwrap_or_default();
+		T::MultiCurrency::free_balance(*reward_currency, &pool_info.reward_issuer)
+			.saturating_sub(owed)
+	}
+
+	/// How many more blocks the charged rewards keep each reward currency emitting. `None` means
+	/// the emission tails off before the funds run out.
+	pub fn get_remaining_funded_blocks(
+		pid: PoolId,
+	) -> Result<Vec<(CurrencyIdOf<T>, Option<BlockNumberFor<T>>)>, DispatchError> {
+		let pool_info = Self::pool_infos(&pid).ok_or(Error::<T>::PoolDoesNotExist)?;
+		ensure!(
+			pool_info.state == PoolState::UnCharged ||
+				pool_info.state == PoolState::Charged ||
+				pool_info.state == PoolState::Ongoing,
+			Error::<T>::InvalidPoolState
+		);
+		let current_block_number: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+		let next_elapsed = pool_info.block_startup.map_or(Zero::zero(), |block_startup| {
+			current_block_number.saturating_sub(block_startup)
+		});
+
+		Ok(pool_info
+			.basic_rewards
+			.iter()
+			.map(|(reward_currency, basic_reward)| {
+				let available = Self::unallocated_rewards(&pool_info, reward_currency);
+				let schedule = Self::emission_schedules(pid, reward_currency);
+				let constant_rate = schedule
+					.as_ref()
+					.map_or(*basic_reward, |_| Zero::zero())
+					.saturating_add(
+						Self::boost_basic_rewards(pid, reward_currency).unwrap_or_default(),
+					)
+					.saturated_into::<u128>();
+				let emitted_within = |blocks: BlockNumberFor<T>| -> BalanceOf<T> {
+					let scheduled = schedule.as_ref().map_or(0, |schedule| {
+						schedule.emitted_between(next_elapsed, next_elapsed.saturating_add(blocks))
+					});
+					constant_rate
+						.saturating_mul(blocks.saturated_into())
+						.saturating_add(scheduled)
+						.unique_saturated_into()
+				};
+
+				let mut funded: BlockNumberFor<T> = Zero::zero();
+				let mut unfunded: BlockNumberFor<T> = u32::MAX.into();
+				if emitted_within(unfunded) <= available {
+					return (*reward_currency, None);
+				}
+				while unfunded.saturating_sub(funded) > One::one() {
+					let middle = funded.saturating_add(
+						unfunded.saturating_sub(funded) / BlockNumberFor::<T>::from(2u32),
+					);
+					if emitted_within(middle) <= available {
+						funded = middle;
+					} else {
+						unfunded = middle;
+					}
+				}
+				(*reward_currency, Some(funded))
+			})
+			.collect())
+	}
 }
@@ -125,6 +125,29 @@ fn withdraw() {
 	})
 }
 
+#[test]
+fn withdraw_through_farming_operator() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		let (pid, _tokens) = init_no_gauge();
+		Farming::on_initialize(0);
+		Farming::on_initialize(0);
+		System::set_block_number(System::block_number() + 1);
+		assert_ok!(<Farming as FarmingOperator<_, _, _, _>>::withdraw(&ALICE, pid, 100));
+		// A withdrawal unlocking in the same block is merged into the earlier one.
+		assert_ok!(<Farming as FarmingOperator<_, _, _, _>>::withdraw(&ALICE, pid, 200));
+		assert_eq!(
+			Farming::shares_and_withdrawn_rewards(pid, &ALICE).unwrap().withdraw_list,
+			vec![(System::block_number() + 10, 300)]
+		);
+
+		System::set_block_number(System::block_number() + 1);
+		assert_err!(
+			<Farming as FarmingOperator<_, _, _, _>>::withdraw(&ALICE, pid, 100),
+			Error::<Runtime>::WithdrawLimitCountExceeded
+		);
+	})
+}
+
 #[test]
 fn gauge() {
 	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
@@ -431,3 +454,79 @@ fn create_farming_pool() {
 		assert_eq!(Tokens::free_balance(KSM, &ALICE), 3800);
 	})
 }
+
+#[test]
+fn emission_schedule() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		let (pid, _tokens) = init_no_gauge();
+		assert_err!(
+			Farming::set_emission_schedule(
+				RuntimeOrigin::signed(ALICE),
+				pid,
+				CurrencyId::Native(TokenSymbol::ASG),
+				Some(EmissionSchedule::Halving { initial: 1000, epoch: 2 })
+			),
+			Error::<Runtime>::NotRewardCurrency
+		);
+		assert_err!(
+			Farming::set_emission_schedule(
+				RuntimeOrigin::signed(ALICE),
+				pid,
+				KSM,
+				Some(EmissionSchedule::Halving { initial: 1000, epoch: 0 })
+			),
+			Error::<Runtime>::InvalidEmissionSchedule
+		);
+		assert_err!(
+			Farming::set_emission_schedule(
+				RuntimeOrigin::signed(ALICE),
+				pid,
+				KSM,
+				Some(EmissionSchedule::Piecewise(vec![(5, 1000), (5, 500)]))
+			),
+			Error::<Runtime>::InvalidEmissionSchedule
+		);
+		assert_ok!(Farming::set_emission_schedule(
+			RuntimeOrigin::signed(ALICE),
+			pid,
+			KSM,
+			Some(EmissionSchedule::Halving { initial: 1000, epoch: 2 })
+		));
+
+		System::set_block_number(10);
+		Farming::on_initialize(10);
+		for n in 11..=14 {
+			System::set_block_number(n);
+			Farming::on_initialize(n);
+		}
+		// 1000 + 1000 + 500 + 500
+		assert_eq!(Farming::pool_infos(pid).unwrap().rewards.get(&KSM), Some(&(3000, 0)));
+
+		assert_ok!(Farming::set_emission_schedule(
+			RuntimeOrigin::signed(ALICE),
+			pid,
+			KSM,
+			Some(EmissionSchedule::Piecewise(vec![(0, 40_000)]))
+		));
+		// 97_000 left to emit covers two more blocks.
+		assert_eq!(Farming::get_remaining_funded_blocks(pid), Ok(vec![(KSM, Some(2))]));
+		for n in 15..=18 {
+			System::set_block_number(n);
+			Farming::on_initialize(n);
+		}
+		assert_eq!(Farming::pool_infos(pid).unwrap().rewards.get(&KSM), Some(&(100_000, 0)));
+		System::assert_has_event(RuntimeEvent::Farming(Event::RewardsExhausted {
+			pid,
+			currency_id: KSM,
+		}));
+		assert_eq!(Farming::get_remaining_funded_blocks(pid), Ok(vec![(KSM, Some(0))]));
+
+		assert_ok!(Farming::set_emission_schedule(
+			RuntimeOrigin::signed(ALICE),
+			pid,
+			KSM,
+			Some(EmissionSchedule::LinearDecline { initial: 100, decline: 100, floor: 0 })
+		));
+		assert_eq!(Farming::get_remaining_funded_blocks(pid), Ok(vec![(KSM, None)]));
+	});
+}
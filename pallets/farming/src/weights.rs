@@ -74,6 +74,7 @@ pub trait WeightInfo {
 	fn start_boost_round() -> Weight;
 	fn end_boost_round() -> Weight;
 	fn charge_boost() -> Weight;
+	fn set_emission_schedule() -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -380,4 +381,17 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
+	/// Storage: Farming PoolInfos (r:1 w:0)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming EmissionSchedules (r:0 w:1)
+	/// Proof Skipped: Farming EmissionSchedules (max_values: None, max_size: None, mode: Measured)
+	fn set_emission_schedule() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `417`
+		//  Estimated: `3882`
+		// Minimum execution time: 38_211_000 picoseconds.
+		Weight::from_parts(39_027_000, 3882)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
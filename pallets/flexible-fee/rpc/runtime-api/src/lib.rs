@@ -18,7 +18,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use bifrost_primitives::{Balance, CurrencyId};
+use bifrost_primitives::{Balance, CurrencyId, FeeBreakdown};
 use parity_scale_codec::Codec;
 use sp_api::decl_runtime_apis;
 
@@ -28,5 +28,8 @@ decl_runtime_apis! {
 	{
 		/// get flexible fee token and amount to be deducted
 		fn get_fee_token_and_amount(who: AccountId, fee: Balance, utx: Block::Extrinsic) -> (CurrencyId, Balance);
+
+		/// explain how the fee of an extrinsic is made up and which fee currency pays it
+		fn get_fee_breakdown(who: AccountId, fee: Balance, utx: Block::Extrinsic) -> Option<FeeBreakdown<Balance>>;
 	}
 }
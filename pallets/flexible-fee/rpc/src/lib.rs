@@ -19,7 +19,7 @@
 use std::{convert::TryInto, marker::PhantomData, sync::Arc};
 
 pub use bifrost_flexible_fee_rpc_runtime_api::FlexibleFeeRuntimeApi as FeeRuntimeApi;
-use bifrost_primitives::{Balance, CurrencyId};
+use bifrost_primitives::{Balance, CurrencyId, FeeBreakdown, FeeCurrencyCandidate};
 use jsonrpsee::{
 	core::{async_trait, RpcResult},
 	proc_macros::rpc,
@@ -55,6 +55,15 @@ pub trait FeeRpcApi<BlockHash, AccountId> {
 		encoded_xt: Bytes,
 		at: Option<BlockHash>,
 	) -> RpcResult<(CurrencyId, NumberOrHex)>;
+
+	/// Explains how the fee of an extrinsic is made up and which fee currency of `who` pays it.
+	#[method(name = "flexibleFee_getFeeBreakdown")]
+	fn get_fee_breakdown(
+		&self,
+		who: AccountId,
+		encoded_xt: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<FeeBreakdown<NumberOrHex>>>;
 }
 
 /// Error type of this RPC api.
@@ -74,29 +83,18 @@ impl From<Error> for i32 {
 	}
 }
 
-#[async_trait]
-impl<C, Block, AccountId> FeeRpcApiServer<<Block as BlockT>::Hash, AccountId>
-	for FlexibleFeeRpc<C, Block>
+impl<C, Block> FlexibleFeeRpc<C, Block>
 where
 	Block: BlockT,
-	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-	C::Api: FeeRuntimeApi<Block, AccountId> + TransactionPaymentRuntimeApi<Block, Balance>,
-	AccountId: Codec,
-	Balance: Codec + std::fmt::Display + std::ops::Add<Output = Balance> + sp_runtime::traits::Zero,
+	C: ProvideRuntimeApi<Block>,
+	C::Api: TransactionPaymentRuntimeApi<Block, Balance>,
 {
-	fn get_fee_token_and_amount(
+	/// Decodes the extrinsic and sums up its inclusion fee.
+	fn query_inclusion_fee(
 		&self,
-		who: AccountId,
+		at: <Block as BlockT>::Hash,
 		encoded_xt: Bytes,
-		at: Option<<Block as BlockT>::Hash>,
-	) -> RpcResult<(CurrencyId, NumberOrHex)> {
-		// Ok((
-		//     CurrencyId::Native(TokenSymbol::BNC),
-		//     sp_rpc::number::NumberOrHex::Number(1200),
-		// ))
-
-		let api = self.client.runtime_api();
-		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+	) -> RpcResult<(Block::Extrinsic, Balance)> {
 		let encoded_len = encoded_xt.len() as u32;
 
 		let uxt: Block::Extrinsic = Decode::decode(&mut &*encoded_xt).map_err(|e| {
@@ -107,13 +105,17 @@ where
 			)
 		})?;
 
-		let fee_details = api.query_fee_details(at, uxt.clone(), encoded_len).map_err(|e| {
-			ErrorObject::owned(
-				Error::RuntimeError.into(),
-				"Unable to query fee details.",
-				Some(format!("{:?}", e)),
-			)
-		})?;
+		let fee_details = self
+			.client
+			.runtime_api()
+			.query_fee_details(at, uxt.clone(), encoded_len)
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to query fee details.",
+					Some(format!("{:?}", e)),
+				)
+			})?;
 
 		let total_inclusion_fee: Balance = {
 			if let Some(inclusion_fee) = fee_details.inclusion_fee {
@@ -127,6 +129,60 @@ where
 			}
 		};
 
+		Ok((uxt, total_inclusion_fee))
+	}
+}
+
+fn candidate_to_hex(candidate: FeeCurrencyCandidate<Balance>) -> FeeCurrencyCandidate<NumberOrHex> {
+	FeeCurrencyCandidate {
+		currency_id: candidate.currency_id,
+		method: candidate.method,
+		amount: NumberOrHex::Hex(candidate.amount.into()),
+		route: candidate.route,
+		skip_reason: candidate.skip_reason,
+	}
+}
+
+fn breakdown_to_hex(breakdown: FeeBreakdown<Balance>) -> FeeBreakdown<NumberOrHex> {
+	FeeBreakdown {
+		base_fee: NumberOrHex::Hex(breakdown.base_fee.into()),
+		extra_fee_name: breakdown.extra_fee_name,
+		xcm_operation: breakdown.xcm_operation,
+		extra_fee_currency: breakdown.extra_fee_currency,
+		extra_fee_amount: NumberOrHex::Hex(breakdown.extra_fee_amount.into()),
+		extra_fee_bnc_amount: NumberOrHex::Hex(breakdown.extra_fee_bnc_amount.into()),
+		extra_fee_route: breakdown.extra_fee_route,
+		total_bnc_fee: NumberOrHex::Hex(breakdown.total_bnc_fee.into()),
+		fee_payment: breakdown.fee_payment.map(candidate_to_hex),
+		candidates: breakdown.candidates.into_iter().map(candidate_to_hex).collect(),
+	}
+}
+
+#[async_trait]
+impl<C, Block, AccountId> FeeRpcApiServer<<Block as BlockT>::Hash, AccountId>
+	for FlexibleFeeRpc<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: FeeRuntimeApi<Block, AccountId> + TransactionPaymentRuntimeApi<Block, Balance>,
+	AccountId: Codec,
+	Balance: Codec + std::fmt::Display + std::ops::Add<Output = Balance> + sp_runtime::traits::Zero,
+{
+	fn get_fee_token_and_amount(
+		&self,
+		who: AccountId,
+		encoded_xt: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<(CurrencyId, NumberOrHex)> {
+		// Ok((
+		//     CurrencyId::Native(TokenSymbol::BNC),
+		//     sp_rpc::number::NumberOrHex::Number(1200),
+		// ))
+
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let (uxt, total_inclusion_fee) = self.query_inclusion_fee(at, encoded_xt)?;
+
 		let rs = api.get_fee_token_and_amount(at, who, total_inclusion_fee, uxt);
 
 		let try_into_rpc_balance = |value: Balance| {
@@ -148,4 +204,24 @@ where
 			)),
 		}
 	}
+	fn get_fee_breakdown(
+		&self,
+		who: AccountId,
+		encoded_xt: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<FeeBreakdown<NumberOrHex>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let (uxt, total_inclusion_fee) = self.query_inclusion_fee(at, encoded_xt)?;
+
+		api.get_fee_breakdown(at, who, total_inclusion_fee, uxt)
+			.map(|maybe_breakdown| maybe_breakdown.map(breakdown_to_hex))
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to query fee breakdown.",
+					Some(format!("{:?}", e)),
+				)
+			})
+	}
 }
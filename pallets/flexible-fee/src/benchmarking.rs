@@ -18,14 +18,35 @@
 
 #![cfg(feature = "runtime-benchmarks")]
 
-use frame_benchmarking::v1::{benchmarks, whitelisted_caller};
-use frame_support::BoundedVec;
+use frame_benchmarking::v1::{account, benchmarks, whitelisted_caller};
+use frame_support::{traits::Currency, BoundedVec};
 
-use bifrost_primitives::{CurrencyId, TokenSymbol};
-use frame_system::RawOrigin;
-use sp_std::vec;
+use bifrost_primitives::{CurrencyId, TokenSymbol, XcmOperationType};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use sp_runtime::{
+	traits::{Get, Saturating},
+	Permill,
+};
+use sp_std::{vec, vec::Vec};
 
-use crate::{Call, Config, Pallet};
+use crate::{
+	Call, Config, ExtraFeeRule, Pallet, PalletBalanceOf, SponsorPolicy, Sponsors, TargetChain,
+};
+
+fn sponsor_policy<T: Config>() -> SponsorPolicy<PalletBalanceOf<T>, BlockNumberFor<T>> {
+	SponsorPolicy {
+		per_user_cap: 1_000_000u32.into(),
+		period_cap: 10_000_000u32.into(),
+		period: 100u32.into(),
+		whitelist_only: false,
+	}
+}
+
+fn fund_account<T: Config>(who: &T::AccountId) -> PalletBalanceOf<T> {
+	let amount = T::Currency::minimum_balance().saturating_mul(1_000u32.into());
+	T::Currency::make_free_balance_be(who, amount.saturating_mul(2u32.into()));
+	amount
+}
 
 benchmarks! {
 	set_user_default_fee_currency {
@@ -36,6 +57,61 @@ benchmarks! {
 		let default_list = BoundedVec::try_from(vec![CurrencyId::Token(TokenSymbol::DOT)]).unwrap();
 	}: _(RawOrigin::Root,default_list)
 
+	set_oracle_fee_currency {
+	}: _(RawOrigin::Root,CurrencyId::Token(TokenSymbol::DOT),Some(Permill::from_percent(1)))
+
+	set_sponsor_policy {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller),sponsor_policy::<T>())
+
+	set_sponsored_calls {
+		let n in 1 .. T::MaxSponsorListLen::get();
+		let caller: T::AccountId = whitelisted_caller();
+		Pallet::<T>::set_sponsor_policy(RawOrigin::Signed(caller.clone()).into(), sponsor_policy::<T>())?;
+		let calls = BoundedVec::try_from((0..n).map(|i| (0u8, Some(i as u8))).collect::<Vec<_>>()).unwrap();
+	}: _(RawOrigin::Signed(caller),calls,true)
+
+	set_sponsor_whitelist {
+		let n in 1 .. T::MaxSponsorListLen::get();
+		let caller: T::AccountId = whitelisted_caller();
+		Pallet::<T>::set_sponsor_policy(RawOrigin::Signed(caller.clone()).into(), sponsor_policy::<T>())?;
+		let accounts = BoundedVec::try_from((0..n).map(|i| account("user", i, 0)).collect::<Vec<_>>()).unwrap();
+	}: _(RawOrigin::Signed(caller),accounts,true)
+
+	fund_sponsor {
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = fund_account::<T>(&caller);
+		Pallet::<T>::set_sponsor_policy(RawOrigin::Signed(caller.clone()).into(), sponsor_policy::<T>())?;
+	}: _(RawOrigin::Signed(caller),amount)
+
+	withdraw_sponsor_budget {
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = fund_account::<T>(&caller);
+		Pallet::<T>::set_sponsor_policy(RawOrigin::Signed(caller.clone()).into(), sponsor_policy::<T>())?;
+		Pallet::<T>::fund_sponsor(RawOrigin::Signed(caller.clone()).into(), amount)?;
+	}: _(RawOrigin::Signed(caller),amount)
+
+	unregister_sponsor {
+		let n in 0 .. T::MaxSponsorListLen::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = fund_account::<T>(&caller);
+		Pallet::<T>::set_sponsor_policy(RawOrigin::Signed(caller.clone()).into(), sponsor_policy::<T>())?;
+		Pallet::<T>::fund_sponsor(RawOrigin::Signed(caller.clone()).into(), amount)?;
+		let accounts = BoundedVec::try_from((0..n).map(|i| account("user", i, 0)).collect::<Vec<_>>()).unwrap();
+		Pallet::<T>::set_sponsor_whitelist(RawOrigin::Signed(caller.clone()).into(), accounts, true)?;
+	}: _(RawOrigin::Signed(caller), n)
+	verify {
+		assert!(Sponsors::<T>::get(&whitelisted_caller::<T::AccountId>()).is_none());
+	}
+
+	set_extra_fee_rule {
+		let rule = ExtraFeeRule {
+			operation: XcmOperationType::StatemineTransfer,
+			currency_id: Some(CurrencyId::Token(TokenSymbol::DOT)),
+			receiver: TargetChain::AssetHub,
+		};
+	}: _(RawOrigin::Root,0u8,0u8,Some(rule))
+
 	impl_benchmark_test_suite!(
 	Pallet,
 	crate::mock::new_test_ext(),
@@ -22,12 +22,14 @@ pub use crate::pallet::*;
 use bifrost_primitives::{
 	currency::WETH,
 	traits::{FeeGetter, XcmDestWeightAndFeeHandler},
-	AccountFeeCurrency, CurrencyId, ExtraFeeName, TryConvertFrom, XcmOperationType, BNC,
+	AccountFeeCurrency, CurrencyId, FeeBreakdown, FeeCurrencyCandidate, FeeCurrencySkipReason,
+	FeePaymentMethod, TryConvertFrom, XcmOperationType, BNC,
 };
 use bifrost_xcm_interface::{polkadot::RelaychainCall, traits::parachains, PolkadotXcmCall};
 use core::convert::Into;
 use cumulus_primitives_core::ParaId;
 use frame_support::{
+	dispatch::{DispatchInfo, PostDispatchInfo},
 	pallet_prelude::*,
 	traits::{
 		Currency, ExistenceRequirement, Get, Imbalance, OnUnbalanced, ReservableCurrency,
@@ -39,13 +41,21 @@ use frame_support::{
 };
 use frame_system::pallet_prelude::*;
 use orml_traits::MultiCurrency;
-use pallet_transaction_payment::OnChargeTransaction;
+use pallet_traits::{evm::OnEvmAddressBound, PriceFeeder};
+use pallet_transaction_payment::{ChargeTransactionPayment, OnChargeTransaction};
 use polkadot_parachain_primitives::primitives::Sibling;
-use sp_arithmetic::traits::{CheckedAdd, SaturatedConversion, UniqueSaturatedInto};
+use sp_arithmetic::{
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{CheckedAdd, SaturatedConversion, UniqueSaturatedInto},
+	Rounding,
+};
 use sp_runtime::{
-	traits::{AccountIdConversion, DispatchInfoOf, PostDispatchInfoOf, Saturating, Zero},
+	traits::{
+		AccountIdConversion, DispatchInfoOf, Dispatchable, PostDispatchInfoOf, Saturating,
+		SignedExtension, Zero,
+	},
 	transaction_validity::TransactionValidityError,
-	BoundedVec,
+	BoundedVec, Permill,
 };
 use sp_std::{boxed::Box, vec, vec::Vec};
 pub use weights::WeightInfo;
@@ -69,6 +79,7 @@ pub type NegativeImbalanceOf<T> =
 pub type PositiveImbalanceOf<T> =
 	<<T as Config>::Currency as Currency<AccountIdOf<T>>>::PositiveImbalance;
 pub type CallOf<T> = <T as frame_system::Config>::RuntimeCall;
+pub type TransactionBalanceOf<T> = pallet_transaction_payment::BalanceOf<T>;
 
 #[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum TargetChain {
@@ -76,6 +87,59 @@ pub enum TargetChain {
 	RelayChain,
 }
 
+/// The extra fee charged on top of the transaction fee of a call to pay for the XCM message it
+/// sends.
+#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ExtraFeeRule<CurrencyId> {
+	/// The XCM operation whose fee is charged
+	pub operation: XcmOperationType,
+	/// The currency the fee is paid in, `None` for the currency the call operates on
+	pub currency_id: Option<CurrencyId>,
+	/// The chain the fee is forwarded to
+	pub receiver: TargetChain,
+}
+
+/// How the fee withdrawn for a transaction was paid, so that it is refunded in kind.
+pub enum PaymentInfo<T: Config> {
+	/// The fee was withdrawn in BNC.
+	Native(NegativeImbalanceOf<T>),
+	/// `amount` of `currency_id` was paid to the treasury for a fee of `fee` BNC, and the tip was
+	/// withdrawn in BNC.
+	Oracle {
+		currency_id: CurrencyIdOf<T>,
+		amount: PalletBalanceOf<T>,
+		fee: PalletBalanceOf<T>,
+		tip: NegativeImbalanceOf<T>,
+	},
+	/// The fee without the tip was withdrawn in BNC from the budget of `sponsor`, and the tip
+	/// was withdrawn in BNC.
+	Sponsored { sponsor: AccountIdOf<T>, paid: NegativeImbalanceOf<T>, tip: NegativeImbalanceOf<T> },
+}
+
+/// The limits on the fees a sponsor pays for the calls it sponsors.
+#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SponsorPolicy<Balance, BlockNumber> {
+	/// The most fees paid for a single account in a period
+	pub per_user_cap: Balance,
+	/// The most fees paid for all accounts in a period
+	pub period_cap: Balance,
+	/// The length of a period in blocks
+	pub period: BlockNumber,
+	/// Whether only the whitelisted accounts are sponsored
+	pub whitelist_only: bool,
+}
+
+#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SponsorInfo<Balance, BlockNumber> {
+	pub policy: SponsorPolicy<Balance, BlockNumber>,
+	/// The BNC reserved from the sponsor to pay fees with
+	pub budget: Balance,
+	/// The block the current period started at
+	pub period_start: BlockNumber,
+	/// The fees paid in the current period
+	pub period_spent: Balance,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -100,8 +164,8 @@ pub mod pallet {
 		type XcmRouter: SendXcm;
 
 		type DexOperator: ExportZenlink<Self::AccountId, AssetId>;
-		/// Filter if this transaction needs to be deducted extra fee besides basic transaction fee,
-		/// and get the name of the fee
+		/// Get the currency a call operates on, which pays its extra fee unless its extra fee rule
+		/// names one
 		type ExtraFeeMatcher: FeeGetter<CallOf<Self>>;
 
 		#[pallet::constant]
@@ -129,6 +193,13 @@ pub mod pallet {
 
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
+
+		/// Prices the fee currencies that are charged without a DEX swap against BNC
+		type PriceFeeder: PriceFeeder;
+
+		/// The most calls or accounts a sponsor can add to or remove from its policy at once
+		#[pallet::constant]
+		type MaxSponsorListLen: Get<u32>;
 	}
 
 	#[pallet::hooks]
@@ -169,18 +240,66 @@ pub mod pallet {
 		FixedRateFeeExchanged(CurrencyIdOf<T>, PalletBalanceOf<T>),
 		// [extra_fee_name, currency_id, amount_in, BNC_amount_out]
 		ExtraFeeDeducted {
-			operation: ExtraFeeName,
+			operation: XcmOperationType,
 			transaction_extra_fee_currency: CurrencyIdOf<T>,
 			transaction_extra_fee_amount: PalletBalanceOf<T>,
 			transaction_extra_fee_bnc_amount: PalletBalanceOf<T>,
 			transaction_extra_fee_receiver: T::AccountId,
 		},
+		OracleFeeCurrencySet {
+			currency_id: CurrencyIdOf<T>,
+			premium: Option<Permill>,
+		},
+		/// `amount` of `currency_id` was charged at its oracle price for a fee of `fee` BNC.
+		OraclePricedFeeCharged {
+			who: T::AccountId,
+			currency_id: CurrencyIdOf<T>,
+			amount: PalletBalanceOf<T>,
+			fee: PalletBalanceOf<T>,
+		},
+		SponsorPolicySet {
+			sponsor: T::AccountId,
+			policy: SponsorPolicy<PalletBalanceOf<T>, BlockNumberFor<T>>,
+		},
+		SponsoredCallsSet {
+			sponsor: T::AccountId,
+			calls: Vec<(u8, Option<u8>)>,
+			sponsored: bool,
+		},
+		SponsorWhitelistSet {
+			sponsor: T::AccountId,
+			accounts: Vec<T::AccountId>,
+			whitelisted: bool,
+		},
+		SponsorFunded {
+			sponsor: T::AccountId,
+			amount: PalletBalanceOf<T>,
+		},
+		SponsorBudgetWithdrawn {
+			sponsor: T::AccountId,
+			amount: PalletBalanceOf<T>,
+		},
+		SponsorUnregistered {
+			sponsor: T::AccountId,
+			budget: PalletBalanceOf<T>,
+		},
+		/// `sponsor` paid the fee of `fee` BNC of a transaction of `who`.
+		SponsoredFeeCharged {
+			sponsor: T::AccountId,
+			who: T::AccountId,
+			fee: PalletBalanceOf<T>,
+		},
+		ExtraFeeRuleSet {
+			pallet_index: u8,
+			call_index: u8,
+			rule: Option<ExtraFeeRule<CurrencyIdOf<T>>>,
+		},
 	}
 
-	/// The current storage version, we set to 2 our new version(after migrate stroage from vec t
-	/// boundedVec).
+	/// The current storage version, we set to 3 our new version(after the hard-coded extra fees
+	/// moved to `ExtraFeeRules`).
 	#[allow(unused)]
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
 	/// Universal fee currency order list for all users
 	#[pallet::storage]
@@ -195,6 +314,74 @@ pub mod pallet {
 	pub type UserDefaultFeeCurrency<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, CurrencyIdOf<T>, OptionQuery>;
 
+	/// Fee currencies that are charged directly when set as the user default fee currency,
+	/// converted from BNC at their oracle price, with the premium added on top
+	#[pallet::storage]
+	#[pallet::getter(fn get_oracle_fee_currency)]
+	pub type OracleFeeCurrencies<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Permill, OptionQuery>;
+
+	/// Accounts that pay the fees of other accounts from a reserved budget
+	#[pallet::storage]
+	#[pallet::getter(fn get_sponsor)]
+	pub type Sponsors<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		SponsorInfo<PalletBalanceOf<T>, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// The calls a sponsor pays for, keyed by pallet index and call index, where no call index
+	/// stands for every call of the pallet
+	#[pallet::storage]
+	pub type SponsoredCalls<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		(u8, Option<u8>),
+		(),
+		OptionQuery,
+	>;
+
+	/// The accounts a sponsor pays for when its policy is whitelist only
+	#[pallet::storage]
+	pub type SponsorWhitelist<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// The fees a sponsor paid for an account, with the start of the period they were paid in
+	#[pallet::storage]
+	pub type SponsoredUsage<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		(BlockNumberFor<T>, PalletBalanceOf<T>),
+		OptionQuery,
+	>;
+
+	/// The extra fee charged for calls, by pallet index and call index
+	#[pallet::storage]
+	#[pallet::getter(fn get_extra_fee_rule_of)]
+	pub type ExtraFeeRules<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u8,
+		Twox64Concat,
+		u8,
+		ExtraFeeRule<CurrencyIdOf<T>>,
+		OptionQuery,
+	>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -210,6 +397,13 @@ pub mod pallet {
 		DexFailedToGetAmountInByPath,
 		UnweighableMessage,
 		XcmExecutionFailed,
+		InvalidOracleFeeCurrency,
+		SponsorNotFound,
+		InvalidSponsorPolicy,
+		NotSponsored,
+		SponsorCapExceeded,
+		SponsorBudgetExhausted,
+		InvalidExtraFeeRule,
 	}
 
 	#[pallet::call]
@@ -247,13 +441,237 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Charge fees paid in `currency_id` at its oracle price plus `maybe_premium`, or stop
+		/// doing so if `None`
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_oracle_fee_currency())]
+		pub fn set_oracle_fee_currency(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			maybe_premium: Option<Permill>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			ensure!(currency_id != BNC, Error::<T>::InvalidOracleFeeCurrency);
+
+			if let Some(premium) = maybe_premium {
+				OracleFeeCurrencies::<T>::insert(currency_id, premium);
+			} else {
+				OracleFeeCurrencies::<T>::remove(currency_id);
+			}
+
+			Self::deposit_event(Event::OracleFeeCurrencySet {
+				currency_id,
+				premium: maybe_premium,
+			});
+
+			Ok(())
+		}
+
+		/// Register as a sponsor with `policy`, or change the policy of a sponsor
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_sponsor_policy())]
+		pub fn set_sponsor_policy(
+			origin: OriginFor<T>,
+			policy: SponsorPolicy<PalletBalanceOf<T>, BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+
+			ensure!(!policy.period.is_zero(), Error::<T>::InvalidSponsorPolicy);
+
+			Sponsors::<T>::mutate(&sponsor, |maybe_info| match maybe_info {
+				Some(info) => info.policy = policy,
+				None =>
+					*maybe_info = Some(SponsorInfo {
+						policy,
+						budget: Zero::zero(),
+						period_start: frame_system::Pallet::<T>::block_number(),
+						period_spent: Zero::zero(),
+					}),
+			});
+
+			Self::deposit_event(Event::SponsorPolicySet { sponsor, policy });
+
+			Ok(())
+		}
+
+		/// Start or stop paying for `calls`, given as pallet index and call index, where no
+		/// call index stands for every call of the pallet
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_sponsored_calls(calls.len() as u32))]
+		pub fn set_sponsored_calls(
+			origin: OriginFor<T>,
+			calls: BoundedVec<(u8, Option<u8>), T::MaxSponsorListLen>,
+			sponsored: bool,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+
+			ensure!(Sponsors::<T>::contains_key(&sponsor), Error::<T>::SponsorNotFound);
+
+			for call in calls.iter() {
+				if sponsored {
+					SponsoredCalls::<T>::insert(&sponsor, call, ());
+				} else {
+					SponsoredCalls::<T>::remove(&sponsor, call);
+				}
+			}
+
+			Self::deposit_event(Event::SponsoredCallsSet {
+				sponsor,
+				calls: calls.into_inner(),
+				sponsored,
+			});
+
+			Ok(())
+		}
+
+		/// Add `accounts` to or remove them from the whitelist of the sponsor
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_sponsor_whitelist(accounts.len() as u32))]
+		pub fn set_sponsor_whitelist(
+			origin: OriginFor<T>,
+			accounts: BoundedVec<T::AccountId, T::MaxSponsorListLen>,
+			whitelisted: bool,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+
+			ensure!(Sponsors::<T>::contains_key(&sponsor), Error::<T>::SponsorNotFound);
+
+			for account in accounts.iter() {
+				if whitelisted {
+					SponsorWhitelist::<T>::insert(&sponsor, account, ());
+				} else {
+					SponsorWhitelist::<T>::remove(&sponsor, account);
+				}
+			}
+
+			Self::deposit_event(Event::SponsorWhitelistSet {
+				sponsor,
+				accounts: accounts.into_inner(),
+				whitelisted,
+			});
+
+			Ok(())
+		}
+
+		/// Reserve `amount` BNC to pay the fees of the sponsored accounts with
+		#[pallet::call_index(6)]
+		#[pallet::weight(<T as Config>::WeightInfo::fund_sponsor())]
+		pub fn fund_sponsor(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: PalletBalanceOf<T>,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+
+			Sponsors::<T>::try_mutate(&sponsor, |maybe_info| -> DispatchResult {
+				let info = maybe_info.as_mut().ok_or(Error::<T>::SponsorNotFound)?;
+				T::Currency::reserve(&sponsor, amount)?;
+				info.budget = info.budget.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::SponsorFunded { sponsor, amount });
+
+			Ok(())
+		}
+
+		/// Unreserve `amount` BNC of the budget of the sponsor
+		#[pallet::call_index(7)]
+		#[pallet::weight(<T as Config>::WeightInfo::withdraw_sponsor_budget())]
+		pub fn withdraw_sponsor_budget(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: PalletBalanceOf<T>,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+
+			Sponsors::<T>::try_mutate(&sponsor, |maybe_info| -> DispatchResult {
+				let info = maybe_info.as_mut().ok_or(Error::<T>::SponsorNotFound)?;
+				ensure!(info.budget >= amount, Error::<T>::NotEnoughBalance);
+				T::Currency::unreserve(&sponsor, amount);
+				info.budget = info.budget.saturating_sub(amount);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::SponsorBudgetWithdrawn { sponsor, amount });
+
+			Ok(())
+		}
+
+		/// Stop sponsoring and unreserve the whole budget, once the sponsored calls, the
+		/// whitelist and the usage of the sponsor are removed. At most `limit` of them are
+		/// removed per call, and the sponsor stays registered until all of them are.
+		#[pallet::call_index(8)]
+		#[pallet::weight(<T as Config>::WeightInfo::unregister_sponsor(*limit))]
+		pub fn unregister_sponsor(origin: OriginFor<T>, limit: u32) -> DispatchResultWithPostInfo {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(Sponsors::<T>::contains_key(&sponsor), Error::<T>::SponsorNotFound);
+
+			let mut result = SponsoredCalls::<T>::clear_prefix(&sponsor, limit, None);
+			let mut removed = result.unique;
+			if result.maybe_cursor.is_none() {
+				result = SponsorWhitelist::<T>::clear_prefix(
+					&sponsor,
+					limit.saturating_sub(removed),
+					None,
+				);
+				removed = removed.saturating_add(result.unique);
+			}
+			if result.maybe_cursor.is_none() {
+				result = SponsoredUsage::<T>::clear_prefix(
+					&sponsor,
+					limit.saturating_sub(removed),
+					None,
+				);
+				removed = removed.saturating_add(result.unique);
+			}
+			let actual_weight = <T as Config>::WeightInfo::unregister_sponsor(removed.min(limit));
+			if result.maybe_cursor.is_some() {
+				return Ok(Some(actual_weight).into());
+			}
+
+			let info = Sponsors::<T>::take(&sponsor).ok_or(Error::<T>::SponsorNotFound)?;
+			T::Currency::unreserve(&sponsor, info.budget);
+
+			Self::deposit_event(Event::SponsorUnregistered { sponsor, budget: info.budget });
+
+			Ok(Some(actual_weight).into())
+		}
+
+		/// Charge an extra fee following `maybe_rule` for the call `call_index` of the pallet
+		/// `pallet_index`, or stop doing so if `None`
+		#[pallet::call_index(9)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_extra_fee_rule())]
+		pub fn set_extra_fee_rule(
+			origin: OriginFor<T>,
+			pallet_index: u8,
+			call_index: u8,
+			maybe_rule: Option<ExtraFeeRule<CurrencyIdOf<T>>>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			if let Some(rule) = maybe_rule {
+				ensure!(rule.currency_id != Some(BNC), Error::<T>::InvalidExtraFeeRule);
+				ExtraFeeRules::<T>::insert(pallet_index, call_index, rule);
+			} else {
+				ExtraFeeRules::<T>::remove(pallet_index, call_index);
+			}
+
+			Self::deposit_event(Event::ExtraFeeRuleSet {
+				pallet_index,
+				call_index,
+				rule: maybe_rule,
+			});
+
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
 	#[transactional]
 	fn handle_fee() -> DispatchResult {
-		let fee_receiver = Self::get_fee_receiver(ExtraFeeName::StatemineTransfer);
+		let fee_receiver = Self::get_fee_receiver(TargetChain::AssetHub);
 		let fee_receiver_balance =
 			T::MultiCurrency::free_balance(T::RelaychainCurrencyId::get(), &fee_receiver);
 		if fee_receiver_balance >= T::MinAssetHubExecutionFee::get() {
@@ -330,7 +748,7 @@ impl<T: Config> Pallet<T> {
 			});
 		}
 
-		let fee_receiver = Self::get_fee_receiver(ExtraFeeName::VoteVtoken);
+		let fee_receiver = Self::get_fee_receiver(TargetChain::RelayChain);
 		let fee_receiver_balance =
 			T::MultiCurrency::free_balance(T::RelaychainCurrencyId::get(), &fee_receiver);
 		if fee_receiver_balance >= T::MinRelaychainExecutionFee::get() {
@@ -350,17 +768,33 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	fn get_fee_receiver(extra_fee_name: ExtraFeeName) -> T::AccountId {
-		match extra_fee_name {
-			ExtraFeeName::SalpContribute |
-			ExtraFeeName::VoteVtoken |
-			ExtraFeeName::VoteRemoveDelegatorVote => T::PalletId::get().into_sub_account_truncating(0u64),
-			ExtraFeeName::StatemineTransfer | ExtraFeeName::EthereumTransfer =>
-				T::PalletId::get().into_sub_account_truncating(1u64),
-			ExtraFeeName::NoExtraFee => T::TreasuryAccount::get(),
+	fn get_fee_receiver(target_chain: TargetChain) -> T::AccountId {
+		match target_chain {
+			TargetChain::RelayChain => T::PalletId::get().into_sub_account_truncating(0u64),
+			TargetChain::AssetHub => T::PalletId::get().into_sub_account_truncating(1u64),
 		}
 	}
 
+	/// Returns the pallet index and call index of `call`.
+	fn get_call_index(call: &CallOf<T>) -> Option<(u8, u8)> {
+		call.using_encoded(|mut encoded| <(u8, u8)>::decode(&mut encoded)).ok()
+	}
+
+	/// Returns the extra fee rule of `call` and the currency its extra fee is paid in, if it is
+	/// charged an extra fee.
+	pub fn get_extra_fee_rule(
+		call: &CallOf<T>,
+	) -> Option<(ExtraFeeRule<CurrencyIdOf<T>>, CurrencyIdOf<T>)> {
+		let (pallet_index, call_index) = Self::get_call_index(call)?;
+		let rule = ExtraFeeRules::<T>::get(pallet_index, call_index)?;
+		let currency_id = rule
+			.currency_id
+			.or_else(|| T::ExtraFeeMatcher::get_fee_currency(call))
+			.unwrap_or_else(T::RelaychainCurrencyId::get);
+
+		Some((rule, currency_id))
+	}
+
 	/// Get user fee charge assets order
 	fn inner_get_user_fee_charge_order_list(account_id: &T::AccountId) -> Vec<CurrencyIdOf<T>> {
 		let mut order_list: Vec<CurrencyIdOf<T>> = Vec::new();
@@ -427,6 +861,360 @@ impl<T: Config> Pallet<T> {
 		Ok(None)
 	}
 
+	/// Quotes paying `fee` BNC in `currency_id` by swapping it on the DEX, or in BNC itself.
+	fn quote_swap_fee(
+		who: &T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		fee: PalletBalanceOf<T>,
+	) -> FeeCurrencyCandidate<PalletBalanceOf<T>> {
+		let (method, route, maybe_amount) = if currency_id == BNC {
+			(FeePaymentMethod::Native, Vec::new(), Some(fee))
+		} else {
+			let maybe_amount = Self::get_currency_asset_id(currency_id)
+				.and_then(|asset_id| {
+					let path = vec![asset_id, Self::get_currency_asset_id(BNC)?];
+					T::DexOperator::get_amount_in_by_path(fee.saturated_into(), &path)
+						.map_err(|_| Error::<T>::DexFailedToGetAmountInByPath)
+				})
+				.ok()
+				.and_then(|amounts| amounts.first().copied())
+				.map(PalletBalanceOf::<T>::saturated_from);
+			(FeePaymentMethod::Swap, vec![currency_id, BNC], maybe_amount)
+		};
+
+		Self::fee_currency_candidate(
+			who,
+			currency_id,
+			method,
+			route,
+			maybe_amount.ok_or(FeeCurrencySkipReason::NoSwapPath),
+		)
+	}
+
+	/// Quotes paying `fee` BNC in `currency_id` at its oracle price plus `premium`.
+	fn quote_oracle_fee(
+		who: &T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		premium: Permill,
+		fee: PalletBalanceOf<T>,
+	) -> FeeCurrencyCandidate<PalletBalanceOf<T>> {
+		Self::fee_currency_candidate(
+			who,
+			currency_id,
+			FeePaymentMethod::Oracle,
+			Vec::new(),
+			Self::get_oracle_fee_amount(currency_id, premium, fee)
+				.ok_or(FeeCurrencySkipReason::NoPrice),
+		)
+	}
+
+	fn fee_currency_candidate(
+		who: &T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		method: FeePaymentMethod,
+		route: Vec<CurrencyIdOf<T>>,
+		quote: Result<PalletBalanceOf<T>, FeeCurrencySkipReason>,
+	) -> FeeCurrencyCandidate<PalletBalanceOf<T>> {
+		let (amount, skip_reason) = match quote {
+			Ok(amount)
+				if T::MultiCurrency::ensure_can_withdraw(currency_id, who, amount).is_ok() =>
+				(amount, None),
+			Ok(amount) => (amount, Some(FeeCurrencySkipReason::InsufficientBalance)),
+			Err(reason) => (Zero::zero(), Some(reason)),
+		};
+
+		FeeCurrencyCandidate { currency_id, method, amount, route, skip_reason }
+	}
+
+	/// Returns the user default fee currency of `who` and its premium, if the currency is
+	/// charged at its oracle price. The extrinsic fee is then paid in it directly, whether or not
+	/// the call has an extra fee, which is always paid in BNC.
+	fn get_user_oracle_fee_currency(who: &T::AccountId) -> Option<(CurrencyIdOf<T>, Permill)> {
+		let currency_id = UserDefaultFeeCurrency::<T>::get(who)?;
+		OracleFeeCurrencies::<T>::get(currency_id).map(|premium| (currency_id, premium))
+	}
+
+	/// Returns the user default fee currency and the amount of it that pays `fee` BNC, if the
+	/// currency is charged at its oracle price and the user can afford it.
+	fn find_out_oracle_fee_amount(
+		who: &T::AccountId,
+		fee: PalletBalanceOf<T>,
+	) -> Option<(CurrencyIdOf<T>, PalletBalanceOf<T>)> {
+		let (currency_id, premium) = Self::get_user_oracle_fee_currency(who)?;
+		let amount = Self::get_oracle_fee_amount(currency_id, premium, fee)?;
+		T::MultiCurrency::ensure_can_withdraw(currency_id, who, amount).ok()?;
+
+		Some((currency_id, amount))
+	}
+
+	/// Converts `fee` BNC into `currency_id` at their oracle prices, rounding up, and adds the
+	/// premium. Returns `None` unless both prices are valid.
+	pub fn get_oracle_fee_amount(
+		currency_id: CurrencyIdOf<T>,
+		premium: Permill,
+		fee: PalletBalanceOf<T>,
+	) -> Option<PalletBalanceOf<T>> {
+		if !T::PriceFeeder::is_price_valid(&BNC) || !T::PriceFeeder::is_price_valid(&currency_id) {
+			return None;
+		}
+		let (bnc_price, _) = T::PriceFeeder::get_price(&BNC)?;
+		let (price, _) = T::PriceFeeder::get_price(&currency_id)?;
+		let amount = multiply_by_rational_with_rounding(
+			fee.saturated_into(),
+			bnc_price.into_inner(),
+			price.into_inner(),
+			Rounding::Up,
+		)?;
+		let amount = amount.checked_add(premium.mul_ceil(amount))?;
+
+		Some(amount.saturated_into())
+	}
+
+	/// Withdraws the predicted fee of a transaction of `who`, the extrinsic fee from `sponsor`
+	/// if one is named.
+	///
+	/// Note: The `fee` already includes the `tip`.
+	fn do_withdraw_fee(
+		who: &T::AccountId,
+		call: &T::RuntimeCall,
+		fee: PalletBalanceOf<T>,
+		tip: PalletBalanceOf<T>,
+		sponsor: Option<T::AccountId>,
+	) -> Result<Option<PaymentInfo<T>>, TransactionValidityError> {
+		if fee.is_zero() {
+			return Ok(None);
+		}
+
+		let withdraw_reason = if tip.is_zero() {
+			WithdrawReasons::TRANSACTION_PAYMENT
+		} else {
+			WithdrawReasons::TRANSACTION_PAYMENT | WithdrawReasons::TIP
+		};
+
+		// See if the this RuntimeCall needs to pay extra fee
+		let extra_fee_rule = Self::get_extra_fee_rule(call);
+		let (total_fee, extra_bnc_fee, fee_value, path) =
+			Self::get_extrinsic_and_extra_fee_total(call, fee)
+				.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Custom(55)))?;
+
+		// The extrinsic fee without the tip of a sponsored transaction is paid by the sponsor, the
+		// user only pays the tip and the extra fee.
+		let sponsored = match sponsor {
+			Some(sponsor) => {
+				let paid = Self::charge_sponsor(
+					&sponsor,
+					who,
+					call,
+					fee.saturating_sub(tip),
+					WithdrawReasons::TRANSACTION_PAYMENT,
+				)
+				.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+				Some((sponsor, paid))
+			},
+			None => None,
+		};
+
+		// The extrinsic fee without the tip is charged directly if the user default fee currency
+		// is priced by the oracle, then only the tip and the extra fee need BNC.
+		let oracle_fee = if sponsored.is_none() {
+			Self::find_out_oracle_fee_amount(who, fee.saturating_sub(tip))
+		} else {
+			None
+		};
+		let bnc_fee = if sponsored.is_some() || oracle_fee.is_some() {
+			extra_bnc_fee.saturating_add(tip)
+		} else {
+			total_fee
+		};
+
+		// Make sure there are enough BNC(extrinsic fee + extra fee) to be deducted if the user has
+		// assets in other form of tokens rather than BNC.
+		if !bnc_fee.is_zero() {
+			Self::ensure_can_charge_fee(who, bnc_fee, withdraw_reason)
+				.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+		}
+
+		if let Some((rule, currency_id)) = extra_fee_rule {
+			// swap BNC for fee_currency
+			T::DexOperator::inner_swap_assets_for_exact_assets(
+				who,
+				fee_value.saturated_into(),
+				extra_bnc_fee.saturated_into(),
+				&path,
+				who,
+			)
+			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Custom(44)))?;
+
+			let transaction_extra_fee_receiver = Self::get_fee_receiver(rule.receiver);
+
+			T::MultiCurrency::transfer(
+				currency_id,
+				who,
+				&transaction_extra_fee_receiver,
+				fee_value,
+			)
+			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+
+			// deposit extra fee deducted event
+			Self::deposit_event(Event::ExtraFeeDeducted {
+				operation: rule.operation,
+				transaction_extra_fee_currency: currency_id,
+				transaction_extra_fee_amount: fee_value,
+				transaction_extra_fee_bnc_amount: extra_bnc_fee,
+				transaction_extra_fee_receiver,
+			});
+		}
+
+		if let Some((sponsor, paid)) = sponsored {
+			let tip = T::Currency::withdraw(
+				who,
+				tip,
+				WithdrawReasons::TIP,
+				ExistenceRequirement::AllowDeath,
+			)
+			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+
+			Self::deposit_event(Event::SponsoredFeeCharged {
+				sponsor: sponsor.clone(),
+				who: who.clone(),
+				fee: paid.peek(),
+			});
+
+			return Ok(Some(PaymentInfo::Sponsored { sponsor, paid, tip }));
+		}
+
+		if let Some((currency_id, amount)) = oracle_fee {
+			let fee = fee.saturating_sub(tip);
+			T::MultiCurrency::transfer(currency_id, who, &T::TreasuryAccount::get(), amount)
+				.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+			let tip = T::Currency::withdraw(
+				who,
+				tip,
+				WithdrawReasons::TIP,
+				ExistenceRequirement::AllowDeath,
+			)
+			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+
+			Self::deposit_event(Event::OraclePricedFeeCharged {
+				who: who.clone(),
+				currency_id,
+				amount,
+				fee,
+			});
+
+			return Ok(Some(PaymentInfo::Oracle { currency_id, amount, fee, tip }));
+		}
+
+		// withdraw normal extrinsic fee
+		match T::Currency::withdraw(who, fee, withdraw_reason, ExistenceRequirement::AllowDeath) {
+			Ok(imbalance) => Ok(Some(PaymentInfo::Native(imbalance))),
+			Err(_) => Err(InvalidTransaction::Payment.into()),
+		}
+	}
+
+	/// Withdraws `fee` from the budget of `sponsor` for `call` of `who`, if the policy of the
+	/// sponsor covers them.
+	#[transactional]
+	fn charge_sponsor(
+		sponsor: &T::AccountId,
+		who: &T::AccountId,
+		call: &CallOf<T>,
+		fee: PalletBalanceOf<T>,
+		reason: WithdrawReasons,
+	) -> Result<NegativeImbalanceOf<T>, DispatchError> {
+		let mut info = Sponsors::<T>::get(sponsor).ok_or(Error::<T>::SponsorNotFound)?;
+		ensure!(
+			!info.policy.whitelist_only || SponsorWhitelist::<T>::contains_key(sponsor, who),
+			Error::<T>::NotSponsored
+		);
+		let (pallet_index, call_index) =
+			Self::get_call_index(call).ok_or(Error::<T>::NotSponsored)?;
+		ensure!(
+			SponsoredCalls::<T>::contains_key(sponsor, (pallet_index, None::<u8>)) ||
+				SponsoredCalls::<T>::contains_key(sponsor, (pallet_index, Some(call_index))),
+			Error::<T>::NotSponsored
+		);
+
+		// Start a new period if the current one is over
+		let now = frame_system::Pallet::<T>::block_number();
+		let elapsed = now.saturating_sub(info.period_start);
+		if elapsed >= info.policy.period {
+			info.period_start = now.saturating_sub(elapsed % info.policy.period);
+			info.period_spent = Zero::zero();
+		}
+
+		let user_spent = SponsoredUsage::<T>::get(sponsor, who)
+			.filter(|(period_start, _)| *period_start == info.period_start)
+			.map_or_else(Zero::zero, |(_, spent)| spent)
+			.checked_add(&fee)
+			.ok_or(Error::<T>::Overflow)?;
+		info.period_spent = info.period_spent.checked_add(&fee).ok_or(Error::<T>::Overflow)?;
+		ensure!(
+			user_spent <= info.policy.per_user_cap && info.period_spent <= info.policy.period_cap,
+			Error::<T>::SponsorCapExceeded
+		);
+		ensure!(info.budget >= fee, Error::<T>::SponsorBudgetExhausted);
+
+		T::Currency::unreserve(sponsor, fee);
+		let paid = T::Currency::withdraw(sponsor, fee, reason, ExistenceRequirement::AllowDeath)?;
+		info.budget = info.budget.saturating_sub(fee);
+		SponsoredUsage::<T>::insert(sponsor, who, (info.period_start, user_spent));
+		Sponsors::<T>::insert(sponsor, info);
+
+		Ok(paid)
+	}
+
+	/// Puts `refund` back into the budget of `sponsor` and takes it off the fees paid for `who`.
+	fn restore_sponsor_budget(
+		sponsor: &T::AccountId,
+		who: &T::AccountId,
+		refund: PalletBalanceOf<T>,
+	) {
+		if refund.is_zero() || T::Currency::reserve(sponsor, refund).is_err() {
+			return;
+		}
+		Sponsors::<T>::mutate(sponsor, |maybe_info| {
+			if let Some(info) = maybe_info {
+				info.budget = info.budget.saturating_add(refund);
+				info.period_spent = info.period_spent.saturating_sub(refund);
+			}
+		});
+		SponsoredUsage::<T>::mutate(sponsor, who, |maybe_usage| {
+			if let Some((_, spent)) = maybe_usage {
+				*spent = spent.saturating_sub(refund);
+			}
+		});
+	}
+
+	/// Refunds `payer` what `paid` exceeds `corrected_fee` by, and hands the rest over to
+	/// `OnUnbalanced`. Returns the amount refunded.
+	fn refund_and_deposit_fee(
+		payer: &T::AccountId,
+		paid: NegativeImbalanceOf<T>,
+		corrected_fee: PalletBalanceOf<T>,
+		tip: PalletBalanceOf<T>,
+	) -> Result<PalletBalanceOf<T>, TransactionValidityError> {
+		// Calculate how much refund we should return
+		let refund_amount = paid.peek().saturating_sub(corrected_fee);
+
+		// refund to the the account that paid the fees. If this fails, the
+		// account might have dropped below the existential balance. In
+		// that case we don't refund anything.
+		let refund_imbalance = T::Currency::deposit_into_existing(payer, refund_amount)
+			.unwrap_or_else(|_| PositiveImbalanceOf::<T>::zero());
+		let refunded = refund_imbalance.peek();
+		// merge the imbalance caused by paying the fees and refunding parts of it again.
+		let adjusted_paid = paid
+			.offset(refund_imbalance)
+			.same()
+			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+		// RuntimeCall someone else to handle the imbalance (fee and tip separately)
+		let imbalances = adjusted_paid.split(tip);
+		T::OnUnbalanced::on_unbalanceds(Some(imbalances.0).into_iter().chain(Some(imbalances.1)));
+
+		Ok(refunded)
+	}
+
 	/// Make sure there are enough BNC to be deducted if the user has assets in other form of tokens
 	/// rather than BNC.
 	fn ensure_can_charge_fee(
@@ -461,13 +1249,67 @@ impl<T: Config> Pallet<T> {
 		Ok(fee)
 	}
 
+	/// Explains how the fee of `call` is charged to `who`: the extra fee on top of `fee`, and
+	/// what every currency of the fee charge order list of `who` would pay. This function is for
+	/// runtime-api to call.
+	pub fn get_fee_breakdown(
+		who: &T::AccountId,
+		fee: PalletBalanceOf<T>,
+		call: &CallOf<T>,
+	) -> Result<FeeBreakdown<PalletBalanceOf<T>>, Error<T>> {
+		let (total_bnc_fee, extra_fee_bnc_amount, extra_fee_amount, _) =
+			Self::get_extrinsic_and_extra_fee_total(call, fee)?;
+		let (xcm_operation, extra_fee_currency, extra_fee_route) =
+			match Self::get_extra_fee_rule(call) {
+				Some((rule, currency_id)) =>
+					(Some(rule.operation), currency_id, vec![BNC, currency_id]),
+				None => (None, BNC, Vec::new()),
+			};
+
+		// As in `withdraw_fee`, only the user default fee currency can be charged at its oracle
+		// price, and then it only pays the extrinsic fee.
+		let oracle_fee_currency = Self::get_user_oracle_fee_currency(who);
+		let mut candidates: Vec<FeeCurrencyCandidate<PalletBalanceOf<T>>> = Vec::new();
+		for currency_id in Self::inner_get_user_fee_charge_order_list(who) {
+			if candidates.iter().any(|candidate| candidate.currency_id == currency_id) {
+				continue;
+			}
+			let candidate = match oracle_fee_currency {
+				Some((oracle_currency_id, premium)) if oracle_currency_id == currency_id =>
+					Self::quote_oracle_fee(who, currency_id, premium, fee),
+				_ => Self::quote_swap_fee(who, currency_id, total_bnc_fee),
+			};
+			candidates.push(candidate);
+		}
+		let fee_payment =
+			candidates.iter().find(|candidate| candidate.skip_reason.is_none()).cloned();
+
+		Ok(FeeBreakdown {
+			base_fee: fee,
+			xcm_operation,
+			extra_fee_currency,
+			extra_fee_amount,
+			extra_fee_bnc_amount,
+			extra_fee_route,
+			total_bnc_fee,
+			fee_payment,
+			candidates,
+		})
+	}
+
 	/// This function is for runtime-api to call
+	///
+	/// A fee charged at the oracle price only covers the extrinsic fee, the extra fee is paid in
+	/// BNC on top of it.
 	pub fn cal_fee_token_and_amount(
 		who: &T::AccountId,
 		fee: PalletBalanceOf<T>,
 		utx: &CallOf<T>,
 	) -> Result<(CurrencyIdOf<T>, PalletBalanceOf<T>), Error<T>> {
 		let total_fee_info = Self::get_extrinsic_and_extra_fee_total(utx, fee)?;
+		if let Some(oracle_fee) = Self::find_out_oracle_fee_amount(who, fee) {
+			return Ok(oracle_fee);
+		}
 		let (currency_id, amount_in, _amount_out) =
 			Self::find_out_fee_currency_and_amount(who, total_fee_info.0)
 				.map_err(|_| Error::<T>::DexFailedToGetAmountInByPath)?
@@ -487,26 +1329,15 @@ impl<T: Config> Pallet<T> {
 		let mut path = vec![native_asset_id, native_asset_id];
 
 		// See if the this RuntimeCall needs to pay extra fee
-		let fee_info = T::ExtraFeeMatcher::get_fee_info(call);
-		if fee_info.extra_fee_name != ExtraFeeName::NoExtraFee {
-			// if the fee_info.extra_fee_name is not NoExtraFee, it means this RuntimeCall needs to
-			// pay extra fee
-			let operation = match fee_info.extra_fee_name {
-				ExtraFeeName::SalpContribute => XcmOperationType::UmpContributeTransact,
-				ExtraFeeName::StatemineTransfer => XcmOperationType::StatemineTransfer,
-				ExtraFeeName::EthereumTransfer => XcmOperationType::EthereumTransfer,
-				ExtraFeeName::VoteVtoken => XcmOperationType::Vote,
-				ExtraFeeName::VoteRemoveDelegatorVote => XcmOperationType::RemoveVote,
-				ExtraFeeName::NoExtraFee => XcmOperationType::Any,
-			};
-
+		if let Some((rule, currency_id)) = Self::get_extra_fee_rule(call) {
+			// if the RuntimeCall has an extra fee rule, it needs to pay extra fee
 			let (_, fee_value) = T::XcmWeightAndFeeHandler::get_operation_weight_and_fee(
-				fee_info.extra_fee_currency,
-				operation,
+				currency_id,
+				rule.operation,
 			)
 			.ok_or(Error::<T>::WeightAndFeeNotExist)?;
 
-			let asset_id = Self::get_currency_asset_id(fee_info.extra_fee_currency)?;
+			let asset_id = Self::get_currency_asset_id(currency_id)?;
 			path = vec![native_asset_id, asset_id];
 
 			// get the fee currency value in BNC
@@ -540,7 +1371,7 @@ where
 	NegativeImbalanceOf<T>: Imbalance<PalletBalanceOf<T>, Opposite = PositiveImbalanceOf<T>>,
 {
 	type Balance = PalletBalanceOf<T>;
-	type LiquidityInfo = Option<NegativeImbalanceOf<T>>;
+	type LiquidityInfo = Option<PaymentInfo<T>>;
 
 	/// Withdraw the predicted fee from the transaction origin.
 	///
@@ -552,63 +1383,7 @@ where
 		fee: Self::Balance,
 		tip: Self::Balance,
 	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
-		if fee.is_zero() {
-			return Ok(None);
-		}
-
-		let withdraw_reason = if tip.is_zero() {
-			WithdrawReasons::TRANSACTION_PAYMENT
-		} else {
-			WithdrawReasons::TRANSACTION_PAYMENT | WithdrawReasons::TIP
-		};
-
-		// See if the this RuntimeCall needs to pay extra fee
-		let fee_info = T::ExtraFeeMatcher::get_fee_info(&call);
-		let (total_fee, extra_bnc_fee, fee_value, path) =
-			Self::get_extrinsic_and_extra_fee_total(call, fee)
-				.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Custom(55)))?;
-
-		// Make sure there are enough BNC(extrinsic fee + extra fee) to be deducted if the user has
-		// assets in other form of tokens rather than BNC.
-		Self::ensure_can_charge_fee(who, total_fee, withdraw_reason)
-			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
-
-		if fee_info.extra_fee_name != ExtraFeeName::NoExtraFee {
-			// swap BNC for fee_currency
-			T::DexOperator::inner_swap_assets_for_exact_assets(
-				who,
-				fee_value.saturated_into(),
-				extra_bnc_fee.saturated_into(),
-				&path,
-				who,
-			)
-			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Custom(44)))?;
-
-			let transaction_extra_fee_receiver = Self::get_fee_receiver(fee_info.extra_fee_name);
-
-			T::MultiCurrency::transfer(
-				fee_info.extra_fee_currency,
-				who,
-				&transaction_extra_fee_receiver,
-				fee_value,
-			)
-			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
-
-			// deposit extra fee deducted event
-			Self::deposit_event(Event::ExtraFeeDeducted {
-				operation: fee_info.extra_fee_name,
-				transaction_extra_fee_currency: fee_info.extra_fee_currency,
-				transaction_extra_fee_amount: fee_value,
-				transaction_extra_fee_bnc_amount: extra_bnc_fee,
-				transaction_extra_fee_receiver,
-			});
-		}
-
-		// withdraw normal extrinsic fee
-		match T::Currency::withdraw(who, fee, withdraw_reason, ExistenceRequirement::AllowDeath) {
-			Ok(imbalance) => Ok(Some(imbalance)),
-			Err(_) => Err(InvalidTransaction::Payment.into()),
-		}
+		Self::do_withdraw_fee(who, call, fee, tip, None)
 	}
 
 	/// Hand the fee and the tip over to the `[OnUnbalanced]` implementation.
@@ -624,25 +1399,44 @@ where
 		tip: Self::Balance,
 		already_withdrawn: Self::LiquidityInfo,
 	) -> Result<(), TransactionValidityError> {
-		if let Some(paid) = already_withdrawn {
-			// Calculate how much refund we should return
-			let refund_amount = paid.peek().saturating_sub(corrected_fee);
-
-			// refund to the the account that paid the fees. If this fails, the
-			// account might have dropped below the existential balance. In
-			// that case we don't refund anything.
-			let refund_imbalance = T::Currency::deposit_into_existing(who, refund_amount)
-				.unwrap_or_else(|_| PositiveImbalanceOf::<T>::zero());
-			// merge the imbalance caused by paying the fees and refunding parts of it again.
-			let adjusted_paid = paid
-				.offset(refund_imbalance)
-				.same()
-				.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
-			// RuntimeCall someone else to handle the imbalance (fee and tip separately)
-			let imbalances = adjusted_paid.split(tip);
-			T::OnUnbalanced::on_unbalanceds(
-				Some(imbalances.0).into_iter().chain(Some(imbalances.1)),
-			);
+		match already_withdrawn {
+			Some(PaymentInfo::Native(paid)) => {
+				Self::refund_and_deposit_fee(who, paid, corrected_fee, tip)?;
+			},
+			Some(PaymentInfo::Sponsored { sponsor, paid, tip: paid_tip }) => {
+				// The tip is used up, so the refund of the whole fee goes back to the sponsor
+				let refunded = Self::refund_and_deposit_fee(
+					&sponsor,
+					paid.merge(paid_tip),
+					corrected_fee,
+					tip,
+				)?;
+				Self::restore_sponsor_budget(&sponsor, who, refunded);
+			},
+			Some(PaymentInfo::Oracle { currency_id, amount, fee, tip: paid_tip }) => {
+				// Refund the unused share of the fee in the currency it was paid in, and as for
+				// BNC, don't refund anything if this fails.
+				let refund_amount = multiply_by_rational_with_rounding(
+					amount.saturated_into(),
+					fee.saturating_sub(corrected_fee.saturating_sub(tip)).saturated_into(),
+					fee.saturated_into(),
+					Rounding::Down,
+				)
+				.unwrap_or_default();
+				if refund_amount > 0 {
+					let _ = T::MultiCurrency::transfer(
+						currency_id,
+						&T::TreasuryAccount::get(),
+						who,
+						refund_amount.saturated_into(),
+					);
+				}
+				// The tip was withdrawn in BNC and is handed over along with a zero fee
+				T::OnUnbalanced::on_unbalanceds(
+					Some(NegativeImbalanceOf::<T>::zero()).into_iter().chain(Some(paid_tip)),
+				);
+			},
+			None => {},
 		}
 		Ok(())
 	}
@@ -653,4 +1447,195 @@ impl<T: Config> AccountFeeCurrency<T::AccountId> for Pallet<T> {
 	fn get(who: &T::AccountId) -> CurrencyId {
 		Pallet::<T>::get_user_default_fee_currency(who).unwrap_or_else(|| WETH)
 	}
+
+	/// The fee charge order list of `who`, with WETH last as the fallback
+	fn get_order_list(who: &T::AccountId) -> Vec<CurrencyId> {
+		let mut order_list = Self::inner_get_user_fee_charge_order_list(who);
+		if !order_list.contains(&WETH) {
+			order_list.push(WETH);
+		}
+		order_list
+	}
+}
+
+impl<T: Config> OnEvmAddressBound<T::AccountId> for Pallet<T> {
+	/// Keeps the fee currency set by an EVM address before it was bound, unless the bound account
+	/// has one of its own.
+	fn on_evm_address_bound(truncated: &T::AccountId, bound: &T::AccountId) {
+		if let Some(fee_currency) = UserDefaultFeeCurrency::<T>::take(truncated) {
+			if !UserDefaultFeeCurrency::<T>::contains_key(bound) {
+				UserDefaultFeeCurrency::<T>::insert(bound, fee_currency);
+			}
+		}
+	}
+}
+
+/// Charges the fee of the transaction as `ChargeTransactionPayment` does, and instead of the
+/// signer charges the extrinsic fee to the sponsor it names, if the policy of the sponsor covers
+/// the transaction, rejecting the transaction otherwise. It replaces `ChargeTransactionPayment`
+/// in the signed extensions, with `FlexibleFee` as the `OnChargeTransaction`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ChargeTransactionPaymentWithSponsor<T: Config> {
+	#[codec(compact)]
+	tip: TransactionBalanceOf<T>,
+	sponsor: Option<T::AccountId>,
+}
+
+/// The fee withdrawn by `ChargeTransactionPaymentWithSponsor` before dispatch.
+pub enum SponsorPre<T: Config> {
+	/// The tip, the signer and the fee withdrawn from the signer by `ChargeTransactionPayment`
+	Signer(
+		TransactionBalanceOf<T>,
+		T::AccountId,
+		<<T as pallet_transaction_payment::Config>::OnChargeTransaction as OnChargeTransaction<
+			T,
+		>>::LiquidityInfo,
+	),
+	/// The tip, the signer and the fee withdrawn from the sponsor
+	Sponsor(TransactionBalanceOf<T>, T::AccountId, Option<PaymentInfo<T>>),
+}
+
+impl<T: Config> ChargeTransactionPaymentWithSponsor<T> {
+	pub fn new(tip: TransactionBalanceOf<T>, sponsor: Option<T::AccountId>) -> Self {
+		Self { tip, sponsor }
+	}
+
+	fn to_balance(amount: TransactionBalanceOf<T>) -> PalletBalanceOf<T> {
+		amount.saturated_into::<u128>().unique_saturated_into()
+	}
+}
+
+impl<T: Config> ChargeTransactionPaymentWithSponsor<T>
+where
+	CallOf<T>: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+{
+	// withdraws the fee of the transaction, the extrinsic fee from `sponsor`
+	fn withdraw_sponsored_fee(
+		&self,
+		sponsor: &T::AccountId,
+		who: &T::AccountId,
+		call: &CallOf<T>,
+		info: &DispatchInfoOf<CallOf<T>>,
+		len: usize,
+	) -> Result<(TransactionBalanceOf<T>, Option<PaymentInfo<T>>), TransactionValidityError> {
+		let fee = pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, info, self.tip);
+		let paid = Pallet::<T>::do_withdraw_fee(
+			who,
+			call,
+			Self::to_balance(fee),
+			Self::to_balance(self.tip),
+			Some(sponsor.clone()),
+		)?;
+		Ok((fee, paid))
+	}
+}
+
+impl<T: Config> sp_std::fmt::Debug for ChargeTransactionPaymentWithSponsor<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ChargeTransactionPaymentWithSponsor<{:?}, {:?}>", self.tip, self.sponsor)
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for ChargeTransactionPaymentWithSponsor<T>
+where
+	TransactionBalanceOf<T>: Send + Sync + From<u64>,
+	CallOf<T>: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+{
+	const IDENTIFIER: &'static str = "ChargeTransactionPaymentWithSponsor";
+	type AccountId = T::AccountId;
+	type Call = CallOf<T>;
+	type AdditionalSigned = ();
+	type Pre = SponsorPre<T>;
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		match &self.sponsor {
+			Some(sponsor) => {
+				let (fee, _) = self.withdraw_sponsored_fee(sponsor, who, call, info, len)?;
+				Ok(ValidTransaction {
+					priority: ChargeTransactionPayment::<T>::get_priority(info, len, self.tip, fee),
+					..Default::default()
+				})
+			},
+			None => ChargeTransactionPayment::<T>::from(self.tip).validate(who, call, info, len),
+		}
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		match &self.sponsor {
+			Some(sponsor) => {
+				let (_, paid) = self.withdraw_sponsored_fee(sponsor, who, call, info, len)?;
+				Ok(SponsorPre::Sponsor(self.tip, who.clone(), paid))
+			},
+			None => {
+				let (tip, who, imbalance) = ChargeTransactionPayment::<T>::from(self.tip)
+					.pre_dispatch(who, call, info, len)?;
+				Ok(SponsorPre::Signer(tip, who, imbalance))
+			},
+		}
+	}
+
+	fn post_dispatch(
+		maybe_pre: Option<Self::Pre>,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		match maybe_pre {
+			Some(SponsorPre::Signer(tip, who, imbalance)) =>
+				ChargeTransactionPayment::<T>::post_dispatch(
+					Some((tip, who, imbalance)),
+					info,
+					post_info,
+					len,
+					result,
+				),
+			Some(SponsorPre::Sponsor(tip, who, paid)) => {
+				let actual_fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
+					len as u32, info, post_info, tip,
+				);
+				<Pallet<T> as OnChargeTransaction<T>>::correct_and_deposit_fee(
+					&who,
+					info,
+					post_info,
+					Self::to_balance(actual_fee),
+					Self::to_balance(tip),
+					paid,
+				)?;
+				frame_system::Pallet::<T>::deposit_event(
+					<T as pallet_transaction_payment::Config>::RuntimeEvent::from(
+						pallet_transaction_payment::Event::TransactionFeePaid {
+							who,
+							actual_fee,
+							tip,
+						},
+					),
+				);
+				Ok(())
+			},
+			None => Ok(()),
+		}
+	}
 }
@@ -0,0 +1,81 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::*;
+use frame_support::traits::OnRuntimeUpgrade;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+const LOG_TARGET: &str = "flexible-fee::migration";
+
+/// Fills `ExtraFeeRules` with the extra fees that used to be hard-coded in the runtime.
+pub struct InitExtraFeeRules<T, R>(sp_std::marker::PhantomData<(T, R)>);
+impl<T, R> OnRuntimeUpgrade for InitExtraFeeRules<T, R>
+where
+	T: Config,
+	R: Get<Vec<(u8, u8, ExtraFeeRule<CurrencyIdOf<T>>)>>,
+{
+	fn on_runtime_upgrade() -> frame_support::weights::Weight {
+		// Check the storage version
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+		if onchain_version < 3 {
+			log::info!(target: LOG_TARGET, "Start to migrate flexible-fee storage...");
+			let rules = R::get();
+			let count = rules.len() as u64;
+			for (pallet_index, call_index, rule) in rules {
+				ExtraFeeRules::<T>::insert(pallet_index, call_index, rule);
+			}
+
+			// Update the storage version
+			StorageVersion::new(3).put::<Pallet<T>>();
+
+			// Return the consumed weight
+			T::DbWeight::get().reads_writes(1, count + 1)
+		} else {
+			// We don't do anything here.
+			Weight::zero()
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+		let cnt = ExtraFeeRules::<T>::iter().count();
+
+		// print out the pre-migrate storage count
+		log::info!(target: LOG_TARGET, "ExtraFeeRules pre-migrate storage count: {:?}", cnt);
+		Ok((cnt as u64).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_cnt: Vec<u8>) -> Result<(), TryRuntimeError> {
+		let new_count = ExtraFeeRules::<T>::iter().count();
+
+		// print out the post-migrate storage count
+		log::info!(
+			target: LOG_TARGET,
+			"ExtraFeeRules post-migrate storage count: {:?}",
+			new_count
+		);
+
+		ensure!(
+			new_count == R::get().len(),
+			"Post-migration storage count does not match the extra fee rules"
+		);
+		Ok(())
+	}
+}
@@ -22,8 +22,8 @@ use super::*;
 use crate::{self as flexible_fee, tests::CHARLIE};
 use bifrost_asset_registry::AssetIdMaps;
 use bifrost_primitives::{
-	Balance, CurrencyId, DerivativeAccountHandler, DerivativeIndex, ExtraFeeInfo, MessageId,
-	ParaId, TokenSymbol, VTokenSupplyProvider, VKSM,
+	Balance, CurrencyId, DerivativeAccountHandler, DerivativeIndex, MessageId, ParaId, PriceDetail,
+	TokenSymbol, VTokenSupplyProvider, VKSM,
 };
 use bifrost_vtoken_voting::AccountVote;
 use bifrost_xcm_interface::traits::XcmHelper;
@@ -44,7 +44,7 @@ use sp_arithmetic::Percent;
 use sp_runtime::{
 	testing::Header,
 	traits::{AccountIdConversion, BlockNumberProvider, IdentityLookup, UniqueSaturatedInto},
-	AccountId32, BuildStorage, SaturatedConversion,
+	AccountId32, BuildStorage, FixedPointNumber, FixedU128, SaturatedConversion,
 };
 use sp_std::marker::PhantomData;
 use std::convert::TryInto;
@@ -200,6 +200,24 @@ impl crate::Config for Test {
 	type RelaychainCurrencyId = RelayCurrencyId;
 	type XcmRouter = ();
 	type PalletId = FlexibleFeePalletId;
+	type PriceFeeder = MockPriceFeeder;
+	type MaxSponsorListLen = ConstU32<50>;
+}
+
+/// BNC is worth 1 and KSM 4, other currencies have no price.
+pub struct MockPriceFeeder;
+impl PriceFeeder for MockPriceFeeder {
+	fn get_price(asset_id: &CurrencyId) -> Option<PriceDetail> {
+		match *asset_id {
+			BNC => Some((FixedU128::from_u32(1), 0)),
+			CurrencyId::Token(TokenSymbol::KSM) => Some((FixedU128::from_u32(4), 0)),
+			_ => None,
+		}
+	}
+
+	fn get_normal_price(_asset_id: &CurrencyId) -> Option<u128> {
+		None
+	}
 }
 
 pub struct XcmDestWeightAndFee;
@@ -222,18 +240,11 @@ impl XcmDestWeightAndFeeHandler<CurrencyId, Balance> for XcmDestWeightAndFee {
 
 pub struct ExtraFeeMatcher;
 impl FeeGetter<RuntimeCall> for ExtraFeeMatcher {
-	fn get_fee_info(c: &RuntimeCall) -> ExtraFeeInfo {
+	fn get_fee_currency(c: &RuntimeCall) -> Option<CurrencyId> {
 		match *c {
-			RuntimeCall::Salp(bifrost_salp::Call::contribute { .. }) => ExtraFeeInfo {
-				extra_fee_name: ExtraFeeName::SalpContribute,
-				extra_fee_currency: RelayCurrencyId::get(),
-			},
 			RuntimeCall::VtokenVoting(bifrost_vtoken_voting::Call::vote { vtoken, .. }) =>
-				ExtraFeeInfo {
-					extra_fee_name: ExtraFeeName::VoteVtoken,
-					extra_fee_currency: vtoken.to_token().unwrap_or(vtoken),
-				},
-			_ => ExtraFeeInfo::default(),
+				Some(vtoken.to_token().unwrap_or(vtoken)),
+			_ => None,
 		}
 	}
 }
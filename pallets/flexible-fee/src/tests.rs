@@ -23,18 +23,25 @@
 use bifrost_primitives::TryConvertFrom;
 // use balances::Call as BalancesCall;
 use crate::{
-	mock::*, BlockNumberFor, BoundedVec, Config, DispatchError::BadOrigin, UserDefaultFeeCurrency,
+	mock::*, BlockNumberFor, BoundedVec, ChargeTransactionPaymentWithSponsor, Config,
+	DispatchError::BadOrigin, Error, ExtraFeeRule, ExtraFeeRules, OracleFeeCurrencies,
+	SponsorPolicy, SponsorPre, SponsoredUsage, Sponsors, TargetChain, UserDefaultFeeCurrency,
+};
+use bifrost_primitives::{
+	currency::WETH, AccountFeeCurrency, CurrencyId, FeeCurrencySkipReason, FeePaymentMethod,
+	TokenSymbol, XcmOperationType,
 };
-use bifrost_primitives::{CurrencyId, TokenSymbol};
 use frame_support::{
 	assert_noop, assert_ok,
-	dispatch::{GetDispatchInfo, Pays, PostDispatchInfo},
-	traits::WithdrawReasons,
+	dispatch::{DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo},
+	traits::{ReservableCurrency, WithdrawReasons},
 	weights::Weight,
 };
 use orml_traits::MultiCurrency;
+use pallet_traits::evm::OnEvmAddressBound;
 use pallet_transaction_payment::OnChargeTransaction;
-use sp_runtime::{testing::TestXt, AccountId32};
+use parity_scale_codec::Encode;
+use sp_runtime::{testing::TestXt, traits::SignedExtension, AccountId32, Permill};
 use zenlink_protocol::AssetId;
 
 // some common variables
@@ -42,12 +49,23 @@ pub const CHARLIE: AccountId32 = AccountId32::new([0u8; 32]);
 pub const BOB: AccountId32 = AccountId32::new([1u8; 32]);
 pub const ALICE: AccountId32 = AccountId32::new([2u8; 32]);
 pub const DICK: AccountId32 = AccountId32::new([3u8; 32]);
+pub const EVE: AccountId32 = AccountId32::new([4u8; 32]);
 pub const CURRENCY_ID_0: CurrencyId = CurrencyId::Native(TokenSymbol::BNC);
 pub const CURRENCY_ID_1: CurrencyId = CurrencyId::Stable(TokenSymbol::KUSD);
 pub const CURRENCY_ID_2: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
 pub const CURRENCY_ID_3: CurrencyId = CurrencyId::VToken(TokenSymbol::DOT);
 pub const CURRENCY_ID_4: CurrencyId = CurrencyId::Token(TokenSymbol::KSM);
 
+fn set_extra_fee_rule(call: &RuntimeCall, rule: ExtraFeeRule<CurrencyId>) {
+	let (pallet_index, call_index) = call.using_encoded(|encoded| (encoded[0], encoded[1]));
+	assert_ok!(FlexibleFee::set_extra_fee_rule(
+		RuntimeOrigin::root(),
+		pallet_index,
+		call_index,
+		Some(rule)
+	));
+}
+
 fn basic_setup() {
 	// Deposit some money in Alice, Bob and Charlie's accounts.
 	// Alice
@@ -165,6 +183,24 @@ fn basic_setup() {
 		1,
 		deadline
 	));
+
+	// charge extra fees for salp contribute and vtoken-voting vote calls
+	set_extra_fee_rule(
+		&SALP_CONTRIBUTE_CALL,
+		ExtraFeeRule {
+			operation: XcmOperationType::UmpContributeTransact,
+			currency_id: Some(CURRENCY_ID_4),
+			receiver: TargetChain::RelayChain,
+		},
+	);
+	set_extra_fee_rule(
+		&VTOKENVOTING_VOTE_CALL,
+		ExtraFeeRule {
+			operation: XcmOperationType::Vote,
+			currency_id: None,
+			receiver: TargetChain::RelayChain,
+		},
+	);
 }
 
 #[test]
@@ -441,6 +477,67 @@ fn cal_fee_token_and_amount_should_work() {
 	});
 }
 
+#[test]
+fn get_fee_breakdown_should_work() {
+	new_test_ext().execute_with(|| {
+		basic_setup();
+
+		let asset_order_list_vec: BoundedVec<
+			CurrencyId,
+			<Test as Config>::MaxFeeCurrencyOrderListLen,
+		> = BoundedVec::try_from(vec![
+			CURRENCY_ID_4,
+			CURRENCY_ID_3,
+			CURRENCY_ID_2,
+			CURRENCY_ID_1,
+			CURRENCY_ID_0,
+		])
+		.unwrap();
+		assert_ok!(FlexibleFee::set_universal_fee_currency_order_list(
+			RuntimeOrigin::root(),
+			asset_order_list_vec
+		));
+
+		// no extra fee, charlie pays in BNC
+		let breakdown =
+			FlexibleFee::get_fee_breakdown(&CHARLIE, 20, &BALANCE_TRANSFER_CALL).unwrap();
+		assert_eq!(breakdown.xcm_operation, None);
+		assert_eq!(breakdown.extra_fee_bnc_amount, 0);
+		assert!(breakdown.extra_fee_route.is_empty());
+		assert_eq!(breakdown.total_bnc_fee, 20);
+		assert_eq!(breakdown.candidates.len(), 5);
+		let fee_payment = breakdown.fee_payment.unwrap();
+		assert_eq!(fee_payment.currency_id, CURRENCY_ID_0);
+		assert_eq!(fee_payment.method, FeePaymentMethod::Native);
+		assert_eq!(fee_payment.amount, 20);
+
+		// salp contribute call with extra fee, alice can't afford it in BNC or KUSD
+		let breakdown = FlexibleFee::get_fee_breakdown(&ALICE, 88, &SALP_CONTRIBUTE_CALL).unwrap();
+		assert_eq!(breakdown.base_fee, 88);
+		assert_eq!(breakdown.xcm_operation, Some(XcmOperationType::UmpContributeTransact));
+		assert_eq!(breakdown.extra_fee_currency, CURRENCY_ID_4);
+		assert_eq!(breakdown.extra_fee_amount, 100);
+		assert_eq!(breakdown.extra_fee_bnc_amount, 112);
+		assert_eq!(breakdown.extra_fee_route, vec![CURRENCY_ID_0, CURRENCY_ID_4]);
+		assert_eq!(breakdown.total_bnc_fee, 200);
+
+		let candidates = breakdown.candidates;
+		assert_eq!(
+			candidates.iter().map(|candidate| candidate.currency_id).collect::<Vec<_>>(),
+			vec![CURRENCY_ID_4, CURRENCY_ID_3, CURRENCY_ID_2, CURRENCY_ID_1, CURRENCY_ID_0]
+		);
+		assert_eq!(candidates[0].method, FeePaymentMethod::Swap);
+		assert_eq!(candidates[0].amount, 251);
+		assert_eq!(candidates[0].route, vec![CURRENCY_ID_4, CURRENCY_ID_0]);
+		assert_eq!(candidates[0].skip_reason, None);
+		assert_eq!(candidates[3].skip_reason, Some(FeeCurrencySkipReason::InsufficientBalance));
+		assert_eq!(candidates[4].method, FeePaymentMethod::Native);
+		assert_eq!(candidates[4].amount, 200);
+		assert_eq!(candidates[4].skip_reason, Some(FeeCurrencySkipReason::InsufficientBalance));
+		assert_eq!(breakdown.fee_payment, Some(candidates[0].clone()));
+	});
+}
+
 #[test]
 fn withdraw_fee_should_work() {
 	new_test_ext().execute_with(|| {
@@ -551,3 +648,411 @@ fn get_currency_asset_id_should_work() {
 		assert_eq!(asset_id, ksm_asset_id);
 	});
 }
+
+#[test]
+fn set_oracle_fee_currency_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FlexibleFee::set_oracle_fee_currency(
+				RuntimeOrigin::signed(ALICE),
+				CURRENCY_ID_4,
+				Some(Permill::from_percent(10))
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			FlexibleFee::set_oracle_fee_currency(
+				RuntimeOrigin::root(),
+				CURRENCY_ID_0,
+				Some(Permill::from_percent(10))
+			),
+			Error::<Test>::InvalidOracleFeeCurrency
+		);
+
+		assert_ok!(FlexibleFee::set_oracle_fee_currency(
+			RuntimeOrigin::root(),
+			CURRENCY_ID_4,
+			Some(Permill::from_percent(10))
+		));
+		assert_eq!(
+			OracleFeeCurrencies::<Test>::get(CURRENCY_ID_4),
+			Some(Permill::from_percent(10))
+		);
+
+		assert_ok!(FlexibleFee::set_oracle_fee_currency(
+			RuntimeOrigin::root(),
+			CURRENCY_ID_4,
+			None
+		));
+		assert_eq!(OracleFeeCurrencies::<Test>::get(CURRENCY_ID_4), None);
+	});
+}
+
+#[test]
+fn oracle_priced_fee_should_work() {
+	new_test_ext().execute_with(|| {
+		basic_setup();
+		let call = RuntimeCall::FlexibleFee(crate::Call::set_user_default_fee_currency {
+			maybe_fee_currency: Some(CURRENCY_ID_0),
+		});
+		let xt = TestXt::new(call.clone(), Some((0u64, ())));
+		let info = xt.get_dispatch_info();
+		let post_info = PostDispatchInfo {
+			actual_weight: Some(Weight::from_parts(20, 0)),
+			pays_fee: Pays::Yes,
+		};
+
+		assert_ok!(FlexibleFee::set_user_default_fee_currency(
+			RuntimeOrigin::signed(CHARLIE),
+			Some(CURRENCY_ID_4)
+		));
+		assert_ok!(FlexibleFee::set_oracle_fee_currency(
+			RuntimeOrigin::root(),
+			CURRENCY_ID_4,
+			Some(Permill::from_percent(10))
+		));
+
+		// 107 BNC at a price of 1/4 is 27 KSM rounded up, plus a premium of 3 KSM
+		assert_eq!(
+			FlexibleFee::cal_fee_token_and_amount(&CHARLIE, 107, &call).unwrap(),
+			(CURRENCY_ID_4, 30)
+		);
+		// The extrinsic fee of a call with an extra fee is quoted at the oracle price as well
+		assert_eq!(
+			FlexibleFee::cal_fee_token_and_amount(&CHARLIE, 107, &SALP_CONTRIBUTE_CALL).unwrap(),
+			(CURRENCY_ID_4, 30)
+		);
+		let breakdown =
+			FlexibleFee::get_fee_breakdown(&CHARLIE, 107, &SALP_CONTRIBUTE_CALL).unwrap();
+		assert_eq!(breakdown.extra_fee_bnc_amount, 112);
+		let fee_payment = breakdown.fee_payment.unwrap();
+		assert_eq!(fee_payment.currency_id, CURRENCY_ID_4);
+		assert_eq!(fee_payment.method, FeePaymentMethod::Oracle);
+		assert_eq!(fee_payment.amount, 30);
+		// The 99 BNC without the tip are 25 KSM rounded up plus a premium of 3 KSM, while the tip
+		// of 8 is paid in BNC
+		let already_withdrawn = FlexibleFee::withdraw_fee(&CHARLIE, &call, &info, 107, 8).unwrap();
+		assert_eq!(<Test as crate::Config>::Currency::free_balance(&CHARLIE), 192);
+		assert_eq!(Currencies::free_balance(CURRENCY_ID_4, &CHARLIE), 22);
+		assert_eq!(Currencies::free_balance(CURRENCY_ID_4, &TREASURY_ACCOUNT), 28);
+
+		// 27 of the 99 BNC are refunded, that is 7 of the 28 KSM rounded down, the tip is kept
+		assert_ok!(FlexibleFee::correct_and_deposit_fee(
+			&CHARLIE,
+			&info,
+			&post_info,
+			80,
+			8,
+			already_withdrawn
+		));
+		assert_eq!(Currencies::free_balance(CURRENCY_ID_4, &CHARLIE), 29);
+		assert_eq!(Currencies::free_balance(CURRENCY_ID_4, &TREASURY_ACCOUNT), 21);
+		assert_eq!(<Test as crate::Config>::Currency::free_balance(&CHARLIE), 192);
+
+		// Without a price the fee is paid in BNC
+		assert_ok!(FlexibleFee::set_user_default_fee_currency(
+			RuntimeOrigin::signed(CHARLIE),
+			Some(CURRENCY_ID_2)
+		));
+		assert_ok!(FlexibleFee::set_oracle_fee_currency(
+			RuntimeOrigin::root(),
+			CURRENCY_ID_2,
+			Some(Permill::from_percent(10))
+		));
+		assert_ok!(FlexibleFee::withdraw_fee(&CHARLIE, &call, &info, 107, 8));
+		assert_eq!(<Test as crate::Config>::Currency::free_balance(&CHARLIE), 85);
+		assert_eq!(Currencies::free_balance(CURRENCY_ID_2, &CHARLIE), 30);
+	});
+}
+
+#[test]
+fn sponsored_fee_should_work() {
+	new_test_ext().execute_with(|| {
+		basic_setup();
+		assert_ok!(Currencies::deposit(CURRENCY_ID_0, &BOB, 1000));
+		let call = RuntimeCall::FlexibleFee(crate::Call::set_user_default_fee_currency {
+			maybe_fee_currency: Some(CURRENCY_ID_0),
+		});
+		let pallet_index = call.using_encoded(|encoded| encoded[0]);
+		let xt = TestXt::new(call.clone(), Some((0u64, ())));
+		let info = xt.get_dispatch_info();
+		let post_info = PostDispatchInfo {
+			actual_weight: Some(Weight::from_parts(20, 0)),
+			pays_fee: Pays::Yes,
+		};
+		let policy =
+			SponsorPolicy { per_user_cap: 150, period_cap: 300, period: 10, whitelist_only: false };
+
+		assert_noop!(
+			FlexibleFee::fund_sponsor(RuntimeOrigin::signed(BOB), 1000),
+			Error::<Test>::SponsorNotFound
+		);
+		assert_noop!(
+			FlexibleFee::set_sponsor_policy(
+				RuntimeOrigin::signed(BOB),
+				SponsorPolicy { period: 0, ..policy }
+			),
+			Error::<Test>::InvalidSponsorPolicy
+		);
+		assert_ok!(FlexibleFee::set_sponsor_policy(RuntimeOrigin::signed(BOB), policy));
+		assert_ok!(FlexibleFee::set_sponsored_calls(
+			RuntimeOrigin::signed(BOB),
+			BoundedVec::try_from(vec![(pallet_index, None)]).unwrap(),
+			true
+		));
+		assert_ok!(FlexibleFee::fund_sponsor(RuntimeOrigin::signed(BOB), 1000));
+		assert_eq!(Balances::reserved_balance(&BOB), 1000);
+
+		// EVE only holds BNC for the tip and names BOB as the sponsor
+		assert_ok!(Currencies::deposit(CURRENCY_ID_0, &EVE, 20));
+		let already_withdrawn =
+			FlexibleFee::do_withdraw_fee(&EVE, &call, 107, 8, Some(BOB)).unwrap();
+		// BOB pays the fee without the tip, EVE pays the tip
+		assert_eq!(Balances::reserved_balance(&BOB), 901);
+		assert_eq!(<Test as crate::Config>::Currency::free_balance(&EVE), 12);
+		assert_ok!(FlexibleFee::correct_and_deposit_fee(
+			&EVE,
+			&info,
+			&post_info,
+			80,
+			8,
+			already_withdrawn
+		));
+		// The budget is charged the corrected fee without the tip
+		assert_eq!(Balances::reserved_balance(&BOB), 928);
+		assert_eq!(<Test as crate::Config>::Currency::free_balance(&EVE), 12);
+		let sponsor = Sponsors::<Test>::get(BOB).unwrap();
+		assert_eq!((sponsor.budget, sponsor.period_spent), (928, 72));
+		assert_eq!(SponsoredUsage::<Test>::get(BOB, EVE).map(|(_, spent)| spent), Some(72));
+
+		// EVE has 78 of the 150 per user cap left
+		assert!(FlexibleFee::do_withdraw_fee(&EVE, &call, 107, 8, Some(BOB)).is_err());
+		// The call is not sponsored
+		assert!(
+			FlexibleFee::do_withdraw_fee(&EVE, &BALANCE_TRANSFER_CALL, 50, 0, Some(BOB)).is_err()
+		);
+
+		// Only the whitelisted accounts are sponsored
+		assert_ok!(FlexibleFee::set_sponsor_policy(
+			RuntimeOrigin::signed(BOB),
+			SponsorPolicy { whitelist_only: true, ..policy }
+		));
+		assert!(FlexibleFee::do_withdraw_fee(&ALICE, &call, 50, 0, Some(BOB)).is_err());
+		assert_ok!(FlexibleFee::set_sponsor_whitelist(
+			RuntimeOrigin::signed(BOB),
+			BoundedVec::try_from(vec![ALICE]).unwrap(),
+			true
+		));
+		assert_ok!(FlexibleFee::do_withdraw_fee(&ALICE, &call, 50, 0, Some(BOB)));
+		assert_eq!(Balances::reserved_balance(&BOB), 878);
+		assert_eq!(<Test as crate::Config>::Currency::free_balance(&ALICE), 50);
+
+		// A new period resets the caps
+		System::set_block_number(11);
+		assert_ok!(FlexibleFee::set_sponsor_whitelist(
+			RuntimeOrigin::signed(BOB),
+			BoundedVec::try_from(vec![EVE]).unwrap(),
+			true
+		));
+		assert_ok!(FlexibleFee::do_withdraw_fee(&EVE, &call, 107, 8, Some(BOB)));
+		assert_eq!(Sponsors::<Test>::get(BOB).unwrap().period_start, 10);
+
+		assert_noop!(
+			FlexibleFee::withdraw_sponsor_budget(RuntimeOrigin::signed(BOB), 1000),
+			Error::<Test>::NotEnoughBalance
+		);
+		assert_ok!(FlexibleFee::withdraw_sponsor_budget(RuntimeOrigin::signed(BOB), 63));
+		assert_ok!(FlexibleFee::unregister_sponsor(RuntimeOrigin::signed(BOB), 10));
+		assert_eq!(Balances::reserved_balance(&BOB), 0);
+		assert_eq!(Sponsors::<Test>::get(BOB), None);
+	});
+}
+
+#[test]
+fn charge_transaction_payment_with_sponsor_should_work() {
+	new_test_ext().execute_with(|| {
+		basic_setup();
+		let call = RuntimeCall::FlexibleFee(crate::Call::set_user_default_fee_currency {
+			maybe_fee_currency: Some(CURRENCY_ID_0),
+		});
+		let pallet_index = call.using_encoded(|encoded| encoded[0]);
+		let info = DispatchInfo { weight: Weight::from_parts(100, 0), ..Default::default() };
+		let post_info = PostDispatchInfo {
+			actual_weight: Some(Weight::from_parts(20, 0)),
+			pays_fee: Pays::Yes,
+		};
+		let len = 10;
+		let fee = TransactionPayment::compute_fee(len as u32, &info, 0);
+		let budget = fee * 2;
+		let policy = SponsorPolicy {
+			per_user_cap: budget,
+			period_cap: budget,
+			period: 10,
+			whitelist_only: false,
+		};
+		assert_ok!(FlexibleFee::set_sponsor_policy(RuntimeOrigin::signed(BOB), policy));
+		assert_ok!(FlexibleFee::set_sponsored_calls(
+			RuntimeOrigin::signed(BOB),
+			BoundedVec::try_from(vec![(pallet_index, None)]).unwrap(),
+			true
+		));
+		assert_ok!(Currencies::deposit(CURRENCY_ID_0, &BOB, budget));
+		assert_ok!(FlexibleFee::fund_sponsor(RuntimeOrigin::signed(BOB), budget));
+
+		// The sponsor named by the extension pays for EVE, who holds no BNC
+		let pre = ChargeTransactionPaymentWithSponsor::<Test>::new(0, Some(BOB))
+			.pre_dispatch(&EVE, &call, &info, len)
+			.unwrap();
+		assert!(matches!(pre, SponsorPre::Sponsor(_, ref who, _) if *who == EVE));
+		assert_eq!(Balances::reserved_balance(&BOB), budget - fee);
+		assert_ok!(ChargeTransactionPaymentWithSponsor::<Test>::post_dispatch(
+			Some(pre),
+			&info,
+			&post_info,
+			len,
+			&Ok(())
+		));
+		let actual_fee = TransactionPayment::compute_actual_fee(len as u32, &info, &post_info, 0);
+		assert_eq!(Balances::reserved_balance(&BOB), budget - actual_fee);
+		assert_eq!(<Test as crate::Config>::Currency::free_balance(&EVE), 0);
+
+		// Without a sponsor EVE pays, and can't
+		assert!(ChargeTransactionPaymentWithSponsor::<Test>::new(0, None)
+			.pre_dispatch(&EVE, &call, &info, len)
+			.is_err());
+	});
+}
+
+#[test]
+fn unregister_sponsor_should_remove_at_most_limit() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		let policy =
+			SponsorPolicy { per_user_cap: 150, period_cap: 300, period: 10, whitelist_only: true };
+		assert_ok!(FlexibleFee::set_sponsor_policy(RuntimeOrigin::signed(BOB), policy));
+		assert_ok!(FlexibleFee::set_sponsor_whitelist(
+			RuntimeOrigin::signed(BOB),
+			BoundedVec::try_from(vec![ALICE, CHARLIE, EVE]).unwrap(),
+			true
+		));
+	});
+	ext.commit_all().unwrap();
+	ext.execute_with(|| {
+		// The sponsor stays registered until its whitelist is removed
+		assert_ok!(FlexibleFee::unregister_sponsor(RuntimeOrigin::signed(BOB), 2));
+		assert!(Sponsors::<Test>::get(BOB).is_some());
+	});
+	ext.commit_all().unwrap();
+	ext.execute_with(|| {
+		assert_ok!(FlexibleFee::unregister_sponsor(RuntimeOrigin::signed(BOB), 2));
+		assert_eq!(Sponsors::<Test>::get(BOB), None);
+		assert_noop!(
+			FlexibleFee::unregister_sponsor(RuntimeOrigin::signed(BOB), 2),
+			Error::<Test>::SponsorNotFound
+		);
+	});
+}
+
+#[test]
+fn set_extra_fee_rule_should_work() {
+	new_test_ext().execute_with(|| {
+		let (pallet_index, call_index) =
+			BALANCE_TRANSFER_CALL.using_encoded(|encoded| (encoded[0], encoded[1]));
+		let rule = ExtraFeeRule {
+			operation: XcmOperationType::StatemineTransfer,
+			currency_id: Some(CURRENCY_ID_4),
+			receiver: TargetChain::AssetHub,
+		};
+
+		assert_noop!(
+			FlexibleFee::set_extra_fee_rule(
+				RuntimeOrigin::signed(ALICE),
+				pallet_index,
+				call_index,
+				Some(rule)
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			FlexibleFee::set_extra_fee_rule(
+				RuntimeOrigin::root(),
+				pallet_index,
+				call_index,
+				Some(ExtraFeeRule { currency_id: Some(CURRENCY_ID_0), ..rule })
+			),
+			Error::<Test>::InvalidExtraFeeRule
+		);
+
+		assert_eq!(FlexibleFee::get_extra_fee_rule(&BALANCE_TRANSFER_CALL), None);
+		assert_ok!(FlexibleFee::set_extra_fee_rule(
+			RuntimeOrigin::root(),
+			pallet_index,
+			call_index,
+			Some(rule)
+		));
+		assert_eq!(ExtraFeeRules::<Test>::get(pallet_index, call_index), Some(rule));
+		assert_eq!(
+			FlexibleFee::get_extra_fee_rule(&BALANCE_TRANSFER_CALL),
+			Some((rule, CURRENCY_ID_4))
+		);
+
+		// the fee currency of a vote is the token of the vtoken voted with
+		set_extra_fee_rule(
+			&VTOKENVOTING_VOTE_CALL,
+			ExtraFeeRule { currency_id: None, operation: XcmOperationType::Vote, ..rule },
+		);
+		assert_eq!(
+			FlexibleFee::get_extra_fee_rule(&VTOKENVOTING_VOTE_CALL)
+				.map(|(_, currency_id)| currency_id),
+			Some(CURRENCY_ID_4)
+		);
+
+		assert_ok!(FlexibleFee::set_extra_fee_rule(
+			RuntimeOrigin::root(),
+			pallet_index,
+			call_index,
+			None
+		));
+		assert_eq!(FlexibleFee::get_extra_fee_rule(&BALANCE_TRANSFER_CALL), None);
+	});
+}
+
+#[test]
+fn evm_fee_currency_order_list_should_work() {
+	new_test_ext().execute_with(|| {
+		let asset_order_list_vec: BoundedVec<
+			CurrencyId,
+			<Test as Config>::MaxFeeCurrencyOrderListLen,
+		> = BoundedVec::try_from(vec![CURRENCY_ID_4, CURRENCY_ID_0]).unwrap();
+		assert_ok!(FlexibleFee::set_universal_fee_currency_order_list(
+			RuntimeOrigin::root(),
+			asset_order_list_vec
+		));
+
+		// WETH is the fallback after the universal fee currency order list
+		assert_eq!(
+			<FlexibleFee as AccountFeeCurrency<AccountId32>>::get_order_list(&ALICE),
+			vec![CURRENCY_ID_4, CURRENCY_ID_0, WETH]
+		);
+
+		// the fee currency set by an EVM address is kept when it is bound to alice
+		assert_ok!(FlexibleFee::set_user_default_fee_currency(
+			RuntimeOrigin::signed(EVE),
+			Some(CURRENCY_ID_2),
+		));
+		FlexibleFee::on_evm_address_bound(&EVE, &ALICE);
+		assert_eq!(UserDefaultFeeCurrency::<Test>::get(EVE), None);
+		assert_eq!(
+			<FlexibleFee as AccountFeeCurrency<AccountId32>>::get_order_list(&ALICE),
+			vec![CURRENCY_ID_2, CURRENCY_ID_4, CURRENCY_ID_0, WETH]
+		);
+
+		// but doesn't replace the one alice already has
+		assert_ok!(FlexibleFee::set_user_default_fee_currency(
+			RuntimeOrigin::signed(EVE),
+			Some(CURRENCY_ID_3),
+		));
+		FlexibleFee::on_evm_address_bound(&EVE, &ALICE);
+		assert_eq!(<FlexibleFee as AccountFeeCurrency<AccountId32>>::get(&ALICE), CURRENCY_ID_2);
+	});
+}
@@ -14,9 +14,12 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use bifrost_primitives::{CurrencyId, Liquidity, Rate, Ratio, Shortfall};
+use bifrost_primitives::{
+	AccountHealth, CurrencyId, LiquidationCandidate, Liquidity, Rate, Ratio, Shortfall,
+};
 use parity_scale_codec::Codec;
 use sp_runtime::{DispatchError, FixedU128};
+use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
 	pub trait LendMarketApi<AccountId, Balance> where
@@ -25,5 +28,10 @@ sp_api::decl_runtime_apis! {
 		fn get_account_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError>;
 		fn get_market_status(asset_id: CurrencyId) -> Result<(Rate, Rate, Rate, Ratio, Balance, Balance, FixedU128), DispatchError>;
 		fn get_liquidation_threshold_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError>;
+		fn get_market_bad_debt(asset_id: CurrencyId) -> Balance;
+		fn get_account_health(account: AccountId) -> Result<AccountHealth<Balance>, DispatchError>;
+		/// Scans at most `limit` borrows after `start_key` for the accounts whose health factor is
+		/// below `health_factor`. Returns the key to resume from, `None` once every borrow is scanned.
+		fn get_liquidation_candidates(health_factor: FixedU128, start_key: Option<Vec<u8>>, limit: u32) -> (Vec<LiquidationCandidate<AccountId, Balance>>, Option<Vec<u8>>);
 	}
 }
@@ -51,6 +51,12 @@ where
 		account: AccountId,
 		at: Option<BlockHash>,
 	) -> RpcResult<(Liquidity, Shortfall, Liquidity, Shortfall)>;
+	#[method(name = "lend_market_getMarketBadDebt")]
+	fn get_market_bad_debt(
+		&self,
+		asset_id: CurrencyId,
+		at: Option<BlockHash>,
+	) -> RpcResult<NumberOrHex>;
 }
 
 /// A struct that implements the [`LendMarketApi`].
@@ -155,6 +161,21 @@ where
 			.map_err(runtime_error_into_rpc_error)?
 			.map_err(account_liquidity_error_into_rpc_error)
 	}
+
+	fn get_market_bad_debt(
+		&self,
+		asset_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		);
+		let bad_debt =
+			api.get_market_bad_debt(at, asset_id).map_err(runtime_error_into_rpc_error)?;
+		try_into_rpc_balance(bad_debt)
+	}
 }
 
 /// Converts a runtime trap into an RPC error.
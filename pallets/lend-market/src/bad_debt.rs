@@ -0,0 +1,165 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+	/// Returns true if `borrower` still has outstanding borrows while none of its deposits is
+	/// collateral anymore, so the debt can never be liquidated.
+	///
+	/// The deposits that aren't collateral are seized when the debt is written off.
+	pub fn has_bad_debt(borrower: &T::AccountId) -> bool {
+		let has_collateral = Markets::<T>::iter_keys().any(|asset_id| {
+			let deposits = Self::account_deposits(asset_id, borrower);
+			deposits.is_collateral && !deposits.voucher_balance.is_zero()
+		});
+
+		!has_collateral &&
+			Markets::<T>::iter_keys()
+				.any(|asset_id| !Self::account_borrows(asset_id, borrower).principal.is_zero())
+	}
+
+	/// Seizes the remaining deposits of `borrower` into the reserves, then writes off its
+	/// borrows in every market.
+	#[require_transactional]
+	pub(crate) fn do_write_off_bad_debt(borrower: &T::AccountId) -> DispatchResult {
+		ensure!(Self::has_bad_debt(borrower), Error::<T>::NoBadDebt);
+
+		let asset_ids: Vec<AssetIdOf<T>> = Markets::<T>::iter_keys().collect();
+		for &asset_id in &asset_ids {
+			if Self::account_deposits(asset_id, borrower).voucher_balance.is_zero() {
+				continue;
+			}
+			Self::accrue_interest(asset_id)?;
+			Self::seize_supply(borrower, asset_id)?;
+		}
+		for asset_id in asset_ids {
+			if Self::account_borrows(asset_id, borrower).principal.is_zero() {
+				continue;
+			}
+			Self::accrue_interest(asset_id)?;
+			Self::write_off_market_bad_debt(borrower, asset_id)?;
+		}
+
+		Ok(())
+	}
+
+	/// Burns the vouchers of `borrower` in `asset_id` market and adds their underlying amount
+	/// to the reserves, the exchange rate of the other suppliers doesn't change.
+	fn seize_supply(borrower: &T::AccountId, asset_id: AssetIdOf<T>) -> DispatchResult {
+		Self::update_reward_supply_index(asset_id)?;
+		Self::distribute_supplier_reward(asset_id, borrower)?;
+
+		let voucher_amount = Self::account_deposits(asset_id, borrower).voucher_balance;
+		let amount =
+			Self::calc_underlying_amount(voucher_amount, Self::exchange_rate_stored(asset_id)?)?;
+		AccountDeposits::<T>::remove(asset_id, borrower);
+		TotalSupply::<T>::try_mutate(asset_id, |total_supply| -> DispatchResult {
+			*total_supply =
+				total_supply.checked_sub(voucher_amount).ok_or(ArithmeticError::Underflow)?;
+			Ok(())
+		})?;
+		TotalReserves::<T>::try_mutate(asset_id, |total_reserves| -> DispatchResult {
+			*total_reserves =
+				total_reserves.checked_add(amount).ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::<T>::BadDebtSupplySeized(
+			borrower.clone(),
+			asset_id,
+			voucher_amount,
+			amount,
+		));
+
+		Ok(())
+	}
+
+	/// Writes off the borrow of `borrower` in `asset_id` market.
+	///
+	/// The debt is covered by the market reserves first, the remainder is socialised across
+	/// the suppliers by lowering the exchange rate. The exchange rate can't fall below
+	/// `MIN_EXCHANGE_RATE`, the debt that suppliers can't absorb stays on the borrower.
+	fn write_off_market_bad_debt(
+		borrower: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+	) -> DispatchResult {
+		let account_borrows = Self::current_borrow_balance(borrower, asset_id)?;
+		let total_cash = Self::get_total_cash(asset_id);
+		let total_borrows = Self::total_borrows(asset_id);
+		let total_reserves = Self::total_reserves(asset_id);
+
+		let reserves_amount = account_borrows.min(total_reserves);
+		let suppliers_value = total_cash
+			.checked_add(total_borrows)
+			.and_then(|r| r.checked_sub(total_reserves))
+			.ok_or(ArithmeticError::Overflow)?;
+		let min_suppliers_value =
+			Rate::from_inner(MIN_EXCHANGE_RATE).saturating_mul_int(Self::total_supply(asset_id));
+		let socialised_amount = account_borrows
+			.saturating_sub(reserves_amount)
+			.min(suppliers_value.saturating_sub(min_suppliers_value));
+		let written_off_amount = reserves_amount
+			.checked_add(socialised_amount)
+			.ok_or(ArithmeticError::Overflow)?;
+		if written_off_amount.is_zero() {
+			return Ok(());
+		}
+
+		Self::update_reward_borrow_index(asset_id)?;
+		Self::distribute_borrower_reward(asset_id, borrower)?;
+
+		let account_borrows_new = account_borrows
+			.checked_sub(written_off_amount)
+			.ok_or(ArithmeticError::Underflow)?;
+		AccountBorrows::<T>::insert(
+			asset_id,
+			borrower,
+			BorrowSnapshot {
+				principal: account_borrows_new,
+				borrow_index: Self::borrow_index(asset_id),
+			},
+		);
+		// total_borrows can be less than account_borrows, see `do_repay_borrow_with_amount`
+		let total_borrows_new = total_borrows.saturating_sub(written_off_amount);
+		TotalBorrows::<T>::insert(asset_id, total_borrows_new);
+		let total_reserves_new =
+			total_reserves.checked_sub(reserves_amount).ok_or(ArithmeticError::Underflow)?;
+		TotalReserves::<T>::insert(asset_id, total_reserves_new);
+		BadDebt::<T>::try_mutate(asset_id, |bad_debt| -> DispatchResult {
+			*bad_debt =
+				bad_debt.checked_add(written_off_amount).ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})?;
+
+		ExchangeRate::<T>::insert(asset_id, Self::exchange_rate_stored(asset_id)?);
+		UtilizationRatio::<T>::insert(
+			asset_id,
+			Self::calc_utilization_ratio(total_cash, total_borrows_new, total_reserves_new)?,
+		);
+
+		Self::deposit_event(Event::<T>::BadDebtWrittenOff(
+			borrower.clone(),
+			asset_id,
+			reserves_amount,
+			socialised_amount,
+		));
+
+		Ok(())
+	}
+}
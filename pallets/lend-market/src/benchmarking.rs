@@ -380,6 +380,156 @@ benchmarks! {
 	verify {
 		assert_last_event::<T>(Event::<T>::LiquidationFreeCollateralsUpdated(vec![PHA]).into());
 	}
+
+	update_liquidation_keeper {
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), DOT_U, pending_market_mock::<T>(LUSDT)));
+		let limit = LiquidationKeeperLimit { max_repay_amount: 1_000_000_000, min_profit: 1_000 };
+	}: _(SystemOrigin::Root, DOT_U, Some(limit))
+	verify {
+		assert_last_event::<T>(Event::<T>::LiquidationKeeperUpdated(DOT_U, Some(limit)).into());
+	}
+
+	write_off_bad_debt {
+		let alice: T::AccountId = account("Sample", 100, SEED);
+		let bob: T::AccountId = account("Sample", 101, SEED);
+		transfer_initial_balance::<T>(alice.clone());
+		transfer_initial_balance::<T>(bob.clone());
+		let deposit_amount: u32 = 200_000_000;
+		let borrowed_amount: u32 = 50_000_000;
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), KSM, pending_market_mock::<T>(LKSM)));
+		assert_ok!(LendMarket::<T>::activate_market(SystemOrigin::Root.into(), KSM));
+		assert_ok!(LendMarket::<T>::mint(SystemOrigin::Signed(bob.clone()).into(), KSM, deposit_amount.into()));
+		set_account_borrows::<T>(alice.clone(), KSM, borrowed_amount.into());
+	}: _(SystemOrigin::Signed(bob), alice.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::BadDebtWrittenOff(alice, KSM, 0, borrowed_amount.into()).into());
+	}
+
+	flash_loan {
+		let caller: T::AccountId = whitelisted_caller();
+		transfer_initial_balance::<T>(caller.clone());
+		let deposit_amount: u32 = 200_000_000;
+		let loan_amount: u32 = 100_000_000;
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), DOT_U, pending_market_mock::<T>(LUSDT)));
+		assert_ok!(LendMarket::<T>::activate_market(SystemOrigin::Root.into(), DOT_U));
+		assert_ok!(LendMarket::<T>::mint(SystemOrigin::Signed(caller.clone()).into(), DOT_U, deposit_amount.into()));
+		let call: <T as Config>::RuntimeCall = frame_system::Call::<T>::remark { remark: vec![] }.into();
+	}: _(SystemOrigin::Signed(caller.clone()), DOT_U, loan_amount.into(), Box::new(call))
+	verify {
+		assert_last_event::<T>(Event::<T>::FlashLoaned(caller, DOT_U, loan_amount.into(), 0).into());
+	}
+
+	update_flash_loan_fee_rate {
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), DOT_U, pending_market_mock::<T>(LUSDT)));
+	}: _(SystemOrigin::Root, DOT_U, Ratio::from_percent(1))
+	verify {
+		assert_last_event::<T>(Event::<T>::FlashLoanFeeRateUpdated(DOT_U, Ratio::from_percent(1)).into());
+	}
+
+	approve_borrow_allowance {
+		let caller: T::AccountId = whitelisted_caller();
+		let delegatee: T::AccountId = account("Sample", 100, SEED);
+		let amount: u32 = 100_000_000;
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), DOT_U, pending_market_mock::<T>(LUSDT)));
+		assert_ok!(LendMarket::<T>::activate_market(SystemOrigin::Root.into(), DOT_U));
+	}: _(SystemOrigin::Signed(caller.clone()), delegatee.clone(), DOT_U, amount.into())
+	verify {
+		assert_last_event::<T>(Event::<T>::BorrowAllowanceApproved(caller, delegatee, DOT_U, amount.into()).into());
+	}
+
+	revoke_borrow_allowance {
+		let caller: T::AccountId = whitelisted_caller();
+		let delegatee: T::AccountId = account("Sample", 100, SEED);
+		let amount: u32 = 100_000_000;
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), DOT_U, pending_market_mock::<T>(LUSDT)));
+		assert_ok!(LendMarket::<T>::activate_market(SystemOrigin::Root.into(), DOT_U));
+		assert_ok!(LendMarket::<T>::approve_borrow_allowance(SystemOrigin::Signed(caller.clone()).into(), delegatee.clone(), DOT_U, amount.into()));
+	}: _(SystemOrigin::Signed(caller.clone()), delegatee.clone(), DOT_U)
+	verify {
+		assert_last_event::<T>(Event::<T>::BorrowAllowanceRevoked(caller, delegatee, DOT_U).into());
+	}
+
+	borrow_on_behalf {
+		let caller: T::AccountId = whitelisted_caller();
+		let delegator: T::AccountId = account("Sample", 100, SEED);
+		transfer_initial_balance::<T>(delegator.clone());
+		let deposit_amount: u32 = 200_000_000;
+		let borrowed_amount: u32 = 100_000_000;
+		assert_ok!(LendMarket::<T>::add_market_bond(
+			SystemOrigin::Root.into(),
+			DOT_U,
+			vec![DOT_U]
+		));
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), DOT_U, pending_market_mock::<T>(LUSDT)));
+		assert_ok!(LendMarket::<T>::activate_market(SystemOrigin::Root.into(), DOT_U));
+		assert_ok!(LendMarket::<T>::mint(SystemOrigin::Signed(delegator.clone()).into(), DOT_U, deposit_amount.into()));
+		assert_ok!(LendMarket::<T>::collateral_asset(SystemOrigin::Signed(delegator.clone()).into(), DOT_U, true));
+		assert_ok!(LendMarket::<T>::approve_borrow_allowance(SystemOrigin::Signed(delegator.clone()).into(), caller.clone(), DOT_U, borrowed_amount.into()));
+	}: _(SystemOrigin::Signed(caller.clone()), delegator.clone(), DOT_U, borrowed_amount.into())
+	verify {
+		assert_last_event::<T>(Event::<T>::DelegatedBorrowed(delegator, caller, DOT_U, borrowed_amount.into()).into());
+	}
+
+	repay_borrow_on_behalf {
+		let caller: T::AccountId = whitelisted_caller();
+		let borrower: T::AccountId = account("Sample", 100, SEED);
+		transfer_initial_balance::<T>(caller.clone());
+		transfer_initial_balance::<T>(borrower.clone());
+		let deposit_amount: u32 = 200_000_000;
+		let borrowed_amount: u32 = 100_000_000;
+		let repay_amount: u32 = 100;
+		assert_ok!(LendMarket::<T>::add_market_bond(
+			SystemOrigin::Root.into(),
+			DOT_U,
+			vec![DOT_U]
+		));
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), DOT_U, pending_market_mock::<T>(LUSDT)));
+		assert_ok!(LendMarket::<T>::activate_market(SystemOrigin::Root.into(), DOT_U));
+		assert_ok!(LendMarket::<T>::mint(SystemOrigin::Signed(borrower.clone()).into(), DOT_U, deposit_amount.into()));
+		assert_ok!(LendMarket::<T>::collateral_asset(SystemOrigin::Signed(borrower.clone()).into(), DOT_U, true));
+		assert_ok!(LendMarket::<T>::borrow(SystemOrigin::Signed(borrower.clone()).into(), DOT_U, borrowed_amount.into()));
+	}: _(SystemOrigin::Signed(caller.clone()), borrower.clone(), DOT_U, repay_amount.into())
+	verify {
+		assert_last_event::<T>(Event::<T>::RepaidBorrow(borrower, DOT_U, repay_amount.into()).into());
+	}
+
+	wind_down_market {
+		let liquidate_incentive = Rate::from_inner(Rate::DIV / 100 * 120);
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), DOT_U, pending_market_mock::<T>(LUSDT)));
+		assert_ok!(LendMarket::<T>::activate_market(SystemOrigin::Root.into(), DOT_U));
+	}: _(SystemOrigin::Root, DOT_U, 86400, liquidate_incentive, 0)
+	verify {
+		assert_eq!(LendMarket::<T>::market_wind_down(DOT_U).map(|w| w.liquidate_incentive), Some(liquidate_incentive));
+	}
+
+	settle_position {
+		let caller: T::AccountId = whitelisted_caller();
+		let supplier: T::AccountId = account("Sample", 100, SEED);
+		transfer_initial_balance::<T>(supplier.clone());
+		let deposit_amount: u32 = 100_000_000;
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), DOT_U, pending_market_mock::<T>(LUSDT)));
+		assert_ok!(LendMarket::<T>::activate_market(SystemOrigin::Root.into(), DOT_U));
+		assert_ok!(LendMarket::<T>::mint(SystemOrigin::Signed(supplier.clone()).into(), DOT_U, deposit_amount.into()));
+		assert_ok!(LendMarket::<T>::wind_down_market(SystemOrigin::Root.into(), DOT_U, 0, Rate::from_inner(Rate::DIV / 100 * 110), 0));
+	}: _(SystemOrigin::Signed(caller), DOT_U, supplier.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::PositionSettled(supplier, DOT_U, deposit_amount.into()).into());
+	}
+
+	delist_market {
+		let l in 0 .. 1000;
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), DOT_U, pending_market_mock::<T>(LUSDT)));
+		assert_ok!(LendMarket::<T>::activate_market(SystemOrigin::Root.into(), DOT_U));
+		assert_ok!(LendMarket::<T>::add_market_bond(SystemOrigin::Root.into(), DOT_U, vec![DOT_U]));
+		assert_ok!(LendMarket::<T>::wind_down_market(SystemOrigin::Root.into(), DOT_U, 0, Rate::from_inner(Rate::DIV / 100 * 110), 0));
+		for i in 0 .. l {
+			let borrower: T::AccountId = account("borrower", i, SEED);
+			AccountBorrows::<T>::insert(DOT_U, borrower, BorrowSnapshot::default());
+		}
+	}: _(SystemOrigin::Root, DOT_U, l)
+	verify {
+		assert_last_event::<T>(Event::<T>::MarketDelisted(DOT_U).into());
+	}
 }
 
 impl_benchmark_test_suite!(LendMarket, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,61 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Credit delegation.
+//!
+//! A delegator allows a delegatee to borrow an asset against the delegator's collateral, up to
+//! an amount. The borrows are recorded as the delegator's debt, so they count against the
+//! delegator's liquidity and the delegator is liquidated in shortfall. The allowance shrinks
+//! with every borrow and isn't restored by repayments.
+
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+	/// `delegatee` borrows `amount` of `asset_id` against the collateral of `delegator`.
+	#[require_transactional]
+	pub fn do_borrow_on_behalf(
+		delegatee: &T::AccountId,
+		delegator: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+		BorrowAllowances::<T>::try_mutate_exists(
+			delegator,
+			(delegatee, asset_id),
+			|allowance| -> DispatchResult {
+				let remaining = allowance
+					.unwrap_or_default()
+					.checked_sub(amount)
+					.ok_or(Error::<T>::InsufficientBorrowAllowance)?;
+				*allowance = Some(remaining).filter(|remaining| !remaining.is_zero());
+				Ok(())
+			},
+		)?;
+
+		Self::borrow_to(delegator, delegatee, asset_id, amount)?;
+
+		Self::deposit_event(Event::<T>::DelegatedBorrowed(
+			delegator.clone(),
+			delegatee.clone(),
+			asset_id,
+			amount,
+		));
+		Ok(())
+	}
+}
@@ -0,0 +1,295 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Account health and liquidation candidates, read by the runtime API.
+
+use bifrost_primitives::{AccountHealth, LiquidationCandidate, MarketPosition, MaxLiquidation};
+use frame_support::storage::StoragePrefixedMap;
+
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+	/// Returns every position of `account` in the active markets and the account health.
+	pub fn get_account_health(
+		account: &T::AccountId,
+	) -> Result<AccountHealth<BalanceOf<T>>, DispatchError> {
+		let mut positions = Vec::new();
+		// The liquidation threshold value of each position
+		let mut threshold_values = Vec::new();
+		let mut collateral_value = FixedU128::zero();
+		let mut borrow_value = FixedU128::zero();
+		for (asset_id, _) in Self::active_markets() {
+			let deposits = Self::account_deposits(asset_id, account);
+			let borrow_amount = Self::current_borrow_balance(account, asset_id)?;
+			if deposits.voucher_balance.is_zero() && borrow_amount.is_zero() {
+				continue;
+			}
+			let supply_amount = Self::calc_underlying_amount(
+				deposits.voucher_balance,
+				Self::exchange_rate_stored(asset_id)?,
+			)?;
+			let position = MarketPosition {
+				asset_id,
+				supply_amount,
+				supply_value: Self::get_asset_value(asset_id, supply_amount)?,
+				borrow_amount,
+				borrow_value: Self::get_asset_value(asset_id, borrow_amount)?,
+				is_collateral: deposits.is_collateral,
+				liquidation_price: None,
+			};
+			let threshold_value = Self::liquidation_threshold_asset_value(account, asset_id)?;
+			collateral_value = collateral_value
+				.checked_add(&threshold_value)
+				.ok_or(ArithmeticError::Overflow)?;
+			borrow_value = borrow_value
+				.checked_add(&position.borrow_value)
+				.ok_or(ArithmeticError::Overflow)?;
+			positions.push(position);
+			threshold_values.push(threshold_value);
+		}
+
+		for (position, threshold_value) in positions.iter_mut().zip(threshold_values) {
+			position.liquidation_price = Self::liquidation_price(
+				position.asset_id,
+				collateral_value,
+				borrow_value,
+				threshold_value,
+				position.borrow_value,
+			)?;
+		}
+		let health_factor = if borrow_value.is_zero() {
+			None
+		} else {
+			Some(collateral_value.checked_div(&borrow_value).ok_or(ArithmeticError::Underflow)?)
+		};
+
+		Ok(AccountHealth { positions, collateral_value, borrow_value, health_factor })
+	}
+
+	/// The price of `asset_id` at which the liquidation threshold value of the collateral
+	/// equals the borrow value, as both are linear in the price.
+	fn liquidation_price(
+		asset_id: AssetIdOf<T>,
+		collateral_value: FixedU128,
+		borrow_value: FixedU128,
+		position_collateral_value: FixedU128,
+		position_borrow_value: FixedU128,
+	) -> Result<Option<Price>, DispatchError> {
+		let (numerator, denominator) = if position_collateral_value > position_borrow_value {
+			// The account is liquidated when the price falls below the liquidation price,
+			// unless the other positions are healthy on their own.
+			let other_shortfall = borrow_value
+				.checked_add(&position_collateral_value)
+				.and_then(|r| r.checked_sub(&collateral_value.checked_add(&position_borrow_value)?))
+				.unwrap_or_default();
+			if other_shortfall.is_zero() {
+				return Ok(None);
+			}
+			(other_shortfall, position_collateral_value - position_borrow_value)
+		} else if position_borrow_value > position_collateral_value {
+			// The account is liquidated when the price rises above the liquidation price.
+			let other_liquidity = collateral_value
+				.checked_add(&position_borrow_value)
+				.and_then(|r| r.checked_sub(&borrow_value.checked_add(&position_collateral_value)?))
+				.unwrap_or_default();
+			(other_liquidity, position_borrow_value - position_collateral_value)
+		} else {
+			return Ok(None);
+		};
+
+		let liquidation_price = Self::get_price(asset_id)?
+			.checked_mul(&numerator)
+			.and_then(|r| r.checked_div(&denominator))
+			.ok_or(ArithmeticError::Overflow)?;
+
+		Ok(Some(liquidation_price))
+	}
+
+	/// Scans at most `limit` borrows after `start_key` and returns the borrowers whose health
+	/// factor is below `health_factor`, with the key to resume from. `None` is returned once
+	/// every borrow has been scanned.
+	pub fn get_liquidation_candidates(
+		health_factor: FixedU128,
+		start_key: Option<Vec<u8>>,
+		limit: u32,
+	) -> (Vec<LiquidationCandidate<T::AccountId, BalanceOf<T>>>, Option<Vec<u8>>) {
+		if limit == 0 {
+			// Nothing is scanned, resume from where this scan was asked to start.
+			return (
+				Vec::new(),
+				Some(start_key.unwrap_or_else(|| AccountBorrows::<T>::final_prefix().to_vec())),
+			);
+		}
+		let mut iter = match start_key {
+			Some(raw_key) => AccountBorrows::<T>::iter_from(raw_key),
+			None => AccountBorrows::<T>::iter(),
+		};
+		let mut candidates = Vec::new();
+		let mut last_key = None;
+		for _ in 0..limit {
+			let (asset_id, borrower, snapshot) = match iter.next() {
+				Some(item) => item,
+				None => return (candidates, None),
+			};
+			last_key = Some(AccountBorrows::<T>::hashed_key_for(asset_id, &borrower));
+			if snapshot.principal.is_zero() || !Self::is_first_borrow(asset_id, &borrower) {
+				continue;
+			}
+
+			match Self::liquidation_candidate(&borrower, health_factor) {
+				Ok(Some(candidate)) => candidates.push(candidate),
+				Ok(None) => {},
+				Err(e) => log::trace!(
+					target: "lend-market::get_liquidation_candidates",
+					"borrower: {:?}, error: {:?}",
+					borrower,
+					e,
+				),
+			}
+		}
+
+		(candidates, last_key)
+	}
+
+	// An account borrowing in several markets is reported once, at its first borrow in the
+	// `AccountBorrows` iteration order.
+	fn is_first_borrow(asset_id: AssetIdOf<T>, borrower: &T::AccountId) -> bool {
+		let raw_key = AccountBorrows::<T>::hashed_key_for(asset_id, borrower);
+		!Markets::<T>::iter_keys().any(|other_asset_id| {
+			other_asset_id != asset_id &&
+				!Self::account_borrows(other_asset_id, borrower).principal.is_zero() &&
+				AccountBorrows::<T>::hashed_key_for(other_asset_id, borrower) < raw_key
+		})
+	}
+
+	fn liquidation_candidate(
+		borrower: &T::AccountId,
+		health_factor: FixedU128,
+	) -> Result<Option<LiquidationCandidate<T::AccountId, BalanceOf<T>>>, DispatchError> {
+		let account_health_factor = match Self::get_account_health(borrower)?.health_factor {
+			Some(account_health_factor) if account_health_factor < health_factor =>
+				account_health_factor,
+			_ => return Ok(None),
+		};
+
+		Ok(Some(LiquidationCandidate {
+			account: borrower.clone(),
+			health_factor: account_health_factor,
+			liquidation: Self::max_liquidation(borrower)?,
+		}))
+	}
+
+	/// Returns the liquidation of `borrower` that repays the most value.
+	fn max_liquidation(
+		borrower: &T::AccountId,
+	) -> Result<Option<MaxLiquidation<BalanceOf<T>>>, DispatchError> {
+		let mut max_liquidation: Option<(MaxLiquidation<BalanceOf<T>>, FixedU128)> = None;
+		for (liquidation_asset_id, market) in Self::active_markets() {
+			if Self::account_borrows(liquidation_asset_id, borrower).principal.is_zero() {
+				continue;
+			}
+			let (collateral_asset_id, repay_amount) =
+				match Self::liquidation_plan(borrower, liquidation_asset_id)? {
+					Some(plan) => plan,
+					None => continue,
+				};
+			let liquidate_value = Self::get_asset_value(liquidation_asset_id, repay_amount)?;
+			if max_liquidation
+				.as_ref()
+				.map_or(false, |(_, max_value)| liquidate_value <= *max_value)
+			{
+				continue;
+			}
+
+			// Mirrors the seized amount of `do_liquidate_borrow`
+			let collateral_token_price = Self::get_price(collateral_asset_id)?;
+			let max_seizable_amount = liquidate_value
+				.checked_mul(&market.liquidate_incentive)
+				.and_then(|r| r.checked_div(&collateral_token_price))
+				.ok_or(ArithmeticError::Overflow)?
+				.into_inner();
+			max_liquidation = Some((
+				MaxLiquidation {
+					liquidation_asset_id,
+					max_repay_amount: repay_amount,
+					collateral_asset_id,
+					max_seizable_amount,
+				},
+				liquidate_value,
+			));
+		}
+
+		Ok(max_liquidation.map(|(liquidation, _)| liquidation))
+	}
+
+	/// Returns the collateral to seize and the largest amount to repay if `borrower`'s debt in
+	/// `liquidation_asset_id` can be liquidated.
+	///
+	/// The most valuable collateral is seized, and the repay amount is bounded by the close
+	/// factor and the value of that collateral.
+	pub(crate) fn liquidation_plan(
+		borrower: &T::AccountId,
+		liquidation_asset_id: AssetIdOf<T>,
+	) -> Result<Option<(AssetIdOf<T>, BalanceOf<T>)>, DispatchError> {
+		let (liquidity, shortfall, lf_liquidity, _) =
+			Self::get_account_liquidation_threshold_liquidity(borrower)?;
+		if shortfall.is_zero() && liquidity >= lf_liquidity {
+			return Ok(None);
+		}
+		let market = Self::ensure_active_market(liquidation_asset_id)?;
+
+		let mut collateral: Option<(AssetIdOf<T>, FixedU128)> = None;
+		for (asset_id, _) in Self::active_markets() {
+			if Self::liquidation_free_collaterals().contains(&asset_id) {
+				continue;
+			}
+			let deposits = Self::account_deposits(asset_id, borrower);
+			if !deposits.is_collateral || deposits.voucher_balance.is_zero() {
+				continue;
+			}
+			let exchange_rate = Self::exchange_rate_stored(asset_id)?;
+			let underlying_amount =
+				Self::calc_underlying_amount(deposits.voucher_balance, exchange_rate)?;
+			let value = Self::get_asset_value(asset_id, underlying_amount)?;
+			if collateral.map_or(true, |(_, max_value)| value > max_value) {
+				collateral = Some((asset_id, value));
+			}
+		}
+		let (collateral_asset_id, collateral_value) = match collateral {
+			Some(collateral) => collateral,
+			None => return Ok(None),
+		};
+
+		let close_factor_value = FixedU128::from_inner(market.close_factor.mul_floor(
+			Self::liquidatable_borrows_value(borrower, liquidation_asset_id)?.into_inner(),
+		));
+		let seizable_value = collateral_value
+			.checked_div(&market.liquidate_incentive)
+			.ok_or(ArithmeticError::Underflow)?;
+		let repay_amount = close_factor_value
+			.min(seizable_value)
+			.checked_div(&Self::get_price(liquidation_asset_id)?)
+			.ok_or(ArithmeticError::Underflow)?
+			.into_inner();
+		if repay_amount.is_zero() {
+			return Ok(None);
+		}
+
+		Ok(Some((collateral_asset_id, repay_amount)))
+	}
+}
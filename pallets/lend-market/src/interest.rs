@@ -41,6 +41,15 @@ impl<T: Config> Pallet<T> {
 			borrow_index_new,
 		) = Self::get_market_status(asset_id)?;
 
+		if let InterestRateModel::Adaptive(model) = Self::market(asset_id)?.rate_model {
+			let rate_at_target = model.adjusted_rate_at_target(
+				Self::current_rate_at_target(asset_id, &model),
+				util,
+				now - last_accrued_interest_time,
+			);
+			RateAtTarget::<T>::insert(asset_id, rate_at_target);
+		}
+
 		Self::update_last_accrued_interest_time(asset_id, now)?;
 		TotalBorrows::<T>::insert(asset_id, total_borrows_new);
 		TotalReserves::<T>::insert(asset_id, total_reserves_new);
@@ -66,8 +75,12 @@ impl<T: Config> Pallet<T> {
 		let mut borrow_index = Self::borrow_index(asset_id);
 
 		let util = Self::calc_utilization_ratio(total_cash, total_borrows, total_reserves)?;
-		let borrow_rate =
-			market.rate_model.get_borrow_rate(util).ok_or(ArithmeticError::Overflow)?;
+		let borrow_rate = match market.rate_model {
+			InterestRateModel::Adaptive(model) =>
+				model.get_borrow_rate(util, Self::current_rate_at_target(asset_id, &model)),
+			rate_model => rate_model.get_borrow_rate(util),
+		}
+		.ok_or(ArithmeticError::Overflow)?;
 		let supply_rate =
 			InterestRateModel::get_supply_rate(borrow_rate, util, market.reserve_factor);
 
@@ -106,6 +119,17 @@ impl<T: Config> Pallet<T> {
 		))
 	}
 
+	/// The rate at target utilization of a market using the adaptive rate model
+	///
+	/// The interest of an accrual period is charged at the rate at target of its start, the
+	/// adjusted rate at target is persisted at its end.
+	pub fn current_rate_at_target(asset_id: AssetIdOf<T>, model: &AdaptiveModel) -> Rate {
+		RateAtTarget::<T>::get(asset_id)
+			.unwrap_or(model.initial_rate_at_target)
+			.max(model.min_rate_at_target)
+			.min(model.max_rate_at_target)
+	}
+
 	/// Update the exchange rate according to the totalCash, totalBorrows and totalSupply.
 	/// This function does not accrue interest before calculating the exchange rate.
 	/// exchangeRate = (totalCash + totalBorrows - totalReserves) / totalSupply
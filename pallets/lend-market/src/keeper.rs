@@ -0,0 +1,196 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The built-in liquidation keeper.
+//!
+//! Borrows in shortfall are liquidated by the pallet itself without touching the market cash:
+//! the debt is repaid on credit, the seized collateral is swapped into the repay asset through
+//! the stable pools or Zenlink, the swap settles the repay and the surplus goes to the reserves.
+
+use frame_support::weights::WeightMeter;
+
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+	/// Returns the liquidation keeper account
+	pub fn keeper_account_id() -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating(b"keeper")
+	}
+
+	/// Scans `AccountBorrows` from `KeeperScanCursor` and liquidates the borrows in shortfall
+	/// of the markets that have keeper limits, until `limit` is used up.
+	pub(crate) fn run_liquidation_keeper(limit: Weight) -> Weight {
+		let mut meter = WeightMeter::with_limit(limit);
+		if meter.try_consume(T::DbWeight::get().reads_writes(2, 1)).is_err() {
+			return meter.consumed();
+		}
+		if LiquidationKeeperLimits::<T>::iter_keys().next().is_none() {
+			return meter.consumed();
+		}
+
+		let mut cursor = KeeperScanCursor::<T>::get();
+		let mut iter = match cursor.clone() {
+			Some(raw_key) => AccountBorrows::<T>::iter_from(raw_key),
+			None => AccountBorrows::<T>::iter(),
+		};
+		let mut liquidations = 0u32;
+		while liquidations < T::MaxKeeperLiquidationsPerBlock::get() &&
+			meter.try_consume(T::WeightInfo::keeper_scan_account()).is_ok()
+		{
+			let (asset_id, borrower, snapshot) = match iter.next() {
+				Some(item) => item,
+				None => {
+					// Every borrow has been scanned, start over in the next round.
+					cursor = None;
+					break;
+				},
+			};
+
+			if let Some(keeper_limit) = LiquidationKeeperLimits::<T>::get(asset_id)
+				.filter(|_| !snapshot.principal.is_zero())
+			{
+				match Self::keeper_liquidation_plan(&borrower, asset_id, &keeper_limit) {
+					Ok(Some((collateral_asset_id, repay_amount))) => {
+						// Leave the borrower to the next block if the liquidation doesn't fit.
+						if meter.try_consume(T::WeightInfo::keeper_liquidate()).is_err() {
+							break;
+						}
+						liquidations += 1;
+						if let Err(e) = Self::keeper_liquidate(
+							&borrower,
+							asset_id,
+							collateral_asset_id,
+							repay_amount,
+							&keeper_limit,
+						) {
+							log::error!(
+								target: "lend-market::keeper",
+								"borrower: {:?}, liquidation_asset_id: {:?}, collateral_asset_id: {:?}, error: {:?}",
+								borrower,
+								asset_id,
+								collateral_asset_id,
+								e,
+							);
+						}
+					},
+					Ok(None) => {},
+					Err(e) => log::trace!(
+						target: "lend-market::keeper",
+						"borrower: {:?}, liquidation_asset_id: {:?}, error: {:?}",
+						borrower,
+						asset_id,
+						e,
+					),
+				}
+			}
+			cursor = Some(AccountBorrows::<T>::hashed_key_for(asset_id, &borrower));
+		}
+
+		match cursor {
+			Some(raw_key) => KeeperScanCursor::<T>::put(raw_key),
+			None => KeeperScanCursor::<T>::kill(),
+		}
+		meter.consumed()
+	}
+
+	/// Returns the collateral to seize and the amount to repay if `borrower`'s debt in
+	/// `liquidation_asset_id` can be liquidated by the keeper, see `liquidation_plan`.
+	///
+	/// The repay amount is also bounded by `keeper_limit.max_repay_amount`.
+	pub(crate) fn keeper_liquidation_plan(
+		borrower: &T::AccountId,
+		liquidation_asset_id: AssetIdOf<T>,
+		keeper_limit: &LiquidationKeeperLimit<BalanceOf<T>>,
+	) -> Result<Option<(AssetIdOf<T>, BalanceOf<T>)>, DispatchError> {
+		Ok(Self::liquidation_plan(borrower, liquidation_asset_id)?.map(
+			|(collateral_asset_id, repay_amount)| {
+				(collateral_asset_id, repay_amount.min(keeper_limit.max_repay_amount))
+			},
+		))
+	}
+
+	/// Liquidates `repay_amount` of `borrower`'s debt in `liquidation_asset_id` on behalf of
+	/// the protocol and seizes `collateral_asset_id`.
+	///
+	/// The repay is paid with the swap of the seized collateral, which must return at least
+	/// `repay_amount` plus `keeper_limit.min_profit`, otherwise the whole liquidation is
+	/// reverted.
+	#[transactional]
+	pub(crate) fn keeper_liquidate(
+		borrower: &T::AccountId,
+		liquidation_asset_id: AssetIdOf<T>,
+		collateral_asset_id: AssetIdOf<T>,
+		repay_amount: BalanceOf<T>,
+		keeper_limit: &LiquidationKeeperLimit<BalanceOf<T>>,
+	) -> DispatchResult {
+		let keeper = Self::keeper_account_id();
+		Self::accrue_interest(liquidation_asset_id)?;
+		Self::accrue_interest(collateral_asset_id)?;
+
+		Self::liquidate_borrow_on_credit(
+			&keeper,
+			borrower,
+			liquidation_asset_id,
+			repay_amount,
+			collateral_asset_id,
+		)?;
+
+		let seized_vouchers = Self::account_deposits(collateral_asset_id, &keeper).voucher_balance;
+		let seized_amount = Self::do_redeem_voucher(&keeper, collateral_asset_id, seized_vouchers)?;
+		let min_amount_out = repay_amount
+			.checked_add(keeper_limit.min_profit)
+			.ok_or(ArithmeticError::Overflow)?;
+		let amount_out = T::SwapRouter::swap_exact_in(
+			&keeper,
+			collateral_asset_id,
+			liquidation_asset_id,
+			seized_amount,
+			min_amount_out,
+		)?;
+		ensure!(amount_out >= min_amount_out, Error::<T>::KeeperLiquidationUnprofitable);
+
+		// Pay the repay and keep the surplus as reserves.
+		T::Assets::transfer(
+			liquidation_asset_id,
+			&keeper,
+			&Self::account_id(),
+			amount_out,
+			Preservation::Expendable,
+		)?;
+		let profit = amount_out.checked_sub(repay_amount).ok_or(ArithmeticError::Underflow)?;
+		TotalReserves::<T>::try_mutate(liquidation_asset_id, |total_reserves| -> DispatchResult {
+			*total_reserves =
+				total_reserves.checked_add(profit).ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})?;
+		if Self::has_bad_debt(borrower) {
+			Self::do_write_off_bad_debt(borrower)?;
+		}
+
+		Self::deposit_event(Event::<T>::KeeperLiquidated(
+			borrower.clone(),
+			liquidation_asset_id,
+			collateral_asset_id,
+			repay_amount,
+			seized_amount,
+			profit,
+		));
+
+		Ok(())
+	}
+}
@@ -26,15 +26,16 @@ use core::cmp::max;
 
 pub use crate::rate_model::*;
 use bifrost_primitives::{
-	Balance, CurrencyId, Liquidity, Price, Rate, Ratio, Shortfall, Timestamp,
+	Balance, CurrencyId, Liquidity, Price, Rate, Ratio, Shortfall, SwapRouter, Timestamp,
 };
 use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
 	pallet_prelude::*,
 	require_transactional,
 	traits::{
 		fungibles::{Inspect, Mutate},
 		tokens::{Fortitude, Preservation},
-		UnixTime,
+		Contains, UnixTime,
 	},
 	transactional, PalletId,
 };
@@ -47,16 +48,19 @@ use pallet_traits::{
 };
 use sp_runtime::{
 	traits::{
-		AccountIdConversion, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One,
+		AccountIdConversion, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Dispatchable, One,
 		SaturatedConversion, Saturating, StaticLookup, Zero,
 	},
 	ArithmeticError, FixedPointNumber, FixedU128,
 };
-use sp_std::{result::Result, vec::Vec};
+use sp_std::{boxed::Box, result::Result, vec::Vec};
 
 use log;
 use sp_io::hashing::blake2_256;
-pub use types::{BorrowSnapshot, Deposits, EarnedSnapshot, Market, MarketState, RewardMarketState};
+pub use types::{
+	BorrowSnapshot, Deposits, EarnedSnapshot, LiquidationKeeperLimit, Market, MarketState,
+	RewardMarketState, WindDown,
+};
 pub use weights::WeightInfo;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -67,11 +71,16 @@ pub mod mock;
 #[cfg(test)]
 mod tests;
 
+mod bad_debt;
+mod delegation;
 mod farming;
+mod health;
 mod interest;
+mod keeper;
 mod lend_token;
 mod rate_model;
 mod types;
+mod wind_down;
 
 pub mod weights;
 
@@ -132,6 +141,23 @@ pub mod pallet {
 
 		#[pallet::constant]
 		type LiquidationFreeAssetId: Get<AssetIdOf<Self>>;
+
+		/// Swaps the collateral seized by the liquidation keeper through the stable pools and
+		/// Zenlink.
+		type SwapRouter: SwapRouter<Self::AccountId, BalanceOf<Self>>;
+
+		/// The maximum number of liquidations the keeper executes in one block.
+		#[pallet::constant]
+		type MaxKeeperLiquidationsPerBlock: Get<u32>;
+
+		/// The overarching call type, dispatched with the flash loaned funds.
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>;
+
+		/// The calls that can be dispatched with the flash loaned funds.
+		type FlashLoanCallFilter: Contains<<Self as Config>::RuntimeCall>;
 	}
 
 	#[pallet::error]
@@ -164,6 +190,8 @@ pub mod pallet {
 		PriceOracleNotReady,
 		/// Oracle price is zero
 		PriceIsZero,
+		/// The price failed the freshness or deviation checks of the price feeder
+		InvalidPrice,
 		/// Invalid asset id
 		InvalidCurrencyId,
 		/// Invalid lend token id
@@ -196,6 +224,26 @@ pub mod pallet {
 		CollateralReserved,
 		/// Market bond does not exist
 		MarketBondDoesNotExist,
+		/// The swapped collateral doesn't cover the repay amount and the minimum profit
+		KeeperLiquidationUnprofitable,
+		/// The borrower still has collateral or has no borrows
+		NoBadDebt,
+		/// The borrow exceeds the allowance granted by the delegator
+		InsufficientBorrowAllowance,
+		/// An account cannot grant a borrow allowance to itself
+		SelfDelegation,
+		/// Market is being wound down, supplies and borrows are frozen
+		MarketWindingDown,
+		/// Market is not being wound down
+		MarketNotWindingDown,
+		/// The wind-down schedule of the market hasn't ended yet
+		WindDownNotFinished,
+		/// Market still has open positions
+		MarketNotEmpty,
+		/// A flash loan is running, the loaned market can't be entered again
+		FlashLoanInProgress,
+		/// The call can't be dispatched with flash loaned funds
+		FlashLoanCallNotAllowed,
 	}
 
 	#[pallet::event]
@@ -266,6 +314,57 @@ pub mod pallet {
 			asset_id: AssetIdOf<T>,
 			market_bond: Vec<AssetIdOf<T>>,
 		},
+		/// Liquidation keeper limits of a market have been updated
+		/// [asset_id, limit]
+		LiquidationKeeperUpdated(AssetIdOf<T>, Option<LiquidationKeeperLimit<BalanceOf<T>>>),
+		/// Event emitted when a borrow is liquidated by the liquidation keeper
+		/// [borrower, liquidation_asset_id, collateral_asset_id, repay_amount,
+		/// collateral_amount, profit]
+		KeeperLiquidated(
+			T::AccountId,
+			AssetIdOf<T>,
+			AssetIdOf<T>,
+			BalanceOf<T>,
+			BalanceOf<T>,
+			BalanceOf<T>,
+		),
+		/// Event emitted when the debt of a borrower without collateral is written off
+		/// [borrower, asset_id, reserves_amount, socialised_amount]
+		BadDebtWrittenOff(T::AccountId, AssetIdOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Event emitted when the deposit of a borrower with bad debt is seized into the reserves
+		/// [borrower, asset_id, voucher_amount, amount]
+		BadDebtSupplySeized(T::AccountId, AssetIdOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Event emitted when a flash loan is repaid
+		/// [borrower, asset_id, amount, fee]
+		FlashLoaned(T::AccountId, AssetIdOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Flash loan fee rate of a market has been updated
+		/// [asset_id, fee_rate]
+		FlashLoanFeeRateUpdated(AssetIdOf<T>, Ratio),
+		/// A delegator allowed a delegatee to borrow against its collateral
+		/// [delegator, delegatee, asset_id, amount]
+		BorrowAllowanceApproved(T::AccountId, T::AccountId, AssetIdOf<T>, BalanceOf<T>),
+		/// A delegator revoked the borrow allowance of a delegatee
+		/// [delegator, delegatee, asset_id]
+		BorrowAllowanceRevoked(T::AccountId, T::AccountId, AssetIdOf<T>),
+		/// A delegatee borrowed against the collateral of a delegator
+		/// [delegator, delegatee, asset_id, amount]
+		DelegatedBorrowed(T::AccountId, T::AccountId, AssetIdOf<T>, BalanceOf<T>),
+		/// Market started winding down
+		/// [asset_id, wind_down]
+		MarketWindDownStarted(AssetIdOf<T>, WindDown<BalanceOf<T>>),
+		/// The deposits of an account in a wound down market were redeemed
+		/// [account, asset_id, redeem_amount]
+		PositionSettled(T::AccountId, AssetIdOf<T>, BalanceOf<T>),
+		/// The dust borrows left in a wound down market were written off
+		/// [asset_id, reserves_amount, socialised_amount]
+		WindDownDustWrittenOff(AssetIdOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Some account storage of a market being delisted was removed, the market is
+		/// delisted by a later call
+		/// [asset_id]
+		MarketDelistingInProgress(AssetIdOf<T>),
+		/// Market was delisted and its storage removed
+		/// [asset_id]
+		MarketDelisted(AssetIdOf<T>),
 	}
 
 	/// The timestamp of the last calculation of accrued interest
@@ -373,6 +472,11 @@ pub mod pallet {
 	pub type UtilizationRatio<T: Config> =
 		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, Ratio, ValueQuery>;
 
+	/// The rate at target utilization of the markets using the adaptive rate model, the model's
+	/// initial rate at target is used until the first accrual
+	#[pallet::storage]
+	pub type RateAtTarget<T: Config> = StorageMap<_, Blake2_128Concat, AssetIdOf<T>, Rate>;
+
 	/// Mapping of asset id to its market
 	#[pallet::storage]
 	pub type Markets<T: Config> =
@@ -456,6 +560,58 @@ pub mod pallet {
 	pub type MarketBond<T: Config> =
 		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, Vec<AssetIdOf<T>>>;
 
+	/// Limits of the liquidation keeper for each borrow market. The keeper only liquidates
+	/// borrows of the markets listed here.
+	/// CurrencyId -> LiquidationKeeperLimit
+	#[pallet::storage]
+	#[pallet::getter(fn liquidation_keeper_limit)]
+	pub type LiquidationKeeperLimits<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, LiquidationKeeperLimit<BalanceOf<T>>>;
+
+	/// The raw `AccountBorrows` key after which the liquidation keeper resumes scanning.
+	#[pallet::storage]
+	pub type KeeperScanCursor<T: Config> = StorageValue<_, Vec<u8>>;
+
+	/// Total bad debt written off by each market
+	/// CurrencyId -> Balance
+	#[pallet::storage]
+	#[pallet::getter(fn bad_debt)]
+	pub type BadDebt<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// The fee rate charged on flash loans of each market
+	/// CurrencyId -> Ratio
+	#[pallet::storage]
+	#[pallet::getter(fn flash_loan_fee_rate)]
+	pub type FlashLoanFeeRate<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, Ratio, ValueQuery>;
+
+	/// The market whose cash is flash loaned by the running flash loan, if any
+	#[pallet::storage]
+	#[pallet::getter(fn flash_loan_in_progress)]
+	pub type FlashLoanInProgress<T: Config> = StorageValue<_, AssetIdOf<T>, OptionQuery>;
+
+	/// The amount a delegatee can still borrow against the collateral of a delegator
+	/// delegator -> (delegatee, CurrencyId) -> Balance
+	#[pallet::storage]
+	#[pallet::getter(fn borrow_allowance)]
+	pub type BorrowAllowances<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(T::AccountId, AssetIdOf<T>),
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The wind-down schedule of each market being wound down
+	/// CurrencyId -> WindDown
+	#[pallet::storage]
+	#[pallet::getter(fn market_wind_down)]
+	pub type MarketWindDowns<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, WindDown<BalanceOf<T>>>;
+
 	/// DefaultVersion is using for initialize the StorageVersion
 	#[pallet::type_value]
 	pub(super) fn DefaultVersion() -> Versions {
@@ -471,6 +627,13 @@ pub mod pallet {
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(PhantomData<T>);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::run_liquidation_keeper(remaining_weight)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Stores a new market and its related currency. Returns `Err` if a currency
@@ -557,6 +720,7 @@ pub mod pallet {
 				stored_market.state = MarketState::Active;
 				stored_market.clone()
 			})?;
+			MarketWindDowns::<T>::remove(asset_id);
 			Self::deposit_event(Event::<T>::ActivatedMarket(asset_id));
 			Ok(().into())
 		}
@@ -580,6 +744,10 @@ pub mod pallet {
 				stored_market.rate_model = rate_model;
 				stored_market.clone()
 			})?;
+			// The rate at target of an adaptive model is kept across parameter updates
+			if !matches!(rate_model, InterestRateModel::Adaptive(_)) {
+				RateAtTarget::<T>::remove(asset_id);
+			}
 			Self::deposit_event(Event::<T>::UpdatedMarket(asset_id, market));
 
 			Ok(().into())
@@ -611,6 +779,7 @@ pub mod pallet {
 			T::UpdateOrigin::ensure_origin(origin)?;
 
 			let market = Self::market(asset_id)?;
+			ensure!(market.state != MarketState::WindingDown, Error::<T>::MarketWindingDown);
 
 			let collateral_factor = collateral_factor.unwrap_or(market.collateral_factor);
 			let liquidation_threshold =
@@ -673,6 +842,12 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			T::UpdateOrigin::ensure_origin(origin)?;
 			ensure!(market.rate_model.check_model(), Error::<T>::InvalidRateModelParam);
+			// A wind-down is only started by `wind_down_market`, which sets its schedule
+			ensure!(
+				market.state != MarketState::WindingDown ||
+					MarketWindDowns::<T>::contains_key(asset_id),
+				Error::<T>::MarketNotWindingDown
+			);
 			if UnderlyingAssetId::<T>::contains_key(market.lend_token_id) {
 				ensure!(
 					Self::underlying_id(market.lend_token_id)? == asset_id,
@@ -684,6 +859,9 @@ pub mod pallet {
 				*stored_market = market;
 				stored_market.clone()
 			})?;
+			if updated_market.state != MarketState::WindingDown {
+				MarketWindDowns::<T>::remove(asset_id);
+			}
 
 			Self::deposit_event(Event::<T>::UpdatedMarket(asset_id, updated_market));
 			Ok(().into())
@@ -1151,6 +1329,287 @@ pub mod pallet {
 			Self::deposit_event(Event::<T>::MarketBonded { asset_id, market_bond });
 			Ok(().into())
 		}
+
+		/// Updates the liquidation keeper limits of a borrow market.
+		///
+		/// The keeper only liquidates borrows of markets that have limits, `None` stops the
+		/// keeper for the market.
+		///
+		/// - `asset_id`: market related currency
+		/// - `limit`: the new keeper limits
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::update_liquidation_keeper())]
+		#[transactional]
+		pub fn update_liquidation_keeper(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			limit: Option<LiquidationKeeperLimit<BalanceOf<T>>>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::market(asset_id)?;
+			match limit {
+				Some(limit) => {
+					ensure!(!limit.max_repay_amount.is_zero(), Error::<T>::InvalidAmount);
+					LiquidationKeeperLimits::<T>::insert(asset_id, limit);
+				},
+				None => LiquidationKeeperLimits::<T>::remove(asset_id),
+			}
+			Self::deposit_event(Event::<T>::LiquidationKeeperUpdated(asset_id, limit));
+			Ok(().into())
+		}
+
+		/// Writes off the debt of a borrower whose collateral has been fully seized.
+		///
+		/// The deposits of the borrower that aren't collateral are seized into the reserves,
+		/// then the debt is covered by the market reserves first, the remainder is spread across
+		/// the suppliers through the exchange rate.
+		///
+		/// - `borrower`: the borrower without collateral
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::write_off_bad_debt())]
+		#[transactional]
+		pub fn write_off_bad_debt(
+			origin: OriginFor<T>,
+			borrower: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::do_write_off_bad_debt(&borrower)?;
+			Ok(().into())
+		}
+
+		/// Lends the cash of an active market and dispatches `call` with the sender as origin.
+		///
+		/// The sender must hold the loan plus the flash loan fee once `call` is dispatched,
+		/// otherwise the whole extrinsic is reverted.
+		///
+		/// - `asset_id`: the asset to be flash loaned.
+		/// - `amount`: the amount to be flash loaned.
+		/// - `call`: the call dispatched with the flash loaned funds, it must pass
+		///   `FlashLoanCallFilter`.
+		#[pallet::call_index(25)]
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			T::WeightInfo::flash_loan().saturating_add(dispatch_info.weight)
+		})]
+		#[transactional]
+		pub fn flash_loan(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(T::FlashLoanCallFilter::contains(&call), Error::<T>::FlashLoanCallNotAllowed);
+			Self::do_flash_loan(&who, asset_id, amount, || {
+				call.dispatch(frame_system::RawOrigin::Signed(who.clone()).into())
+					.map(|_| ())
+					.map_err(|e| e.error)
+			})?;
+			Ok(().into())
+		}
+
+		/// Updates the fee rate of flash loans of a market, the fee goes to the reserves.
+		///
+		/// - `asset_id`: market related currency
+		/// - `fee_rate`: the new fee rate
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::update_flash_loan_fee_rate())]
+		#[transactional]
+		pub fn update_flash_loan_fee_rate(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			fee_rate: Ratio,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::market(asset_id)?;
+			FlashLoanFeeRate::<T>::insert(asset_id, fee_rate);
+			Self::deposit_event(Event::<T>::FlashLoanFeeRateUpdated(asset_id, fee_rate));
+			Ok(().into())
+		}
+
+		/// Sender allows `delegatee` to borrow up to `amount` of `asset_id` against the
+		/// sender's collateral. The borrows are the sender's debt and can be liquidated.
+		///
+		/// - `delegatee`: the account allowed to borrow.
+		/// - `asset_id`: the asset to be borrowed.
+		/// - `amount`: the new allowance, which replaces the current one.
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::approve_borrow_allowance())]
+		#[transactional]
+		pub fn approve_borrow_allowance(
+			origin: OriginFor<T>,
+			delegatee: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(who != delegatee, Error::<T>::SelfDelegation);
+			Self::ensure_open_market(asset_id)?;
+
+			BorrowAllowances::<T>::insert(&who, (&delegatee, asset_id), amount);
+			Self::deposit_event(Event::<T>::BorrowAllowanceApproved(
+				who, delegatee, asset_id, amount,
+			));
+			Ok(().into())
+		}
+
+		/// Sender revokes the borrow allowance of `delegatee`. The debt already borrowed by
+		/// `delegatee` stays on the sender.
+		///
+		/// - `delegatee`: the account allowed to borrow.
+		/// - `asset_id`: the asset to be borrowed.
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::revoke_borrow_allowance())]
+		#[transactional]
+		pub fn revoke_borrow_allowance(
+			origin: OriginFor<T>,
+			delegatee: T::AccountId,
+			asset_id: AssetIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			BorrowAllowances::<T>::remove(&who, (&delegatee, asset_id));
+			Self::deposit_event(Event::<T>::BorrowAllowanceRevoked(who, delegatee, asset_id));
+			Ok(().into())
+		}
+
+		/// Sender borrows against the collateral of `delegator` within the allowance
+		/// `delegator` granted.
+		///
+		/// - `delegator`: the account whose collateral backs the borrow.
+		/// - `asset_id`: the asset to be borrowed.
+		/// - `borrow_amount`: the amount to be borrowed.
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::borrow_on_behalf())]
+		#[transactional]
+		pub fn borrow_on_behalf(
+			origin: OriginFor<T>,
+			delegator: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			#[pallet::compact] borrow_amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			Self::do_borrow_on_behalf(&who, &delegator, asset_id, borrow_amount)?;
+
+			Ok(().into())
+		}
+
+		/// Sender repays some of the debts of `borrower`.
+		///
+		/// - `borrower`: the account whose debt is repaid.
+		/// - `asset_id`: the asset to be repaid.
+		/// - `repay_amount`: the amount to be repaid.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::repay_borrow_on_behalf())]
+		#[transactional]
+		pub fn repay_borrow_on_behalf(
+			origin: OriginFor<T>,
+			borrower: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			#[pallet::compact] repay_amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_active_market(asset_id)?;
+			Self::accrue_interest(asset_id)?;
+
+			let account_borrows = Self::current_borrow_balance(&borrower, asset_id)?;
+			Self::do_repay_borrow_with_amount(
+				&who,
+				&borrower,
+				asset_id,
+				account_borrows,
+				repay_amount,
+			)?;
+			Self::deposit_event(Event::<T>::RepaidBorrow(borrower, asset_id, repay_amount));
+
+			Ok(().into())
+		}
+
+		/// Starts winding down an active market.
+		///
+		/// Supplies and borrows are frozen, the collateral factor and the liquidation threshold
+		/// ramp down to zero over `duration` and the liquidation incentive rises to
+		/// `liquidate_incentive`. Reactivating the market cancels the wind-down.
+		///
+		/// - `asset_id`: market related currency
+		/// - `duration`: the length of the wind-down in seconds
+		/// - `liquidate_incentive`: the liquidation incentive at the end of the wind-down
+		/// - `dust_borrows`: the total borrows written off when the deposits are settled
+		#[pallet::call_index(31)]
+		#[pallet::weight(T::WeightInfo::wind_down_market())]
+		#[transactional]
+		pub fn wind_down_market(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			duration: Timestamp,
+			liquidate_incentive: Rate,
+			dust_borrows: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let market = Self::market(asset_id)?;
+			ensure!(market.state == MarketState::Active, Error::<T>::MarketNotActivated);
+			ensure!(liquidate_incentive >= market.liquidate_incentive, Error::<T>::InvalidFactor);
+
+			let start = T::UnixTime::now().as_secs();
+			let wind_down = WindDown {
+				start,
+				end: start.saturating_add(duration),
+				liquidate_incentive,
+				dust_borrows,
+			};
+			Self::mutate_market(asset_id, |stored_market| {
+				stored_market.state = MarketState::WindingDown;
+				stored_market.clone()
+			})?;
+			MarketWindDowns::<T>::insert(asset_id, wind_down);
+			Self::deposit_event(Event::<T>::MarketWindDownStarted(asset_id, wind_down));
+			Ok(().into())
+		}
+
+		/// Redeems all the deposits of `account` once the wind-down of the market has ended
+		/// and no more than its dust borrows are left.
+		///
+		/// - `asset_id`: market related currency
+		/// - `account`: the supplier whose deposits are redeemed
+		#[pallet::call_index(32)]
+		#[pallet::weight(T::WeightInfo::settle_position())]
+		#[transactional]
+		pub fn settle_position(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			account: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let redeem_amount = Self::do_settle_position(asset_id, &account)?;
+			Self::deposit_event(Event::<T>::PositionSettled(account, asset_id, redeem_amount));
+			Ok(().into())
+		}
+
+		/// Removes a wound down market without supplies, borrows and reserves, along with
+		/// its lend token and reward state.
+		///
+		/// At most `limit` account entries are removed per call, the market itself is removed
+		/// by the call that removes the last of them.
+		///
+		/// - `asset_id`: market related currency
+		/// - `limit`: the maximum number of account entries to remove
+		#[pallet::call_index(33)]
+		#[pallet::weight(T::WeightInfo::delist_market(*limit))]
+		#[transactional]
+		pub fn delist_market(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			limit: u32,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if Self::do_delist_market(asset_id, limit)? {
+				Self::deposit_event(Event::<T>::MarketDelisted(asset_id));
+			} else {
+				Self::deposit_event(Event::<T>::MarketDelistingInProgress(asset_id));
+			}
+			Ok(().into())
+		}
 	}
 }
 
@@ -1428,6 +1887,7 @@ impl<T: Config> Pallet<T> {
 		asset_id: AssetIdOf<T>,
 		voucher_amount: BalanceOf<T>,
 	) -> Result<BalanceOf<T>, DispatchError> {
+		Self::ensure_no_flash_loan(asset_id)?;
 		Self::redeem_allowed(asset_id, who, voucher_amount)?;
 		Self::update_reward_supply_index(asset_id)?;
 		Self::distribute_supplier_reward(asset_id, who)?;
@@ -1474,7 +1934,6 @@ impl<T: Config> Pallet<T> {
 		borrow_amount: BalanceOf<T>,
 	) -> DispatchResult {
 		Self::ensure_under_borrow_cap(asset_id, borrow_amount)?;
-		Self::ensure_enough_cash(asset_id, borrow_amount)?;
 		let borrow_value = Self::get_asset_value(asset_id, borrow_amount)?;
 		Self::ensure_liquidity(
 			borrower,
@@ -1492,7 +1951,6 @@ impl<T: Config> Pallet<T> {
 		borrow_amount: BalanceOf<T>,
 	) -> DispatchResult {
 		Self::ensure_under_borrow_cap(borrow_asset_id, borrow_amount)?;
-		Self::ensure_enough_cash(borrow_asset_id, borrow_amount)?;
 		let borrow_value = Self::get_asset_value(borrow_asset_id, borrow_amount)?;
 		Self::ensure_liquidity_for_market_bond(borrow_asset_id, borrower, borrow_value)?;
 
@@ -1501,6 +1959,7 @@ impl<T: Config> Pallet<T> {
 
 	#[require_transactional]
 	fn do_repay_borrow_with_amount(
+		payer: &T::AccountId,
 		borrower: &T::AccountId,
 		asset_id: AssetIdOf<T>,
 		account_borrows: BalanceOf<T>,
@@ -1514,7 +1973,7 @@ impl<T: Config> Pallet<T> {
 
 		T::Assets::transfer(
 			asset_id,
-			borrower,
+			payer,
 			&Self::account_id(),
 			repay_amount,
 			Preservation::Expendable,
@@ -1541,6 +2000,64 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Records `amount` of `asset_id` as debt of `borrower` and transfers it to `receiver`.
+	pub(crate) fn borrow_to(
+		borrower: &AccountIdOf<T>,
+		receiver: &AccountIdOf<T>,
+		asset_id: AssetIdOf<T>,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		Self::ensure_open_market(asset_id)?;
+		Self::ensure_no_flash_loan(asset_id)?;
+
+		Self::ensure_valid_price(asset_id)?;
+
+		Self::accrue_interest(asset_id)?;
+		Self::ensure_enough_cash(asset_id, amount)?;
+		Self::add_borrow(borrower, asset_id, amount)?;
+
+		T::Assets::transfer(
+			asset_id,
+			&Self::account_id(),
+			receiver,
+			amount,
+			Preservation::Expendable,
+		)?;
+		Self::deposit_event(Event::<T>::Borrowed(borrower.clone(), asset_id, amount));
+		Ok(())
+	}
+
+	/// Records `amount` of `asset_id` as debt of `borrower`, the cash is moved by the caller.
+	fn add_borrow(
+		borrower: &AccountIdOf<T>,
+		asset_id: AssetIdOf<T>,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		Self::borrow_allowed_for_market_bond(asset_id, borrower, amount)?;
+		Self::borrow_allowed(asset_id, borrower, amount)?;
+
+		// update borrow index after accrue interest.
+		Self::update_reward_borrow_index(asset_id)?;
+		Self::distribute_borrower_reward(asset_id, borrower)?;
+
+		let account_borrows = Self::current_borrow_balance(borrower, asset_id)?;
+		let account_borrows_new =
+			account_borrows.checked_add(amount).ok_or(ArithmeticError::Overflow)?;
+		let total_borrows = Self::total_borrows(asset_id);
+		let total_borrows_new =
+			total_borrows.checked_add(amount).ok_or(ArithmeticError::Overflow)?;
+		AccountBorrows::<T>::insert(
+			asset_id,
+			borrower,
+			BorrowSnapshot {
+				principal: account_borrows_new,
+				borrow_index: Self::borrow_index(asset_id),
+			},
+		);
+		TotalBorrows::<T>::insert(asset_id, total_borrows_new);
+		Ok(())
+	}
+
 	// Calculates and returns the most recent amount of borrowed balance of `currency_id`
 	// for `who`.
 	pub fn current_borrow_balance(
@@ -1617,19 +2134,9 @@ impl<T: Config> Pallet<T> {
 
 		// The liquidator may not repay more than 50%(close_factor) of the borrower's borrow
 		// balance.
-		let account_borrows = Self::current_borrow_balance(borrower, liquidation_asset_id)?;
-		let account_borrows_value = Self::get_asset_value(liquidation_asset_id, account_borrows)?;
 		let repay_value = Self::get_asset_value(liquidation_asset_id, repay_amount)?;
-		let effects_borrows_value = if liquidation_asset_id == T::LiquidationFreeAssetId::get() {
-			let base_position = Self::get_lf_base_position(borrower)?;
-			if account_borrows_value > base_position {
-				account_borrows_value - base_position
-			} else {
-				FixedU128::zero()
-			}
-		} else {
-			account_borrows_value
-		};
+		let effects_borrows_value =
+			Self::liquidatable_borrows_value(borrower, liquidation_asset_id)?;
 
 		if market.close_factor.mul_ceil(effects_borrows_value.into_inner()) <
 			repay_value.into_inner()
@@ -1640,6 +2147,25 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// The value of `borrower`'s debt in `liquidation_asset_id` that can be liquidated, the debt
+	/// of the liquidation free asset covered by the liquidation free collaterals is excluded.
+	fn liquidatable_borrows_value(
+		borrower: &T::AccountId,
+		liquidation_asset_id: AssetIdOf<T>,
+	) -> Result<FixedU128, DispatchError> {
+		let account_borrows = Self::current_borrow_balance(borrower, liquidation_asset_id)?;
+		let account_borrows_value = Self::get_asset_value(liquidation_asset_id, account_borrows)?;
+		if liquidation_asset_id == T::LiquidationFreeAssetId::get() {
+			let base_position = Self::get_lf_base_position(borrower)?;
+			if account_borrows_value > base_position {
+				return Ok(account_borrows_value - base_position);
+			}
+			return Ok(FixedU128::zero());
+		}
+
+		Ok(account_borrows_value)
+	}
+
 	/// Note:
 	/// - liquidation_asset_id is borrower's debt asset.
 	/// - collateral_asset_id is borrower's collateral asset.
@@ -1656,18 +2182,55 @@ impl<T: Config> Pallet<T> {
 		liquidation_asset_id: AssetIdOf<T>,
 		repay_amount: BalanceOf<T>,
 		collateral_asset_id: AssetIdOf<T>,
+	) -> DispatchResult {
+		Self::liquidate_borrow_on_credit(
+			&liquidator,
+			&borrower,
+			liquidation_asset_id,
+			repay_amount,
+			collateral_asset_id,
+		)?;
+		// The liquidator repays the borrower's debt
+		T::Assets::transfer(
+			liquidation_asset_id,
+			&liquidator,
+			&Self::account_id(),
+			repay_amount,
+			Preservation::Expendable,
+		)?;
+
+		// The remaining debt can't be liquidated anymore once all collateral is seized.
+		if Self::has_bad_debt(&borrower) {
+			Self::do_write_off_bad_debt(&borrower)?;
+		}
+
+		Ok(())
+	}
+
+	/// Reduces `borrower`'s debt by `repay_amount` and gives the seized collateral to
+	/// `liquidator`, leaving the liquidator to pay `repay_amount` into the pallet account in the
+	/// same transaction.
+	pub(crate) fn liquidate_borrow_on_credit(
+		liquidator: &T::AccountId,
+		borrower: &T::AccountId,
+		liquidation_asset_id: AssetIdOf<T>,
+		repay_amount: BalanceOf<T>,
+		collateral_asset_id: AssetIdOf<T>,
 	) -> DispatchResult {
 		Self::ensure_active_market(liquidation_asset_id)?;
 		Self::ensure_active_market(collateral_asset_id)?;
+		Self::ensure_no_flash_loan(collateral_asset_id)?;
+		Self::ensure_valid_price(liquidation_asset_id)?;
+		Self::ensure_valid_price(collateral_asset_id)?;
 
 		let market = Self::market(liquidation_asset_id)?;
 
 		if borrower == liquidator {
 			return Err(Error::<T>::LiquidatorIsBorrower.into());
 		}
-		Self::liquidate_borrow_allowed(&borrower, liquidation_asset_id, repay_amount, &market)?;
+		Self::liquidate_borrow_allowed(borrower, liquidation_asset_id, repay_amount, &market)?;
 
-		let deposits = AccountDeposits::<T>::get(collateral_asset_id, &borrower);
+		let deposits = AccountDeposits::<T>::get(collateral_asset_id, borrower);
 		if !deposits.is_collateral {
 			return Err(Error::<T>::DepositsAreNotCollateral.into());
 		}
@@ -1702,18 +2265,15 @@ impl<T: Config> Pallet<T> {
 			.ok_or(ArithmeticError::Underflow)?
 			.into_inner();
 
-		//inside transfer token
 		Self::liquidated_transfer(
-			&liquidator,
-			&borrower,
+			liquidator,
+			borrower,
 			liquidation_asset_id,
 			collateral_asset_id,
 			repay_amount,
 			real_collateral_underlying_amount,
 			&market,
-		)?;
-
-		Ok(())
+		)
 	}
 
 	#[require_transactional]
@@ -1741,18 +2301,11 @@ impl<T: Config> Pallet<T> {
 		// update borrow index after accrue interest.
 		Self::update_reward_borrow_index(liquidation_asset_id)?;
 		Self::distribute_borrower_reward(liquidation_asset_id, liquidator)?;
+		// The repay is paid after the debt is reduced, so the exchange rate is taken before, when
+		// both are still counted.
+		let exchange_rate = Self::exchange_rate_stored(collateral_asset_id)?;
 
-		// 1.liquidator repay borrower's debt,
-		// transfer from liquidator to module account
-		T::Assets::transfer(
-			liquidation_asset_id,
-			liquidator,
-			&Self::account_id(),
-			repay_amount,
-			Preservation::Expendable,
-		)?;
-
-		// 2.the system reduce borrower's debt
+		// 1.the system reduce borrower's debt
 		let account_borrows = Self::current_borrow_balance(borrower, liquidation_asset_id)?;
 		let account_borrows_new =
 			account_borrows.checked_sub(repay_amount).ok_or(ArithmeticError::Underflow)?;
@@ -1778,8 +2331,7 @@ impl<T: Config> Pallet<T> {
 			&Self::incentive_reward_account_id()?,
 		)?;
 
-		// 3.the liquidator will receive voucher token from borrower
-		let exchange_rate = Self::exchange_rate_stored(collateral_asset_id)?;
+		// 2.the liquidator will receive voucher token from borrower
 		let collateral_amount =
 			Self::calc_collateral_amount(collateral_underlying_amount, exchange_rate)?;
 		AccountDeposits::<T>::try_mutate(
@@ -1844,6 +2396,13 @@ impl<T: Config> Pallet<T> {
 			.ok_or_else(|| Error::<T>::MarketNotActivated.into())
 	}
 
+	/// Ensures the market accepts new supplies and borrows.
+	fn ensure_open_market(asset_id: AssetIdOf<T>) -> Result<Market<BalanceOf<T>>, DispatchError> {
+		let market = Self::ensure_active_market(asset_id)?;
+		ensure!(market.state != MarketState::WindingDown, Error::<T>::MarketWindingDown);
+		Ok(market)
+	}
+
 	/// Ensure market is enough to supply `amount` asset.
 	fn ensure_under_supply_cap(asset_id: AssetIdOf<T>, amount: BalanceOf<T>) -> DispatchResult {
 		let market = Self::market(asset_id)?;
@@ -1866,6 +2425,13 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Make sure the cash of the market isn't lent out by the running flash loan, exchange
+	/// rates computed from it would be wrong.
+	fn ensure_no_flash_loan(asset_id: AssetIdOf<T>) -> DispatchResult {
+		ensure!(Self::flash_loan_in_progress() != Some(asset_id), Error::<T>::FlashLoanInProgress);
+		Ok(())
+	}
+
 	/// Make sure there is enough cash available in the pool
 	fn ensure_enough_cash(asset_id: AssetIdOf<T>, amount: BalanceOf<T>) -> DispatchResult {
 		let reducible_cash = Self::get_total_cash(asset_id)
@@ -1990,6 +2556,13 @@ impl<T: Config> Pallet<T> {
 		Ok(price)
 	}
 
+	/// Borrows and liquidations are refused while the price of an asset fails the checks of
+	/// the price feeder.
+	fn ensure_valid_price(asset_id: AssetIdOf<T>) -> DispatchResult {
+		ensure!(T::PriceFeeder::is_price_valid(&asset_id), Error::<T>::InvalidPrice);
+		Ok(())
+	}
+
 	// Returns the value of the asset, in dollars.
 	// Formula: `value = oracle_price * balance / 1e18(oracle_price_decimal) / asset_decimal`
 	// As the price is a result of `oracle_price * 10.pow(18 - asset_decimal)`,
@@ -2012,7 +2585,9 @@ impl<T: Config> Pallet<T> {
 	//
 	// Returns `Err` if market does not exist.
 	pub fn market(asset_id: AssetIdOf<T>) -> Result<Market<BalanceOf<T>>, DispatchError> {
-		Markets::<T>::try_get(asset_id).map_err(|_err| Error::<T>::MarketDoesNotExist.into())
+		Markets::<T>::try_get(asset_id)
+			.map(|market| Self::apply_wind_down(asset_id, market))
+			.map_err(|_err| Error::<T>::MarketDoesNotExist.into())
 	}
 
 	// Mutates a stored Market.
@@ -2033,9 +2608,14 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
-	// All markets that are `MarketStatus::Active`.
+	// All markets that are `MarketStatus::Active`, and the markets being wound down as their
+	// positions are still open.
 	fn active_markets() -> impl Iterator<Item = (AssetIdOf<T>, Market<BalanceOf<T>>)> {
-		Markets::<T>::iter().filter(|(_, market)| market.state == MarketState::Active)
+		Markets::<T>::iter()
+			.filter(|(_, market)| {
+				matches!(market.state, MarketState::Active | MarketState::WindingDown)
+			})
+			.map(|(asset_id, market)| (asset_id, Self::apply_wind_down(asset_id, market)))
 	}
 
 	// Returns a stored asset_id
@@ -2077,6 +2657,42 @@ impl<T: Config> Pallet<T> {
 		Self::deposit_event(Event::<T>::Redeemed(who.clone(), asset_id, redeem_amount));
 		Ok(redeem_amount)
 	}
+
+	/// Lends `amount` of `asset_id` to `borrower` while `f` runs. The loaned market can't be
+	/// entered again until `f` returns, and flash loans can't be nested.
+	fn run_flash_loan<R>(
+		borrower: &AccountIdOf<T>,
+		asset_id: AssetIdOf<T>,
+		amount: BalanceOf<T>,
+		f: impl FnOnce() -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError> {
+		ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+		ensure!(!FlashLoanInProgress::<T>::exists(), Error::<T>::FlashLoanInProgress);
+		Self::ensure_open_market(asset_id)?;
+		Self::accrue_interest(asset_id)?;
+		Self::ensure_enough_cash(asset_id, amount)?;
+
+		FlashLoanInProgress::<T>::put(asset_id);
+		T::Assets::transfer(
+			asset_id,
+			&Self::account_id(),
+			borrower,
+			amount,
+			Preservation::Expendable,
+		)?;
+		let result = f()?;
+		FlashLoanInProgress::<T>::kill();
+
+		Ok(result)
+	}
+
+	/// The flash loan fee goes to the reserves.
+	fn add_flash_loan_fee(asset_id: AssetIdOf<T>, fee: BalanceOf<T>) -> DispatchResult {
+		TotalReserves::<T>::try_mutate(asset_id, |total_reserves| -> DispatchResult {
+			*total_reserves = total_reserves.checked_add(fee).ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})
+	}
 }
 
 impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for Pallet<T> {
@@ -2086,7 +2702,8 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 		amount: BalanceOf<T>,
 	) -> Result<(), DispatchError> {
 		ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
-		Self::ensure_active_market(asset_id)?;
+		Self::ensure_open_market(asset_id)?;
+		Self::ensure_no_flash_loan(asset_id)?;
 		Self::ensure_under_supply_cap(asset_id, amount)?;
 
 		Self::accrue_interest(asset_id)?;
@@ -2129,40 +2746,7 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 		asset_id: AssetIdOf<T>,
 		amount: BalanceOf<T>,
 	) -> Result<(), DispatchError> {
-		Self::ensure_active_market(asset_id)?;
-
-		Self::accrue_interest(asset_id)?;
-		Self::borrow_allowed_for_market_bond(asset_id, borrower, amount)?;
-		Self::borrow_allowed(asset_id, borrower, amount)?;
-
-		// update borrow index after accrue interest.
-		Self::update_reward_borrow_index(asset_id)?;
-		Self::distribute_borrower_reward(asset_id, borrower)?;
-
-		let account_borrows = Self::current_borrow_balance(borrower, asset_id)?;
-		let account_borrows_new =
-			account_borrows.checked_add(amount).ok_or(ArithmeticError::Overflow)?;
-		let total_borrows = Self::total_borrows(asset_id);
-		let total_borrows_new =
-			total_borrows.checked_add(amount).ok_or(ArithmeticError::Overflow)?;
-		AccountBorrows::<T>::insert(
-			asset_id,
-			borrower,
-			BorrowSnapshot {
-				principal: account_borrows_new,
-				borrow_index: Self::borrow_index(asset_id),
-			},
-		);
-		TotalBorrows::<T>::insert(asset_id, total_borrows_new);
-		T::Assets::transfer(
-			asset_id,
-			&Self::account_id(),
-			borrower,
-			amount,
-			Preservation::Expendable,
-		)?;
-		Self::deposit_event(Event::<T>::Borrowed(borrower.clone(), asset_id, amount));
-		Ok(())
+		Self::borrow_to(borrower, borrower, asset_id, amount)
 	}
 
 	fn do_collateral_asset(
@@ -2175,6 +2759,7 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 		let mut deposits = Self::account_deposits(asset_id, supplier);
 		// turn on the collateral button
 		if enable {
+			Self::ensure_open_market(asset_id)?;
 			deposits.is_collateral = true;
 			AccountDeposits::<T>::insert(asset_id, supplier, deposits);
 			Self::deposit_event(Event::<T>::CollateralAssetAdded(supplier.clone(), asset_id));
@@ -2214,7 +2799,7 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 		Self::ensure_active_market(asset_id)?;
 		Self::accrue_interest(asset_id)?;
 		let account_borrows = Self::current_borrow_balance(borrower, asset_id)?;
-		Self::do_repay_borrow_with_amount(borrower, asset_id, account_borrows, amount)?;
+		Self::do_repay_borrow_with_amount(borrower, borrower, asset_id, account_borrows, amount)?;
 		Self::deposit_event(Event::<T>::RepaidBorrow(borrower.clone(), asset_id, amount));
 		Ok(())
 	}
@@ -2233,6 +2818,55 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 		Self::deposit_event(Event::<T>::Redeemed(supplier.clone(), asset_id, redeem_amount));
 		Ok(())
 	}
+
+	fn flash_loan_fee(asset_id: AssetIdOf<T>, amount: BalanceOf<T>) -> BalanceOf<T> {
+		Self::flash_loan_fee_rate(asset_id).mul_ceil(amount)
+	}
+
+	#[transactional]
+	fn do_flash_loan<R>(
+		borrower: &AccountIdOf<T>,
+		asset_id: AssetIdOf<T>,
+		amount: BalanceOf<T>,
+		f: impl FnOnce() -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError> {
+		let fee = Self::flash_loan_fee(asset_id, amount);
+		let result = Self::run_flash_loan(borrower, asset_id, amount, f)?;
+
+		let repay_amount = amount.checked_add(fee).ok_or(ArithmeticError::Overflow)?;
+		T::Assets::transfer(
+			asset_id,
+			borrower,
+			&Self::account_id(),
+			repay_amount,
+			Preservation::Expendable,
+		)?;
+		Self::add_flash_loan_fee(asset_id, fee)?;
+
+		Self::deposit_event(Event::<T>::FlashLoaned(borrower.clone(), asset_id, amount, fee));
+		Ok(result)
+	}
+
+	#[transactional]
+	fn do_flash_borrow<R>(
+		borrower: &AccountIdOf<T>,
+		asset_id: AssetIdOf<T>,
+		amount: BalanceOf<T>,
+		f: impl FnOnce() -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError> {
+		Self::ensure_valid_price(asset_id)?;
+		let fee = Self::flash_loan_fee(asset_id, amount);
+		let result = Self::run_flash_loan(borrower, asset_id, amount, f)?;
+
+		// The loaned cash stays with the borrower, the loan and its fee become its debt.
+		let borrow_amount = amount.checked_add(fee).ok_or(ArithmeticError::Overflow)?;
+		Self::add_borrow(borrower, asset_id, borrow_amount)?;
+		Self::add_flash_loan_fee(asset_id, fee)?;
+
+		Self::deposit_event(Event::<T>::FlashLoaned(borrower.clone(), asset_id, amount, fee));
+		Self::deposit_event(Event::<T>::Borrowed(borrower.clone(), asset_id, borrow_amount));
+		Ok(result)
+	}
 }
 
 impl<T: Config> LendMarketMarketDataProvider<AssetIdOf<T>, BalanceOf<T>> for Pallet<T> {
@@ -2274,6 +2908,10 @@ impl<T: Config> LendMarketMarketDataProvider<AssetIdOf<T>, BalanceOf<T>> for Pal
 		if let Ok(market) = Self::market(asset_id) {
 			let rate = match market.rate_model {
 				InterestRateModel::Jump(jump) => Some(jump.full_rate),
+				InterestRateModel::Adaptive(adaptive) => Some(
+					Self::current_rate_at_target(asset_id, &adaptive)
+						.saturating_mul(adaptive.curve_steepness),
+				),
 				_ => None,
 			};
 			return rate;
@@ -19,20 +19,29 @@ use sp_runtime::BuildStorage;
 
 use bifrost_asset_registry::AssetIdMaps;
 pub use bifrost_primitives::{currency::*, *};
+use bifrost_stable_pool::{traits::StablePoolHandler, PoolTokenIndex, StableAssetPoolId};
+use cumulus_primitives_core::ParaId;
 use frame_support::{
 	construct_runtime, derive_impl, parameter_types,
-	traits::{AsEnsureOriginWithArg, Nothing, SortedMembers},
+	traits::{AsEnsureOriginWithArg, Contains, Nothing, SortedMembers},
 	PalletId,
 };
 use frame_system::{EnsureRoot, EnsureSigned, EnsureSignedBy};
-use orml_traits::{DataFeeder, DataProvider, DataProviderExtended};
-use sp_runtime::{traits::IdentityLookup, AccountId32};
-use sp_std::vec::Vec;
+use orml_traits::{DataFeeder, DataProvider, DataProviderExtended, MultiCurrency};
+use sp_runtime::{
+	traits::{IdentityLookup, UniqueSaturatedInto},
+	AccountId32, DispatchError, DispatchResult, FixedU128,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
 use std::{
 	cell::RefCell,
 	collections::HashMap,
 	hash::{Hash, Hasher},
 };
+use zenlink_protocol::{
+	AssetBalance, AssetId as ZenlinkAssetId, ExportZenlink, LocalAssetHandler, PairLpGenerate,
+	ZenlinkMultiAssets,
+};
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -47,6 +56,7 @@ construct_runtime!(
 		TimestampPallet: pallet_timestamp,
 		Assets: pallet_assets,
 		Prices: pallet_prices,
+		ZenlinkProtocol: zenlink_protocol,
 	}
 );
 
@@ -233,6 +243,13 @@ impl MockPriceFeeder {
 					.collect()
 			)
 		};
+		pub static INVALID_PRICES: RefCell<Vec<CurrencyId>> = RefCell::new(vec![]);
+		pub static USE_PRICES_PALLET: RefCell<bool> = RefCell::new(false);
+	}
+
+	/// Prices every asset with the prices pallet instead of the mock prices.
+	pub fn use_prices_pallet() {
+		Self::USE_PRICES_PALLET.with(|use_prices_pallet| *use_prices_pallet.borrow_mut() = true);
 	}
 
 	pub fn set_price(asset_id: CurrencyId, price: Price) {
@@ -246,18 +263,39 @@ impl MockPriceFeeder {
 			for (_, val) in prices.borrow_mut().iter_mut() {
 				*val = Some((Price::saturating_from_integer(1), 1u64));
 			}
-		})
+		});
+		Self::INVALID_PRICES.with(|invalid_prices| invalid_prices.borrow_mut().clear());
+	}
+
+	pub fn set_price_valid(asset_id: CurrencyId, valid: bool) {
+		Self::INVALID_PRICES.with(|invalid_prices| {
+			let mut invalid_prices = invalid_prices.borrow_mut();
+			invalid_prices.retain(|&id| id != asset_id);
+			if !valid {
+				invalid_prices.push(asset_id);
+			}
+		});
 	}
 }
 
 impl PriceFeeder for MockPriceFeeder {
 	fn get_price(asset_id: &CurrencyId) -> Option<PriceDetail> {
+		if Self::USE_PRICES_PALLET.with(|use_prices_pallet| *use_prices_pallet.borrow()) {
+			return Prices::get_price(asset_id);
+		}
 		Self::PRICES.with(|prices| *prices.borrow().get(&CurrencyIdWrap(*asset_id)).unwrap())
 	}
 
-	fn get_normal_price(_asset_id: &CurrencyId) -> Option<u128> {
+	fn get_normal_price(asset_id: &CurrencyId) -> Option<u128> {
+		if Self::USE_PRICES_PALLET.with(|use_prices_pallet| *use_prices_pallet.borrow()) {
+			return Prices::get_normal_price(asset_id);
+		}
 		todo!()
 	}
+
+	fn is_price_valid(asset_id: &CurrencyId) -> bool {
+		Self::INVALID_PRICES.with(|invalid_prices| !invalid_prices.borrow().contains(asset_id))
+	}
 }
 
 parameter_types! {
@@ -290,6 +328,119 @@ impl pallet_assets::Config for Test {
 	type CallbackHandle = ();
 }
 
+// Pool 0, whose LP token stands for 2 KSM at the invariant
+pub struct MockStablePool;
+impl StablePoolHandler for MockStablePool {
+	type Balance = Balance;
+	type AccountId = AccountId;
+	type CurrencyId = CurrencyId;
+
+	fn add_liquidity(
+		_who: AccountId,
+		_pool_id: StableAssetPoolId,
+		_amounts: Vec<Balance>,
+		_min_mint_amount: Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn swap(
+		_who: &AccountId,
+		_pool_id: StableAssetPoolId,
+		_currency_id_in: PoolTokenIndex,
+		_currency_id_out: PoolTokenIndex,
+		_amount: Balance,
+		_min_dy: Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn redeem_single(
+		_who: AccountId,
+		_pool_id: StableAssetPoolId,
+		_amount: Balance,
+		_i: PoolTokenIndex,
+		_min_redeem_amount: Balance,
+		_asset_length: u32,
+	) -> Result<(Balance, Balance), DispatchError> {
+		Ok((0, 0))
+	}
+
+	fn redeem_multi(
+		_who: AccountId,
+		_pool_id: StableAssetPoolId,
+		_amounts: Vec<Balance>,
+		_max_redeem_amount: Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn redeem_proportion(
+		_who: AccountId,
+		_pool_id: StableAssetPoolId,
+		_amount: Balance,
+		_min_redeem_amounts: Vec<Balance>,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn get_pool_token_index(
+		_pool_id: StableAssetPoolId,
+		_currency_id: CurrencyId,
+	) -> Option<PoolTokenIndex> {
+		None
+	}
+
+	fn get_swap_output(
+		_pool_id: StableAssetPoolId,
+		_currency_id_in: PoolTokenIndex,
+		_currency_id_out: PoolTokenIndex,
+		_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("no swap"))
+	}
+
+	fn get_swap_input(
+		_pool_id: StableAssetPoolId,
+		_currency_id_in: PoolTokenIndex,
+		_currency_id_out: PoolTokenIndex,
+		_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("no swap"))
+	}
+
+	fn get_twap(
+		_pool_id: StableAssetPoolId,
+		_currency_id_in: PoolTokenIndex,
+		_currency_id_out: PoolTokenIndex,
+		_window: u32,
+	) -> Option<FixedU128> {
+		None
+	}
+
+	fn get_pool_id(
+		_currency_id_in: &CurrencyId,
+		_currency_id_out: &CurrencyId,
+	) -> Option<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)> {
+		None
+	}
+
+	fn get_pool_ids(
+		_currency_id_in: &CurrencyId,
+		_currency_id_out: &CurrencyId,
+	) -> Vec<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)> {
+		vec![]
+	}
+
+	fn get_lp_token_value(
+		pool_id: StableAssetPoolId,
+		amount: Balance,
+	) -> Result<Vec<(CurrencyId, Balance)>, DispatchError> {
+		ensure!(pool_id == 0, DispatchError::Other("pool not found"));
+		Ok(vec![(KSM, amount * 2)])
+	}
+}
+
 impl pallet_prices::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Source = MockDataProvider;
@@ -298,7 +449,157 @@ impl pallet_prices::Config for Test {
 	type RelayCurrency = RelayCurrency;
 	type Assets = Currencies;
 	type CurrencyIdConvert = AssetIdMaps<Test>;
+	type VtokenMinting = ();
+	type StablePoolHandler = MockStablePool;
+	type AggregatedSources = ();
+	type MarketPriceWindow = ConstU32<300>;
+	type DexOperator = ZenlinkProtocol;
+	type ParachainId = ParachainId;
+	type UnixTime = TimestampPallet;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const ZenlinkPalletId: PalletId = PalletId(*b"/zenlink");
+	pub const SelfParaId: u32 = 2001;
+}
+
+impl zenlink_protocol::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type MultiAssetsHandler = MultiAssets;
+	type PalletId = ZenlinkPalletId;
+	type SelfParaId = SelfParaId;
+	type TargetChains = ();
 	type WeightInfo = ();
+	type AssetId = ZenlinkAssetId;
+	type LpGenerate = PairLpGenerate<Self>;
+}
+
+type MultiAssets = ZenlinkMultiAssets<ZenlinkProtocol, Balances, LocalAssetAdaptor<Currencies>>;
+
+// Below is the implementation of tokens manipulation functions other than native token.
+pub struct LocalAssetAdaptor<Local>(PhantomData<Local>);
+
+impl<Local, AccountId> LocalAssetHandler<AccountId> for LocalAssetAdaptor<Local>
+where
+	Local: MultiCurrency<AccountId, CurrencyId = CurrencyId>,
+{
+	fn local_balance_of(asset_id: ZenlinkAssetId, who: &AccountId) -> AssetBalance {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::free_balance(currency_id, &who).saturated_into()
+	}
+
+	fn local_total_supply(asset_id: ZenlinkAssetId) -> AssetBalance {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::total_issuance(currency_id).saturated_into()
+	}
+
+	fn local_is_exists(asset_id: ZenlinkAssetId) -> bool {
+		let rs: Result<CurrencyId, _> = asset_id.try_into();
+		rs.is_ok()
+	}
+
+	fn local_transfer(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		target: &AccountId,
+		amount: AssetBalance,
+	) -> DispatchResult {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::transfer(currency_id, &origin, &target, amount.unique_saturated_into())?;
+
+		Ok(())
+	}
+
+	fn local_deposit(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		amount: AssetBalance,
+	) -> Result<AssetBalance, DispatchError> {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::deposit(currency_id, &origin, amount.unique_saturated_into())?;
+		Ok(amount)
+	}
+
+	fn local_withdraw(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		amount: AssetBalance,
+	) -> Result<AssetBalance, DispatchError> {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::withdraw(currency_id, &origin, amount.unique_saturated_into())?;
+
+		Ok(amount)
+	}
+}
+
+pub struct ParachainId;
+impl Get<ParaId> for ParachainId {
+	fn get() -> ParaId {
+		2001.into()
+	}
+}
+
+/// Swaps through the Zenlink pair of the two currencies.
+pub struct MockSwapRouter;
+impl MockSwapRouter {
+	fn path(
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+	) -> Result<Vec<ZenlinkAssetId>, DispatchError> {
+		let convert = |currency_id| {
+			ZenlinkAssetId::try_convert_from(currency_id, 2001)
+				.map_err(|_| DispatchError::Other("no route"))
+		};
+		Ok(vec![convert(asset_in)?, convert(asset_out)?])
+	}
+}
+
+impl SwapRouter<AccountId, Balance> for MockSwapRouter {
+	fn get_best_quote(
+		_asset_in: CurrencyId,
+		_asset_out: CurrencyId,
+		_amount: Balance,
+		_swap_type: SwapType,
+	) -> Result<SwapQuote<Balance>, DispatchError> {
+		Err(DispatchError::Other("no quote"))
+	}
+
+	fn swap_exact_in(
+		who: &AccountId,
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount_in: Balance,
+		min_amount_out: Balance,
+	) -> Result<Balance, DispatchError> {
+		let balance_before = Currencies::free_balance(asset_out, who);
+		ZenlinkProtocol::inner_swap_exact_assets_for_assets(
+			who,
+			amount_in,
+			min_amount_out,
+			&Self::path(asset_in, asset_out)?,
+			who,
+		)?;
+		Ok(Currencies::free_balance(asset_out, who) - balance_before)
+	}
+
+	fn swap_exact_out(
+		who: &AccountId,
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount_out: Balance,
+		max_amount_in: Balance,
+	) -> Result<Balance, DispatchError> {
+		let balance_before = Currencies::free_balance(asset_in, who);
+		ZenlinkProtocol::inner_swap_assets_for_exact_assets(
+			who,
+			amount_out,
+			max_amount_in,
+			&Self::path(asset_in, asset_out)?,
+			who,
+		)?;
+		Ok(balance_before - Currencies::free_balance(asset_in, who))
+	}
 }
 
 parameter_types! {
@@ -307,6 +608,13 @@ parameter_types! {
 	pub const LiquidationFreeAssetId: CurrencyId = DOT;
 }
 
+pub struct FlashLoanCallFilter;
+impl Contains<RuntimeCall> for FlashLoanCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		!matches!(call, RuntimeCall::LendMarket(crate::Call::flash_loan { .. }))
+	}
+}
+
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type PriceFeeder = MockPriceFeeder;
@@ -318,6 +626,10 @@ impl Config for Test {
 	type Assets = Currencies;
 	type RewardAssetId = RewardAssetId;
 	type LiquidationFreeAssetId = LiquidationFreeAssetId;
+	type SwapRouter = MockSwapRouter;
+	type MaxKeeperLiquidationsPerBlock = ConstU32<5>;
+	type RuntimeCall = RuntimeCall;
+	type FlashLoanCallFilter = FlashLoanCallFilter;
 }
 
 pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use bifrost_primitives::{Rate, Ratio};
+use bifrost_primitives::{Rate, Ratio, Timestamp, SECONDS_PER_YEAR};
 use scale_info::TypeInfo;
 use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedSub, Saturating};
 
@@ -24,6 +24,7 @@ use crate::*;
 pub enum InterestRateModel {
 	Jump(JumpModel),
 	Curve(CurveModel),
+	Adaptive(AdaptiveModel),
 }
 
 impl Default for InterestRateModel {
@@ -51,18 +52,42 @@ impl InterestRateModel {
 		Self::Curve(CurveModel::new_model(base_rate))
 	}
 
+	pub fn new_adaptive_model(
+		target_utilization: Ratio,
+		initial_rate_at_target: Rate,
+		min_rate_at_target: Rate,
+		max_rate_at_target: Rate,
+		adjustment_speed: Rate,
+		curve_steepness: Rate,
+	) -> Self {
+		Self::Adaptive(AdaptiveModel::new_model(
+			target_utilization,
+			initial_rate_at_target,
+			min_rate_at_target,
+			max_rate_at_target,
+			adjustment_speed,
+			curve_steepness,
+		))
+	}
+
 	pub fn check_model(&self) -> bool {
 		match self {
 			Self::Jump(jump) => jump.check_model(),
 			Self::Curve(curve) => curve.check_model(),
+			Self::Adaptive(adaptive) => adaptive.check_model(),
 		}
 	}
 
 	/// Calculates the current borrow interest rate
+	///
+	/// The adaptive model is evaluated at its initial rate at target utilization, the rate at
+	/// target of a market is kept by the pallet.
 	pub fn get_borrow_rate(&self, utilization: Ratio) -> Option<Rate> {
 		match self {
 			Self::Jump(jump) => jump.get_borrow_rate(utilization),
 			Self::Curve(curve) => curve.get_borrow_rate(utilization),
+			Self::Adaptive(adaptive) =>
+				adaptive.get_borrow_rate(utilization, adaptive.initial_rate_at_target),
 		}
 	}
 
@@ -176,6 +201,142 @@ impl CurveModel {
 	}
 }
 
+/// The adaptive interest rate model
+///
+/// The borrow rate follows a curve around the rate at target utilization, which is adjusted
+/// over time: it rises while the utilization stays above target and falls while it stays below.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, Default, TypeInfo)]
+pub struct AdaptiveModel {
+	/// The utilization the rate at target steers the market towards
+	pub target_utilization: Ratio,
+	/// The rate at target utilization before the first adjustment
+	pub initial_rate_at_target: Rate,
+	/// The lower bound of the rate at target utilization
+	pub min_rate_at_target: Rate,
+	/// The upper bound of the rate at target utilization
+	pub max_rate_at_target: Rate,
+	/// The yearly relative change of the rate at target when utilization is 0% or 100%
+	pub adjustment_speed: Rate,
+	/// The borrow rate at 100% utilization is the rate at target multiplied by the curve
+	/// steepness, the borrow rate at 0% utilization is the rate at target divided by it
+	pub curve_steepness: Rate,
+}
+
+impl AdaptiveModel {
+	pub const MAX_RATE_AT_TARGET: Rate = Rate::from_inner(Rate::DIV / 100 * 50); // 50%
+	pub const MAX_ADJUSTMENT_SPEED: Rate = Rate::from_inner(Rate::DIV * 100); // 100x per year
+	pub const MAX_CURVE_STEEPNESS: Rate = Rate::from_inner(Rate::DIV * 10);
+
+	/// Create a new adaptive model
+	pub fn new_model(
+		target_utilization: Ratio,
+		initial_rate_at_target: Rate,
+		min_rate_at_target: Rate,
+		max_rate_at_target: Rate,
+		adjustment_speed: Rate,
+		curve_steepness: Rate,
+	) -> AdaptiveModel {
+		Self {
+			target_utilization,
+			initial_rate_at_target,
+			min_rate_at_target,
+			max_rate_at_target,
+			adjustment_speed,
+			curve_steepness,
+		}
+	}
+
+	/// Check the adaptive model for sanity
+	pub fn check_model(&self) -> bool {
+		if self.target_utilization.is_zero() || self.target_utilization == Ratio::one() {
+			return false;
+		}
+		// A zero rate at target could never rise again
+		if self.min_rate_at_target.is_zero() ||
+			self.min_rate_at_target > self.initial_rate_at_target ||
+			self.initial_rate_at_target > self.max_rate_at_target ||
+			self.max_rate_at_target > Self::MAX_RATE_AT_TARGET
+		{
+			return false;
+		}
+		if self.adjustment_speed > Self::MAX_ADJUSTMENT_SPEED ||
+			self.curve_steepness < Rate::one() ||
+			self.curve_steepness > Self::MAX_CURVE_STEEPNESS
+		{
+			return false;
+		}
+
+		true
+	}
+
+	/// Calculates the borrow interest rate of adaptive model at `rate_at_target`
+	pub fn get_borrow_rate(&self, utilization: Ratio, rate_at_target: Rate) -> Option<Rate> {
+		let (above_target, error) = self.utilization_error(utilization)?;
+		let factor = if above_target {
+			// 1 + (curve_steepness - 1) * error
+			self.curve_steepness
+				.checked_sub(&Rate::one())?
+				.checked_mul(&error)?
+				.checked_add(&Rate::one())?
+		} else {
+			// 1 - (1 - 1 / curve_steepness) * error
+			let slope =
+				Rate::one().checked_sub(&Rate::one().checked_div(&self.curve_steepness)?)?;
+			Rate::one().checked_sub(&slope.checked_mul(&error)?)?
+		};
+
+		rate_at_target.checked_mul(&factor)
+	}
+
+	/// Adjusts `rate_at_target` for `utilization` held during `delta_time` seconds
+	///
+	/// The rate at target changes by `adjustment_speed * error` per year, where the error is
+	/// the distance of utilization from target normalised to [-1, 1]. The result is bounded
+	/// by `min_rate_at_target` and `max_rate_at_target`.
+	pub fn adjusted_rate_at_target(
+		&self,
+		rate_at_target: Rate,
+		utilization: Ratio,
+		delta_time: Timestamp,
+	) -> Rate {
+		let (above_target, error) = match self.utilization_error(utilization) {
+			Some(error) => error,
+			None => return rate_at_target,
+		};
+		let change = self
+			.adjustment_speed
+			.saturating_mul(error)
+			.saturating_mul(Rate::saturating_from_integer(delta_time))
+			.checked_div(&Rate::saturating_from_integer(SECONDS_PER_YEAR))
+			.unwrap_or_default();
+		let factor = if above_target {
+			Rate::one().saturating_add(change)
+		} else {
+			Rate::one().saturating_sub(change)
+		};
+
+		rate_at_target
+			.saturating_mul(factor)
+			.max(self.min_rate_at_target)
+			.min(self.max_rate_at_target)
+	}
+
+	/// Returns whether `utilization` is above target, and its distance from target normalised
+	/// to [0, 1] by the distance from target to 100% or 0%
+	fn utilization_error(&self, utilization: Ratio) -> Option<(bool, Rate)> {
+		if utilization > self.target_utilization {
+			let error = Rate::from(utilization.saturating_sub(self.target_utilization))
+				.checked_div(&Ratio::one().saturating_sub(self.target_utilization).into())?;
+			Some((true, error))
+		} else {
+			let error = Rate::from(self.target_utilization.saturating_sub(utilization))
+				.checked_div(&self.target_utilization.into())?;
+			Some((false, error))
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -259,4 +420,159 @@ mod tests {
 			Rate::from_inner(154217728000000000)
 		);
 	}
+
+	// Test adaptive model
+	fn adaptive_model() -> AdaptiveModel {
+		AdaptiveModel::new_model(
+			Ratio::from_percent(90),
+			Rate::saturating_from_rational(4, 100),
+			Rate::saturating_from_rational(1, 1000),
+			Rate::saturating_from_rational(40, 100),
+			Rate::saturating_from_integer(50),
+			Rate::saturating_from_integer(4),
+		)
+	}
+
+	// Utilization from 0% to 100% in steps of 0.5%
+	fn utilizations() -> impl Iterator<Item = Ratio> {
+		(0..=200u32).map(|i| Ratio::from_rational(i, 200))
+	}
+
+	// Rates at target from the model's min to max bound and a bit beyond
+	fn rates_at_target(model: &AdaptiveModel) -> impl Iterator<Item = Rate> {
+		let step = model.max_rate_at_target.into_inner() / 50;
+		(0..=60u128).map(move |i| {
+			Rate::from_inner(model.min_rate_at_target.into_inner().saturating_add(step * i))
+		})
+	}
+
+	#[test]
+	fn check_adaptive_model_works() {
+		assert!(adaptive_model().check_model());
+		assert!(
+			!AdaptiveModel { target_utilization: Ratio::zero(), ..adaptive_model() }.check_model()
+		);
+		assert!(
+			!AdaptiveModel { target_utilization: Ratio::one(), ..adaptive_model() }.check_model()
+		);
+		assert!(
+			!AdaptiveModel { min_rate_at_target: Rate::zero(), ..adaptive_model() }.check_model()
+		);
+		assert!(!AdaptiveModel {
+			initial_rate_at_target: Rate::saturating_from_rational(50, 100),
+			..adaptive_model()
+		}
+		.check_model());
+		assert!(!AdaptiveModel {
+			max_rate_at_target: Rate::saturating_from_rational(60, 100),
+			..adaptive_model()
+		}
+		.check_model());
+		assert!(!AdaptiveModel {
+			curve_steepness: Rate::saturating_from_rational(1, 2),
+			..adaptive_model()
+		}
+		.check_model());
+		assert!(!AdaptiveModel {
+			adjustment_speed: Rate::saturating_from_integer(101),
+			..adaptive_model()
+		}
+		.check_model());
+	}
+
+	#[test]
+	fn adaptive_model_calculates_borrow_rate() {
+		let model = adaptive_model();
+		let rate_at_target = model.initial_rate_at_target;
+		assert_eq!(
+			model.get_borrow_rate(model.target_utilization, rate_at_target),
+			Some(rate_at_target)
+		);
+		assert_eq!(
+			model.get_borrow_rate(Ratio::one(), rate_at_target),
+			Some(Rate::saturating_from_rational(16, 100))
+		);
+		assert_eq!(
+			model.get_borrow_rate(Ratio::zero(), rate_at_target),
+			Some(Rate::saturating_from_rational(1, 100))
+		);
+		// Half way from target to 100%: 4% * (1 + 3 * 0.5)
+		assert_eq!(
+			model.get_borrow_rate(Ratio::from_percent(95), rate_at_target),
+			Some(Rate::saturating_from_rational(10, 100))
+		);
+	}
+
+	#[test]
+	fn adaptive_borrow_rate_is_bounded_and_monotonic() {
+		let model = adaptive_model();
+		for rate_at_target in rates_at_target(&model) {
+			let min_rate = rate_at_target / model.curve_steepness;
+			let max_rate = rate_at_target * model.curve_steepness;
+			let mut last_rate = Rate::zero();
+			for utilization in utilizations() {
+				let rate = model.get_borrow_rate(utilization, rate_at_target).unwrap();
+				// Allow for the rounding of 1 / curve_steepness
+				assert!(rate + Rate::from_inner(1) >= min_rate, "{:?} {:?}", utilization, rate);
+				assert!(rate <= max_rate, "{:?} {:?}", utilization, rate);
+				assert!(rate >= last_rate, "{:?} {:?}", utilization, rate);
+				last_rate = rate;
+			}
+		}
+	}
+
+	#[test]
+	fn adaptive_rate_at_target_moves_towards_target_and_is_bounded() {
+		let model = adaptive_model();
+		for rate_at_target in rates_at_target(&model) {
+			for utilization in utilizations() {
+				for delta_time in [0, 1, 6, 3600, SECONDS_PER_YEAR, 100 * SECONDS_PER_YEAR] {
+					let adjusted =
+						model.adjusted_rate_at_target(rate_at_target, utilization, delta_time);
+					assert!(adjusted >= model.min_rate_at_target);
+					assert!(adjusted <= model.max_rate_at_target);
+
+					let bounded =
+						rate_at_target.max(model.min_rate_at_target).min(model.max_rate_at_target);
+					if utilization > model.target_utilization {
+						assert!(adjusted >= bounded);
+					} else {
+						assert!(adjusted <= bounded);
+					}
+					if utilization == model.target_utilization || delta_time == 0 {
+						assert_eq!(adjusted, bounded);
+					}
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn adaptive_rate_at_target_adjusts_with_speed() {
+		let model = adaptive_model();
+		let rate_at_target = model.initial_rate_at_target;
+		// 100% utilization for 1% of a year: 4% * (1 + 50 * 0.01)
+		assert_eq!(
+			model.adjusted_rate_at_target(rate_at_target, Ratio::one(), SECONDS_PER_YEAR / 100),
+			Rate::saturating_from_rational(6, 100)
+		);
+		// 45% utilization for 1% of a year: 4% * (1 - 0.5 * 50 * 0.01)
+		assert_eq!(
+			model.adjusted_rate_at_target(
+				rate_at_target,
+				Ratio::from_percent(45),
+				SECONDS_PER_YEAR / 100
+			),
+			Rate::saturating_from_rational(3, 100)
+		);
+		// The rate at target stops at its bounds
+		assert_eq!(
+			model.adjusted_rate_at_target(rate_at_target, Ratio::one(), SECONDS_PER_YEAR),
+			model.max_rate_at_target
+		);
+		assert_eq!(
+			model.adjusted_rate_at_target(rate_at_target, Ratio::zero(), SECONDS_PER_YEAR),
+			model.min_rate_at_target
+		);
+	}
 }
@@ -12,11 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod bad_debt;
+mod delegation;
 mod edge_cases;
+mod flash_loan;
+mod health;
 mod interest_rate;
+mod keeper;
 mod lend_tokens;
 mod liquidate_borrow;
 mod market;
+mod wind_down;
 
 use crate::mock::*;
 use frame_support::{assert_err, assert_noop, assert_ok};
@@ -0,0 +1,127 @@
+use crate::{
+	mock::{new_test_ext, LendMarket, RuntimeOrigin, ALICE, BOB, DOT_U, KSM, *},
+	tests::unit,
+	AccountDeposits, Error, Event, MIN_EXCHANGE_RATE,
+};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::MultiCurrency;
+use sp_runtime::FixedU128;
+
+#[test]
+fn write_off_bad_debt_fails_with_collateral() {
+	new_test_ext().execute_with(|| {
+		initial_setup(unit(100));
+		assert!(!LendMarket::has_bad_debt(&ALICE));
+		assert_noop!(
+			LendMarket::write_off_bad_debt(RuntimeOrigin::signed(BOB), ALICE),
+			Error::<Test>::NoBadDebt
+		);
+		// Bob has no borrows
+		assert_noop!(
+			LendMarket::write_off_bad_debt(RuntimeOrigin::signed(ALICE), BOB),
+			Error::<Test>::NoBadDebt
+		);
+	})
+}
+
+#[test]
+fn write_off_bad_debt_uses_reserves_before_suppliers() {
+	new_test_ext().execute_with(|| {
+		// KSM market before the write-off:
+		// cash 270, borrows 50, reserves 20, exchange rate 300 / 10000 = 0.03
+		initial_setup(unit(100));
+		seize_all_collateral();
+		assert!(LendMarket::has_bad_debt(&ALICE));
+
+		assert_ok!(LendMarket::write_off_bad_debt(RuntimeOrigin::signed(BOB), ALICE));
+		System::assert_last_event(RuntimeEvent::LendMarket(Event::BadDebtWrittenOff(
+			ALICE,
+			KSM,
+			unit(20),
+			unit(30),
+		)));
+
+		assert_eq!(LendMarket::current_borrow_balance(&ALICE, KSM), Ok(0));
+		assert_eq!(LendMarket::total_borrows(KSM), 0);
+		assert_eq!(LendMarket::total_reserves(KSM), 0);
+		assert_eq!(LendMarket::bad_debt(KSM), unit(50));
+		// Suppliers absorb the 30 KSM that the reserves couldn't cover: 270 / 10000
+		assert_eq!(LendMarket::exchange_rate(KSM), FixedU128::from_rational(27, 1000));
+		assert!(!LendMarket::has_bad_debt(&ALICE));
+	})
+}
+
+#[test]
+fn write_off_bad_debt_seizes_supply_without_collateral() {
+	new_test_ext().execute_with(|| {
+		// KSM market before the write-off:
+		// cash 300, borrows 50, reserves 20, exchange rate 330 / 11000 = 0.03
+		initial_setup(unit(100));
+		// Alice supplies 30 KSM without marking it as collateral
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(ALICE), KSM, unit(30)));
+		seize_all_collateral();
+		assert!(LendMarket::has_bad_debt(&ALICE));
+
+		assert_ok!(LendMarket::write_off_bad_debt(RuntimeOrigin::signed(BOB), ALICE));
+		System::assert_has_event(RuntimeEvent::LendMarket(Event::BadDebtSupplySeized(
+			ALICE,
+			KSM,
+			unit(1000),
+			unit(30),
+		)));
+		System::assert_last_event(RuntimeEvent::LendMarket(Event::BadDebtWrittenOff(
+			ALICE,
+			KSM,
+			unit(50),
+			0,
+		)));
+
+		assert_eq!(LendMarket::account_deposits(KSM, ALICE).voucher_balance, 0);
+		assert_eq!(LendMarket::current_borrow_balance(&ALICE, KSM), Ok(0));
+		assert_eq!(LendMarket::total_reserves(KSM), 0);
+		assert_eq!(LendMarket::bad_debt(KSM), unit(50));
+		// The seized supply covers the debt, Bob keeps his exchange rate: 300 / 10000
+		assert_eq!(LendMarket::exchange_rate(KSM), FixedU128::from_rational(3, 100));
+	})
+}
+
+#[test]
+fn write_off_bad_debt_keeps_exchange_rate_valid() {
+	new_test_ext().execute_with(|| {
+		// KSM market before the write-off:
+		// cash 180, borrows 50, reserves 20, exchange rate 210 / 10000 = 0.021
+		initial_setup(unit(10));
+		seize_all_collateral();
+
+		assert_ok!(LendMarket::write_off_bad_debt(RuntimeOrigin::signed(BOB), ALICE));
+		System::assert_last_event(RuntimeEvent::LendMarket(Event::BadDebtWrittenOff(
+			ALICE,
+			KSM,
+			unit(20),
+			unit(10),
+		)));
+
+		// The debt suppliers can't absorb stays on the borrower.
+		assert_eq!(LendMarket::current_borrow_balance(&ALICE, KSM), Ok(unit(20)));
+		assert_eq!(LendMarket::bad_debt(KSM), unit(30));
+		assert_eq!(LendMarket::exchange_rate(KSM), FixedU128::from_inner(MIN_EXCHANGE_RATE));
+		assert!(LendMarket::has_bad_debt(&ALICE));
+	})
+}
+
+fn initial_setup(donation: Balance) {
+	assert_ok!(LendMarket::add_market_bond(RuntimeOrigin::root(), KSM, vec![DOT_U, KSM]));
+	// Bob deposits 200 KSM
+	assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), KSM, unit(200)));
+	// Alice deposits 200 DOT_U as collateral and borrows 50 KSM
+	assert_ok!(LendMarket::mint(RuntimeOrigin::signed(ALICE), DOT_U, unit(200)));
+	assert_ok!(LendMarket::collateral_asset(RuntimeOrigin::signed(ALICE), DOT_U, true));
+	assert_ok!(LendMarket::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(50)));
+	assert_ok!(LendMarket::add_reserves(RuntimeOrigin::root(), ALICE, KSM, unit(20)));
+	// Raise the exchange rate of KSM market above the minimum
+	assert_ok!(Currencies::transfer(KSM, &ALICE, &LendMarket::account_id(), donation));
+}
+
+fn seize_all_collateral() {
+	AccountDeposits::<Test>::mutate(DOT_U, ALICE, |deposits| deposits.voucher_balance = 0);
+}
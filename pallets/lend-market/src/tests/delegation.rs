@@ -0,0 +1,143 @@
+use crate::{
+	mock::{new_test_ext, LendMarket, RuntimeOrigin, ALICE, BOB, DOT_U, EVE, KSM, *},
+	tests::unit,
+	Error, Event,
+};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::MultiCurrency;
+
+#[test]
+fn approve_and_revoke_borrow_allowance_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LendMarket::approve_borrow_allowance(
+				RuntimeOrigin::signed(ALICE),
+				ALICE,
+				KSM,
+				unit(10)
+			),
+			Error::<Test>::SelfDelegation
+		);
+		assert_noop!(
+			LendMarket::approve_borrow_allowance(RuntimeOrigin::signed(ALICE), EVE, VKSM, unit(10)),
+			Error::<Test>::MarketNotActivated
+		);
+
+		assert_ok!(LendMarket::approve_borrow_allowance(
+			RuntimeOrigin::signed(ALICE),
+			EVE,
+			KSM,
+			unit(10)
+		));
+		System::assert_last_event(RuntimeEvent::LendMarket(Event::BorrowAllowanceApproved(
+			ALICE,
+			EVE,
+			KSM,
+			unit(10),
+		)));
+		assert_eq!(LendMarket::borrow_allowance(ALICE, (EVE, KSM)), unit(10));
+
+		assert_ok!(LendMarket::revoke_borrow_allowance(RuntimeOrigin::signed(ALICE), EVE, KSM));
+		System::assert_last_event(RuntimeEvent::LendMarket(Event::BorrowAllowanceRevoked(
+			ALICE, EVE, KSM,
+		)));
+		assert_eq!(LendMarket::borrow_allowance(ALICE, (EVE, KSM)), 0);
+	})
+}
+
+#[test]
+fn borrow_on_behalf_works() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		assert_ok!(LendMarket::approve_borrow_allowance(
+			RuntimeOrigin::signed(ALICE),
+			EVE,
+			KSM,
+			unit(80)
+		));
+
+		assert_ok!(LendMarket::borrow_on_behalf(RuntimeOrigin::signed(EVE), ALICE, KSM, unit(50)));
+		System::assert_last_event(RuntimeEvent::LendMarket(Event::DelegatedBorrowed(
+			ALICE,
+			EVE,
+			KSM,
+			unit(50),
+		)));
+		// The debt is Alice's, the funds are Eve's
+		assert_eq!(LendMarket::current_borrow_balance(&ALICE, KSM), Ok(unit(50)));
+		assert_eq!(LendMarket::current_borrow_balance(&EVE, KSM), Ok(0));
+		assert_eq!(Tokens::free_balance(KSM, &EVE), unit(50));
+		assert_eq!(LendMarket::borrow_allowance(ALICE, (EVE, KSM)), unit(30));
+
+		assert_noop!(
+			LendMarket::borrow_on_behalf(RuntimeOrigin::signed(EVE), ALICE, KSM, unit(40)),
+			Error::<Test>::InsufficientBorrowAllowance
+		);
+		assert_noop!(
+			LendMarket::borrow_on_behalf(RuntimeOrigin::signed(BOB), ALICE, KSM, unit(1)),
+			Error::<Test>::InsufficientBorrowAllowance
+		);
+
+		assert_ok!(LendMarket::borrow_on_behalf(RuntimeOrigin::signed(EVE), ALICE, KSM, unit(30)));
+		assert!(!crate::BorrowAllowances::<Test>::contains_key(ALICE, (EVE, KSM)));
+	})
+}
+
+#[test]
+fn borrow_on_behalf_is_limited_by_delegator_liquidity() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		assert_ok!(LendMarket::approve_borrow_allowance(
+			RuntimeOrigin::signed(ALICE),
+			EVE,
+			KSM,
+			unit(200)
+		));
+		assert_ok!(LendMarket::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(50)));
+
+		// 50% of $200 DOT_U, $50 of which Alice already borrowed
+		assert_noop!(
+			LendMarket::borrow_on_behalf(RuntimeOrigin::signed(EVE), ALICE, KSM, unit(51)),
+			Error::<Test>::InsufficientLiquidity
+		);
+		assert_ok!(LendMarket::borrow_on_behalf(RuntimeOrigin::signed(EVE), ALICE, KSM, unit(50)));
+	})
+}
+
+#[test]
+fn repay_borrow_on_behalf_works() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		assert_ok!(LendMarket::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(50)));
+
+		assert_ok!(LendMarket::repay_borrow_on_behalf(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			KSM,
+			unit(20)
+		));
+		System::assert_last_event(RuntimeEvent::LendMarket(Event::RepaidBorrow(
+			ALICE,
+			KSM,
+			unit(20),
+		)));
+		assert_eq!(LendMarket::current_borrow_balance(&ALICE, KSM), Ok(unit(30)));
+		// Bob pays, Alice keeps the borrowed KSM
+		assert_eq!(Tokens::free_balance(KSM, &BOB), unit(780));
+		assert_eq!(Tokens::free_balance(KSM, &ALICE), unit(1050));
+
+		assert_noop!(
+			LendMarket::repay_borrow_on_behalf(RuntimeOrigin::signed(BOB), ALICE, KSM, unit(31)),
+			Error::<Test>::TooMuchRepay
+		);
+	})
+}
+
+fn initial_setup() {
+	assert_ok!(LendMarket::add_market_bond(RuntimeOrigin::root(), KSM, vec![DOT_U, KSM]));
+	// Bob deposits 200 KSM
+	assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), KSM, unit(200)));
+	// Alice deposits 200 DOT_U as collateral
+	assert_ok!(LendMarket::mint(RuntimeOrigin::signed(ALICE), DOT_U, unit(200)));
+	assert_ok!(LendMarket::collateral_asset(RuntimeOrigin::signed(ALICE), DOT_U, true));
+}
@@ -0,0 +1,191 @@
+use crate::{
+	mock::{new_test_ext, LendMarket, RuntimeOrigin, ALICE, BOB, DOT, *},
+	tests::unit,
+	Error, Event, FlashLoanInProgress,
+};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::MultiCurrency;
+use pallet_traits::LendMarket as LendMarketTrait;
+use sp_runtime::traits::BadOrigin;
+
+fn remark() -> Box<RuntimeCall> {
+	Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![] }))
+}
+
+#[test]
+fn update_flash_loan_fee_rate_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LendMarket::update_flash_loan_fee_rate(
+				RuntimeOrigin::signed(ALICE),
+				DOT,
+				Ratio::from_percent(1)
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			LendMarket::update_flash_loan_fee_rate(
+				RuntimeOrigin::root(),
+				VKSM,
+				Ratio::from_percent(1)
+			),
+			Error::<Test>::MarketDoesNotExist
+		);
+		assert_ok!(LendMarket::update_flash_loan_fee_rate(
+			RuntimeOrigin::root(),
+			DOT,
+			Ratio::from_percent(1)
+		));
+		assert_eq!(LendMarket::flash_loan_fee_rate(DOT), Ratio::from_percent(1));
+	})
+}
+
+#[test]
+fn flash_loan_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), DOT, unit(200)));
+		assert_ok!(LendMarket::update_flash_loan_fee_rate(
+			RuntimeOrigin::root(),
+			DOT,
+			Ratio::from_percent(1)
+		));
+		let exchange_rate = LendMarket::exchange_rate_stored(DOT).unwrap();
+
+		assert_ok!(LendMarket::flash_loan(RuntimeOrigin::signed(ALICE), DOT, unit(100), remark()));
+		System::assert_last_event(RuntimeEvent::LendMarket(Event::FlashLoaned(
+			ALICE,
+			DOT,
+			unit(100),
+			unit(1),
+		)));
+
+		// The fee goes to the reserves, suppliers are not affected.
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), unit(999));
+		assert_eq!(Tokens::free_balance(DOT, &LendMarket::account_id()), unit(201));
+		assert_eq!(LendMarket::total_reserves(DOT), unit(1));
+		assert_eq!(LendMarket::exchange_rate_stored(DOT), Ok(exchange_rate));
+		assert!(!FlashLoanInProgress::<Test>::exists());
+	})
+}
+
+#[test]
+fn flash_loaned_market_cannot_be_entered() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), DOT, unit(200)));
+
+		// Depositing the flash loaned DOT would be priced at a wrong exchange rate.
+		let mint = Box::new(RuntimeCall::LendMarket(crate::Call::mint {
+			asset_id: DOT,
+			mint_amount: unit(100),
+		}));
+		assert_noop!(
+			LendMarket::flash_loan(RuntimeOrigin::signed(ALICE), DOT, unit(100), mint),
+			Error::<Test>::FlashLoanInProgress
+		);
+		let borrow = Box::new(RuntimeCall::LendMarket(crate::Call::borrow {
+			asset_id: DOT,
+			borrow_amount: unit(10),
+		}));
+		assert_noop!(
+			LendMarket::flash_loan(RuntimeOrigin::signed(ALICE), DOT, unit(100), borrow),
+			Error::<Test>::FlashLoanInProgress
+		);
+
+		// Other markets can be entered.
+		let mint = Box::new(RuntimeCall::LendMarket(crate::Call::mint {
+			asset_id: KSM,
+			mint_amount: unit(100),
+		}));
+		assert_ok!(LendMarket::flash_loan(RuntimeOrigin::signed(ALICE), DOT, unit(100), mint));
+		assert!(!FlashLoanInProgress::<Test>::exists());
+	})
+}
+
+#[test]
+fn flash_loans_cannot_be_nested() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), DOT, unit(200)));
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), KSM, unit(200)));
+
+		let nested = Box::new(RuntimeCall::LendMarket(crate::Call::flash_loan {
+			asset_id: KSM,
+			amount: unit(100),
+			call: remark(),
+		}));
+		assert_noop!(
+			LendMarket::flash_loan(RuntimeOrigin::signed(ALICE), DOT, unit(100), nested),
+			Error::<Test>::FlashLoanCallNotAllowed
+		);
+		assert_noop!(
+			LendMarket::do_flash_loan(&ALICE, DOT, unit(100), || {
+				LendMarket::do_flash_loan(&ALICE, KSM, unit(100), || Ok(()))
+			}),
+			Error::<Test>::FlashLoanInProgress
+		);
+	})
+}
+
+#[test]
+fn flash_borrow_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), DOT, unit(200)));
+		assert_ok!(LendMarket::update_flash_loan_fee_rate(
+			RuntimeOrigin::root(),
+			DOT,
+			Ratio::from_percent(1)
+		));
+
+		// The borrow is checked against the collateral supplied while the loan runs.
+		assert_noop!(
+			LendMarket::do_flash_borrow(&ALICE, DOT, unit(100), || Ok(())),
+			Error::<Test>::InsufficientLiquidity
+		);
+		assert_ok!(LendMarket::do_flash_borrow(&ALICE, DOT, unit(100), || {
+			LendMarket::do_mint(&ALICE, KSM, unit(300))?;
+			LendMarket::do_collateral_asset(&ALICE, KSM, true)
+		}));
+		System::assert_last_event(RuntimeEvent::LendMarket(Event::Borrowed(ALICE, DOT, unit(101))));
+
+		// The loan stays with Alice, the loan and its fee are her debt.
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), unit(1100));
+		assert_eq!(Tokens::free_balance(DOT, &LendMarket::account_id()), unit(100));
+		assert_eq!(LendMarket::current_borrow_balance(&ALICE, DOT), Ok(unit(101)));
+		assert_eq!(LendMarket::total_borrows(DOT), unit(101));
+		assert_eq!(LendMarket::total_reserves(DOT), unit(1));
+		assert!(!FlashLoanInProgress::<Test>::exists());
+	})
+}
+
+#[test]
+fn flash_loan_fails_if_not_repaid() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), DOT, unit(200)));
+		assert_noop!(
+			LendMarket::flash_loan(RuntimeOrigin::signed(ALICE), DOT, unit(201), remark()),
+			Error::<Test>::InsufficientCash
+		);
+		assert_noop!(
+			LendMarket::flash_loan(RuntimeOrigin::signed(ALICE), DOT, 0, remark()),
+			Error::<Test>::InvalidAmount
+		);
+
+		// The call fails.
+		let set_heap_pages =
+			Box::new(RuntimeCall::System(frame_system::Call::set_heap_pages { pages: 1 }));
+		assert_noop!(
+			LendMarket::flash_loan(RuntimeOrigin::signed(ALICE), DOT, unit(100), set_heap_pages),
+			BadOrigin
+		);
+
+		// The call spends the flash loaned funds, so the loan can't be repaid.
+		let transfer_all = Box::new(RuntimeCall::Tokens(orml_tokens::Call::transfer_all {
+			dest: EVE,
+			currency_id: DOT,
+			keep_alive: false,
+		}));
+		assert_noop!(
+			LendMarket::flash_loan(RuntimeOrigin::signed(ALICE), DOT, unit(100), transfer_all),
+			orml_tokens::Error::<Test>::BalanceTooLow
+		);
+	})
+}
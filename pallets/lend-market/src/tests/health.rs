@@ -0,0 +1,121 @@
+use crate::{
+	mock::{new_test_ext, LendMarket, MockPriceFeeder, RuntimeOrigin, ALICE, BOB, DOT_U, KSM, *},
+	tests::unit,
+};
+use bifrost_primitives::MaxLiquidation;
+use frame_support::assert_ok;
+use sp_runtime::{traits::One, FixedU128};
+
+#[test]
+fn get_account_health_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::add_market_bond(RuntimeOrigin::root(), KSM, vec![DOT_U, KSM]));
+		// Bob deposits 200 KSM
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), KSM, unit(200)));
+		// Alice deposits 200 DOT_U as collateral and borrows 100 KSM
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(ALICE), DOT_U, unit(200)));
+		assert_ok!(LendMarket::collateral_asset(RuntimeOrigin::signed(ALICE), DOT_U, true));
+		assert_ok!(LendMarket::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(100)));
+
+		let health = LendMarket::get_account_health(&ALICE).unwrap();
+		assert_eq!(health.positions.len(), 2);
+		// 55% of $200 DOT_U against $100 KSM
+		assert_eq!(health.collateral_value, FixedU128::from_inner(unit(110)));
+		assert_eq!(health.borrow_value, FixedU128::from_inner(unit(100)));
+		assert_eq!(health.health_factor, Some(FixedU128::from_rational(11, 10)));
+
+		let dot_u = health.positions.iter().find(|p| p.asset_id == DOT_U).unwrap();
+		assert_eq!(dot_u.supply_amount, unit(200));
+		assert_eq!(dot_u.supply_value, FixedU128::from_inner(unit(200)));
+		assert_eq!(dot_u.borrow_amount, 0);
+		assert!(dot_u.is_collateral);
+		// Alice is liquidated when DOT_U falls below $100 / 110
+		assert_eq!(dot_u.liquidation_price, Some(FixedU128::from_rational(10, 11)));
+
+		let ksm = health.positions.iter().find(|p| p.asset_id == KSM).unwrap();
+		assert_eq!(ksm.supply_amount, 0);
+		assert_eq!(ksm.borrow_amount, unit(100));
+		assert_eq!(ksm.borrow_value, FixedU128::from_inner(unit(100)));
+		assert!(!ksm.is_collateral);
+		// or when KSM rises above $1.1
+		assert_eq!(ksm.liquidation_price, Some(FixedU128::from_rational(11, 10)));
+
+		// Bob has no borrows
+		let health = LendMarket::get_account_health(&BOB).unwrap();
+		assert_eq!(health.positions.len(), 1);
+		assert_eq!(health.health_factor, None);
+		assert_eq!(health.positions[0].liquidation_price, None);
+	})
+}
+
+#[test]
+fn get_liquidation_candidates_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::add_market_bond(RuntimeOrigin::root(), KSM, vec![DOT_U, KSM]));
+		assert_ok!(LendMarket::add_market_bond(RuntimeOrigin::root(), DOT, vec![DOT_U, DOT]));
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), KSM, unit(200)));
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), DOT, unit(200)));
+		// Alice borrows 50 KSM and 50 DOT, Dave borrows 20 KSM
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(ALICE), DOT_U, unit(200)));
+		assert_ok!(LendMarket::collateral_asset(RuntimeOrigin::signed(ALICE), DOT_U, true));
+		assert_ok!(LendMarket::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(50)));
+		assert_ok!(LendMarket::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(50)));
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(DAVE), DOT_U, unit(200)));
+		assert_ok!(LendMarket::collateral_asset(RuntimeOrigin::signed(DAVE), DOT_U, true));
+		assert_ok!(LendMarket::borrow(RuntimeOrigin::signed(DAVE), KSM, unit(20)));
+
+		// Alice is close to liquidation but not liquidatable yet
+		let (candidates, next_key) =
+			LendMarket::get_liquidation_candidates(FixedU128::from_rational(12, 10), None, 10);
+		assert_eq!(next_key, None);
+		assert_eq!(candidates.len(), 1);
+		assert_eq!(candidates[0].account, ALICE);
+		assert_eq!(candidates[0].health_factor, FixedU128::from_rational(11, 10));
+		assert_eq!(candidates[0].liquidation, None);
+
+		// A scan of no borrows doesn't end the scan
+		let (candidates, next_key) =
+			LendMarket::get_liquidation_candidates(FixedU128::from_rational(12, 10), None, 0);
+		assert!(candidates.is_empty());
+		assert!(next_key.is_some());
+		let (candidates, resume_key) = LendMarket::get_liquidation_candidates(
+			FixedU128::from_rational(12, 10),
+			next_key.clone(),
+			0,
+		);
+		assert!(candidates.is_empty());
+		assert_eq!(resume_key, next_key);
+		let (candidates, next_key) =
+			LendMarket::get_liquidation_candidates(FixedU128::from_rational(12, 10), next_key, 10);
+		assert_eq!(next_key, None);
+		assert_eq!(candidates.len(), 1);
+
+		// Alice's health factor drops to 110 / 150
+		MockPriceFeeder::set_price(KSM, 2.into());
+		let mut candidates = Vec::new();
+		let mut start_key = None;
+		loop {
+			let (page, next_key) =
+				LendMarket::get_liquidation_candidates(FixedU128::one(), start_key, 1);
+			candidates.extend(page);
+			start_key = next_key;
+			if start_key.is_none() {
+				break;
+			}
+		}
+		// Alice is reported once for her two borrows
+		assert_eq!(candidates.len(), 1);
+		assert_eq!(candidates[0].account, ALICE);
+		assert_eq!(candidates[0].health_factor, FixedU128::from_rational(110, 150));
+		// 50% of the $100 KSM debt is repaid for 110% of its value in DOT_U
+		assert_eq!(
+			candidates[0].liquidation,
+			Some(MaxLiquidation {
+				liquidation_asset_id: KSM,
+				max_repay_amount: unit(25),
+				collateral_asset_id: DOT_U,
+				max_seizable_amount: unit(55),
+			})
+		);
+	})
+}
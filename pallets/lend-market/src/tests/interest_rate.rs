@@ -1,6 +1,7 @@
-use crate::{mock::*, tests::LendMarket, Markets};
+use crate::{mock::*, tests::LendMarket, Markets, RateAtTarget};
 use bifrost_primitives::{Rate, Ratio, SECONDS_PER_YEAR};
 use frame_support::assert_ok;
+use pallet_traits::LendMarketMarketDataProvider;
 use sp_runtime::{
 	traits::{CheckedDiv, One, Saturating},
 	FixedPointNumber,
@@ -446,3 +447,60 @@ fn a_market_can_only_accrue_interest_once_in_a_block() {
 		assert_eq!(LendMarket::borrow_index(DOT), Rate::from_inner(1000000003805175038),);
 	})
 }
+
+#[test]
+fn adaptive_rate_model_adjusts_rate_at_target() {
+	new_test_ext().execute_with(|| {
+		let model = AdaptiveModel::new_model(
+			Ratio::from_percent(40),
+			Rate::saturating_from_rational(4, 100),
+			Rate::saturating_from_rational(1, 1000),
+			Rate::saturating_from_rational(40, 100),
+			Rate::saturating_from_integer(50),
+			Rate::saturating_from_integer(4),
+		);
+		assert_ok!(LendMarket::update_rate_model(
+			RuntimeOrigin::root(),
+			DOT,
+			InterestRateModel::Adaptive(model)
+		));
+		assert_ok!(LendMarket::add_market_bond(
+			RuntimeOrigin::root(),
+			DOT,
+			vec![DOT, BNC, KSM, DOT_U, PHA]
+		));
+		// Deposit 200 DOT and borrow 100 DOT, the utilization stays above target
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(ALICE), DOT, unit(200)));
+		assert_ok!(LendMarket::collateral_asset(RuntimeOrigin::signed(ALICE), DOT, true));
+		assert_ok!(LendMarket::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(100)));
+		assert_eq!(LendMarket::current_rate_at_target(DOT, &model), model.initial_rate_at_target);
+
+		TimestampPallet::set_timestamp(6000 + SECONDS_PER_YEAR * 10);
+		assert_ok!(LendMarket::accrue_interest(DOT));
+		let rate_at_target = RateAtTarget::<Test>::get(DOT).unwrap();
+		assert!(rate_at_target > model.initial_rate_at_target);
+		// The elapsed period is charged at the rate at target of its start
+		let borrow_rate = LendMarket::borrow_rate(DOT);
+		assert_eq!(
+			Some(borrow_rate),
+			model.get_borrow_rate(Ratio::from_percent(50), model.initial_rate_at_target)
+		);
+		assert_eq!(
+			LendMarket::get_full_interest_rate(DOT),
+			Some(rate_at_target * model.curve_steepness)
+		);
+
+		TimestampPallet::set_timestamp(6000 + SECONDS_PER_YEAR * 20);
+		assert_ok!(LendMarket::accrue_interest(DOT));
+		assert!(LendMarket::borrow_rate(DOT) > borrow_rate);
+		assert!(RateAtTarget::<Test>::get(DOT).unwrap() > rate_at_target);
+
+		// Switching to a static model drops the rate at target
+		assert_ok!(LendMarket::update_rate_model(
+			RuntimeOrigin::root(),
+			DOT,
+			InterestRateModel::default()
+		));
+		assert!(!RateAtTarget::<Test>::contains_key(DOT));
+	})
+}
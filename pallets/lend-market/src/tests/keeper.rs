@@ -0,0 +1,153 @@
+use crate::{
+	mock::{new_test_ext, LendMarket, MockPriceFeeder, RuntimeOrigin, ALICE, BOB, DOT_U, KSM, *},
+	tests::unit,
+	Error, Event, KeeperScanCursor, LiquidationKeeperLimit, LiquidationKeeperLimits, TotalReserves,
+};
+use frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight};
+use orml_traits::MultiCurrency;
+use sp_runtime::traits::BadOrigin;
+use zenlink_protocol::AssetId as ZenlinkAssetId;
+
+const KEEPER_LIMIT: LiquidationKeeperLimit<Balance> = LiquidationKeeperLimit {
+	max_repay_amount: 1_000_000_000_000_000,
+	min_profit: 1_000_000_000_000,
+};
+
+#[test]
+fn update_liquidation_keeper_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LendMarket::update_liquidation_keeper(
+				RuntimeOrigin::signed(ALICE),
+				KSM,
+				Some(KEEPER_LIMIT)
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			LendMarket::update_liquidation_keeper(RuntimeOrigin::root(), VKSM, Some(KEEPER_LIMIT)),
+			Error::<Test>::MarketDoesNotExist
+		);
+		assert_noop!(
+			LendMarket::update_liquidation_keeper(
+				RuntimeOrigin::root(),
+				KSM,
+				Some(LiquidationKeeperLimit { max_repay_amount: 0, min_profit: 0 })
+			),
+			Error::<Test>::InvalidAmount
+		);
+
+		assert_ok!(LendMarket::update_liquidation_keeper(
+			RuntimeOrigin::root(),
+			KSM,
+			Some(KEEPER_LIMIT)
+		));
+		assert_eq!(LendMarket::liquidation_keeper_limit(KSM), Some(KEEPER_LIMIT));
+		System::assert_last_event(RuntimeEvent::LendMarket(Event::LiquidationKeeperUpdated(
+			KSM,
+			Some(KEEPER_LIMIT),
+		)));
+
+		assert_ok!(LendMarket::update_liquidation_keeper(RuntimeOrigin::root(), KSM, None));
+		assert_eq!(LendMarket::liquidation_keeper_limit(KSM), None);
+	})
+}
+
+#[test]
+fn keeper_liquidates_borrow_in_shortfall() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		assert_ok!(LendMarket::update_liquidation_keeper(
+			RuntimeOrigin::root(),
+			KSM,
+			Some(KEEPER_LIMIT)
+		));
+		// Bob takes out the whole KSM cash, the keeper doesn't need it
+		assert_ok!(LendMarket::redeem(RuntimeOrigin::signed(BOB), KSM, unit(100)));
+		assert_eq!(Tokens::free_balance(KSM, &LendMarket::account_id()), 0);
+		// Adjust KSM price to make shortfall
+		MockPriceFeeder::set_price(KSM, 2.into());
+
+		let reserves_before = TotalReserves::<Test>::get(KSM);
+		let vouchers_before = LendMarket::account_deposits(DOT_U, ALICE).voucher_balance;
+		let lf_vouchers_before = LendMarket::account_deposits(PHA, ALICE).voucher_balance;
+		LendMarket::on_idle(1, Weight::MAX);
+
+		// The keeper repays 50% of Alice's $200 KSM debt and seizes her DOT_U, the liquidation
+		// free PHA is left untouched.
+		assert_eq!(LendMarket::current_borrow_balance(&ALICE, KSM), Ok(unit(50)));
+		assert!(LendMarket::account_deposits(DOT_U, ALICE).voucher_balance < vouchers_before);
+		assert_eq!(LendMarket::account_deposits(PHA, ALICE).voucher_balance, lf_vouchers_before);
+		// The swapped collateral pays the repay and the surplus goes to the reserves.
+		let profit = TotalReserves::<Test>::get(KSM) - reserves_before;
+		assert!(profit >= KEEPER_LIMIT.min_profit);
+		assert_eq!(Tokens::free_balance(KSM, &LendMarket::account_id()), unit(50) + profit);
+		let keeper = LendMarket::keeper_account_id();
+		assert_eq!(Tokens::free_balance(KSM, &keeper), 0);
+		assert_eq!(LendMarket::account_deposits(DOT_U, keeper).voucher_balance, 0);
+		assert_eq!(KeeperScanCursor::<Test>::get(), None);
+	})
+}
+
+#[test]
+fn keeper_skips_markets_without_limits() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		MockPriceFeeder::set_price(KSM, 2.into());
+
+		LendMarket::on_idle(1, Weight::MAX);
+		assert_eq!(LendMarket::current_borrow_balance(&ALICE, KSM), Ok(unit(100)));
+	})
+}
+
+#[test]
+fn keeper_liquidation_reverts_when_unprofitable() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		assert_ok!(LendMarket::update_liquidation_keeper(
+			RuntimeOrigin::root(),
+			KSM,
+			Some(LiquidationKeeperLimit { min_profit: unit(100), ..KEEPER_LIMIT })
+		));
+		MockPriceFeeder::set_price(KSM, 2.into());
+		let reserves_before = TotalReserves::<Test>::get(KSM);
+
+		LendMarket::on_idle(1, Weight::MAX);
+		assert_eq!(LendMarket::current_borrow_balance(&ALICE, KSM), Ok(unit(100)));
+		assert_eq!(TotalReserves::<Test>::get(KSM), reserves_before);
+		assert!(LiquidationKeeperLimits::<Test>::contains_key(KSM));
+	})
+}
+
+fn initial_setup() {
+	assert_ok!(LendMarket::add_market_bond(
+		RuntimeOrigin::root(),
+		KSM,
+		vec![DOT, BNC, KSM, DOT_U, PHA]
+	));
+	// Bob deposits 200 KSM
+	assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), KSM, unit(200)));
+	// Alice deposits 200 DOT_U and 200 PHA as collateral and borrows 100 KSM
+	assert_ok!(LendMarket::mint(RuntimeOrigin::signed(ALICE), DOT_U, unit(200)));
+	assert_ok!(LendMarket::collateral_asset(RuntimeOrigin::signed(ALICE), DOT_U, true));
+	assert_ok!(LendMarket::mint(RuntimeOrigin::signed(ALICE), PHA, unit(200)));
+	assert_ok!(LendMarket::collateral_asset(RuntimeOrigin::signed(ALICE), PHA, true));
+	assert_ok!(LendMarket::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(100)));
+
+	// Eve provides a DOT_U/KSM pair priced at 1 KSM = 2 DOT_U
+	assert_ok!(Currencies::deposit(KSM, &EVE, unit(10_000)));
+	assert_ok!(Currencies::deposit(DOT_U, &EVE, unit(20_000)));
+	let ksm = ZenlinkAssetId::try_convert_from(KSM, 2001).unwrap();
+	let dot_u = ZenlinkAssetId::try_convert_from(DOT_U, 2001).unwrap();
+	assert_ok!(ZenlinkProtocol::create_pair(RuntimeOrigin::root(), ksm, dot_u, ALICE));
+	assert_ok!(ZenlinkProtocol::add_liquidity(
+		RuntimeOrigin::signed(EVE),
+		ksm,
+		dot_u,
+		unit(10_000),
+		unit(20_000),
+		1,
+		1,
+		100
+	));
+}
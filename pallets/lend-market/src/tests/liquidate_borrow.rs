@@ -269,6 +269,36 @@ fn liquidator_must_not_be_borrower() {
 	})
 }
 
+#[test]
+fn invalid_price_refuses_borrows_and_liquidations() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		MockPriceFeeder::set_price_valid(KSM, false);
+		assert_noop!(
+			LendMarket::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(100)),
+			Error::<Test>::InvalidPrice
+		);
+		MockPriceFeeder::set_price_valid(KSM, true);
+		alice_borrows_100_ksm();
+
+		MockPriceFeeder::set_price(KSM, 2.into());
+		// The price of the collateral fails its checks
+		MockPriceFeeder::set_price_valid(DOT_U, false);
+		assert_noop!(
+			LendMarket::liquidate_borrow(RuntimeOrigin::signed(BOB), ALICE, KSM, unit(50), DOT_U),
+			Error::<Test>::InvalidPrice
+		);
+		MockPriceFeeder::set_price_valid(DOT_U, true);
+		assert_ok!(LendMarket::liquidate_borrow(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			KSM,
+			unit(50),
+			DOT_U
+		));
+	})
+}
+
 fn alice_borrows_100_ksm() {
 	assert_ok!(LendMarket::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(100)));
 }
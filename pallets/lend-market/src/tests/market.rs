@@ -1,12 +1,14 @@
 use crate::{
 	mock::{
-		market_mock, new_test_ext, LendMarket, RuntimeOrigin, Test, ACTIVE_MARKET_MOCK, ALICE, DOT,
-		LDOT, LUSDT, MARKET_MOCK, VDOT,
+		market_mock, new_test_ext, Currencies, LendMarket, MockPriceFeeder, Prices, RuntimeOrigin,
+		Test, ACTIVE_MARKET_MOCK, ALICE, BOB, DOT, KSM, LDOT, LUSDT, MARKET_MOCK, VDOT,
 	},
+	tests::unit,
 	Error, InterestRateModel, MarketState,
 };
-use bifrost_primitives::{Rate, Ratio};
+use bifrost_primitives::{CurrencyId, Price, PriceFeeder, Rate, Ratio};
 use frame_support::{assert_noop, assert_ok, error::BadOrigin};
+use orml_traits::MultiCurrency;
 use sp_runtime::{traits::Zero, FixedPointNumber};
 
 macro_rules! rate_model_sanity_check {
@@ -406,3 +408,45 @@ fn update_rate_model_works() {
 		);
 	})
 }
+
+#[test]
+fn stable_pool_lp_token_can_be_used_as_collateral() {
+	new_test_ext().execute_with(|| {
+		let blp = CurrencyId::BLP(0);
+		// 1 BLP of pool 0 stands for 2 KSM at $1
+		MockPriceFeeder::use_prices_pallet();
+		assert_ok!(Prices::set_price(
+			RuntimeOrigin::root(),
+			KSM,
+			Price::saturating_from_integer(1)
+		));
+		assert_eq!(
+			Prices::get_price(&blp).map(|(price, _)| price),
+			Prices::get_price(&KSM).map(|(price, _)| price * Price::saturating_from_integer(2))
+		);
+		// The LP token market is collateral only
+		assert_ok!(LendMarket::add_market(RuntimeOrigin::root(), blp, {
+			let mut market = market_mock(CurrencyId::Lend(4));
+			market.borrow_cap = 0;
+			market
+		}));
+		assert_ok!(LendMarket::activate_market(RuntimeOrigin::root(), blp));
+		assert_ok!(LendMarket::add_market_bond(RuntimeOrigin::root(), KSM, vec![blp]));
+
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), KSM, unit(200)));
+		assert_ok!(Currencies::deposit(blp, &ALICE, unit(100)));
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(ALICE), blp, unit(100)));
+		assert_ok!(LendMarket::collateral_asset(RuntimeOrigin::signed(ALICE), blp, true));
+
+		// 100 BLP at $2 with a 50% collateral factor back $100 of borrows
+		assert_noop!(
+			LendMarket::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(101)),
+			Error::<Test>::InsufficientLiquidity
+		);
+		assert_ok!(LendMarket::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(100)));
+		assert_noop!(
+			LendMarket::borrow(RuntimeOrigin::signed(BOB), blp, unit(1)),
+			Error::<Test>::BorrowCapacityExceeded
+		);
+	})
+}
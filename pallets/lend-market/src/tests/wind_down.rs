@@ -0,0 +1,220 @@
+use crate::{
+	mock::{new_test_ext, LendMarket, RuntimeOrigin, ALICE, BOB, DOT, DOT_U, EVE, KSM, *},
+	tests::unit,
+	Error, Event, MarketBond, MarketWindDowns, Markets, WindDown,
+};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{traits::BadOrigin, FixedPointNumber};
+
+#[test]
+fn wind_down_market_freezes_supply_and_borrow() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::add_market_bond(RuntimeOrigin::root(), KSM, vec![DOT_U, KSM]));
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), KSM, unit(200)));
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(ALICE), DOT_U, unit(200)));
+		assert_ok!(LendMarket::collateral_asset(RuntimeOrigin::signed(ALICE), DOT_U, true));
+
+		assert_noop!(
+			LendMarket::wind_down_market(RuntimeOrigin::signed(ALICE), KSM, 100, incentive(130), 0),
+			BadOrigin
+		);
+		assert_noop!(
+			LendMarket::wind_down_market(RuntimeOrigin::root(), KSM, 100, incentive(100), 0),
+			Error::<Test>::InvalidFactor
+		);
+		assert_ok!(LendMarket::wind_down_market(
+			RuntimeOrigin::root(),
+			KSM,
+			100,
+			incentive(130),
+			0
+		));
+		System::assert_last_event(RuntimeEvent::LendMarket(Event::MarketWindDownStarted(
+			KSM,
+			WindDown { start: 6, end: 106, liquidate_incentive: incentive(130), dust_borrows: 0 },
+		)));
+		assert_eq!(LendMarket::market(KSM).unwrap().state, MarketState::WindingDown);
+		assert_noop!(
+			LendMarket::wind_down_market(RuntimeOrigin::root(), KSM, 100, incentive(130), 0),
+			Error::<Test>::MarketNotActivated
+		);
+
+		assert_noop!(
+			LendMarket::mint(RuntimeOrigin::signed(BOB), KSM, unit(10)),
+			Error::<Test>::MarketWindingDown
+		);
+		assert_noop!(
+			LendMarket::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(10)),
+			Error::<Test>::MarketWindingDown
+		);
+		assert_noop!(
+			LendMarket::collateral_asset(RuntimeOrigin::signed(BOB), KSM, true),
+			Error::<Test>::MarketWindingDown
+		);
+		assert_noop!(
+			LendMarket::update_market(
+				RuntimeOrigin::root(),
+				KSM,
+				Some(Ratio::from_percent(60)),
+				None,
+				None,
+				None,
+				None,
+				None,
+				None,
+				None,
+			),
+			Error::<Test>::MarketWindingDown
+		);
+		// The wind-down schedule can't be skipped
+		let mut market = LendMarket::market(DOT).unwrap();
+		market.state = MarketState::WindingDown;
+		assert_noop!(
+			LendMarket::force_update_market(RuntimeOrigin::root(), DOT, market),
+			Error::<Test>::MarketNotWindingDown
+		);
+		// Suppliers can still leave the market
+		assert_ok!(LendMarket::redeem(RuntimeOrigin::signed(BOB), KSM, unit(100)));
+
+		// Reactivating the market cancels the wind-down
+		assert_ok!(LendMarket::activate_market(RuntimeOrigin::root(), KSM));
+		assert!(!MarketWindDowns::<Test>::contains_key(KSM));
+		assert_ok!(LendMarket::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(10)));
+	})
+}
+
+#[test]
+fn wind_down_market_ramps_collateral_factor_and_liquidation_incentive() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::add_market_bond(RuntimeOrigin::root(), DOT_U, vec![KSM]));
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(ALICE), DOT_U, unit(200)));
+		// Bob deposits 200 KSM as collateral and borrows 50 DOT_U
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), KSM, unit(200)));
+		assert_ok!(LendMarket::collateral_asset(RuntimeOrigin::signed(BOB), KSM, true));
+		assert_ok!(LendMarket::borrow(RuntimeOrigin::signed(BOB), DOT_U, unit(50)));
+		assert_ok!(LendMarket::wind_down_market(
+			RuntimeOrigin::root(),
+			KSM,
+			100,
+			incentive(130),
+			0
+		));
+
+		// Halfway through the wind-down
+		TimestampPallet::set_timestamp(56000);
+		let market = LendMarket::market(KSM).unwrap();
+		assert_eq!(market.collateral_factor, Ratio::from_percent(25));
+		assert_eq!(market.liquidation_threshold, Ratio::from_parts(275_000));
+		assert_eq!(market.liquidate_incentive, incentive(120));
+		// The stored market is unchanged
+		assert_eq!(Markets::<Test>::get(KSM).unwrap().collateral_factor, Ratio::from_percent(50));
+		// 27.5% of $200 KSM still covers the $50 DOT_U debt
+		assert_noop!(
+			LendMarket::liquidate_borrow(RuntimeOrigin::signed(ALICE), BOB, DOT_U, unit(10), KSM),
+			Error::<Test>::InsufficientShortfall
+		);
+
+		// 22% of $200 KSM doesn't
+		TimestampPallet::set_timestamp(66000);
+		assert_eq!(LendMarket::market(KSM).unwrap().liquidate_incentive, incentive(122));
+		assert_ok!(LendMarket::liquidate_borrow(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			DOT_U,
+			unit(10),
+			KSM
+		));
+
+		// After the wind-down
+		TimestampPallet::set_timestamp(206000);
+		let market = LendMarket::market(KSM).unwrap();
+		assert_eq!(market.collateral_factor, Ratio::from_percent(0));
+		assert_eq!(market.liquidation_threshold, Ratio::from_percent(0));
+		assert_eq!(market.liquidate_incentive, incentive(130));
+	})
+}
+
+#[test]
+fn wound_down_market_is_settled_and_delisted() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::add_market_bond(RuntimeOrigin::root(), KSM, vec![DOT_U]));
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), KSM, unit(200)));
+		// Alice deposits 200 DOT_U as collateral and borrows 20 KSM
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(ALICE), DOT_U, unit(200)));
+		assert_ok!(LendMarket::collateral_asset(RuntimeOrigin::signed(ALICE), DOT_U, true));
+		assert_ok!(LendMarket::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(20)));
+
+		assert_ok!(LendMarket::wind_down_market(
+			RuntimeOrigin::root(),
+			KSM,
+			100,
+			incentive(110),
+			1_000
+		));
+		assert_noop!(
+			LendMarket::settle_position(RuntimeOrigin::signed(EVE), KSM, BOB),
+			Error::<Test>::WindDownNotFinished
+		);
+		assert_noop!(
+			LendMarket::delist_market(RuntimeOrigin::root(), KSM, 10),
+			Error::<Test>::WindDownNotFinished
+		);
+		assert_noop!(
+			LendMarket::delist_market(RuntimeOrigin::root(), DOT, 10),
+			Error::<Test>::MarketNotWindingDown
+		);
+
+		// Make the wind-down end without accruing interest
+		MarketWindDowns::<Test>::mutate(KSM, |wind_down| {
+			wind_down.as_mut().unwrap().end = 6;
+		});
+		assert_noop!(
+			LendMarket::settle_position(RuntimeOrigin::signed(EVE), KSM, BOB),
+			Error::<Test>::MarketNotEmpty
+		);
+		// Alice is healthy, so her borrow can't be liquidated
+		assert_noop!(
+			LendMarket::liquidate_borrow(RuntimeOrigin::signed(BOB), ALICE, KSM, unit(20), DOT_U),
+			Error::<Test>::InsufficientShortfall
+		);
+		// Alice repays all but dust, which the reserves cover
+		assert_ok!(LendMarket::repay_borrow(RuntimeOrigin::signed(ALICE), KSM, unit(20) - 1_001));
+		assert_noop!(
+			LendMarket::settle_position(RuntimeOrigin::signed(EVE), KSM, BOB),
+			Error::<Test>::MarketNotEmpty
+		);
+		assert_ok!(LendMarket::repay_borrow(RuntimeOrigin::signed(ALICE), KSM, 1));
+		assert_ok!(LendMarket::add_reserves(RuntimeOrigin::root(), ALICE, KSM, unit(1)));
+
+		assert_ok!(LendMarket::settle_position(RuntimeOrigin::signed(EVE), KSM, BOB));
+		System::assert_has_event(RuntimeEvent::LendMarket(Event::WindDownDustWrittenOff(
+			KSM, 1_000, 0,
+		)));
+		System::assert_last_event(RuntimeEvent::LendMarket(Event::PositionSettled(
+			BOB,
+			KSM,
+			unit(200),
+		)));
+		assert!(!crate::AccountDeposits::<Test>::contains_key(KSM, BOB));
+		assert_eq!(LendMarket::total_borrows(KSM), 0);
+		assert_eq!(LendMarket::bad_debt(KSM), 1_000);
+		assert_ok!(LendMarket::reduce_reserves(RuntimeOrigin::root(), ALICE, KSM, unit(1) - 1_000));
+
+		assert_noop!(LendMarket::delist_market(RuntimeOrigin::signed(ALICE), KSM, 10), BadOrigin);
+		// The dust borrow snapshot of Alice and the reward indexes are removed over two calls
+		assert_ok!(LendMarket::delist_market(RuntimeOrigin::root(), KSM, 1));
+		System::assert_last_event(RuntimeEvent::LendMarket(Event::MarketDelistingInProgress(KSM)));
+		assert!(LendMarket::market(KSM).is_ok());
+		assert_ok!(LendMarket::delist_market(RuntimeOrigin::root(), KSM, 10));
+		System::assert_last_event(RuntimeEvent::LendMarket(Event::MarketDelisted(KSM)));
+		assert!(!crate::AccountBorrows::<Test>::contains_key(KSM, ALICE));
+		assert_eq!(LendMarket::market(KSM), Err(Error::<Test>::MarketDoesNotExist.into()));
+		assert!(!MarketBond::<Test>::contains_key(KSM));
+		assert!(!MarketWindDowns::<Test>::contains_key(KSM));
+		assert_eq!(LendMarket::total_supply(KSM), 0);
+	})
+}
+
+fn incentive(percent: u128) -> Rate {
+	Rate::saturating_from_rational(percent, 100)
+}
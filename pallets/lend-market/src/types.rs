@@ -1,5 +1,5 @@
 use crate::InterestRateModel;
-use bifrost_primitives::{CurrencyId, Rate, Ratio};
+use bifrost_primitives::{CurrencyId, Rate, Ratio, Timestamp};
 use frame_support::pallet_prelude::*;
 use scale_info::TypeInfo;
 
@@ -47,6 +47,9 @@ pub enum MarketState {
 	Active,
 	Pending,
 	Supervision,
+	/// Supplies and borrows are frozen while the market is being retired, the open
+	/// positions can still be redeemed, repaid and liquidated.
+	WindingDown,
 }
 
 /// Market.
@@ -95,3 +98,28 @@ pub struct RewardMarketState<BlockNumber, Balance> {
 	/// total amount of staking asset user deposited
 	pub block: BlockNumber,
 }
+
+/// Limits applied by the built-in liquidation keeper to a borrow market.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, Default, TypeInfo)]
+pub struct LiquidationKeeperLimit<Balance> {
+	/// Upper bound of the borrow repaid by a single keeper liquidation
+	pub max_repay_amount: Balance,
+	/// Minimum surplus of the repay asset a keeper liquidation must leave to the reserves
+	pub min_profit: Balance,
+}
+
+/// The schedule of a market being wound down.
+///
+/// The collateral factor and the liquidation threshold ramp down linearly from their stored
+/// values to zero between `start` and `end`, while the liquidation incentive rises to
+/// `liquidate_incentive`. Once the schedule ends, the borrows left below `dust_borrows` are
+/// written off so the deposits can be settled.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+pub struct WindDown<Balance> {
+	pub start: Timestamp,
+	pub end: Timestamp,
+	/// Liquidation incentive ratio at the end of the wind-down
+	pub liquidate_incentive: Rate,
+	/// Total borrows small enough to be written off at settlement
+	pub dust_borrows: Balance,
+}
@@ -69,6 +69,19 @@ pub trait WeightInfo {
 	fn withdraw_missing_reward() -> Weight;
 	fn collateral_asset() -> Weight;
 	fn update_liquidation_free_collateral() -> Weight;
+	fn update_liquidation_keeper() -> Weight;
+	fn keeper_scan_account() -> Weight;
+	fn keeper_liquidate() -> Weight;
+	fn write_off_bad_debt() -> Weight;
+	fn flash_loan() -> Weight;
+	fn update_flash_loan_fee_rate() -> Weight;
+	fn approve_borrow_allowance() -> Weight;
+	fn revoke_borrow_allowance() -> Weight;
+	fn borrow_on_behalf() -> Weight;
+	fn repay_borrow_on_behalf() -> Weight;
+	fn wind_down_market() -> Weight;
+	fn settle_position() -> Weight;
+	fn delist_market(l: u32, ) -> Weight;
 }
 
 /// Weights for loans using the Substrate node and recommended hardware.
@@ -690,6 +703,285 @@ impl<T: frame_system::Config> WeightInfo for BifrostWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(8_u64))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
+	/// Storage: Loans Markets (r:1 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans LiquidationKeeperLimits (r:0 w:1)
+	/// Proof Skipped: Loans LiquidationKeeperLimits (max_values: None, max_size: None, mode: Measured)
+	fn update_liquidation_keeper() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1504`
+		//  Estimated: `4969`
+		// Minimum execution time: 32_137_000 picoseconds.
+		Weight::from_parts(32_755_000, 4969)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Loans AccountBorrows (r:1 w:0)
+	/// Proof Skipped: Loans AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans LiquidationKeeperLimits (r:1 w:0)
+	/// Proof Skipped: Loans LiquidationKeeperLimits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans Markets (r:6 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountDeposits (r:5 w:0)
+	/// Proof Skipped: Loans AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans ExchangeRate (r:5 w:0)
+	/// Proof Skipped: Loans ExchangeRate (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans BorrowIndex (r:5 w:0)
+	/// Proof Skipped: Loans BorrowIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans LiquidationFreeCollaterals (r:1 w:0)
+	/// Proof Skipped: Loans LiquidationFreeCollaterals (max_values: Some(1), max_size: None, mode: Measured)
+	fn keeper_scan_account() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3854`
+		//  Estimated: `67914`
+		// Minimum execution time: 182_411_000 picoseconds.
+		Weight::from_parts(184_263_000, 67914)
+			.saturating_add(T::DbWeight::get().reads(24_u64))
+	}
+	/// Storage: Loans Markets (r:3 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountBorrows (r:1 w:1)
+	/// Proof Skipped: Loans AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountDeposits (r:2 w:2)
+	/// Proof Skipped: Loans AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalReserves (r:2 w:1)
+	/// Proof Skipped: Loans TotalReserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:8 w:8)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	/// Storage: ZenlinkProtocol PairStatuses (r:1 w:0)
+	/// Proof Skipped: ZenlinkProtocol PairStatuses (max_values: None, max_size: None, mode: Measured)
+	fn keeper_liquidate() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6318`
+		//  Estimated: `245174`
+		// Minimum execution time: 1_038_512_000 picoseconds.
+		Weight::from_parts(1_045_217_000, 245174)
+			.saturating_add(T::DbWeight::get().reads(62_u64))
+			.saturating_add(T::DbWeight::get().writes(31_u64))
+	}
+	/// Storage: Loans Markets (r:6 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountDeposits (r:5 w:0)
+	/// Proof Skipped: Loans AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountBorrows (r:5 w:1)
+	/// Proof Skipped: Loans AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalBorrows (r:1 w:1)
+	/// Proof Skipped: Loans TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalReserves (r:1 w:1)
+	/// Proof Skipped: Loans TotalReserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans BadDebt (r:1 w:1)
+	/// Proof Skipped: Loans BadDebt (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans ExchangeRate (r:0 w:1)
+	/// Proof Skipped: Loans ExchangeRate (max_values: None, max_size: None, mode: Measured)
+	fn write_off_bad_debt() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3947`
+		//  Estimated: `71232`
+		// Minimum execution time: 214_380_000 picoseconds.
+		Weight::from_parts(216_921_000, 71232)
+			.saturating_add(T::DbWeight::get().reads(28_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: Loans Markets (r:1 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Loans LastAccruedInterestTime (r:1 w:1)
+	/// Proof Skipped: Loans LastAccruedInterestTime (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans FlashLoanFeeRate (r:1 w:0)
+	/// Proof Skipped: Loans FlashLoanFeeRate (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans FlashLoanInProgress (r:1 w:1)
+	/// Proof Skipped: Loans FlashLoanInProgress (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Loans TotalReserves (r:2 w:1)
+	/// Proof Skipped: Loans TotalReserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn flash_loan() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2931`
+		//  Estimated: `26757`
+		// Minimum execution time: 121_508_000 picoseconds.
+		Weight::from_parts(123_271_000, 26757)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Loans Markets (r:1 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans FlashLoanFeeRate (r:0 w:1)
+	/// Proof Skipped: Loans FlashLoanFeeRate (max_values: None, max_size: None, mode: Measured)
+	fn update_flash_loan_fee_rate() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1504`
+		//  Estimated: `4969`
+		// Minimum execution time: 31_806_000 picoseconds.
+		Weight::from_parts(32_419_000, 4969)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Loans Markets (r:1 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans BorrowAllowances (r:0 w:1)
+	/// Proof Skipped: Loans BorrowAllowances (max_values: None, max_size: None, mode: Measured)
+	fn approve_borrow_allowance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1504`
+		//  Estimated: `4969`
+		// Minimum execution time: 31_781_000 picoseconds.
+		Weight::from_parts(33_281_000, 4969)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Loans BorrowAllowances (r:0 w:1)
+	/// Proof Skipped: Loans BorrowAllowances (max_values: None, max_size: None, mode: Measured)
+	fn revoke_borrow_allowance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 20_014_000 picoseconds.
+		Weight::from_parts(21_514_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Loans BorrowAllowances (r:1 w:1)
+	/// Proof Skipped: Loans BorrowAllowances (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans Markets (r:2 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Loans LastAccruedInterestTime (r:1 w:1)
+	/// Proof Skipped: Loans LastAccruedInterestTime (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountBorrows (r:1 w:1)
+	/// Proof Skipped: Loans AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalBorrows (r:1 w:1)
+	/// Proof Skipped: Loans TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	fn borrow_on_behalf() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3468`
+		//  Estimated: `93794`
+		// Minimum execution time: 279_902_000 picoseconds.
+		Weight::from_parts(281_402_000, 93794)
+			.saturating_add(T::DbWeight::get().reads(21_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: Loans Markets (r:2 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Loans LastAccruedInterestTime (r:1 w:1)
+	/// Proof Skipped: Loans LastAccruedInterestTime (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountBorrows (r:1 w:1)
+	/// Proof Skipped: Loans AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalBorrows (r:1 w:1)
+	/// Proof Skipped: Loans TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	fn repay_borrow_on_behalf() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3521`
+		//  Estimated: `63686`
+		// Minimum execution time: 184_810_000 picoseconds.
+		Weight::from_parts(186_310_000, 63686)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: Loans Markets (r:1 w:1)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Loans MarketWindDowns (r:0 w:1)
+	/// Proof Skipped: Loans MarketWindDowns (max_values: None, max_size: None, mode: Measured)
+	fn wind_down_market() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `514`
+		//  Estimated: `3979`
+		// Minimum execution time: 39_620_000 picoseconds.
+		Weight::from_parts(41_120_000, 3979)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Loans Markets (r:1 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans MarketWindDowns (r:1 w:0)
+	/// Proof Skipped: Loans MarketWindDowns (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Loans LastAccruedInterestTime (r:1 w:1)
+	/// Proof Skipped: Loans LastAccruedInterestTime (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalBorrows (r:1 w:0)
+	/// Proof Skipped: Loans TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountDeposits (r:1 w:1)
+	/// Proof Skipped: Loans AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans ExchangeRate (r:1 w:0)
+	/// Proof Skipped: Loans ExchangeRate (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalSupply (r:1 w:1)
+	/// Proof Skipped: Loans TotalSupply (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans RewardSupplyState (r:1 w:1)
+	/// Proof Skipped: Loans RewardSupplyState (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans RewardSupplySpeed (r:1 w:0)
+	/// Proof Skipped: Loans RewardSupplySpeed (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans RewardSupplierIndex (r:1 w:1)
+	/// Proof Skipped: Loans RewardSupplierIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans RewardAccrued (r:1 w:1)
+	/// Proof Skipped: Loans RewardAccrued (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	fn settle_position() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3066`
+		//  Estimated: `6531`
+		// Minimum execution time: 96_920_000 picoseconds.
+		Weight::from_parts(98_420_000, 6531)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Loans Markets (r:1 w:1)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans MarketWindDowns (r:1 w:1)
+	/// Proof Skipped: Loans MarketWindDowns (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Loans LastAccruedInterestTime (r:1 w:1)
+	/// Proof Skipped: Loans LastAccruedInterestTime (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalSupply (r:1 w:1)
+	/// Proof Skipped: Loans TotalSupply (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalBorrows (r:1 w:1)
+	/// Proof Skipped: Loans TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalReserves (r:1 w:1)
+	/// Proof Skipped: Loans TotalReserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans MarketBond (r:2 w:1)
+	/// Proof Skipped: Loans MarketBond (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans LiquidationFreeCollaterals (r:1 w:1)
+	/// Proof Skipped: Loans LiquidationFreeCollaterals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans UnderlyingAssetId (r:0 w:1)
+	/// Proof Skipped: Loans UnderlyingAssetId (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountBorrows (r:1 w:0)
+	/// Proof Skipped: Loans AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountDeposits (r:1 w:0)
+	/// Proof Skipped: Loans AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountEarned (r:1 w:0)
+	/// Proof Skipped: Loans AccountEarned (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans RewardSupplierIndex (r:1 w:0)
+	/// Proof Skipped: Loans RewardSupplierIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans RewardBorrowerIndex (r:1 w:0)
+	/// Proof Skipped: Loans RewardBorrowerIndex (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `l` is `[0, 1000]`.
+	fn delist_market(l: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `816 + l * (87 ±0)`
+		//  Estimated: `4281 + l * (2562 ±0)`
+		// Minimum execution time: 111_030_000 picoseconds.
+		Weight::from_parts(112_530_000, 4281)
+			// Standard Error: 6_812
+			.saturating_add(Weight::from_parts(9_418_000, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(17_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(l.into())))
+			.saturating_add(T::DbWeight::get().writes(19_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(l.into())))
+			.saturating_add(Weight::from_parts(0, 2562).saturating_mul(l.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -1310,4 +1602,283 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(8_u64))
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
+	/// Storage: Loans Markets (r:1 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans LiquidationKeeperLimits (r:0 w:1)
+	/// Proof Skipped: Loans LiquidationKeeperLimits (max_values: None, max_size: None, mode: Measured)
+	fn update_liquidation_keeper() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1504`
+		//  Estimated: `4969`
+		// Minimum execution time: 32_137_000 picoseconds.
+		Weight::from_parts(32_755_000, 4969)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Loans AccountBorrows (r:1 w:0)
+	/// Proof Skipped: Loans AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans LiquidationKeeperLimits (r:1 w:0)
+	/// Proof Skipped: Loans LiquidationKeeperLimits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans Markets (r:6 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountDeposits (r:5 w:0)
+	/// Proof Skipped: Loans AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans ExchangeRate (r:5 w:0)
+	/// Proof Skipped: Loans ExchangeRate (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans BorrowIndex (r:5 w:0)
+	/// Proof Skipped: Loans BorrowIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans LiquidationFreeCollaterals (r:1 w:0)
+	/// Proof Skipped: Loans LiquidationFreeCollaterals (max_values: Some(1), max_size: None, mode: Measured)
+	fn keeper_scan_account() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3854`
+		//  Estimated: `67914`
+		// Minimum execution time: 182_411_000 picoseconds.
+		Weight::from_parts(184_263_000, 67914)
+			.saturating_add(RocksDbWeight::get().reads(24_u64))
+	}
+	/// Storage: Loans Markets (r:3 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountBorrows (r:1 w:1)
+	/// Proof Skipped: Loans AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountDeposits (r:2 w:2)
+	/// Proof Skipped: Loans AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalReserves (r:2 w:1)
+	/// Proof Skipped: Loans TotalReserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:8 w:8)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	/// Storage: ZenlinkProtocol PairStatuses (r:1 w:0)
+	/// Proof Skipped: ZenlinkProtocol PairStatuses (max_values: None, max_size: None, mode: Measured)
+	fn keeper_liquidate() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6318`
+		//  Estimated: `245174`
+		// Minimum execution time: 1_038_512_000 picoseconds.
+		Weight::from_parts(1_045_217_000, 245174)
+			.saturating_add(RocksDbWeight::get().reads(62_u64))
+			.saturating_add(RocksDbWeight::get().writes(31_u64))
+	}
+	/// Storage: Loans Markets (r:6 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountDeposits (r:5 w:0)
+	/// Proof Skipped: Loans AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountBorrows (r:5 w:1)
+	/// Proof Skipped: Loans AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalBorrows (r:1 w:1)
+	/// Proof Skipped: Loans TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalReserves (r:1 w:1)
+	/// Proof Skipped: Loans TotalReserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans BadDebt (r:1 w:1)
+	/// Proof Skipped: Loans BadDebt (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans ExchangeRate (r:0 w:1)
+	/// Proof Skipped: Loans ExchangeRate (max_values: None, max_size: None, mode: Measured)
+	fn write_off_bad_debt() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3947`
+		//  Estimated: `71232`
+		// Minimum execution time: 214_380_000 picoseconds.
+		Weight::from_parts(216_921_000, 71232)
+			.saturating_add(RocksDbWeight::get().reads(28_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: Loans Markets (r:1 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Loans LastAccruedInterestTime (r:1 w:1)
+	/// Proof Skipped: Loans LastAccruedInterestTime (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans FlashLoanFeeRate (r:1 w:0)
+	/// Proof Skipped: Loans FlashLoanFeeRate (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans FlashLoanInProgress (r:1 w:1)
+	/// Proof Skipped: Loans FlashLoanInProgress (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Loans TotalReserves (r:2 w:1)
+	/// Proof Skipped: Loans TotalReserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn flash_loan() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2931`
+		//  Estimated: `26757`
+		// Minimum execution time: 121_508_000 picoseconds.
+		Weight::from_parts(123_271_000, 26757)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Loans Markets (r:1 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans FlashLoanFeeRate (r:0 w:1)
+	/// Proof Skipped: Loans FlashLoanFeeRate (max_values: None, max_size: None, mode: Measured)
+	fn update_flash_loan_fee_rate() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1504`
+		//  Estimated: `4969`
+		// Minimum execution time: 31_806_000 picoseconds.
+		Weight::from_parts(32_419_000, 4969)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Loans Markets (r:1 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans BorrowAllowances (r:0 w:1)
+	/// Proof Skipped: Loans BorrowAllowances (max_values: None, max_size: None, mode: Measured)
+	fn approve_borrow_allowance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1504`
+		//  Estimated: `4969`
+		// Minimum execution time: 31_781_000 picoseconds.
+		Weight::from_parts(33_281_000, 4969)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Loans BorrowAllowances (r:0 w:1)
+	/// Proof Skipped: Loans BorrowAllowances (max_values: None, max_size: None, mode: Measured)
+	fn revoke_borrow_allowance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 20_014_000 picoseconds.
+		Weight::from_parts(21_514_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Loans BorrowAllowances (r:1 w:1)
+	/// Proof Skipped: Loans BorrowAllowances (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans Markets (r:2 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Loans LastAccruedInterestTime (r:1 w:1)
+	/// Proof Skipped: Loans LastAccruedInterestTime (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountBorrows (r:1 w:1)
+	/// Proof Skipped: Loans AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalBorrows (r:1 w:1)
+	/// Proof Skipped: Loans TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	fn borrow_on_behalf() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3468`
+		//  Estimated: `93794`
+		// Minimum execution time: 279_902_000 picoseconds.
+		Weight::from_parts(281_402_000, 93794)
+			.saturating_add(RocksDbWeight::get().reads(21_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: Loans Markets (r:2 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Loans LastAccruedInterestTime (r:1 w:1)
+	/// Proof Skipped: Loans LastAccruedInterestTime (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountBorrows (r:1 w:1)
+	/// Proof Skipped: Loans AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalBorrows (r:1 w:1)
+	/// Proof Skipped: Loans TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	fn repay_borrow_on_behalf() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3521`
+		//  Estimated: `63686`
+		// Minimum execution time: 184_810_000 picoseconds.
+		Weight::from_parts(186_310_000, 63686)
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: Loans Markets (r:1 w:1)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Loans MarketWindDowns (r:0 w:1)
+	/// Proof Skipped: Loans MarketWindDowns (max_values: None, max_size: None, mode: Measured)
+	fn wind_down_market() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `514`
+		//  Estimated: `3979`
+		// Minimum execution time: 39_620_000 picoseconds.
+		Weight::from_parts(41_120_000, 3979)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Loans Markets (r:1 w:0)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans MarketWindDowns (r:1 w:0)
+	/// Proof Skipped: Loans MarketWindDowns (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Loans LastAccruedInterestTime (r:1 w:1)
+	/// Proof Skipped: Loans LastAccruedInterestTime (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalBorrows (r:1 w:0)
+	/// Proof Skipped: Loans TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountDeposits (r:1 w:1)
+	/// Proof Skipped: Loans AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans ExchangeRate (r:1 w:0)
+	/// Proof Skipped: Loans ExchangeRate (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalSupply (r:1 w:1)
+	/// Proof Skipped: Loans TotalSupply (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans RewardSupplyState (r:1 w:1)
+	/// Proof Skipped: Loans RewardSupplyState (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans RewardSupplySpeed (r:1 w:0)
+	/// Proof Skipped: Loans RewardSupplySpeed (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans RewardSupplierIndex (r:1 w:1)
+	/// Proof Skipped: Loans RewardSupplierIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans RewardAccrued (r:1 w:1)
+	/// Proof Skipped: Loans RewardAccrued (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	fn settle_position() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3066`
+		//  Estimated: `6531`
+		// Minimum execution time: 96_920_000 picoseconds.
+		Weight::from_parts(98_420_000, 6531)
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Loans Markets (r:1 w:1)
+	/// Proof Skipped: Loans Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans MarketWindDowns (r:1 w:1)
+	/// Proof Skipped: Loans MarketWindDowns (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Loans LastAccruedInterestTime (r:1 w:1)
+	/// Proof Skipped: Loans LastAccruedInterestTime (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalSupply (r:1 w:1)
+	/// Proof Skipped: Loans TotalSupply (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalBorrows (r:1 w:1)
+	/// Proof Skipped: Loans TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans TotalReserves (r:1 w:1)
+	/// Proof Skipped: Loans TotalReserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans MarketBond (r:2 w:1)
+	/// Proof Skipped: Loans MarketBond (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans LiquidationFreeCollaterals (r:1 w:1)
+	/// Proof Skipped: Loans LiquidationFreeCollaterals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans UnderlyingAssetId (r:0 w:1)
+	/// Proof Skipped: Loans UnderlyingAssetId (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountBorrows (r:1 w:0)
+	/// Proof Skipped: Loans AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountDeposits (r:1 w:0)
+	/// Proof Skipped: Loans AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans AccountEarned (r:1 w:0)
+	/// Proof Skipped: Loans AccountEarned (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans RewardSupplierIndex (r:1 w:0)
+	/// Proof Skipped: Loans RewardSupplierIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Loans RewardBorrowerIndex (r:1 w:0)
+	/// Proof Skipped: Loans RewardBorrowerIndex (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `l` is `[0, 1000]`.
+	fn delist_market(l: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `816 + l * (87 ±0)`
+		//  Estimated: `4281 + l * (2562 ±0)`
+		// Minimum execution time: 111_030_000 picoseconds.
+		Weight::from_parts(112_530_000, 4281)
+			// Standard Error: 6_812
+			.saturating_add(Weight::from_parts(9_418_000, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(17_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(l.into())))
+			.saturating_add(RocksDbWeight::get().writes(19_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(l.into())))
+			.saturating_add(Weight::from_parts(0, 2562).saturating_mul(l.into()))
+	}
 }
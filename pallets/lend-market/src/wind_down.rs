@@ -0,0 +1,207 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Market wind-down and delisting.
+//!
+//! A market being wound down is frozen: nothing can be supplied or borrowed, while the open
+//! positions can still be redeemed, repaid and liquidated. Its collateral factor and
+//! liquidation threshold ramp down to zero over the schedule and its liquidation incentive
+//! rises, so the borrowers backed by it are pushed into liquidation. Once the schedule ends
+//! and no more than the dust borrows of the schedule are left, the dust is written off and the
+//! deposits are settled to their owners. The empty market is then delisted and its storage
+//! removed over as many calls as its accounts need.
+
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+	/// Returns `market` with the collateral factor, liquidation threshold and liquidation
+	/// incentive of its wind-down schedule at the current time.
+	pub(crate) fn apply_wind_down(
+		asset_id: AssetIdOf<T>,
+		mut market: Market<BalanceOf<T>>,
+	) -> Market<BalanceOf<T>> {
+		if market.state != MarketState::WindingDown {
+			return market;
+		}
+		let wind_down = match Self::market_wind_down(asset_id) {
+			Some(wind_down) => wind_down,
+			None => return market,
+		};
+
+		let now = T::UnixTime::now().as_secs();
+		let duration = wind_down.end.saturating_sub(wind_down.start);
+		let elapsed = now.saturating_sub(wind_down.start).min(duration);
+		let remaining = Ratio::from_rational(duration - elapsed, duration.max(1));
+		market.collateral_factor = market.collateral_factor * remaining;
+		market.liquidation_threshold = market.liquidation_threshold * remaining;
+		if wind_down.liquidate_incentive > market.liquidate_incentive {
+			let increase = (wind_down.liquidate_incentive - market.liquidate_incentive)
+				.saturating_mul(Rate::saturating_from_rational(elapsed, duration.max(1)));
+			market.liquidate_incentive = market.liquidate_incentive.saturating_add(increase);
+		}
+
+		market
+	}
+
+	/// Returns true if `asset_id` is being wound down and its schedule has ended.
+	fn is_wind_down_finished(asset_id: AssetIdOf<T>) -> bool {
+		Self::market_wind_down(asset_id)
+			.map_or(false, |wind_down| T::UnixTime::now().as_secs() >= wind_down.end)
+	}
+
+	fn ensure_wind_down_finished(asset_id: AssetIdOf<T>) -> DispatchResult {
+		let market = Self::market(asset_id)?;
+		ensure!(market.state == MarketState::WindingDown, Error::<T>::MarketNotWindingDown);
+		ensure!(Self::is_wind_down_finished(asset_id), Error::<T>::WindDownNotFinished);
+		Ok(())
+	}
+
+	/// Redeems all the deposits of `account` in a wound down market without borrows other than
+	/// dust.
+	#[require_transactional]
+	pub(crate) fn do_settle_position(
+		asset_id: AssetIdOf<T>,
+		account: &T::AccountId,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		Self::ensure_wind_down_finished(asset_id)?;
+		Self::accrue_interest(asset_id)?;
+		Self::write_off_dust_borrows(asset_id)?;
+		let voucher_balance = Self::account_deposits(asset_id, account).voucher_balance;
+		ensure!(!voucher_balance.is_zero(), Error::<T>::InsufficientDeposit);
+
+		let redeem_amount = Self::do_redeem_voucher(account, asset_id, voucher_balance)?;
+		AccountEarned::<T>::remove(asset_id, account);
+
+		Ok(redeem_amount)
+	}
+
+	/// Writes off the borrows left in a wound down market if they are no more than the dust
+	/// borrows of its schedule.
+	///
+	/// As with bad debt, the dust is covered by the market reserves first and the remainder is
+	/// socialised across the suppliers. The borrow snapshots of the accounts are removed when
+	/// the market is delisted.
+	fn write_off_dust_borrows(asset_id: AssetIdOf<T>) -> DispatchResult {
+		let total_borrows = Self::total_borrows(asset_id);
+		if total_borrows.is_zero() {
+			return Ok(());
+		}
+		let dust_borrows = Self::market_wind_down(asset_id)
+			.map_or(Zero::zero(), |wind_down| wind_down.dust_borrows);
+		ensure!(total_borrows <= dust_borrows, Error::<T>::MarketNotEmpty);
+
+		let total_reserves = Self::total_reserves(asset_id);
+		let reserves_amount = total_borrows.min(total_reserves);
+		let total_reserves_new = total_reserves - reserves_amount;
+		TotalBorrows::<T>::insert(asset_id, BalanceOf::<T>::zero());
+		TotalReserves::<T>::insert(asset_id, total_reserves_new);
+		BadDebt::<T>::try_mutate(asset_id, |bad_debt| -> DispatchResult {
+			*bad_debt = bad_debt.checked_add(total_borrows).ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})?;
+
+		ExchangeRate::<T>::insert(asset_id, Self::exchange_rate_stored(asset_id)?);
+		UtilizationRatio::<T>::insert(
+			asset_id,
+			Self::calc_utilization_ratio(
+				Self::get_total_cash(asset_id),
+				Zero::zero(),
+				total_reserves_new,
+			)?,
+		);
+
+		Self::deposit_event(Event::<T>::WindDownDustWrittenOff(
+			asset_id,
+			reserves_amount,
+			total_borrows - reserves_amount,
+		));
+
+		Ok(())
+	}
+
+	/// Removes the storage of a wound down market once all its positions are settled.
+	///
+	/// Removes at most `limit` account entries and returns whether the market was removed, which
+	/// only happens once none of its account entries is left.
+	#[require_transactional]
+	pub(crate) fn do_delist_market(
+		asset_id: AssetIdOf<T>,
+		limit: u32,
+	) -> Result<bool, DispatchError> {
+		Self::ensure_wind_down_finished(asset_id)?;
+		Self::accrue_interest(asset_id)?;
+		ensure!(
+			Self::total_supply(asset_id).is_zero() &&
+				Self::total_borrows(asset_id).is_zero() &&
+				Self::total_reserves(asset_id).is_zero(),
+			Error::<T>::MarketNotEmpty
+		);
+		let market = Self::market(asset_id)?;
+
+		// Repaid borrows and written off dust leave principal snapshots behind
+		let mut remaining = limit as usize;
+		remaining -= AccountBorrows::<T>::drain_prefix(asset_id).take(remaining).count();
+		remaining -= AccountDeposits::<T>::drain_prefix(asset_id).take(remaining).count();
+		remaining -= AccountEarned::<T>::drain_prefix(asset_id).take(remaining).count();
+		remaining -= RewardSupplierIndex::<T>::drain_prefix(asset_id).take(remaining).count();
+		let _ = RewardBorrowerIndex::<T>::drain_prefix(asset_id).take(remaining).count();
+		if AccountBorrows::<T>::iter_prefix(asset_id).next().is_some() ||
+			AccountDeposits::<T>::iter_prefix(asset_id).next().is_some() ||
+			AccountEarned::<T>::iter_prefix(asset_id).next().is_some() ||
+			RewardSupplierIndex::<T>::iter_prefix(asset_id).next().is_some() ||
+			RewardBorrowerIndex::<T>::iter_prefix(asset_id).next().is_some()
+		{
+			return Ok(false);
+		}
+
+		Markets::<T>::remove(asset_id);
+		UnderlyingAssetId::<T>::remove(market.lend_token_id);
+		MarketWindDowns::<T>::remove(asset_id);
+		LastAccruedInterestTime::<T>::remove(asset_id);
+		TotalSupply::<T>::remove(asset_id);
+		TotalBorrows::<T>::remove(asset_id);
+		TotalReserves::<T>::remove(asset_id);
+		BorrowIndex::<T>::remove(asset_id);
+		ExchangeRate::<T>::remove(asset_id);
+		BorrowRate::<T>::remove(asset_id);
+		SupplyRate::<T>::remove(asset_id);
+		UtilizationRatio::<T>::remove(asset_id);
+		RateAtTarget::<T>::remove(asset_id);
+		BadDebt::<T>::remove(asset_id);
+		FlashLoanFeeRate::<T>::remove(asset_id);
+		LiquidationKeeperLimits::<T>::remove(asset_id);
+
+		RewardSupplySpeed::<T>::remove(asset_id);
+		RewardBorrowSpeed::<T>::remove(asset_id);
+		RewardSupplyState::<T>::remove(asset_id);
+		RewardBorrowState::<T>::remove(asset_id);
+
+		MarketBond::<T>::remove(asset_id);
+		let bonds: Vec<(AssetIdOf<T>, Vec<AssetIdOf<T>>)> =
+			MarketBond::<T>::iter().filter(|(_, bond)| bond.contains(&asset_id)).collect();
+		for (bonded_asset_id, mut bond) in bonds {
+			bond.retain(|id| *id != asset_id);
+			MarketBond::<T>::insert(bonded_asset_id, bond);
+		}
+		LiquidationFreeCollaterals::<T>::mutate(|collaterals| {
+			collaterals.retain(|id| *id != asset_id)
+		});
+
+		Ok(true)
+	}
+}
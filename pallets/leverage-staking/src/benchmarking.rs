@@ -187,6 +187,98 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn set_min_health_factor() -> Result<(), BenchmarkError> {
+		let health_factor = FixedU128::from_rational(11, 10);
+
+		#[extrinsic_call]
+		Pallet::<T>::set_min_health_factor(SystemOrigin::Root, KSM.into(), health_factor);
+
+		assert_eq!(MinHealthFactors::<T>::get(KSM), Some(health_factor));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_deleverage_trigger() -> Result<(), BenchmarkError> {
+		init::<T>()?;
+		let caller: AccountIdOf<T> = account("caller", 1, SEED);
+		assert_ok!(Pallet::<T>::set_min_health_factor(
+			SystemOrigin::Root.into(),
+			KSM.into(),
+			FixedU128::from_rational(11, 10)
+		));
+		let trigger = DeleverageTrigger {
+			health_factor: FixedU128::from_rational(12, 10),
+			rate: FixedU128::from_rational(1, 2),
+		};
+
+		#[extrinsic_call]
+		Pallet::<T>::set_deleverage_trigger(
+			SystemOrigin::Signed(caller.clone()),
+			KSM.into(),
+			trigger,
+		);
+
+		assert_eq!(DeleverageTriggers::<T>::get(&caller, KSM), Some(trigger));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_deleverage_trigger() -> Result<(), BenchmarkError> {
+		let caller: AccountIdOf<T> = account("caller", 1, SEED);
+		let trigger = DeleverageTrigger {
+			health_factor: FixedU128::from_rational(12, 10),
+			rate: FixedU128::from_rational(1, 2),
+		};
+		DeleverageTriggers::<T>::insert(&caller, KSM, trigger);
+
+		#[extrinsic_call]
+		Pallet::<T>::remove_deleverage_trigger(SystemOrigin::Signed(caller.clone()), KSM.into());
+
+		assert_eq!(DeleverageTriggers::<T>::get(&caller, KSM), None);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_max_price_impact() -> Result<(), BenchmarkError> {
+		let caller: AccountIdOf<T> = account("caller", 1, SEED);
+
+		#[extrinsic_call]
+		Pallet::<T>::set_max_price_impact(
+			SystemOrigin::Signed(caller.clone()),
+			Some(Permill::from_percent(1)),
+		);
+
+		assert_eq!(MaxPriceImpacts::<T>::get(&caller), Some(Permill::from_percent(1)));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn deleverage_scan_account() -> Result<(), BenchmarkError> {
+		init::<T>()?;
+		let caller: AccountIdOf<T> = account("caller", 1, SEED);
+		assert_ok!(Pallet::<T>::flash_loan_deposit(
+			SystemOrigin::Signed(caller.clone()).into(),
+			KSM.into(),
+			FixedU128::from_inner(unit(990_000))
+		));
+		// The position stays above the trigger, so the scan doesn't deleverage it.
+		let trigger =
+			DeleverageTrigger { health_factor: FixedU128::one(), rate: FixedU128::zero() };
+		DeleverageTriggers::<T>::insert(&caller, KSM, trigger);
+
+		#[block]
+		{
+			Pallet::<T>::run_auto_deleverage(
+				T::DbWeight::get()
+					.reads_writes(1, 1)
+					.saturating_add(<T as pallet::Config>::WeightInfo::deleverage_scan_account()),
+			);
+		}
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		crate::mock::ExtBuilder::default().new_test_ext().build(),
@@ -26,17 +26,11 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+mod route;
 pub mod weights;
 
-use bifrost_primitives::{CurrencyIdConversion, Rate, VtokenMintingInterface};
-use frame_support::{
-	pallet_prelude::*,
-	traits::{
-		fungibles::Mutate,
-		tokens::{Fortitude, Precision, Preservation},
-	},
-	transactional, BoundedVec,
-};
+use bifrost_primitives::{CurrencyIdConversion, Rate, Ratio, VtokenMintingInterface};
+use frame_support::{pallet_prelude::*, transactional, weights::WeightMeter, BoundedVec};
 use frame_system::{ensure_signed, pallet_prelude::*};
 pub use pallet_traits::{
 	ConvertToBigUint, LendMarket as LendMarketTrait, LendMarketMarketDataProvider,
@@ -44,14 +38,35 @@ pub use pallet_traits::{
 };
 pub use parity_scale_codec::{Decode, Encode};
 use sp_runtime::{
-	traits::{CheckedSub, Zero},
-	ArithmeticError, FixedPointNumber, FixedU128, SaturatedConversion,
+	traits::{CheckedSub, One, Zero},
+	ArithmeticError, FixedPointNumber, FixedU128, PerThing, Permill, SaturatedConversion,
 };
-use sp_std::{cmp::Ordering, marker::PhantomData};
+use sp_std::{cmp::Ordering, marker::PhantomData, vec::Vec};
 pub use weights::WeightInfo;
 
 use bifrost_stable_pool::traits::StablePoolHandler;
 use lend_market::{AccountDeposits, AccountIdOf, AssetIdOf, BalanceOf};
+pub use route::{RouteLeg, ROUTE_SPLIT_PARTS};
+
+/// A way to exchange a token for its vtoken.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum Route {
+	/// Minting through vtoken-minting, only to buy vtokens
+	Mint,
+	StablePool,
+	Zenlink,
+}
+
+/// The opt-in auto-deleverage of a leveraged position.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DeleverageTrigger {
+	/// The position is deleveraged when the lend-market health factor of the account falls
+	/// below this floor
+	pub health_factor: FixedU128,
+	/// The leverage rate the position is reduced to
+	pub rate: Rate,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -89,6 +104,15 @@ pub mod pallet {
 	pub enum Error<T> {
 		ArgumentsError,
 		NotSupportTokenType,
+		/// The leverage rate is above the maximum leverage of the market
+		LeverageTooHigh,
+		/// The health factor must be above 1
+		InvalidHealthFactor,
+		/// No route can exchange the amount
+		NoRouteAvailable,
+		/// The route loses more than the maximum price impact of the account against the
+		/// vtoken-minting exchange rate
+		PriceImpactTooHigh,
 	}
 
 	#[pallet::event]
@@ -100,6 +124,68 @@ pub mod pallet {
 			old_rate: Rate,
 			new_rate: Rate,
 		},
+		MinHealthFactorSet {
+			asset_id: AssetIdOf<T>,
+			health_factor: FixedU128,
+		},
+		DeleverageTriggerSet {
+			who: AccountIdOf<T>,
+			asset_id: AssetIdOf<T>,
+			trigger: DeleverageTrigger,
+		},
+		DeleverageTriggerRemoved {
+			who: AccountIdOf<T>,
+			asset_id: AssetIdOf<T>,
+		},
+		AutoDeleveraged {
+			who: AccountIdOf<T>,
+			asset_id: AssetIdOf<T>,
+			health_factor: FixedU128,
+			old_rate: Rate,
+			new_rate: Rate,
+		},
+		MaxPriceImpactSet {
+			who: AccountIdOf<T>,
+			max_price_impact: Option<Permill>,
+		},
+		Routed {
+			who: AccountIdOf<T>,
+			asset_id: AssetIdOf<T>,
+			route: Route,
+			amount_in: BalanceOf<T>,
+			amount_out: BalanceOf<T>,
+		},
+	}
+
+	/// The lend-market health factor a position keeps at the maximum leverage.
+	#[pallet::storage]
+	pub type MinHealthFactors<T: Config> = StorageMap<_, Blake2_128Concat, AssetIdOf<T>, FixedU128>;
+
+	/// The auto-deleverage triggers of the leveraged positions.
+	#[pallet::storage]
+	pub type DeleverageTriggers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		DeleverageTrigger,
+	>;
+
+	/// The largest loss against the vtoken-minting exchange rate an account accepts when its
+	/// leverage is adjusted, mint fees included.
+	#[pallet::storage]
+	pub type MaxPriceImpacts<T: Config> = StorageMap<_, Blake2_128Concat, AccountIdOf<T>, Permill>;
+
+	/// The raw key of `DeleverageTriggers` the next `on_idle` scan starts after.
+	#[pallet::storage]
+	pub type DeleverageScanCursor<T: Config> = StorageValue<_, Vec<u8>>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::run_auto_deleverage(remaining_weight)
+		}
 	}
 
 	#[pallet::call]
@@ -113,6 +199,73 @@ pub mod pallet {
 		) -> DispatchResult {
 			Pallet::<T>::flash_loan_deposit_inner(origin, asset_id, rate)
 		}
+
+		/// Sets the health factor that positions in `asset_id` keep at the maximum leverage.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_min_health_factor())]
+		pub fn set_min_health_factor(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			health_factor: FixedU128,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+			ensure!(health_factor > FixedU128::one(), Error::<T>::InvalidHealthFactor);
+
+			MinHealthFactors::<T>::insert(asset_id, health_factor);
+			Self::deposit_event(Event::<T>::MinHealthFactorSet { asset_id, health_factor });
+			Ok(())
+		}
+
+		/// Reduces the leverage of the sender's position in `asset_id` to `trigger.rate` once
+		/// its health factor falls below `trigger.health_factor`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_deleverage_trigger())]
+		pub fn set_deleverage_trigger(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			trigger: DeleverageTrigger,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(trigger.health_factor > FixedU128::one(), Error::<T>::InvalidHealthFactor);
+			let vtoken_id = T::CurrencyIdConversion::convert_to_vtoken(asset_id)
+				.map_err(|_| Error::<T>::NotSupportTokenType)?;
+			ensure!(
+				trigger.rate <= Self::max_leverage(asset_id, vtoken_id)?,
+				Error::<T>::LeverageTooHigh
+			);
+
+			DeleverageTriggers::<T>::insert(&who, asset_id, trigger);
+			Self::deposit_event(Event::<T>::DeleverageTriggerSet { who, asset_id, trigger });
+			Ok(())
+		}
+
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::remove_deleverage_trigger())]
+		pub fn remove_deleverage_trigger(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			DeleverageTriggers::<T>::remove(&who, asset_id);
+			Self::deposit_event(Event::<T>::DeleverageTriggerRemoved { who, asset_id });
+			Ok(())
+		}
+
+		/// Sets the maximum price impact of the sender's leverage adjustments, `None` removes
+		/// the limit.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_max_price_impact())]
+		pub fn set_max_price_impact(
+			origin: OriginFor<T>,
+			max_price_impact: Option<Permill>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			MaxPriceImpacts::<T>::set(&who, max_price_impact);
+			Self::deposit_event(Event::<T>::MaxPriceImpactSet { who, max_price_impact });
+			Ok(())
+		}
 	}
 }
 
@@ -125,24 +278,31 @@ impl<T: Config> Pallet<T> {
 	) -> DispatchResult {
 		let who = ensure_signed(origin)?;
 
+		let current_rate = Self::adjust_leverage(&who, asset_id, rate)?;
+		Self::deposit_event(Event::<T>::FlashLoanDeposited {
+			who,
+			asset_id,
+			old_rate: current_rate,
+			new_rate: rate,
+		});
+		Ok(())
+	}
+
+	/// Moves the leverage of `who` in `asset_id` to `rate` and returns the previous rate.
+	fn adjust_leverage(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		rate: Rate,
+	) -> Result<Rate, DispatchError> {
 		let vtoken_id = T::CurrencyIdConversion::convert_to_vtoken(asset_id)
 			.map_err(|_| Error::<T>::NotSupportTokenType)?;
 
-		let deposits = AccountDeposits::<T>::get(vtoken_id, &who);
+		let deposits = AccountDeposits::<T>::get(vtoken_id, who);
 		if !deposits.is_collateral {
-			T::LendMarket::do_collateral_asset(&who, vtoken_id, true)?;
+			T::LendMarket::do_collateral_asset(who, vtoken_id, true)?;
 		}
-		let account_deposits = Self::current_collateral_amount(&who, vtoken_id)?;
-		let account_borrows = lend_market::Pallet::<T>::get_current_borrow_balance(&who, asset_id)?;
-
-		// Formula
-		// current_rate = account_borrows / ( vtoken_to_token(account_deposits) - account_borrows )
-		let deposits_token_value =
-			T::VtokenMinting::vtoken_to_token(asset_id, vtoken_id, account_deposits)?;
-		let base_token_value = deposits_token_value
-			.checked_sub(account_borrows)
-			.ok_or(ArithmeticError::Overflow)?;
-		let current_rate = FixedU128::saturating_from_rational(account_borrows, base_token_value);
+		let (current_rate, base_token_value, account_borrows) =
+			Self::current_leverage(who, asset_id, vtoken_id)?;
 
 		match rate.cmp(&current_rate) {
 			Ordering::Less => {
@@ -154,22 +314,175 @@ impl<T: Config> Pallet<T> {
 						.and_then(|r| r.checked_mul_int(base_token_value))
 						.ok_or(ArithmeticError::Overflow)?
 				};
-				Self::reduce_leverage(&who, asset_id, vtoken_id, reduce_amount)?;
+				Self::reduce_leverage(who, asset_id, vtoken_id, reduce_amount)?;
 			},
 			Ordering::Equal => return Err(Error::<T>::ArgumentsError.into()),
 			Ordering::Greater => {
+				ensure!(
+					rate <= Self::max_leverage(asset_id, vtoken_id)?,
+					Error::<T>::LeverageTooHigh
+				);
 				let increase_amount = rate
 					.checked_sub(&current_rate)
 					.and_then(|r| r.checked_mul_int(base_token_value))
 					.ok_or(ArithmeticError::Overflow)?;
-				Self::increase_leverage(&who, asset_id, vtoken_id, increase_amount)?;
+				Self::increase_leverage(who, asset_id, vtoken_id, increase_amount)?;
 			},
 		}
-		Self::deposit_event(Event::<T>::FlashLoanDeposited {
-			who,
+		Ok(current_rate)
+	}
+
+	/// Returns the leverage rate of `who` in `asset_id`, with the value of its own tokens and
+	/// its borrows.
+	fn current_leverage(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		vtoken_id: AssetIdOf<T>,
+	) -> Result<(Rate, BalanceOf<T>, BalanceOf<T>), DispatchError> {
+		let account_deposits = Self::current_collateral_amount(who, vtoken_id)?;
+		let account_borrows = lend_market::Pallet::<T>::get_current_borrow_balance(who, asset_id)?;
+
+		// Formula
+		// current_rate = account_borrows / ( vtoken_to_token(account_deposits) - account_borrows )
+		let deposits_token_value =
+			T::VtokenMinting::vtoken_to_token(asset_id, vtoken_id, account_deposits)?;
+		let base_token_value = deposits_token_value
+			.checked_sub(account_borrows)
+			.ok_or(ArithmeticError::Overflow)?;
+		let current_rate = FixedU128::saturating_from_rational(account_borrows, base_token_value);
+
+		Ok((current_rate, base_token_value, account_borrows))
+	}
+
+	/// The highest leverage rate of `asset_id`.
+	///
+	/// At rate `r` the borrows are `r / (1 + r)` of the collateral, which must stay within the
+	/// collateral factor of the vtoken market and, if a minimum health factor `h` is set,
+	/// within `liquidation_threshold / h`.
+	pub fn max_leverage(
+		asset_id: AssetIdOf<T>,
+		vtoken_id: AssetIdOf<T>,
+	) -> Result<Rate, DispatchError> {
+		let market = lend_market::Pallet::<T>::market(vtoken_id)?;
+		let max_borrow_ratio = |ratio: FixedU128| -> Result<Rate, DispatchError> {
+			let rest = FixedU128::one().checked_sub(&ratio).ok_or(ArithmeticError::Underflow)?;
+			Ok(ratio.checked_div(&rest).ok_or(ArithmeticError::Overflow)?)
+		};
+
+		let collateral_factor = Self::ratio_to_fixed(market.collateral_factor);
+		let mut max_rate = max_borrow_ratio(collateral_factor)?;
+		if let Some(health_factor) = MinHealthFactors::<T>::get(asset_id) {
+			let liquidation_threshold = Self::ratio_to_fixed(market.liquidation_threshold)
+				.checked_div(&health_factor)
+				.ok_or(ArithmeticError::Underflow)?;
+			max_rate = max_rate.min(max_borrow_ratio(liquidation_threshold)?);
+		}
+		Ok(max_rate)
+	}
+
+	fn ratio_to_fixed(ratio: Ratio) -> FixedU128 {
+		FixedU128::saturating_from_rational(ratio.deconstruct(), Ratio::ACCURACY)
+	}
+
+	/// Scans `DeleverageTriggers` from `DeleverageScanCursor` and deleverages the positions
+	/// whose health factor is below their trigger, until `limit` is used up.
+	pub(crate) fn run_auto_deleverage(limit: Weight) -> Weight {
+		let mut meter = WeightMeter::with_limit(limit);
+		if meter.try_consume(T::DbWeight::get().reads_writes(1, 1)).is_err() {
+			return meter.consumed();
+		}
+
+		let mut cursor = DeleverageScanCursor::<T>::get();
+		let mut iter = match cursor.clone() {
+			Some(raw_key) => DeleverageTriggers::<T>::iter_from(raw_key),
+			None => DeleverageTriggers::<T>::iter(),
+		};
+		while meter
+			.try_consume(<T as pallet::Config>::WeightInfo::deleverage_scan_account())
+			.is_ok()
+		{
+			let (who, asset_id, trigger) = match iter.next() {
+				Some(item) => item,
+				None => {
+					// Every trigger has been scanned, start over in the next round.
+					cursor = None;
+					break;
+				},
+			};
+
+			match Self::deleverage_health_factor(&who, asset_id, &trigger) {
+				Ok(Some(health_factor)) => {
+					// Leave the position to the next block if the deleverage doesn't fit.
+					if meter
+						.try_consume(<T as pallet::Config>::WeightInfo::flash_loan_deposit())
+						.is_err()
+					{
+						break;
+					}
+					if let Err(e) = Self::auto_deleverage(&who, asset_id, &trigger, health_factor) {
+						log::error!(
+							target: "leverage-staking::auto_deleverage",
+							"who: {:?}, asset_id: {:?}, error: {:?}",
+							who,
+							asset_id,
+							e,
+						);
+					}
+				},
+				Ok(None) => {},
+				Err(e) => log::trace!(
+					target: "leverage-staking::auto_deleverage",
+					"who: {:?}, asset_id: {:?}, error: {:?}",
+					who,
+					asset_id,
+					e,
+				),
+			}
+			cursor = Some(DeleverageTriggers::<T>::hashed_key_for(&who, asset_id));
+		}
+
+		match cursor {
+			Some(raw_key) => DeleverageScanCursor::<T>::put(raw_key),
+			None => DeleverageScanCursor::<T>::kill(),
+		}
+		meter.consumed()
+	}
+
+	/// Returns the health factor of `who` if it is below the trigger and the position is
+	/// leveraged above the trigger rate.
+	fn deleverage_health_factor(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		trigger: &DeleverageTrigger,
+	) -> Result<Option<FixedU128>, DispatchError> {
+		let health_factor = match lend_market::Pallet::<T>::get_account_health(who)?.health_factor {
+			Some(health_factor) if health_factor < trigger.health_factor => health_factor,
+			_ => return Ok(None),
+		};
+		let vtoken_id = T::CurrencyIdConversion::convert_to_vtoken(asset_id)
+			.map_err(|_| Error::<T>::NotSupportTokenType)?;
+		let (current_rate, _, _) = Self::current_leverage(who, asset_id, vtoken_id)?;
+		if current_rate <= trigger.rate {
+			return Ok(None);
+		}
+
+		Ok(Some(health_factor))
+	}
+
+	#[transactional]
+	fn auto_deleverage(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		trigger: &DeleverageTrigger,
+		health_factor: FixedU128,
+	) -> DispatchResult {
+		let old_rate = Self::adjust_leverage(who, asset_id, trigger.rate)?;
+		Self::deposit_event(Event::<T>::AutoDeleveraged {
+			who: who.clone(),
 			asset_id,
-			old_rate: current_rate,
-			new_rate: rate,
+			health_factor,
+			old_rate,
+			new_rate: trigger.rate,
 		});
 		Ok(())
 	}
@@ -180,39 +493,19 @@ impl<T: Config> Pallet<T> {
 		vtoken_id: AssetIdOf<T>,
 		reduce_amount: BalanceOf<T>,
 	) -> DispatchResult {
-		let (pool_id, currency_id_in, currency_id_out) =
-			T::StablePoolHandler::get_pool_id(&vtoken_id, &asset_id)
-				.ok_or(Error::<T>::ArgumentsError)?;
-
-		<T as lend_market::Config>::Assets::mint_into(asset_id, &who, reduce_amount)?;
-
-		T::LendMarket::do_repay_borrow(&who, asset_id, reduce_amount)?;
-		let redeem_amount = T::StablePoolHandler::get_swap_input(
-			pool_id,
-			currency_id_in,
-			currency_id_out,
-			reduce_amount,
-		)?;
-		// Do redeem
-		T::LendMarket::do_redeem(&who, vtoken_id, redeem_amount)?;
-
-		T::StablePoolHandler::swap(
-			&who,
-			pool_id,
-			currency_id_in,
-			currency_id_out,
-			redeem_amount,
-			reduce_amount,
-		)?;
-		<T as lend_market::Config>::Assets::burn_from(
-			asset_id,
-			&who,
-			reduce_amount,
-			Preservation::Protect,
-			Precision::Exact,
-			Fortitude::Force,
-		)?;
-		Ok(())
+		// The flash loan and its fee are repaid with the vtoken swapped out of the collateral.
+		let repay_amount = T::LendMarket::flash_loan_fee(asset_id, reduce_amount)
+			.checked_add(reduce_amount)
+			.ok_or(ArithmeticError::Overflow)?;
+		T::LendMarket::do_flash_loan(who, asset_id, reduce_amount, || {
+			T::LendMarket::do_repay_borrow(who, asset_id, reduce_amount)?;
+			let (legs, redeem_amount) =
+				Self::quote_from_vtoken(who, asset_id, vtoken_id, repay_amount)?;
+			// Do redeem
+			T::LendMarket::do_redeem(who, vtoken_id, redeem_amount)?;
+
+			Self::route_from_vtoken(who, asset_id, vtoken_id, legs)
+		})
 	}
 
 	fn increase_leverage(
@@ -221,25 +514,11 @@ impl<T: Config> Pallet<T> {
 		vtoken_id: AssetIdOf<T>,
 		increase_amount: BalanceOf<T>,
 	) -> DispatchResult {
-		<T as lend_market::Config>::Assets::mint_into(asset_id, &who, increase_amount)?;
-		let vtoken_value = T::VtokenMinting::mint(
-			who.clone(),
-			asset_id,
-			increase_amount,
-			BoundedVec::default(),
-			None,
-		)?;
-		T::LendMarket::do_mint(&who, vtoken_id, vtoken_value)?;
-		T::LendMarket::do_borrow(&who, asset_id, increase_amount)?;
-		<T as lend_market::Config>::Assets::burn_from(
-			asset_id,
-			&who,
-			increase_amount,
-			Preservation::Protect,
-			Precision::Exact,
-			Fortitude::Force,
-		)?;
-		Ok(())
+		// The flash loan and its fee become a borrow backed by the minted vtoken collateral.
+		T::LendMarket::do_flash_borrow(who, asset_id, increase_amount, || {
+			let vtoken_value = Self::route_to_vtoken(who, asset_id, vtoken_id, increase_amount)?;
+			T::LendMarket::do_mint(who, vtoken_id, vtoken_value)
+		})
 	}
 
 	fn current_collateral_amount(
@@ -23,18 +23,22 @@ pub use bifrost_primitives::{
 	currency::*, Balance, CurrencyId, CurrencyIdMapping, SlpOperator, SlpxOperator, TokenSymbol, *,
 };
 use bifrost_runtime_common::milli;
+use cumulus_primitives_core::ParaId;
 use frame_support::{
 	derive_impl, ord_parameter_types, parameter_types,
-	traits::{ConstU128, ConstU32, Everything, Nothing},
+	traits::{ConstU128, ConstU32, ConstU64, Everything, Nothing},
 	PalletId,
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
 use lend_market::{InterestRateModel, JumpModel, Market, MarketState};
 use orml_traits::{
 	location::RelativeReserveProvider, parameter_type_with_key, DataFeeder, DataProvider,
-	DataProviderExtended,
+	DataProviderExtended, MultiCurrency,
+};
+use sp_runtime::{
+	traits::{IdentityLookup, UniqueSaturatedInto},
+	BuildStorage, FixedPointNumber,
 };
-use sp_runtime::{traits::IdentityLookup, BuildStorage, FixedPointNumber};
 use std::{
 	cell::RefCell,
 	collections::HashMap,
@@ -43,6 +47,9 @@ use std::{
 use xcm::{prelude::*, v3::Weight};
 use xcm_builder::{FixedWeightBounds, FrameTransactionalProcessor};
 use xcm_executor::XcmExecutor;
+use zenlink_protocol::{
+	AssetBalance, AssetId as ZenlinkAssetId, LocalAssetHandler, PairLpGenerate, ZenlinkMultiAssets,
+};
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -63,6 +70,7 @@ frame_support::construct_runtime!(
 		TimestampPallet: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		LeverageStaking: leverage_staking::{Pallet, Storage, Call, Event<T>},
 		Prices: pallet_prices::{Pallet, Storage, Call, Event<T>},
+		ZenlinkProtocol: zenlink_protocol,
 		// PolkadotXcm: pallet_xcm,
 	}
 );
@@ -252,6 +260,7 @@ impl bifrost_stable_asset::Config for Test {
 	type WeightInfo = ();
 	type ListingOrigin = EnsureSignedBy<One, u128>;
 	type EnsurePoolAssetId = EnsurePoolAssetId;
+	type MaxPriceObservations = ConstU32<1200>;
 }
 
 impl bifrost_stable_pool::Config for Test {
@@ -263,6 +272,9 @@ impl bifrost_stable_pool::Config for Test {
 	type VtokenMinting = VtokenMinting;
 	type CurrencyIdConversion = AssetIdMaps<Test>;
 	type CurrencyIdRegister = AssetIdMaps<Test>;
+	type DepositCurrencyId = GetNativeCurrencyId;
+	type PoolDeposit = ConstU128<1_000_000_000_000>;
+	type PoolReapDelay = ConstU64<100>;
 }
 
 impl leverage_staking::Config for Test {
@@ -427,6 +439,87 @@ impl PriceFeeder for MockPriceFeeder {
 	}
 }
 
+parameter_types! {
+	pub const ZenlinkPalletId: PalletId = PalletId(*b"/zenlink");
+	pub const SelfParaId: u32 = 2001;
+}
+
+impl zenlink_protocol::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type MultiAssetsHandler = MultiAssets;
+	type PalletId = ZenlinkPalletId;
+	type SelfParaId = SelfParaId;
+	type TargetChains = ();
+	type WeightInfo = ();
+	type AssetId = ZenlinkAssetId;
+	type LpGenerate = PairLpGenerate<Self>;
+}
+
+type MultiAssets = ZenlinkMultiAssets<ZenlinkProtocol, Balances, LocalAssetAdaptor<Currencies>>;
+
+// Below is the implementation of tokens manipulation functions other than native token.
+pub struct LocalAssetAdaptor<Local>(PhantomData<Local>);
+
+impl<Local, AccountId> LocalAssetHandler<AccountId> for LocalAssetAdaptor<Local>
+where
+	Local: MultiCurrency<AccountId, CurrencyId = CurrencyId>,
+{
+	fn local_balance_of(asset_id: ZenlinkAssetId, who: &AccountId) -> AssetBalance {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::free_balance(currency_id, &who).saturated_into()
+	}
+
+	fn local_total_supply(asset_id: ZenlinkAssetId) -> AssetBalance {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::total_issuance(currency_id).saturated_into()
+	}
+
+	fn local_is_exists(asset_id: ZenlinkAssetId) -> bool {
+		let rs: Result<CurrencyId, _> = asset_id.try_into();
+		rs.is_ok()
+	}
+
+	fn local_transfer(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		target: &AccountId,
+		amount: AssetBalance,
+	) -> DispatchResult {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::transfer(currency_id, &origin, &target, amount.unique_saturated_into())?;
+
+		Ok(())
+	}
+
+	fn local_deposit(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		amount: AssetBalance,
+	) -> Result<AssetBalance, DispatchError> {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::deposit(currency_id, &origin, amount.unique_saturated_into())?;
+		Ok(amount)
+	}
+
+	fn local_withdraw(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		amount: AssetBalance,
+	) -> Result<AssetBalance, DispatchError> {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::withdraw(currency_id, &origin, amount.unique_saturated_into())?;
+
+		Ok(amount)
+	}
+}
+
+pub struct ParachainId;
+impl Get<ParaId> for ParachainId {
+	fn get() -> ParaId {
+		2001.into()
+	}
+}
+
 parameter_types! {
 	pub const LendMarketPalletId: PalletId = PalletId(*b"bf/ldmkt");
 	pub const RewardAssetId: CurrencyId = BNC;
@@ -444,6 +537,10 @@ impl lend_market::Config for Test {
 	type Assets = Currencies;
 	type RewardAssetId = RewardAssetId;
 	type LiquidationFreeAssetId = LiquidationFreeAssetId;
+	type SwapRouter = ();
+	type MaxKeeperLiquidationsPerBlock = ConstU32<5>;
+	type RuntimeCall = RuntimeCall;
+	type FlashLoanCallFilter = Everything;
 }
 
 impl pallet_prices::Config for Test {
@@ -454,6 +551,13 @@ impl pallet_prices::Config for Test {
 	type RelayCurrency = RelayCurrencyId;
 	type Assets = Currencies;
 	type CurrencyIdConvert = AssetIdMaps<Test>;
+	type VtokenMinting = ();
+	type StablePoolHandler = ();
+	type AggregatedSources = ();
+	type MarketPriceWindow = ConstU32<300>;
+	type DexOperator = ZenlinkProtocol;
+	type ParachainId = ParachainId;
+	type UnixTime = TimestampPallet;
 	type WeightInfo = ();
 }
 
@@ -0,0 +1,321 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Best-execution routing between a token and its vtoken.
+//!
+//! Leveraging up buys vtokens with an exact token amount, by minting or by swapping through the
+//! stable pool or the Zenlink pair. Deleveraging sells vtokens for an exact token amount through
+//! the swaps only, as redeeming is not instant. The amount is split in `ROUTE_SPLIT_PARTS` parts,
+//! each part going to the route with the best marginal quote.
+
+use bifrost_primitives::TryConvertFrom;
+use frame_support::traits::fungibles::Inspect;
+use zenlink_protocol::{AssetId as ZenlinkAssetId, ExportZenlink};
+
+use crate::*;
+
+/// The number of parts an amount is split in across the routes.
+pub const ROUTE_SPLIT_PARTS: u32 = 4;
+
+/// The amount given to a route, with its quote.
+pub type RouteLeg<Balance> = (Route, Balance, Balance);
+
+impl<T: Config> Pallet<T> {
+	/// Returns the legs buying vtokens with `token_amount`, and the vtoken amount they quote.
+	pub fn quote_to_vtoken(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		vtoken_id: AssetIdOf<T>,
+		token_amount: BalanceOf<T>,
+	) -> Result<(Vec<RouteLeg<BalanceOf<T>>>, BalanceOf<T>), DispatchError> {
+		let legs = Self::split_route(
+			&[Route::Mint, Route::StablePool, Route::Zenlink],
+			token_amount,
+			|route, amount| Self::quote_route_to_vtoken(route, asset_id, vtoken_id, amount),
+			|marginal, best| marginal > best,
+		)?;
+		let vtoken_amount = Self::total_quote(&legs)?;
+
+		// Every token short of the vtoken-minting exchange rate is price impact.
+		let fair_amount = T::VtokenMinting::token_to_vtoken(asset_id, vtoken_id, token_amount)?;
+		Self::ensure_price_impact(who, fair_amount.saturating_sub(vtoken_amount), fair_amount)?;
+
+		Ok((legs, vtoken_amount))
+	}
+
+	/// Returns the legs selling vtokens for `token_amount`, and the vtoken amount they quote.
+	pub fn quote_from_vtoken(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		vtoken_id: AssetIdOf<T>,
+		token_amount: BalanceOf<T>,
+	) -> Result<(Vec<RouteLeg<BalanceOf<T>>>, BalanceOf<T>), DispatchError> {
+		let legs = Self::split_route(
+			&[Route::StablePool, Route::Zenlink],
+			token_amount,
+			|route, amount| Self::quote_route_from_vtoken(route, asset_id, vtoken_id, amount),
+			|marginal, best| marginal < best,
+		)?;
+		let vtoken_amount = Self::total_quote(&legs)?;
+
+		let fair_amount = T::VtokenMinting::token_to_vtoken(asset_id, vtoken_id, token_amount)?;
+		Self::ensure_price_impact(who, vtoken_amount.saturating_sub(fair_amount), fair_amount)?;
+
+		Ok((legs, vtoken_amount))
+	}
+
+	/// Buys vtokens with `token_amount` held by `who` and returns the vtoken amount received.
+	pub(crate) fn route_to_vtoken(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		vtoken_id: AssetIdOf<T>,
+		token_amount: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let (legs, _) = Self::quote_to_vtoken(who, asset_id, vtoken_id, token_amount)?;
+
+		let balance_before = T::Assets::balance(vtoken_id, who);
+		for (route, amount_in, amount_out) in legs {
+			match route {
+				Route::Mint => {
+					T::VtokenMinting::mint(
+						who.clone(),
+						asset_id,
+						amount_in,
+						BoundedVec::default(),
+						None,
+					)?;
+				},
+				Route::StablePool => {
+					let (pool_id, currency_id_in, currency_id_out) =
+						T::StablePoolHandler::get_pool_id(&asset_id, &vtoken_id)
+							.ok_or(Error::<T>::NoRouteAvailable)?;
+					T::StablePoolHandler::swap(
+						who,
+						pool_id,
+						currency_id_in,
+						currency_id_out,
+						amount_in,
+						amount_out,
+					)?;
+				},
+				Route::Zenlink => {
+					T::DexOperator::inner_swap_exact_assets_for_assets(
+						who,
+						amount_in.saturated_into(),
+						amount_out.saturated_into(),
+						&Self::zenlink_path(asset_id, vtoken_id)?,
+						who,
+					)?;
+				},
+			}
+			Self::deposit_event(Event::<T>::Routed {
+				who: who.clone(),
+				asset_id,
+				route,
+				amount_in,
+				amount_out,
+			});
+		}
+		let balance_after = T::Assets::balance(vtoken_id, who);
+
+		Ok(balance_after.checked_sub(balance_before).ok_or(ArithmeticError::Underflow)?)
+	}
+
+	/// Sells the vtokens of `who` quoted by `legs` for the token.
+	pub(crate) fn route_from_vtoken(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		vtoken_id: AssetIdOf<T>,
+		legs: Vec<RouteLeg<BalanceOf<T>>>,
+	) -> DispatchResult {
+		for (route, amount_out, amount_in) in legs {
+			match route {
+				Route::StablePool => {
+					let (pool_id, currency_id_in, currency_id_out) =
+						T::StablePoolHandler::get_pool_id(&vtoken_id, &asset_id)
+							.ok_or(Error::<T>::NoRouteAvailable)?;
+					T::StablePoolHandler::swap(
+						who,
+						pool_id,
+						currency_id_in,
+						currency_id_out,
+						amount_in,
+						amount_out,
+					)?;
+				},
+				Route::Zenlink => {
+					T::DexOperator::inner_swap_assets_for_exact_assets(
+						who,
+						amount_out.saturated_into(),
+						amount_in.saturated_into(),
+						&Self::zenlink_path(vtoken_id, asset_id)?,
+						who,
+					)?;
+				},
+				Route::Mint => return Err(Error::<T>::NoRouteAvailable.into()),
+			}
+			Self::deposit_event(Event::<T>::Routed {
+				who: who.clone(),
+				asset_id,
+				route,
+				amount_in,
+				amount_out,
+			});
+		}
+		Ok(())
+	}
+
+	/// Splits `amount` over `routes`, giving each part to the route whose quote moves the most
+	/// by `is_better`. Routes that can't quote a part are skipped.
+	fn split_route(
+		routes: &[Route],
+		amount: BalanceOf<T>,
+		quote: impl Fn(Route, BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError>,
+		is_better: impl Fn(BalanceOf<T>, BalanceOf<T>) -> bool,
+	) -> Result<Vec<RouteLeg<BalanceOf<T>>>, DispatchError> {
+		let mut legs: Vec<RouteLeg<BalanceOf<T>>> =
+			routes.iter().map(|route| (*route, Zero::zero(), Zero::zero())).collect();
+		let part = amount / BalanceOf::<T>::from(ROUTE_SPLIT_PARTS);
+		let mut remaining = amount;
+		for index in 0..ROUTE_SPLIT_PARTS {
+			// The last part takes the rounding left.
+			let part_amount = if index == ROUTE_SPLIT_PARTS - 1 { remaining } else { part };
+			if part_amount.is_zero() {
+				continue;
+			}
+
+			let mut best: Option<(usize, BalanceOf<T>, BalanceOf<T>)> = None;
+			for (leg_index, (route, leg_amount, leg_quote)) in legs.iter().enumerate() {
+				let new_quote = match quote(*route, leg_amount.saturating_add(part_amount)) {
+					Ok(new_quote) => new_quote,
+					Err(_) => continue,
+				};
+				let marginal = new_quote.saturating_sub(*leg_quote);
+				if best.map_or(true, |(_, _, best_marginal)| is_better(marginal, best_marginal)) {
+					best = Some((leg_index, new_quote, marginal));
+				}
+			}
+			let (leg_index, new_quote, _) = best.ok_or(Error::<T>::NoRouteAvailable)?;
+			legs[leg_index].1 = legs[leg_index].1.saturating_add(part_amount);
+			legs[leg_index].2 = new_quote;
+			remaining = remaining.saturating_sub(part_amount);
+		}
+
+		legs.retain(|(_, leg_amount, _)| !leg_amount.is_zero());
+		Ok(legs)
+	}
+
+	fn total_quote(legs: &[RouteLeg<BalanceOf<T>>]) -> Result<BalanceOf<T>, DispatchError> {
+		legs.iter().try_fold(Zero::zero(), |total: BalanceOf<T>, (_, _, leg_quote)| {
+			total.checked_add(*leg_quote).ok_or(ArithmeticError::Overflow.into())
+		})
+	}
+
+	fn ensure_price_impact(
+		who: &T::AccountId,
+		loss: BalanceOf<T>,
+		fair_amount: BalanceOf<T>,
+	) -> DispatchResult {
+		if let Some(max_price_impact) = MaxPriceImpacts::<T>::get(who) {
+			ensure!(
+				Permill::from_rational(loss, fair_amount) <= max_price_impact,
+				Error::<T>::PriceImpactTooHigh
+			);
+		}
+		Ok(())
+	}
+
+	/// The vtoken amount bought with `token_amount` through `route`.
+	fn quote_route_to_vtoken(
+		route: Route,
+		asset_id: AssetIdOf<T>,
+		vtoken_id: AssetIdOf<T>,
+		token_amount: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		match route {
+			Route::Mint => {
+				let mint_fee = T::VtokenMinting::get_mint_fee_rate() * token_amount;
+				T::VtokenMinting::token_to_vtoken(
+					asset_id,
+					vtoken_id,
+					token_amount.checked_sub(mint_fee).ok_or(ArithmeticError::Underflow)?,
+				)
+			},
+			Route::StablePool => {
+				let (pool_id, currency_id_in, currency_id_out) =
+					T::StablePoolHandler::get_pool_id(&asset_id, &vtoken_id)
+						.ok_or(Error::<T>::NoRouteAvailable)?;
+				T::StablePoolHandler::get_swap_output(
+					pool_id,
+					currency_id_in,
+					currency_id_out,
+					token_amount,
+				)
+			},
+			Route::Zenlink => {
+				let amounts = T::DexOperator::get_amount_out_by_path(
+					token_amount.saturated_into(),
+					&Self::zenlink_path(asset_id, vtoken_id)?,
+				)?;
+				Ok(amounts.last().copied().ok_or(Error::<T>::NoRouteAvailable)?.saturated_into())
+			},
+		}
+	}
+
+	/// The vtoken amount sold for `token_amount` through `route`.
+	fn quote_route_from_vtoken(
+		route: Route,
+		asset_id: AssetIdOf<T>,
+		vtoken_id: AssetIdOf<T>,
+		token_amount: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		match route {
+			Route::StablePool => {
+				let (pool_id, currency_id_in, currency_id_out) =
+					T::StablePoolHandler::get_pool_id(&vtoken_id, &asset_id)
+						.ok_or(Error::<T>::NoRouteAvailable)?;
+				T::StablePoolHandler::get_swap_input(
+					pool_id,
+					currency_id_in,
+					currency_id_out,
+					token_amount,
+				)
+			},
+			Route::Zenlink => {
+				let amounts = T::DexOperator::get_amount_in_by_path(
+					token_amount.saturated_into(),
+					&Self::zenlink_path(vtoken_id, asset_id)?,
+				)?;
+				Ok(amounts.first().copied().ok_or(Error::<T>::NoRouteAvailable)?.saturated_into())
+			},
+			Route::Mint => Err(Error::<T>::NoRouteAvailable.into()),
+		}
+	}
+
+	fn zenlink_path(
+		currency_in: AssetIdOf<T>,
+		currency_out: AssetIdOf<T>,
+	) -> Result<Vec<ZenlinkAssetId>, DispatchError> {
+		let para_id: u32 = T::ParachainId::get().into();
+		let convert = |currency_id| {
+			ZenlinkAssetId::try_convert_from(currency_id, para_id)
+				.map_err(|_| Error::<T>::NoRouteAvailable)
+		};
+		Ok(sp_std::vec![convert(currency_in)?, convert(currency_out)?])
+	}
+}
@@ -17,9 +17,18 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 #![cfg(test)]
 
-use crate::mock::*;
-use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect, BoundedVec};
+use crate::{mock::*, DeleverageTrigger, DeleverageTriggers, Error, Event, MaxPriceImpacts, Route};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{fungibles::Inspect, Hooks},
+	weights::Weight,
+	BoundedVec,
+};
 use lend_market::{AccountBorrows, BorrowSnapshot, Deposits};
+use sp_runtime::{
+	traits::{BadOrigin, One, Zero},
+	FixedU128, Permill,
+};
 
 fn init() {
 	env_logger::try_init().unwrap_or(());
@@ -76,7 +85,7 @@ fn increase_leverage_should_not_work() {
 				DOT,
 				FixedU128::from_inner(unit(1_000_100)),
 			),
-			lend_market::Error::<Test>::InsufficientLiquidity
+			Error::<Test>::LeverageTooHigh
 		);
 	});
 }
@@ -183,3 +192,207 @@ fn reduce_leverage_should_work() {
 		);
 	});
 }
+
+#[test]
+fn max_leverage_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		init();
+		// 50% collateral factor
+		assert_eq!(LeverageStaking::max_leverage(DOT, VDOT), Ok(FixedU128::one()));
+
+		assert_noop!(
+			LeverageStaking::set_min_health_factor(
+				RuntimeOrigin::signed(1),
+				DOT,
+				FixedU128::from_rational(11, 8)
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			LeverageStaking::set_min_health_factor(RuntimeOrigin::root(), DOT, FixedU128::one()),
+			Error::<Test>::InvalidHealthFactor
+		);
+		// 55% liquidation threshold / 1.375 = 40% of the collateral can be borrowed
+		assert_ok!(LeverageStaking::set_min_health_factor(
+			RuntimeOrigin::root(),
+			DOT,
+			FixedU128::from_rational(11, 8)
+		));
+		assert_eq!(LeverageStaking::max_leverage(DOT, VDOT), Ok(FixedU128::from_rational(2, 3)));
+
+		assert_noop!(
+			LeverageStaking::flash_loan_deposit(
+				RuntimeOrigin::signed(1),
+				DOT,
+				FixedU128::from_rational(7, 10),
+			),
+			Error::<Test>::LeverageTooHigh
+		);
+		assert_ok!(LeverageStaking::flash_loan_deposit(
+			RuntimeOrigin::signed(1),
+			DOT,
+			FixedU128::from_rational(6, 10),
+		));
+	});
+}
+
+#[test]
+fn set_deleverage_trigger_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		init();
+		let trigger = DeleverageTrigger {
+			health_factor: FixedU128::from_rational(11, 10),
+			rate: FixedU128::from_rational(1, 2),
+		};
+		assert_noop!(
+			LeverageStaking::set_deleverage_trigger(
+				RuntimeOrigin::signed(1),
+				DOT,
+				DeleverageTrigger { health_factor: FixedU128::one(), ..trigger }
+			),
+			Error::<Test>::InvalidHealthFactor
+		);
+		assert_noop!(
+			LeverageStaking::set_deleverage_trigger(
+				RuntimeOrigin::signed(1),
+				DOT,
+				DeleverageTrigger { rate: FixedU128::from_rational(11, 10), ..trigger }
+			),
+			Error::<Test>::LeverageTooHigh
+		);
+
+		assert_ok!(LeverageStaking::set_deleverage_trigger(RuntimeOrigin::signed(1), DOT, trigger));
+		assert_eq!(DeleverageTriggers::<Test>::get(1, DOT), Some(trigger));
+		assert_ok!(LeverageStaking::remove_deleverage_trigger(RuntimeOrigin::signed(1), DOT));
+		assert_eq!(DeleverageTriggers::<Test>::get(1, DOT), None);
+	});
+}
+
+#[test]
+fn auto_deleverage_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		init();
+		System::set_block_number(1);
+		assert_ok!(LeverageStaking::flash_loan_deposit(
+			RuntimeOrigin::signed(1),
+			DOT,
+			FixedU128::from_inner(unit(900_000)),
+		));
+		assert_ok!(LeverageStaking::set_deleverage_trigger(
+			RuntimeOrigin::signed(1),
+			DOT,
+			DeleverageTrigger {
+				health_factor: FixedU128::from_rational(11, 10),
+				rate: FixedU128::from_rational(1, 2),
+			}
+		));
+
+		// 55% of 190_000 VDOT against 90_000 DOT
+		LeverageStaking::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(
+			AccountBorrows::<Test>::get(DOT, 1),
+			BorrowSnapshot { principal: 90_000, borrow_index: 1.into() },
+		);
+
+		// VDOT depegs, the health factor falls to 1.045
+		MockPriceFeeder::set_price(VDOT, FixedU128::from_rational(9, 10));
+		LeverageStaking::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(
+			AccountBorrows::<Test>::get(DOT, 1),
+			BorrowSnapshot { principal: 50_000, borrow_index: 1.into() },
+		);
+		System::assert_has_event(RuntimeEvent::LeverageStaking(Event::AutoDeleveraged {
+			who: 1,
+			asset_id: DOT,
+			health_factor: FixedU128::from_rational(1045, 1000),
+			old_rate: FixedU128::from_inner(unit(900_000)),
+			new_rate: FixedU128::from_rational(1, 2),
+		}));
+
+		// The position is already at the trigger rate
+		LeverageStaking::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(
+			AccountBorrows::<Test>::get(DOT, 1),
+			BorrowSnapshot { principal: 50_000, borrow_index: 1.into() },
+		);
+	});
+}
+
+#[test]
+fn set_max_price_impact_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		init();
+		assert_ok!(LeverageStaking::flash_loan_deposit(
+			RuntimeOrigin::signed(1),
+			DOT,
+			FixedU128::from_inner(unit(900_000)),
+		));
+		assert_ok!(LeverageStaking::set_max_price_impact(
+			RuntimeOrigin::signed(1),
+			Some(Permill::zero())
+		));
+		assert_eq!(MaxPriceImpacts::<Test>::get(1), Some(Permill::zero()));
+
+		// The stable pool fee is price impact against the vtoken-minting exchange rate
+		assert_noop!(
+			LeverageStaking::flash_loan_deposit(
+				RuntimeOrigin::signed(1),
+				DOT,
+				FixedU128::from_inner(unit(800_000)),
+			),
+			Error::<Test>::PriceImpactTooHigh
+		);
+
+		assert_ok!(LeverageStaking::set_max_price_impact(RuntimeOrigin::signed(1), None));
+		assert_eq!(MaxPriceImpacts::<Test>::get(1), None);
+		assert_ok!(LeverageStaking::flash_loan_deposit(
+			RuntimeOrigin::signed(1),
+			DOT,
+			FixedU128::from_inner(unit(800_000)),
+		));
+	});
+}
+
+#[test]
+fn increase_leverage_should_route_to_the_cheapest() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		init();
+		System::set_block_number(1);
+		let routed = |route| {
+			System::events().iter().any(|record| {
+				matches!(
+					record.event,
+					RuntimeEvent::LeverageStaking(Event::Routed { route: r, .. }) if r == route
+				)
+			})
+		};
+
+		// Minting is at the exchange rate, the stable pool charges a fee
+		assert_ok!(LeverageStaking::flash_loan_deposit(
+			RuntimeOrigin::signed(1),
+			DOT,
+			FixedU128::from_inner(unit(100_000)),
+		));
+		assert!(routed(Route::Mint));
+		assert!(!routed(Route::StablePool));
+
+		// A 10% mint fee makes the stable pool cheaper
+		System::reset_events();
+		assert_ok!(VtokenMinting::set_fees(
+			RuntimeOrigin::root(),
+			Permill::from_percent(10),
+			Permill::zero()
+		));
+		assert_ok!(LeverageStaking::flash_loan_deposit(
+			RuntimeOrigin::signed(1),
+			DOT,
+			FixedU128::from_inner(unit(200_000)),
+		));
+		assert!(!routed(Route::Mint));
+		assert!(routed(Route::StablePool));
+		assert_eq!(
+			AccountBorrows::<Test>::get(DOT, 1),
+			BorrowSnapshot { principal: 20_000, borrow_index: 1.into() },
+		);
+	});
+}
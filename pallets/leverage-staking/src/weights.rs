@@ -51,6 +51,11 @@ use frame_support::{
 
 pub trait WeightInfo {
 	fn flash_loan_deposit() -> Weight;
+	fn set_min_health_factor() -> Weight;
+	fn set_deleverage_trigger() -> Weight;
+	fn remove_deleverage_trigger() -> Weight;
+	fn set_max_price_impact() -> Weight;
+	fn deleverage_scan_account() -> Weight;
 }
 
 /// Weight functions for `leverage_staking`.
@@ -120,6 +125,79 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(41))
 			.saturating_add(T::DbWeight::get().writes(21))
 	}
+	/// Storage: LeverageStaking MinHealthFactors (r:0 w:1)
+	/// Proof Skipped: LeverageStaking MinHealthFactors (max_values: None, max_size: None, mode: Measured)
+	fn set_min_health_factor() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_384_000 picoseconds.
+		Weight::from_parts(9_751_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: AssetRegistry CurrencyMetadatas (r:1 w:0)
+	/// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket Markets (r:1 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LeverageStaking MinHealthFactors (r:1 w:0)
+	/// Proof Skipped: LeverageStaking MinHealthFactors (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LeverageStaking DeleverageTriggers (r:0 w:1)
+	/// Proof Skipped: LeverageStaking DeleverageTriggers (max_values: None, max_size: None, mode: Measured)
+	fn set_deleverage_trigger() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1563`
+		//  Estimated: `5028`
+		// Minimum execution time: 30_172_000 picoseconds.
+		Weight::from_parts(31_046_000, 0)
+			.saturating_add(Weight::from_parts(0, 5028))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: LeverageStaking DeleverageTriggers (r:0 w:1)
+	/// Proof Skipped: LeverageStaking DeleverageTriggers (max_values: None, max_size: None, mode: Measured)
+	fn remove_deleverage_trigger() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 10_120_000 picoseconds.
+		Weight::from_parts(10_533_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: LeverageStaking MaxPriceImpacts (r:0 w:1)
+	/// Proof Skipped: LeverageStaking MaxPriceImpacts (max_values: None, max_size: None, mode: Measured)
+	fn set_max_price_impact() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_870_000 picoseconds.
+		Weight::from_parts(10_214_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: LeverageStaking DeleverageTriggers (r:1 w:0)
+	/// Proof Skipped: LeverageStaking DeleverageTriggers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket Markets (r:3 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountDeposits (r:3 w:0)
+	/// Proof Skipped: LendMarket AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:3 w:0)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket BorrowIndex (r:3 w:0)
+	/// Proof Skipped: LendMarket BorrowIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket ExchangeRate (r:3 w:0)
+	/// Proof Skipped: LendMarket ExchangeRate (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices EmergencyPrice (r:3 w:0)
+	/// Proof Skipped: Prices EmergencyPrice (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket LiquidationFreeCollaterals (r:1 w:0)
+	/// Proof Skipped: LendMarket LiquidationFreeCollaterals (max_values: Some(1), max_size: None, mode: Measured)
+	fn deleverage_scan_account() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2941`
+		//  Estimated: `11356`
+		// Minimum execution time: 88_412_000 picoseconds.
+		Weight::from_parts(90_227_000, 0)
+			.saturating_add(Weight::from_parts(0, 11356))
+			.saturating_add(T::DbWeight::get().reads(19))
+	}
 }
 
 // For backwards compatibility and tests
@@ -188,4 +266,77 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(41))
 			.saturating_add(RocksDbWeight::get().writes(21))
 	}
+	/// Storage: LeverageStaking MinHealthFactors (r:0 w:1)
+	/// Proof Skipped: LeverageStaking MinHealthFactors (max_values: None, max_size: None, mode: Measured)
+	fn set_min_health_factor() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_384_000 picoseconds.
+		Weight::from_parts(9_751_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: AssetRegistry CurrencyMetadatas (r:1 w:0)
+	/// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket Markets (r:1 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LeverageStaking MinHealthFactors (r:1 w:0)
+	/// Proof Skipped: LeverageStaking MinHealthFactors (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LeverageStaking DeleverageTriggers (r:0 w:1)
+	/// Proof Skipped: LeverageStaking DeleverageTriggers (max_values: None, max_size: None, mode: Measured)
+	fn set_deleverage_trigger() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1563`
+		//  Estimated: `5028`
+		// Minimum execution time: 30_172_000 picoseconds.
+		Weight::from_parts(31_046_000, 0)
+			.saturating_add(Weight::from_parts(0, 5028))
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: LeverageStaking DeleverageTriggers (r:0 w:1)
+	/// Proof Skipped: LeverageStaking DeleverageTriggers (max_values: None, max_size: None, mode: Measured)
+	fn remove_deleverage_trigger() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 10_120_000 picoseconds.
+		Weight::from_parts(10_533_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: LeverageStaking MaxPriceImpacts (r:0 w:1)
+	/// Proof Skipped: LeverageStaking MaxPriceImpacts (max_values: None, max_size: None, mode: Measured)
+	fn set_max_price_impact() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_870_000 picoseconds.
+		Weight::from_parts(10_214_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: LeverageStaking DeleverageTriggers (r:1 w:0)
+	/// Proof Skipped: LeverageStaking DeleverageTriggers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket Markets (r:3 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountDeposits (r:3 w:0)
+	/// Proof Skipped: LendMarket AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:3 w:0)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket BorrowIndex (r:3 w:0)
+	/// Proof Skipped: LendMarket BorrowIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket ExchangeRate (r:3 w:0)
+	/// Proof Skipped: LendMarket ExchangeRate (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices EmergencyPrice (r:3 w:0)
+	/// Proof Skipped: Prices EmergencyPrice (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket LiquidationFreeCollaterals (r:1 w:0)
+	/// Proof Skipped: LendMarket LiquidationFreeCollaterals (max_values: Some(1), max_size: None, mode: Measured)
+	fn deleverage_scan_account() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2941`
+		//  Estimated: `11356`
+		// Minimum execution time: 88_412_000 picoseconds.
+		Weight::from_parts(90_227_000, 0)
+			.saturating_add(Weight::from_parts(0, 11356))
+			.saturating_add(RocksDbWeight::get().reads(19))
+	}
 }
\ No newline at end of file
@@ -19,20 +19,42 @@
 //! This pallet provides the price from Oracle Module by implementing the
 //! `PriceFeeder` trait. In case of emergency, the price can be set directly
 //! by Oracle Collective.
+//!
+//! The price of a vtoken is derived from the price of its token and the
+//! vtoken-minting exchange rate, and can be capped by the stable-pool market
+//! price, averaged over time so that it can't be moved within a block.
+//!
+//! The oracle price is aggregated with `AggregatedSources` by median. A price
+//! guard can be set per asset, the price is then checked for its age and its
+//! deviation from the price of the previous block, and the assets failing the
+//! checks are reported with `PriceCheckFailed` at the start of each block.
+//!
+//! The Zenlink price of an asset set with `set_zenlink_twap_quote` is observed
+//! at the start of each block, and `ZenlinkTwapProvider` prices the asset by
+//! its TWAP, so that it can be one of the `AggregatedSources`.
+//!
+//! The price of a stable-pool LP token is derived from the prices of the pool
+//! assets and the pool invariant, so that it can be used as collateral.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use bifrost_asset_registry::AssetMetadata;
 use bifrost_primitives::*;
-use frame_support::{dispatch::DispatchClass, pallet_prelude::*, transactional};
+use bifrost_stable_pool::traits::StablePoolHandler;
+use cumulus_primitives_core::ParaId;
+use frame_support::{dispatch::DispatchClass, pallet_prelude::*, traits::UnixTime, transactional};
 use frame_system::pallet_prelude::*;
 use log;
 use orml_oracle::{DataFeeder, DataProvider, DataProviderExtended};
 pub use pallet::*;
 use pallet_traits::*;
-use sp_runtime::{traits::CheckedDiv, FixedU128};
+use sp_runtime::{
+	traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Saturating, Zero},
+	FixedPointNumber, FixedU128, Permill, SaturatedConversion,
+};
 use sp_std::vec::Vec;
 use xcm::v3::MultiLocation;
+use zenlink_protocol::{AssetId as ZenlinkAssetId, ExportZenlink};
 
 #[cfg(test)]
 mod mock;
@@ -41,6 +63,65 @@ mod tests;
 
 pub mod weights;
 
+/// The units of an LP token its price is computed over, so that an LP token of few decimals,
+/// such as `StableLpToken`, keeps the precision of the pool assets.
+const LP_TOKEN_VALUATION_UNITS: u128 = 1_000_000_000_000_000_000;
+
+/// The deepest an asset price is derived from the prices of other assets, such as a vtoken from
+/// its token or an LP token from its pool assets. It stops cyclic foreign asset mappings.
+const MAX_PRICE_DERIVATION_DEPTH: u32 = 4;
+
+/// The checks the price of an asset must pass to be valid.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PriceGuard {
+	/// The age of the oldest price accepted, in milliseconds
+	pub max_age: Moment,
+	/// The largest change accepted from the price of the previous block
+	pub max_deviation: Permill,
+}
+
+/// Why the price of an asset is not valid.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum PriceCheckFailure {
+	/// No source has a price younger than the maximum age
+	Stale,
+	/// The price moved more than the maximum deviation since the previous block
+	Deviated,
+}
+
+/// The Zenlink price of an asset in its quote asset, observed at the start of a block.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PriceObservation<BlockNumber> {
+	/// The block of the observation
+	pub block: BlockNumber,
+	/// The sum over the blocks before `block` of the price observed at each block
+	pub cumulative_price: FixedU128,
+	/// The price observed at `block`
+	pub price: FixedU128,
+}
+
+/// The observations the Zenlink TWAP of an asset is computed from.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct TwapObservations<BlockNumber> {
+	/// The observation the TWAP is averaged since, `MarketPriceWindow` to twice as many blocks
+	/// before the latest one once enough blocks are observed
+	pub start: PriceObservation<BlockNumber>,
+	/// The observation replacing `start` once it is `MarketPriceWindow` blocks old
+	pub next_start: PriceObservation<BlockNumber>,
+	/// The latest observation
+	pub latest: PriceObservation<BlockNumber>,
+}
+
+/// Adapts a `DataProvider`, such as `StablePoolTwapProvider`, to a `PriceSource` of at most
+/// one price per asset.
+pub struct DataProviderSource<P>(PhantomData<P>);
+
+impl<P: DataProvider<CurrencyId, TimeStampedPrice>> PriceSource for DataProviderSource<P> {
+	fn get_prices(asset_id: &CurrencyId) -> Vec<TimeStampedPrice> {
+		P::get(asset_id).into_iter().collect()
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -82,10 +163,43 @@ pub mod pallet {
 			AssetMetadata<BalanceOf<Self>>,
 		>;
 
+		/// The vtoken exchange rates vtoken prices are derived from.
+		type VtokenMinting: VtokenMintingInterface<Self::AccountId, CurrencyId, Balance>;
+
+		/// The stable pools quoting the market price of vtokens.
+		type StablePoolHandler: StablePoolHandler<Balance = Balance, CurrencyId = CurrencyId>;
+
+		/// The sources aggregated with `Source` by median.
+		type AggregatedSources: PriceSource;
+
+		/// The number of blocks the stable-pool TWAP capping vtoken prices and the Zenlink TWAP
+		/// are averaged over.
+		#[pallet::constant]
+		type MarketPriceWindow: Get<u32>;
+
+		/// Zenlink DEX the TWAP of the assets set with `set_zenlink_twap_quote` is observed in.
+		type DexOperator: ExportZenlink<Self::AccountId, ZenlinkAssetId>;
+
+		/// The parachain id, used to convert currency ids into Zenlink asset ids.
+		type ParachainId: Get<ParaId>;
+
+		/// The time prices are aged against.
+		type UnixTime: UnixTime;
+
 		/// Weight information
 		type WeightInfo: WeightInfo;
 	}
 
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The asset is not a vtoken
+		NotVtoken,
+		/// The maximum age of a price guard is zero
+		InvalidPriceGuard,
+		/// An asset can't be quoted in itself
+		InvalidZenlinkTwapQuote,
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -93,6 +207,16 @@ pub mod pallet {
 		SetPrice(CurrencyId, Price),
 		/// Reset emergency price. \[asset_id\]
 		ResetPrice(CurrencyId),
+		/// Set the market price cap of a vtoken. \[vtoken_id, tolerance\]
+		MarketPriceCapSet(CurrencyId, Option<Permill>),
+		/// Set the price guard of an asset. \[asset_id, price_guard\]
+		PriceGuardSet(CurrencyId, Option<PriceGuard>),
+		/// The price of an asset failed its checks. \[asset_id, failure\]
+		PriceCheckFailed(CurrencyId, PriceCheckFailure),
+		/// The price of an asset passes its checks again. \[asset_id\]
+		PriceCheckRecovered(CurrencyId),
+		/// Set the asset the Zenlink TWAP of an asset is quoted in. \[asset_id, quote_id\]
+		ZenlinkTwapQuoteSet(CurrencyId, Option<CurrencyId>),
 	}
 
 	/// Mapping from currency id to it's emergency price
@@ -107,6 +231,43 @@ pub mod pallet {
 	pub type ForeignToNativeAsset<T: Config> =
 		StorageMap<_, Twox64Concat, CurrencyId, CurrencyId, OptionQuery>;
 
+	/// Mapping from vtoken to the tolerance above its stable-pool market price its derived
+	/// price is capped at
+	#[pallet::storage]
+	#[pallet::getter(fn market_price_cap)]
+	pub type MarketPriceCaps<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, Permill, OptionQuery>;
+
+	/// Mapping from asset to the checks its price must pass
+	#[pallet::storage]
+	#[pallet::getter(fn price_guard)]
+	pub type PriceGuards<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, PriceGuard, OptionQuery>;
+
+	/// Mapping from guarded asset to its price at the start of the block
+	#[pallet::storage]
+	#[pallet::getter(fn previous_price)]
+	pub type PreviousPrices<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
+
+	/// Mapping from guarded asset to the check its price failed at the start of the block
+	#[pallet::storage]
+	#[pallet::getter(fn failed_price_check)]
+	pub type FailedPriceChecks<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, PriceCheckFailure, OptionQuery>;
+
+	/// Mapping from asset to the asset its Zenlink TWAP is quoted in
+	#[pallet::storage]
+	#[pallet::getter(fn zenlink_twap_quote)]
+	pub type ZenlinkTwapQuotes<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, CurrencyId, OptionQuery>;
+
+	/// Mapping from asset to the observations of its Zenlink price in its quote asset
+	#[pallet::storage]
+	#[pallet::getter(fn zenlink_twap_observations)]
+	pub type ZenlinkTwapObservations<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, TwapObservations<BlockNumberFor<T>>, OptionQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -131,6 +292,24 @@ pub mod pallet {
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(PhantomData<T>);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			let mut observed: u32 = 0;
+			for (asset_id, quote_id) in ZenlinkTwapQuotes::<T>::iter() {
+				observed = observed.saturating_add(1);
+				Self::observe_zenlink_price(asset_id, quote_id, n);
+			}
+			let mut count: u32 = 0;
+			for asset_id in PriceGuards::<T>::iter_keys() {
+				count = count.saturating_add(1);
+				Self::update_price_check(asset_id);
+			}
+			<T as Config>::WeightInfo::on_initialize(count)
+				.saturating_add(<T as Config>::WeightInfo::observe_zenlink_prices(observed))
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Set emergency price
@@ -175,6 +354,68 @@ pub mod pallet {
 			ForeignToNativeAsset::<T>::insert(foreign_asset_id, asset_id);
 			Ok(().into())
 		}
+
+		/// Cap the derived price of a vtoken at its stable-pool market price plus `tolerance`,
+		/// `None` removes the cap
+		#[pallet::call_index(3)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_market_price_cap(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_market_price_cap(
+			origin: OriginFor<T>,
+			vtoken_id: CurrencyId,
+			tolerance: Option<Permill>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(vtoken_id.is_vtoken(), Error::<T>::NotVtoken);
+
+			MarketPriceCaps::<T>::set(vtoken_id, tolerance);
+			Self::deposit_event(Event::MarketPriceCapSet(vtoken_id, tolerance));
+			Ok(().into())
+		}
+
+		/// Set the checks the price of an asset must pass, `None` removes them
+		#[pallet::call_index(4)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_price_guard(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_price_guard(
+			origin: OriginFor<T>,
+			asset_id: CurrencyId,
+			price_guard: Option<PriceGuard>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			match price_guard {
+				Some(price_guard) => {
+					ensure!(!price_guard.max_age.is_zero(), Error::<T>::InvalidPriceGuard);
+					PriceGuards::<T>::insert(asset_id, price_guard);
+				},
+				None => {
+					PriceGuards::<T>::remove(asset_id);
+					PreviousPrices::<T>::remove(asset_id);
+					FailedPriceChecks::<T>::remove(asset_id);
+				},
+			}
+			Self::deposit_event(Event::PriceGuardSet(asset_id, price_guard));
+			Ok(().into())
+		}
+
+		/// Observe the Zenlink price of an asset in `quote_id` for its TWAP, `None` stops
+		/// observing it. The observations start over whenever the quote asset is set.
+		#[pallet::call_index(5)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_zenlink_twap_quote(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_zenlink_twap_quote(
+			origin: OriginFor<T>,
+			asset_id: CurrencyId,
+			quote_id: Option<CurrencyId>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(quote_id != Some(asset_id), Error::<T>::InvalidZenlinkTwapQuote);
+
+			ZenlinkTwapQuotes::<T>::set(asset_id, quote_id);
+			ZenlinkTwapObservations::<T>::remove(asset_id);
+			Self::deposit_event(Event::ZenlinkTwapQuoteSet(asset_id, quote_id));
+			Ok(().into())
+		}
 	}
 }
 
@@ -205,11 +446,302 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
+	// get the price of one whole asset: the emergency price, the derived price, then the
+	// aggregated source price
+	fn get_unit_price(
+		asset_id: &CurrencyId,
+		get_source_price: &impl Fn(&CurrencyId) -> Option<TimeStampedPrice>,
+	) -> Option<TimeStampedPrice> {
+		Self::get_derived_unit_price(asset_id, get_source_price, 0)
+	}
+
+	// get the price of one whole asset derived `depth` times from the price of another asset
+	fn get_derived_unit_price(
+		asset_id: &CurrencyId,
+		get_source_price: &impl Fn(&CurrencyId) -> Option<TimeStampedPrice>,
+		depth: u32,
+	) -> Option<TimeStampedPrice> {
+		if depth > MAX_PRICE_DERIVATION_DEPTH {
+			log::warn!(
+				target: "prices::get_derived_unit_price",
+				"asset_id: {:?}, price derived too deep",
+				asset_id
+			);
+			return None;
+		}
+		Self::emergency_price(asset_id)
+			.map(|value| TimeStampedPrice { value, timestamp: 0 })
+			.or_else(|| Self::get_special_asset_price(*asset_id, get_source_price, depth))
+			.or_else(|| Self::get_aggregated_price(asset_id, get_source_price))
+	}
+
+	/// Returns the median of the prices of `asset_id` in `Source` and `AggregatedSources`.
+	/// The prices older than the maximum age of the asset are left out.
+	fn get_aggregated_price(
+		asset_id: &CurrencyId,
+		get_source_price: &impl Fn(&CurrencyId) -> Option<TimeStampedPrice>,
+	) -> Option<TimeStampedPrice> {
+		let mut prices: Vec<TimeStampedPrice> = get_source_price(asset_id)
+			.into_iter()
+			.chain(T::AggregatedSources::get_prices(asset_id))
+			.filter(|price| !Self::is_stale(asset_id, price))
+			.collect();
+		prices.sort_by(|a, b| a.value.cmp(&b.value));
+
+		let middle = prices.len() / 2;
+		if prices.len() % 2 == 1 {
+			return prices.get(middle).cloned();
+		}
+		// The mean of the two middle prices, as old as the older one
+		let (lower, upper) = (prices.get(middle.checked_sub(1)?)?, prices.get(middle)?);
+		Some(TimeStampedPrice {
+			value: lower
+				.value
+				.checked_add(&upper.value)?
+				.checked_div(&FixedU128::saturating_from_integer(2))?,
+			timestamp: lower.timestamp.min(upper.timestamp),
+		})
+	}
+
+	// emergency prices, with a zero timestamp, never go stale
+	fn is_stale(asset_id: &CurrencyId, price: &TimeStampedPrice) -> bool {
+		if price.timestamp.is_zero() {
+			return false;
+		}
+		Self::price_guard(asset_id).map_or(false, |price_guard| {
+			let now: Moment = T::UnixTime::now().as_millis().saturated_into();
+			now.saturating_sub(price.timestamp) > price_guard.max_age
+		})
+	}
+
+	/// Checks the current price of a guarded asset, the emergency price always passes.
+	pub fn check_price(asset_id: &CurrencyId) -> Result<(), PriceCheckFailure> {
+		if Self::emergency_price(asset_id).is_some() {
+			return Ok(());
+		}
+		let price_guard = match Self::price_guard(asset_id) {
+			Some(price_guard) => price_guard,
+			None => return Ok(()),
+		};
+		let price = Self::get_unit_price(asset_id, &|asset_id| T::Source::get(asset_id))
+			.filter(|price| !Self::is_stale(asset_id, price))
+			.ok_or(PriceCheckFailure::Stale)?;
+
+		if let Some(previous_price) = Self::previous_price(asset_id) {
+			let deviation = if price.value > previous_price {
+				price.value.saturating_sub(previous_price)
+			} else {
+				previous_price.saturating_sub(price.value)
+			};
+			if !previous_price.is_zero() &&
+				Permill::from_rational(deviation.into_inner(), previous_price.into_inner()) >
+					price_guard.max_deviation
+			{
+				return Err(PriceCheckFailure::Deviated);
+			}
+		}
+
+		Ok(())
+	}
+
+	// checks the price of a guarded asset at the start of the block against the previous block,
+	// reports the change of its status and records its price for the next block
+	fn update_price_check(asset_id: CurrencyId) {
+		match (Self::check_price(&asset_id), Self::failed_price_check(asset_id)) {
+			(Err(failure), previous_failure) =>
+				if previous_failure != Some(failure) {
+					FailedPriceChecks::<T>::insert(asset_id, failure);
+					Self::deposit_event(Event::PriceCheckFailed(asset_id, failure));
+				},
+			(Ok(()), Some(_)) => {
+				FailedPriceChecks::<T>::remove(asset_id);
+				Self::deposit_event(Event::PriceCheckRecovered(asset_id));
+			},
+			(Ok(()), None) => {},
+		}
+
+		match Self::get_unit_price(&asset_id, &|asset_id| T::Source::get(asset_id)) {
+			Some(price) => PreviousPrices::<T>::insert(asset_id, price.value),
+			None => PreviousPrices::<T>::remove(asset_id),
+		}
+	}
+
+	/// Returns the price of one whole vtoken, its token price times the vtoken-minting exchange
+	/// rate. If a market price cap is set, the price is capped at the stable-pool TWAP over
+	/// `MarketPriceWindow` blocks plus the tolerance, and `None` is returned when the pool has no
+	/// TWAP over the window.
+	///
+	/// Foreign assets mapped to a native asset get the price of the native asset, and stable-pool
+	/// LP tokens get their price from `get_lp_token_price`.
+	///
+	/// `depth` is the number of prices the price of `asset_id` is derived from.
 	fn get_special_asset_price(
-		_asset_id: CurrencyId,
-		_base_price: TimeStampedPrice,
+		asset_id: CurrencyId,
+		get_source_price: &impl Fn(&CurrencyId) -> Option<TimeStampedPrice>,
+		depth: u32,
+	) -> Option<TimeStampedPrice> {
+		let native_asset_id = Self::foreign_to_native_asset(asset_id);
+		let vtoken_id = native_asset_id.unwrap_or(asset_id);
+		if let CurrencyId::BLP(pool_id) | CurrencyId::StableLpToken(pool_id) = vtoken_id {
+			return Self::get_lp_token_price(vtoken_id, pool_id, get_source_price, depth);
+		}
+		if !vtoken_id.is_vtoken() {
+			return native_asset_id.and_then(|native_asset_id| {
+				Self::get_derived_unit_price(&native_asset_id, get_source_price, depth + 1)
+			});
+		}
+
+		let token_id = T::VtokenMinting::token_id(vtoken_id)?;
+		let token_price = Self::get_derived_unit_price(&token_id, get_source_price, depth + 1)?;
+		let vtoken_mantissa = Self::get_asset_mantissa(&vtoken_id)?;
+		let token_mantissa = Self::get_asset_mantissa(&token_id)?;
+		let token_amount =
+			T::VtokenMinting::vtoken_to_token(token_id, vtoken_id, vtoken_mantissa).ok()?;
+		let mut value = token_price
+			.value
+			.checked_mul(&FixedU128::checked_from_rational(token_amount, token_mantissa)?)?;
+
+		if let Some(tolerance) = Self::market_price_cap(vtoken_id) {
+			let (pool_id, currency_id_in, currency_id_out) =
+				T::StablePoolHandler::get_pool_id(&vtoken_id, &token_id)?;
+			let twap = T::StablePoolHandler::get_twap(
+				pool_id,
+				currency_id_in,
+				currency_id_out,
+				T::MarketPriceWindow::get(),
+			)?;
+			let market_price = token_price.value.checked_mul(&twap)?;
+			let cap = market_price
+				.checked_mul(&FixedU128::one().saturating_add(FixedU128::from(tolerance)))?;
+			log::trace!(
+				target: "prices::get_special_asset_price",
+				"vtoken_id: {:?}, derived price: {:?}, cap: {:?}",
+				vtoken_id,
+				value,
+				cap
+			);
+			value = value.min(cap);
+		}
+
+		Some(TimeStampedPrice { value, timestamp: token_price.timestamp })
+	}
+
+	/// Returns the price of one whole stable-pool LP token: the lowest price among the pool
+	/// assets of what the LP token stands for at the pool invariant. Swaps leave the invariant
+	/// unchanged but for their fees, so trading against the pool can't push the price up, and
+	/// the LP token is valued as if the pool held only its cheapest asset.
+	///
+	/// The price is as old as the oldest price of the pool assets, and `None` is returned when
+	/// any of them has no price.
+	fn get_lp_token_price(
+		lp_token_id: CurrencyId,
+		pool_id: PoolId,
+		get_source_price: &impl Fn(&CurrencyId) -> Option<TimeStampedPrice>,
+		depth: u32,
 	) -> Option<TimeStampedPrice> {
-		None
+		let lp_token_mantissa = Self::get_asset_mantissa(&lp_token_id)?;
+		let units = lp_token_mantissa.max(LP_TOKEN_VALUATION_UNITS);
+		let values = T::StablePoolHandler::get_lp_token_value(pool_id, units).ok()?;
+
+		let mut lp_token_price: Option<TimeStampedPrice> = None;
+		for (asset_id, amount) in values {
+			let price = Self::get_derived_unit_price(&asset_id, get_source_price, depth + 1)?;
+			let value = price
+				.value
+				.checked_mul(&FixedU128::checked_from_rational(
+					amount,
+					Self::get_asset_mantissa(&asset_id)?,
+				)?)?
+				.checked_mul(&FixedU128::checked_from_rational(lp_token_mantissa, units)?)?;
+			lp_token_price = Some(match lp_token_price {
+				Some(lowest) => TimeStampedPrice {
+					value: lowest.value.min(value),
+					timestamp: lowest.timestamp.min(price.timestamp),
+				},
+				None => TimeStampedPrice { value, timestamp: price.timestamp },
+			});
+		}
+		log::trace!(
+			target: "prices::get_lp_token_price",
+			"lp_token_id: {:?}, price: {:?}",
+			lp_token_id,
+			lp_token_price
+		);
+
+		lp_token_price
+	}
+
+	// the Zenlink price of one whole asset in its quote asset, what selling it returns
+	fn get_zenlink_spot_price(asset_id: CurrencyId, quote_id: CurrencyId) -> Option<FixedU128> {
+		let para_id: u32 = T::ParachainId::get().into();
+		let path = sp_std::vec![
+			ZenlinkAssetId::try_convert_from(asset_id, para_id).ok()?,
+			ZenlinkAssetId::try_convert_from(quote_id, para_id).ok()?,
+		];
+		let amounts =
+			T::DexOperator::get_amount_out_by_path(Self::get_asset_mantissa(&asset_id)?, &path)
+				.ok()?;
+		FixedU128::checked_from_rational(*amounts.last()?, Self::get_asset_mantissa(&quote_id)?)
+	}
+
+	// observes the Zenlink price of an asset at the start of the block, the price observed
+	// before is added to the cumulative price for every block since
+	fn observe_zenlink_price(asset_id: CurrencyId, quote_id: CurrencyId, block: BlockNumberFor<T>) {
+		let price = match Self::get_zenlink_spot_price(asset_id, quote_id) {
+			Some(price) => price,
+			None => return,
+		};
+		let window: BlockNumberFor<T> = T::MarketPriceWindow::get().into();
+		ZenlinkTwapObservations::<T>::mutate(asset_id, |observations| {
+			*observations = Some(match observations.take() {
+				Some(mut observations) if observations.latest.block < block => {
+					let latest = PriceObservation {
+						block,
+						cumulative_price: Self::get_cumulative_price(&observations.latest, block),
+						price,
+					};
+					if block.saturating_sub(observations.next_start.block) >= window {
+						observations.start = observations.next_start;
+						observations.next_start = latest;
+					}
+					observations.latest = latest;
+					observations
+				},
+				Some(observations) => observations,
+				None => {
+					let first = PriceObservation { block, cumulative_price: Zero::zero(), price };
+					TwapObservations { start: first, next_start: first, latest: first }
+				},
+			});
+		});
+	}
+
+	/// Returns the Zenlink TWAP of an asset in its quote asset, averaged over `MarketPriceWindow`
+	/// to twice as many blocks before the current block. Returns `None` when the observations
+	/// don't reach back `MarketPriceWindow` blocks.
+	pub fn get_zenlink_twap(asset_id: &CurrencyId) -> Option<FixedU128> {
+		let observations = Self::zenlink_twap_observations(asset_id)?;
+		let block = frame_system::Pallet::<T>::block_number();
+		let blocks = block.checked_sub(&observations.start.block)?;
+		if blocks.is_zero() || blocks < T::MarketPriceWindow::get().into() {
+			return None;
+		}
+		Self::get_cumulative_price(&observations.latest, block)
+			.checked_sub(&observations.start.cumulative_price)?
+			.checked_div(&FixedU128::saturating_from_integer(blocks.saturated_into::<u128>()))
+	}
+
+	// the cumulative price at the start of `block`, the price of the observation held since
+	fn get_cumulative_price(
+		observation: &PriceObservation<BlockNumberFor<T>>,
+		block: BlockNumberFor<T>,
+	) -> FixedU128 {
+		let blocks = FixedU128::saturating_from_integer(
+			block.saturating_sub(observation.block).saturated_into::<u128>(),
+		);
+		observation
+			.cumulative_price
+			.saturating_add(observation.price.saturating_mul(blocks))
 	}
 
 	fn normalize_detail_price(price: TimeStampedPrice, mantissa: u128) -> Option<PriceDetail> {
@@ -220,6 +752,22 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+/// Prices an asset by its Zenlink TWAP in the quote asset set with `set_zenlink_twap_quote`,
+/// times the `Source` price of the quote asset.
+pub struct ZenlinkTwapProvider<T>(PhantomData<T>);
+
+impl<T: Config> DataProvider<CurrencyId, TimeStampedPrice> for ZenlinkTwapProvider<T> {
+	fn get(asset_id: &CurrencyId) -> Option<TimeStampedPrice> {
+		let quote_id = Pallet::<T>::zenlink_twap_quote(asset_id)?;
+		let quote_price = T::Source::get(&quote_id)?;
+		let twap = Pallet::<T>::get_zenlink_twap(asset_id)?;
+		Some(TimeStampedPrice {
+			value: quote_price.value.checked_mul(&twap)?,
+			timestamp: quote_price.timestamp,
+		})
+	}
+}
+
 impl<T: Config> PriceFeeder for Pallet<T> {
 	/// Returns the uniform format price and timestamp by asset id.
 	/// Formula: `price = oracle_price * 10.pow(18 - asset_decimal)`
@@ -232,21 +780,22 @@ impl<T: Config> PriceFeeder for Pallet<T> {
 		// if emergency price exists, return it
 		Self::get_emergency_price(asset_id).or_else(|| {
 			let mantissa = Self::get_asset_mantissa(asset_id)?;
-			T::Source::get(&T::RelayCurrency::get())
-				.and_then(|base_price| Self::get_special_asset_price(*asset_id, base_price))
-				.or_else(|| T::Source::get(asset_id))
+			Self::get_unit_price(asset_id, &|asset_id| T::Source::get(asset_id))
 				.and_then(|price| Self::normalize_detail_price(price, mantissa))
 		})
 	}
 
 	fn get_normal_price(asset_id: &CurrencyId) -> Option<u128> {
 		let decimals = Self::get_asset_mantissa(asset_id)?;
-		Self::emergency_price(asset_id)
-			.and_then(|p| Some(p.into_inner().saturating_div(decimals)))
-			.or_else(|| {
-				T::Source::get(&asset_id)
-					.and_then(|price| Some(price.value.into_inner().saturating_div(decimals)))
-			})
+		Self::get_unit_price(asset_id, &|asset_id| T::Source::get(asset_id))
+			.map(|price| price.value.into_inner().saturating_div(decimals))
+	}
+
+	/// The price is valid if it passed its checks at the start of the block and still passes
+	/// them.
+	fn is_price_valid(asset_id: &CurrencyId) -> bool {
+		Self::emergency_price(asset_id).is_some() ||
+			(Self::failed_price_check(asset_id).is_none() && Self::check_price(asset_id).is_ok())
 	}
 }
 
@@ -268,9 +817,9 @@ impl<T: Config> EmergencyPriceFeeder<CurrencyId, Price> for Pallet<T> {
 impl<T: Config> DataProviderExtended<CurrencyId, TimeStampedPrice> for Pallet<T> {
 	fn get_no_op(asset_id: &CurrencyId) -> Option<TimeStampedPrice> {
 		let _mantissa = Self::get_asset_mantissa(asset_id)?;
-		T::Source::get_no_op(&T::RelayCurrency::get())
-			.and_then(|base_price| Self::get_special_asset_price(*asset_id, base_price))
-			.or_else(|| T::Source::get_no_op(asset_id))
+		let get_source_price = |asset_id: &CurrencyId| T::Source::get_no_op(asset_id);
+		Self::get_special_asset_price(*asset_id, &get_source_price, 0)
+			.or_else(|| Self::get_aggregated_price(asset_id, &get_source_price))
 	}
 
 	fn get_all_values() -> Vec<(CurrencyId, Option<TimeStampedPrice>)> {
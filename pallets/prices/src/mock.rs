@@ -17,16 +17,24 @@
 use super::*;
 use frame_support::{
 	construct_runtime, derive_impl, ord_parameter_types, parameter_types,
-	traits::{AsEnsureOriginWithArg, Nothing},
+	traits::{AsEnsureOriginWithArg, ConstU32, Nothing},
+	PalletId,
 };
 use frame_system::{EnsureRoot, EnsureSigned, EnsureSignedBy};
-use sp_runtime::{traits::IdentityLookup, FixedPointNumber};
+use orml_traits::MultiCurrency;
+use sp_runtime::{
+	traits::{IdentityLookup, UniqueSaturatedInto},
+	DispatchError, DispatchResult, FixedPointNumber, FixedU128,
+};
+use sp_std::marker::PhantomData;
+use zenlink_protocol::{AssetBalance, LocalAssetHandler, PairLpGenerate, ZenlinkMultiAssets};
 
 use bifrost_asset_registry::AssetIdMaps;
 pub use bifrost_primitives::{
 	currency::{FIL, VFIL},
 	DOT, KSM, VDOT,
 };
+use bifrost_stable_pool::{PoolTokenIndex, StableAssetPoolId};
 use sp_runtime::BuildStorage;
 
 pub type AccountId = u128;
@@ -50,8 +58,18 @@ impl frame_system::Config for Test {
 
 pub type TimeStampedPrice = orml_oracle::TimestampedValue<Price, Moment>;
 pub struct MockDataProvider;
+parameter_types! {
+	// Oracle prices overriding the fixed ones
+	pub static OraclePrices: Vec<(CurrencyId, TimeStampedPrice)> = vec![];
+	pub static TwapPrices: Vec<(CurrencyId, TimeStampedPrice)> = vec![];
+	pub static Now: Moment = 0;
+}
+
 impl DataProvider<CurrencyId, TimeStampedPrice> for MockDataProvider {
 	fn get(asset_id: &CurrencyId) -> Option<TimeStampedPrice> {
+		if let Some((_, price)) = OraclePrices::get().into_iter().find(|(id, _)| id == asset_id) {
+			return Some(price);
+		}
 		match *asset_id {
 			DOT =>
 				Some(TimeStampedPrice { value: Price::saturating_from_integer(100), timestamp: 0 }),
@@ -90,6 +108,24 @@ impl DataProviderExtended<CurrencyId, TimeStampedPrice> for MockDataProvider {
 	}
 }
 
+pub struct MockTwapSource;
+impl PriceSource for MockTwapSource {
+	fn get_prices(asset_id: &CurrencyId) -> Vec<TimeStampedPrice> {
+		TwapPrices::get()
+			.into_iter()
+			.filter(|(id, _)| id == asset_id)
+			.map(|(_, price)| price)
+			.collect()
+	}
+}
+
+pub struct MockUnixTime;
+impl UnixTime for MockUnixTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_millis(Now::get())
+	}
+}
+
 impl DataFeeder<CurrencyId, TimeStampedPrice, AccountId> for MockDataProvider {
 	fn feed_value(
 		_: Option<AccountId>,
@@ -162,6 +198,294 @@ parameter_types! {
 	pub const NativeCurrencyId: CurrencyId = BNC;
 }
 
+parameter_types! {
+	// The DOT amount the stable pool swaps 1 VDOT for, in 1/10
+	pub static MarketVdotRate: u128 = 11;
+}
+
+// 1 VDOT is minted for 1.2 DOT
+pub struct MockVtokenMinting;
+impl VtokenMintingInterface<AccountId, CurrencyId, Balance> for MockVtokenMinting {
+	fn mint(
+		_exchanger: AccountId,
+		_token_id: CurrencyId,
+		_token_amount: Balance,
+		_remark: BoundedVec<u8, ConstU32<32>>,
+		_channel_id: Option<u32>,
+	) -> Result<Balance, DispatchError> {
+		Ok(0)
+	}
+
+	fn redeem(
+		_exchanger: AccountId,
+		_vtoken_id: CurrencyId,
+		_vtoken_amount: Balance,
+	) -> DispatchResultWithPostInfo {
+		Ok(().into())
+	}
+
+	fn slpx_redeem(
+		_exchanger: AccountId,
+		_vtoken_id: CurrencyId,
+		_vtoken_amount: Balance,
+		_redeem_type: RedeemType<AccountId>,
+	) -> DispatchResultWithPostInfo {
+		Ok(().into())
+	}
+
+	fn token_to_vtoken(
+		_token_id: CurrencyId,
+		_vtoken_id: CurrencyId,
+		token_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		Ok(token_amount * 10 / 12)
+	}
+
+	fn vtoken_to_token(
+		_token_id: CurrencyId,
+		_vtoken_id: CurrencyId,
+		vtoken_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		Ok(vtoken_amount * 12 / 10)
+	}
+
+	fn vtoken_id(token_id: CurrencyId) -> Option<CurrencyId> {
+		(token_id == DOT).then_some(VDOT)
+	}
+
+	fn token_id(vtoken_id: CurrencyId) -> Option<CurrencyId> {
+		(vtoken_id == VDOT).then_some(DOT)
+	}
+
+	fn get_token_pool(_currency_id: CurrencyId) -> Balance {
+		0
+	}
+
+	fn get_minimums_redeem(_vtoken_id: CurrencyId) -> Balance {
+		0
+	}
+
+	fn get_mint_fee_rate() -> Permill {
+		Permill::zero()
+	}
+
+	fn get_astar_parachain_id() -> u32 {
+		0
+	}
+
+	fn get_moonbeam_parachain_id() -> u32 {
+		0
+	}
+
+	fn get_hydradx_parachain_id() -> u32 {
+		0
+	}
+
+	fn get_interlay_parachain_id() -> u32 {
+		0
+	}
+
+	fn get_manta_parachain_id() -> u32 {
+		0
+	}
+}
+
+// A VDOT/DOT pool swapping at `MarketVdotRate`
+pub struct MockStablePool;
+impl StablePoolHandler for MockStablePool {
+	type Balance = Balance;
+	type AccountId = AccountId;
+	type CurrencyId = CurrencyId;
+
+	fn add_liquidity(
+		_who: AccountId,
+		_pool_id: StableAssetPoolId,
+		_amounts: Vec<Balance>,
+		_min_mint_amount: Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn swap(
+		_who: &AccountId,
+		_pool_id: StableAssetPoolId,
+		_currency_id_in: PoolTokenIndex,
+		_currency_id_out: PoolTokenIndex,
+		_amount: Balance,
+		_min_dy: Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn redeem_single(
+		_who: AccountId,
+		_pool_id: StableAssetPoolId,
+		_amount: Balance,
+		_i: PoolTokenIndex,
+		_min_redeem_amount: Balance,
+		_asset_length: u32,
+	) -> Result<(Balance, Balance), DispatchError> {
+		Ok((0, 0))
+	}
+
+	fn redeem_multi(
+		_who: AccountId,
+		_pool_id: StableAssetPoolId,
+		_amounts: Vec<Balance>,
+		_max_redeem_amount: Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn redeem_proportion(
+		_who: AccountId,
+		_pool_id: StableAssetPoolId,
+		_amount: Balance,
+		_min_redeem_amounts: Vec<Balance>,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn get_pool_token_index(
+		_pool_id: StableAssetPoolId,
+		_currency_id: CurrencyId,
+	) -> Option<PoolTokenIndex> {
+		None
+	}
+
+	fn get_swap_output(
+		_pool_id: StableAssetPoolId,
+		_currency_id_in: PoolTokenIndex,
+		_currency_id_out: PoolTokenIndex,
+		amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		Ok(amount * MarketVdotRate::get() / 10)
+	}
+
+	fn get_swap_input(
+		_pool_id: StableAssetPoolId,
+		_currency_id_in: PoolTokenIndex,
+		_currency_id_out: PoolTokenIndex,
+		amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		Ok(amount * 10 / MarketVdotRate::get())
+	}
+
+	fn get_twap(
+		_pool_id: StableAssetPoolId,
+		_currency_id_in: PoolTokenIndex,
+		_currency_id_out: PoolTokenIndex,
+		_window: u32,
+	) -> Option<FixedU128> {
+		Some(FixedU128::saturating_from_rational(MarketVdotRate::get(), 10))
+	}
+
+	fn get_pool_id(
+		currency_id_in: &CurrencyId,
+		currency_id_out: &CurrencyId,
+	) -> Option<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)> {
+		(*currency_id_in == VDOT && *currency_id_out == DOT).then_some((0, 0, 1))
+	}
+
+	fn get_pool_ids(
+		currency_id_in: &CurrencyId,
+		currency_id_out: &CurrencyId,
+	) -> Vec<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)> {
+		Self::get_pool_id(currency_id_in, currency_id_out).into_iter().collect()
+	}
+
+	// One BLP of pool 0, of 12 decimals, stands for 1.05 DOT at the invariant
+	fn get_lp_token_value(
+		pool_id: StableAssetPoolId,
+		amount: Balance,
+	) -> Result<Vec<(CurrencyId, Balance)>, DispatchError> {
+		ensure!(pool_id == 0, DispatchError::Other("pool not found"));
+		let dot_amount = amount * 105 / 100 / 100;
+		Ok(vec![(VDOT, dot_amount * 10 / 12), (DOT, dot_amount)])
+	}
+}
+
+parameter_types! {
+	pub const ZenlinkPalletId: PalletId = PalletId(*b"/zenlink");
+	pub const SelfParaId: u32 = 2001;
+}
+
+impl zenlink_protocol::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type MultiAssetsHandler = MultiAssets;
+	type PalletId = ZenlinkPalletId;
+	type SelfParaId = SelfParaId;
+	type TargetChains = ();
+	type WeightInfo = ();
+	type AssetId = ZenlinkAssetId;
+	type LpGenerate = PairLpGenerate<Self>;
+}
+
+type MultiAssets = ZenlinkMultiAssets<ZenlinkProtocol, Balances, LocalAssetAdaptor<Currencies>>;
+
+// Below is the implementation of tokens manipulation functions other than native token.
+pub struct LocalAssetAdaptor<Local>(PhantomData<Local>);
+
+impl<Local, AccountId> LocalAssetHandler<AccountId> for LocalAssetAdaptor<Local>
+where
+	Local: MultiCurrency<AccountId, CurrencyId = CurrencyId>,
+{
+	fn local_balance_of(asset_id: ZenlinkAssetId, who: &AccountId) -> AssetBalance {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::free_balance(currency_id, &who).saturated_into()
+	}
+
+	fn local_total_supply(asset_id: ZenlinkAssetId) -> AssetBalance {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::total_issuance(currency_id).saturated_into()
+	}
+
+	fn local_is_exists(asset_id: ZenlinkAssetId) -> bool {
+		let rs: Result<CurrencyId, _> = asset_id.try_into();
+		rs.is_ok()
+	}
+
+	fn local_transfer(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		target: &AccountId,
+		amount: AssetBalance,
+	) -> DispatchResult {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::transfer(currency_id, &origin, &target, amount.unique_saturated_into())?;
+
+		Ok(())
+	}
+
+	fn local_deposit(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		amount: AssetBalance,
+	) -> Result<AssetBalance, DispatchError> {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::deposit(currency_id, &origin, amount.unique_saturated_into())?;
+		Ok(amount)
+	}
+
+	fn local_withdraw(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		amount: AssetBalance,
+	) -> Result<AssetBalance, DispatchError> {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::withdraw(currency_id, &origin, amount.unique_saturated_into())?;
+
+		Ok(amount)
+	}
+}
+
+pub struct ParachainId;
+impl Get<ParaId> for ParachainId {
+	fn get() -> ParaId {
+		2001.into()
+	}
+}
+
 impl crate::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Source = MockDataProvider;
@@ -170,6 +494,13 @@ impl crate::Config for Test {
 	type RelayCurrency = RelayCurrency;
 	type CurrencyIdConvert = AssetIdMaps<Test>;
 	type Assets = Currencies;
+	type VtokenMinting = MockVtokenMinting;
+	type StablePoolHandler = MockStablePool;
+	type AggregatedSources = MockTwapSource;
+	type MarketPriceWindow = ConstU32<300>;
+	type DexOperator = ZenlinkProtocol;
+	type ParachainId = ParachainId;
+	type UnixTime = MockUnixTime;
 	type WeightInfo = ();
 }
 
@@ -231,6 +562,7 @@ construct_runtime!(
 		Currencies: bifrost_currencies,
 		Prices: crate,
 		AssetRegistry: bifrost_asset_registry,
+		ZenlinkProtocol: zenlink_protocol,
 	}
 );
 
@@ -257,6 +589,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
 	let endowed_accounts: Vec<(AccountId, CurrencyId, Balance)> = vec![
 		(ALICE, DOT, 1000 * PRICE_ONE),
+		(ALICE, KSM, 1000 * PRICE_ONE),
 		(ALICE, FIL, 1000 * PRICE_ONE),
 		(ALICE, VDOT, 1000 * PRICE_ONE),
 	];
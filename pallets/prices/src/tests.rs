@@ -193,3 +193,279 @@ fn get_foreign_token_price_work() {
 		assert_eq!(Prices::get_price(&FIL), Prices::get_price(&VFIL));
 	});
 }
+
+#[test]
+fn get_vtoken_price_from_exchange_rate() {
+	new_test_ext().execute_with(|| {
+		// 1 VDOT is minted for 1.2 DOT at $100, over the $150 oracle price of VDOT
+		assert_eq!(
+			Prices::get_price(&VDOT),
+			Some((Price::from_inner(12_000_000_000 * PRICE_ONE), 0))
+		);
+		assert_eq!(Prices::get_normal_price(&VDOT), Some(120 * PRICE_ONE / 10_000_000_000));
+
+		// The DOT emergency price applies to VDOT
+		EmergencyPrice::<Test>::insert(DOT, Price::saturating_from_integer(90));
+		assert_eq!(
+			Prices::get_price(&VDOT),
+			Some((Price::from_inner(10_800_000_000 * PRICE_ONE), 0))
+		);
+
+		// as does the VDOT emergency price
+		EmergencyPrice::<Test>::insert(VDOT, Price::saturating_from_integer(100));
+		assert_eq!(
+			Prices::get_price(&VDOT),
+			Some((Price::from_inner(10_000_000_000 * PRICE_ONE), 0))
+		);
+	});
+}
+
+#[test]
+fn set_market_price_cap_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			Prices::set_market_price_cap(RuntimeOrigin::signed(CHARLIE), VDOT, None),
+			BadOrigin
+		);
+		assert_noop!(
+			Prices::set_market_price_cap(RuntimeOrigin::signed(ALICE), DOT, None),
+			Error::<Test>::NotVtoken
+		);
+
+		// The stable pool swaps 1 VDOT for 1.1 DOT
+		assert_ok!(Prices::set_market_price_cap(
+			RuntimeOrigin::signed(ALICE),
+			VDOT,
+			Some(Permill::zero())
+		));
+		System::assert_last_event(RuntimeEvent::Prices(crate::Event::MarketPriceCapSet(
+			VDOT,
+			Some(Permill::zero()),
+		)));
+		assert_eq!(
+			Prices::get_price(&VDOT),
+			Some((Price::from_inner(11_000_000_000 * PRICE_ONE), 0))
+		);
+
+		// 5% above the market price
+		assert_ok!(Prices::set_market_price_cap(
+			RuntimeOrigin::signed(ALICE),
+			VDOT,
+			Some(Permill::from_percent(5))
+		));
+		assert_eq!(
+			Prices::get_price(&VDOT),
+			Some((Price::from_inner(11_550_000_000 * PRICE_ONE), 0))
+		);
+
+		// The market price is above the derived price
+		MarketVdotRate::set(13);
+		assert_eq!(
+			Prices::get_price(&VDOT),
+			Some((Price::from_inner(12_000_000_000 * PRICE_ONE), 0))
+		);
+
+		assert_ok!(Prices::set_market_price_cap(RuntimeOrigin::signed(ALICE), VDOT, None));
+		assert_eq!(Prices::market_price_cap(VDOT), None);
+	});
+}
+
+#[test]
+fn get_lp_token_price_from_pool_invariant() {
+	new_test_ext().execute_with(|| {
+		// 1 BLP stands for 1.05 DOT at $100, or for 0.875 VDOT at $120
+		assert_eq!(
+			Prices::get_price(&CurrencyId::BLP(0)),
+			Some((Price::from_inner(105_000_000 * PRICE_ONE), 0))
+		);
+
+		// A `StableLpToken` of the pool, of a single decimal, gets the same price per unit
+		assert_eq!(
+			Prices::get_price(&CurrencyId::StableLpToken(0)),
+			Some((Price::from_inner(105_000_000 * PRICE_ONE), 0))
+		);
+
+		// The cheapest pool asset sets the price
+		EmergencyPrice::<Test>::insert(VDOT, Price::saturating_from_integer(110));
+		assert_eq!(
+			Prices::get_price(&CurrencyId::BLP(0)),
+			Some((Price::from_inner(96_250_000 * PRICE_ONE), 0))
+		);
+
+		// No pool, no price
+		assert_eq!(Prices::get_price(&CurrencyId::BLP(1)), None);
+		assert_eq!(Prices::get_price(&CurrencyId::StableLpToken(1)), None);
+	});
+}
+
+#[test]
+fn get_price_aggregates_sources_by_median() {
+	new_test_ext().execute_with(|| {
+		let price = |value: u128, timestamp| mock::TimeStampedPrice {
+			value: Price::saturating_from_integer(value),
+			timestamp,
+		};
+		// The oracle price of KSM is $500
+		TwapPrices::set(vec![(KSM, price(480, 0)), (KSM, price(700, 0))]);
+		assert_eq!(Prices::get_normal_price(&KSM), Some(500 * PRICE_ONE / 1_000_000_000_000));
+
+		// The mean of the two middle prices
+		TwapPrices::set(vec![(KSM, price(480, 0))]);
+		assert_eq!(Prices::get_normal_price(&KSM), Some(490 * PRICE_ONE / 1_000_000_000_000));
+	});
+}
+
+#[test]
+fn price_guard_checks_age_and_deviation() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let price = |value: u128, timestamp| mock::TimeStampedPrice {
+			value: Price::saturating_from_integer(value),
+			timestamp,
+		};
+		Now::set(100_000);
+		OraclePrices::set(vec![(KSM, price(500, 90_000))]);
+		let price_guard = PriceGuard { max_age: 60_000, max_deviation: Permill::from_percent(10) };
+		assert_noop!(
+			Prices::set_price_guard(RuntimeOrigin::signed(CHARLIE), KSM, Some(price_guard)),
+			BadOrigin
+		);
+		assert_noop!(
+			Prices::set_price_guard(
+				RuntimeOrigin::signed(ALICE),
+				KSM,
+				Some(PriceGuard { max_age: 0, max_deviation: Permill::zero() })
+			),
+			Error::<Test>::InvalidPriceGuard
+		);
+		assert_ok!(Prices::set_price_guard(RuntimeOrigin::signed(ALICE), KSM, Some(price_guard)));
+		Prices::on_initialize(1);
+		assert_eq!(Prices::previous_price(KSM), Some(Price::saturating_from_integer(500)));
+		assert!(Prices::is_price_valid(&KSM));
+
+		// A 20% move within the block
+		OraclePrices::set(vec![(KSM, price(600, 95_000))]);
+		assert_eq!(Prices::check_price(&KSM), Err(PriceCheckFailure::Deviated));
+		assert!(!Prices::is_price_valid(&KSM));
+
+		// It is reported at the start of the next block, which is refused too
+		System::set_block_number(2);
+		Prices::on_initialize(2);
+		System::assert_last_event(RuntimeEvent::Prices(crate::Event::PriceCheckFailed(
+			KSM,
+			PriceCheckFailure::Deviated,
+		)));
+		assert_eq!(Prices::check_price(&KSM), Ok(()));
+		assert!(!Prices::is_price_valid(&KSM));
+
+		// The price is stable again
+		System::set_block_number(3);
+		Prices::on_initialize(3);
+		System::assert_last_event(RuntimeEvent::Prices(crate::Event::PriceCheckRecovered(KSM)));
+		assert!(Prices::is_price_valid(&KSM));
+
+		// The oracle stops updating, a fresh TWAP keeps the price available
+		Now::set(160_000);
+		assert_eq!(Prices::get_price(&KSM), None);
+		assert_eq!(Prices::check_price(&KSM), Err(PriceCheckFailure::Stale));
+		TwapPrices::set(vec![(KSM, price(610, 150_000))]);
+		assert_eq!(Prices::check_price(&KSM), Ok(()));
+		assert_eq!(Prices::get_normal_price(&KSM), Some(610 * PRICE_ONE / 1_000_000_000_000));
+
+		// An emergency price always passes
+		TwapPrices::set(vec![]);
+		assert!(!Prices::is_price_valid(&KSM));
+		EmergencyPrice::<Test>::insert(KSM, Price::saturating_from_integer(600));
+		assert!(Prices::is_price_valid(&KSM));
+
+		assert_ok!(Prices::set_price_guard(RuntimeOrigin::signed(ALICE), KSM, None));
+		assert_eq!(Prices::previous_price(KSM), None);
+	});
+}
+
+#[test]
+fn cyclic_foreign_asset_mapping_has_no_price() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Prices::set_foreign_asset(RuntimeOrigin::signed(ALICE), ASTR, GLMR));
+		assert_ok!(Prices::set_foreign_asset(RuntimeOrigin::signed(ALICE), GLMR, ASTR));
+		assert_eq!(Prices::get_price(&ASTR), None);
+		assert_eq!(Prices::get_normal_price(&GLMR), None);
+	});
+}
+
+#[test]
+fn zenlink_twap_provider_works() {
+	new_test_ext().execute_with(|| {
+		let ksm = ZenlinkAssetId::try_convert_from(KSM, 2001).unwrap();
+		let dot = ZenlinkAssetId::try_convert_from(DOT, 2001).unwrap();
+		assert_ok!(ZenlinkProtocol::create_pair(RuntimeOrigin::root(), ksm, dot, ALICE));
+		assert_ok!(ZenlinkProtocol::add_liquidity(
+			RuntimeOrigin::signed(ALICE),
+			ksm,
+			dot,
+			1_000 * 1_000_000_000_000,
+			5_000 * 10_000_000_000,
+			1,
+			1,
+			100
+		));
+		let spot_price = || {
+			let amounts =
+				ZenlinkProtocol::get_amount_out_by_path(1_000_000_000_000, &[ksm, dot]).unwrap();
+			FixedU128::checked_from_rational(amounts[1], 10_000_000_000).unwrap()
+		};
+		let price = spot_price();
+
+		assert_noop!(
+			Prices::set_zenlink_twap_quote(RuntimeOrigin::signed(CHARLIE), KSM, Some(DOT)),
+			BadOrigin
+		);
+		assert_noop!(
+			Prices::set_zenlink_twap_quote(RuntimeOrigin::signed(ALICE), KSM, Some(KSM)),
+			Error::<Test>::InvalidZenlinkTwapQuote
+		);
+		assert_ok!(Prices::set_zenlink_twap_quote(RuntimeOrigin::signed(ALICE), KSM, Some(DOT)));
+
+		// No TWAP until the observations reach back a whole window
+		System::set_block_number(1);
+		Prices::on_initialize(1);
+		System::set_block_number(300);
+		Prices::on_initialize(300);
+		assert_eq!(ZenlinkTwapProvider::<Test>::get(&KSM), None);
+
+		// The oracle price of DOT is $100
+		System::set_block_number(301);
+		Prices::on_initialize(301);
+		assert_eq!(Prices::get_zenlink_twap(&KSM), Some(price));
+		assert_eq!(
+			ZenlinkTwapProvider::<Test>::get(&KSM),
+			Some(mock::TimeStampedPrice {
+				value: Price::saturating_from_integer(100).checked_mul(&price).unwrap(),
+				timestamp: 0
+			})
+		);
+
+		// Selling KSM within a block doesn't move the TWAP, which then follows slowly
+		assert_ok!(ZenlinkProtocol::swap_exact_assets_for_assets(
+			RuntimeOrigin::signed(ALICE),
+			100 * 1_000_000_000_000,
+			0,
+			vec![ksm, dot],
+			ALICE,
+			1_000
+		));
+		let new_price = spot_price();
+		System::set_block_number(302);
+		Prices::on_initialize(302);
+		assert_eq!(Prices::get_zenlink_twap(&KSM), Some(price));
+		System::set_block_number(303);
+		Prices::on_initialize(303);
+		let twap = Prices::get_zenlink_twap(&KSM).unwrap();
+		assert!(new_price < twap && twap < price);
+
+		assert_ok!(Prices::set_zenlink_twap_quote(RuntimeOrigin::signed(ALICE), KSM, None));
+		assert_eq!(Prices::zenlink_twap_observations(KSM), None);
+		assert_eq!(ZenlinkTwapProvider::<Test>::get(&KSM), None);
+	});
+}
@@ -29,6 +29,11 @@ pub trait WeightInfo {
 	fn set_price() -> Weight;
 	fn reset_price() -> Weight;
 	fn set_foreign_asset() -> Weight;
+	fn set_market_price_cap() -> Weight;
+	fn set_price_guard() -> Weight;
+	fn on_initialize(n: u32) -> Weight;
+	fn set_zenlink_twap_quote() -> Weight;
+	fn observe_zenlink_prices(n: u32) -> Weight;
 }
 
 /// Weights for pallet_prices using the Substrate node and recommended hardware.
@@ -49,6 +54,35 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as u64))
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	fn set_market_price_cap() -> Weight {
+		Weight::from_parts(22_846_000 as u64, 3979)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_price_guard() -> Weight {
+		Weight::from_parts(23_105_000 as u64, 3979)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	fn on_initialize(n: u32) -> Weight {
+		Weight::from_parts(4_125_000 as u64, 0)
+			.saturating_add(Weight::from_parts(31_460_000 as u64, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((8 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(n as u64)))
+	}
+	fn set_zenlink_twap_quote() -> Weight {
+		Weight::from_parts(22_704_000 as u64, 3979)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn observe_zenlink_prices(n: u32) -> Weight {
+		Weight::from_parts(3_870_000 as u64, 0)
+			.saturating_add(Weight::from_parts(27_315_000 as u64, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((5 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -68,4 +102,33 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as u64))
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
+	fn set_market_price_cap() -> Weight {
+		Weight::from_parts(22_846_000 as u64, 3979)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_price_guard() -> Weight {
+		Weight::from_parts(23_105_000 as u64, 3979)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn on_initialize(n: u32) -> Weight {
+		Weight::from_parts(4_125_000 as u64, 0)
+			.saturating_add(Weight::from_parts(31_460_000 as u64, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((8 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(n as u64)))
+	}
+	fn set_zenlink_twap_quote() -> Weight {
+		Weight::from_parts(22_704_000 as u64, 3979)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn observe_zenlink_prices(n: u32) -> Weight {
+		Weight::from_parts(3_870_000 as u64, 0)
+			.saturating_add(Weight::from_parts(27_315_000 as u64, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((5 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+	}
 }
@@ -348,6 +348,7 @@ impl bifrost_stable_asset::Config for Test {
 	type WeightInfo = ();
 	type ListingOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
 	type EnsurePoolAssetId = EnsurePoolAssetId;
+	type MaxPriceObservations = ConstU32<1200>;
 }
 
 impl bifrost_stable_pool::Config for Test {
@@ -359,6 +360,9 @@ impl bifrost_stable_pool::Config for Test {
 	type VtokenMinting = VtokenMinting;
 	type CurrencyIdConversion = AssetIdMaps<Test>;
 	type CurrencyIdRegister = AssetIdMaps<Test>;
+	type DepositCurrencyId = NativeCurrencyId;
+	type PoolDeposit = ConstU128<1_000_000_000_000>;
+	type PoolReapDelay = ConstU64<100>;
 }
 
 parameter_types! {
@@ -33,7 +33,7 @@ use frame_support::{
 	construct_runtime, derive_impl, ord_parameter_types,
 	pallet_prelude::Get,
 	parameter_types,
-	traits::{ConstU128, ConstU32, Everything, Nothing, ProcessMessageError},
+	traits::{ConstU128, ConstU32, ConstU64, Everything, Nothing, ProcessMessageError},
 	PalletId,
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
@@ -85,6 +85,9 @@ impl bifrost_stable_pool::Config for Runtime {
 	type VtokenMinting = VtokenMinting;
 	type CurrencyIdConversion = AssetIdMaps<Runtime>;
 	type CurrencyIdRegister = AssetIdMaps<Runtime>;
+	type DepositCurrencyId = NativeCurrencyId;
+	type PoolDeposit = ConstU128<1_000_000_000_000>;
+	type PoolReapDelay = ConstU64<100>;
 }
 
 pub struct EnsurePoolAssetId;
@@ -111,6 +114,7 @@ impl bifrost_stable_asset::Config for Runtime {
 	type WeightInfo = ();
 	type ListingOrigin = EnsureSignedBy<One, AccountId>;
 	type EnsurePoolAssetId = EnsurePoolAssetId;
+	type MaxPriceObservations = ConstU32<1200>;
 }
 
 parameter_types! {
@@ -37,7 +37,7 @@ use orml_traits::{
 	location::RelativeReserveProvider, parameter_type_with_key, xcm_transfer::Transferred,
 	MultiCurrency, XcmTransfer,
 };
-use sp_core::ConstU128;
+use sp_core::{ConstU128, ConstU64};
 use sp_runtime::{
 	traits::{Convert, IdentityLookup, UniqueSaturatedInto},
 	AccountId32, SaturatedConversion,
@@ -497,6 +497,7 @@ impl bifrost_stable_asset::Config for Test {
 	type WeightInfo = ();
 	type ListingOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
 	type EnsurePoolAssetId = EnsurePoolAssetId;
+	type MaxPriceObservations = ConstU32<1200>;
 }
 
 impl bifrost_stable_pool::Config for Test {
@@ -508,6 +509,9 @@ impl bifrost_stable_pool::Config for Test {
 	type VtokenMinting = VtokenMinting;
 	type CurrencyIdConversion = AssetIdMaps<Test>;
 	type CurrencyIdRegister = AssetIdMaps<Test>;
+	type DepositCurrencyId = GetNativeCurrencyId;
+	type PoolDeposit = ConstU128<1_000_000_000_000>;
+	type PoolReapDelay = ConstU64<100>;
 }
 
 // Pallet slpx configuration
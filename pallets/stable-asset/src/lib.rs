@@ -40,8 +40,10 @@ use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use sp_core::{U256, U512};
 use sp_runtime::{
-	traits::{AccountIdConversion, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Zero},
-	DispatchError, SaturatedConversion,
+	traits::{
+		AccountIdConversion, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Saturating, Zero,
+	},
+	DispatchError, FixedPointNumber, FixedU128, SaturatedConversion,
 };
 use sp_std::prelude::*;
 
@@ -72,6 +74,50 @@ pub struct StableAssetPoolInfo<AssetId, AtLeast64BitUnsigned, Balance, AccountId
 	pub precision: AtLeast64BitUnsigned,
 }
 
+/// The dynamic fee of a pool, charged on top of its flat fees when a trade moves the pool away
+/// from balance. Both fees are in `FeePrecision`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+pub struct DynamicFeeConfig<AtLeast64BitUnsigned> {
+	/// The fee added when a trade moves the pool from balance to all in one asset
+	pub imbalance_fee: AtLeast64BitUnsigned,
+	/// The highest fee charged, flat fee included
+	pub max_fee: AtLeast64BitUnsigned,
+}
+
+/// The bounds on the parameters of the pools created without governance. Fees are in
+/// `FeePrecision`, and A in `APrecision`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+pub struct PoolParameterBounds<AtLeast64BitUnsigned> {
+	pub min_a: AtLeast64BitUnsigned,
+	pub max_a: AtLeast64BitUnsigned,
+	/// The highest mint, swap and redeem fee
+	pub max_fee: AtLeast64BitUnsigned,
+	/// The highest precision of a pool asset
+	pub max_asset_precision: AtLeast64BitUnsigned,
+	pub min_precision: AtLeast64BitUnsigned,
+	pub max_precision: AtLeast64BitUnsigned,
+}
+
+/// The deposit reserved from the creator of a pool created without governance, released when
+/// the pool is reaped.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct PoolDeposit<AccountId, Balance, BlockNumber> {
+	pub depositor: AccountId,
+	pub amount: Balance,
+	pub created_at: BlockNumber,
+}
+
+/// The prices of the assets of a pool observed at a block, each in the first pool asset.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct PriceObservation<BlockNumber> {
+	/// The block of the observation
+	pub block: BlockNumber,
+	/// The sum over the blocks before `block` of the price at the end of each block
+	pub cumulative_prices: Vec<FixedU128>,
+	/// The prices at the end of `block`
+	pub prices: Vec<FixedU128>,
+}
+
 pub trait WeightInfo {
 	fn create_pool() -> Weight;
 	fn modify_a() -> Weight;
@@ -335,7 +381,9 @@ pub mod traits {
 
 #[frame_support::pallet]
 pub mod pallet {
-	use super::{PoolTokenIndex, StableAssetPoolId, StableAssetPoolInfo};
+	use super::{
+		DynamicFeeConfig, PoolTokenIndex, PriceObservation, StableAssetPoolId, StableAssetPoolInfo,
+	};
 	use crate::{
 		traits::{StableAsset, ValidateAssetId},
 		WeightInfo,
@@ -394,10 +442,18 @@ pub mod pallet {
 
 		/// The origin which may create pool or modify pool.
 		type ListingOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The number of price observations kept per pool, which bounds the TWAP windows. The
+		/// observations are kept in a ring of this size, so changing it needs a migration.
+		#[pallet::constant]
+		type MaxPriceObservations: Get<u32>;
 	}
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
@@ -434,6 +490,74 @@ pub mod pallet {
 	#[pallet::getter(fn token_rate_hardcap)]
 	pub type TokenRateHardcap<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, Permill>;
 
+	/// The dynamic fee of the pools charging one.
+	#[pallet::storage]
+	#[pallet::getter(fn dynamic_fees)]
+	pub type DynamicFees<T: Config> =
+		StorageMap<_, Twox64Concat, StableAssetPoolId, DynamicFeeConfig<T::AtLeast64BitUnsigned>>;
+
+	/// The price observations of each pool, in a ring of `MaxPriceObservations` slots. A pool is
+	/// observed at most once per block, at the end of the block.
+	#[pallet::storage]
+	#[pallet::getter(fn price_observations)]
+	pub type PriceObservations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		StableAssetPoolId,
+		Twox64Concat,
+		u32,
+		PriceObservation<BlockNumberFor<T>>,
+	>;
+
+	/// The slot of the latest price observation of each pool and the number of observations.
+	#[pallet::storage]
+	#[pallet::getter(fn price_observation_cursors)]
+	pub type PriceObservationCursors<T: Config> =
+		StorageMap<_, Blake2_128Concat, StableAssetPoolId, (u32, u32)>;
+
+	/// The base pool of each metapool, the pool holding the pool token of another pool as one of
+	/// its assets.
+	#[pallet::storage]
+	#[pallet::getter(fn base_pools)]
+	pub type BasePools<T: Config> =
+		StorageMap<_, Twox64Concat, StableAssetPoolId, StableAssetPoolId>;
+
+	/// The bounds on the parameters of the pools created without governance, which can't be
+	/// created until they are set.
+	#[pallet::storage]
+	#[pallet::getter(fn parameter_bounds)]
+	pub type ParameterBounds<T: Config> =
+		StorageValue<_, PoolParameterBounds<T::AtLeast64BitUnsigned>>;
+
+	/// The deposits of the pools created without governance.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_deposits)]
+	pub type PoolDeposits<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		StableAssetPoolId,
+		PoolDeposit<T::AccountId, T::Balance, BlockNumberFor<T>>,
+	>;
+
+	/// The pools frozen by governance, which can only be redeemed from.
+	#[pallet::storage]
+	#[pallet::getter(fn frozen_pools)]
+	pub type FrozenPools<T: Config> =
+		StorageMap<_, Twox64Concat, StableAssetPoolId, bool, ValueQuery>;
+
+	/// The pool of each pool token.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_asset_pools)]
+	pub type PoolAssetPools<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, StableAssetPoolId>;
+
+	/// The pool designated by governance for each pair of assets, under both orders of the pair.
+	/// Only these pools are used as price sources and swap routes, so that a pool anyone can
+	/// create can't stand in for them.
+	#[pallet::storage]
+	#[pallet::getter(fn canonical_pools)]
+	pub type CanonicalPools<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AssetId, Twox64Concat, T::AssetId, StableAssetPoolId>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -553,6 +677,41 @@ pub mod pallet {
 		TokenRateRefreshFailed {
 			pool_id: StableAssetPoolId,
 		},
+		DynamicFeeConfigured {
+			pool_id: StableAssetPoolId,
+			config: DynamicFeeConfig<T::AtLeast64BitUnsigned>,
+		},
+		DynamicFeeRemoved {
+			pool_id: StableAssetPoolId,
+		},
+		MetaPoolCreated {
+			pool_id: StableAssetPoolId,
+			base_pool_id: StableAssetPoolId,
+		},
+		ParameterBoundsSet {
+			bounds: PoolParameterBounds<T::AtLeast64BitUnsigned>,
+		},
+		PoolDepositReserved {
+			pool_id: StableAssetPoolId,
+			depositor: T::AccountId,
+			amount: T::Balance,
+		},
+		PoolReaped {
+			pool_id: StableAssetPoolId,
+			depositor: T::AccountId,
+			amount: T::Balance,
+		},
+		PoolFrozen {
+			pool_id: StableAssetPoolId,
+		},
+		PoolUnfrozen {
+			pool_id: StableAssetPoolId,
+		},
+		CanonicalPoolSet {
+			asset_a: T::AssetId,
+			asset_b: T::AssetId,
+			pool_id: Option<StableAssetPoolId>,
+		},
 	}
 
 	#[pallet::error]
@@ -569,6 +728,8 @@ pub mod pallet {
 		RedeemUnderMin,
 		RedeemOverMax,
 		TokenRateNotCleared,
+		PoolFrozen,
+		DynamicFeeNotConfigured,
 	}
 
 	#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, Debug)]
@@ -644,9 +805,10 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::ListingOrigin::ensure_origin(origin.clone())?;
 			ensure!(T::EnsurePoolAssetId::validate(pool_asset), Error::<T>::InvalidPoolAsset);
+			let pool_id = PoolCount::<T>::get();
 			<Self as StableAsset>::create_pool(
 				pool_asset,
-				assets,
+				assets.clone(),
 				precisions,
 				mint_fee,
 				swap_fee,
@@ -655,7 +817,9 @@ pub mod pallet {
 				fee_recipient,
 				yield_recipient,
 				precision,
-			)
+			)?;
+			Self::designate_canonical_pool(pool_id, &assets);
+			Ok(())
 		}
 
 		#[pallet::call_index(1)]
@@ -1510,6 +1674,186 @@ impl<T: Config> Pallet<T> {
 		Ok(cloned_stable_asset_info)
 	}
 
+	/// Returns the price of one unit of each pool asset in the first pool asset: the marginal
+	/// price of the invariant at the pool balances, scaled by the token rates.
+	pub fn get_spot_prices(
+		pool_info: &StableAssetPoolInfo<
+			T::AssetId,
+			T::AtLeast64BitUnsigned,
+			T::Balance,
+			T::AccountId,
+			BlockNumberFor<T>,
+		>,
+	) -> Option<Vec<FixedU128>> {
+		let a: T::AtLeast64BitUnsigned = Self::get_a(
+			pool_info.a,
+			pool_info.a_block,
+			pool_info.future_a,
+			pool_info.future_a_block,
+		)?;
+		let balances: Vec<T::AtLeast64BitUnsigned> =
+			Self::convert_vec_balance_to_number(pool_info.balances.clone());
+		let d: U512 = U512::from(Self::get_d(&balances, a)?.saturated_into::<u128>());
+		let balance_size: U512 = U512::from(balances.len());
+		let a_precision_u512: U512 = U512::from(T::APrecision::get().saturated_into::<u128>());
+		let mut ann: U512 = U512::from(a.saturated_into::<u128>());
+		// D^(n+1) / (n^n * prod(balances)), as in `get_d`
+		let mut p_d: U512 = d;
+		for x in balances.iter() {
+			let balance: U512 = U512::from((*x).saturated_into::<u128>());
+			ann = ann.checked_mul(balance_size)?;
+			p_d = p_d.checked_mul(d)?.checked_div(balance.checked_mul(balance_size)?)?;
+		}
+		let p_d: U512 = p_d.checked_mul(a_precision_u512)?;
+
+		let token_rate = |asset_id: T::AssetId| -> (U512, U512) {
+			Self::get_token_rate(pool_info.pool_id, asset_id).map_or(
+				(U512::one(), U512::one()),
+				|(denominator, numerator)| {
+					(
+						U512::from(denominator.saturated_into::<u128>()),
+						U512::from(numerator.saturated_into::<u128>()),
+					)
+				},
+			)
+		};
+		let x_0: U512 = U512::from((*balances.first()?).saturated_into::<u128>());
+		let (denominator_0, numerator_0) = token_rate(*pool_info.assets.first()?);
+		balances
+			.iter()
+			.zip(pool_info.assets.iter())
+			.map(|(x, asset_id)| {
+				// -dx_0 / dx_i = x_0 * (ann * x_i + p_d) / (x_i * (ann * x_0 + p_d))
+				let x_i: U512 = U512::from((*x).saturated_into::<u128>());
+				let (denominator_i, numerator_i) = token_rate(*asset_id);
+				let price = x_0
+					.checked_mul(ann.checked_mul(x_i)?.checked_add(p_d)?)?
+					.checked_mul(numerator_i)?
+					.checked_mul(denominator_0)?
+					.checked_mul(U512::from(FixedU128::DIV))?
+					.checked_div(
+						x_i.checked_mul(ann.checked_mul(x_0)?.checked_add(p_d)?)?
+							.checked_mul(denominator_i)?
+							.checked_mul(numerator_0)?,
+					)?;
+				Some(FixedU128::from_inner(u128::try_from(price).ok()?))
+			})
+			.collect()
+	}
+
+	/// Observes the prices of a pool after a change of its balances. The last change in a block
+	/// sets the prices of the block, and the prices of the previous observation are added to the
+	/// cumulative prices for every block since.
+	pub fn update_price_observations(
+		pool_id: StableAssetPoolId,
+		pool_info: &StableAssetPoolInfo<
+			T::AssetId,
+			T::AtLeast64BitUnsigned,
+			T::Balance,
+			T::AccountId,
+			BlockNumberFor<T>,
+		>,
+	) {
+		let prices = match Self::get_spot_prices(pool_info) {
+			Some(prices) => prices,
+			None => return,
+		};
+		let block = frame_system::Pallet::<T>::block_number();
+		let cursor = PriceObservationCursors::<T>::get(pool_id);
+		let last = cursor.and_then(|(slot, _)| PriceObservations::<T>::get(pool_id, slot));
+		let (slot, count, cumulative_prices) = match (cursor, last) {
+			(Some((slot, count)), Some(last)) if last.block == block =>
+				(slot, count, last.cumulative_prices),
+			(Some((slot, count)), Some(last)) => (
+				slot.saturating_add(1) % T::MaxPriceObservations::get().max(1),
+				count.saturating_add(1).min(T::MaxPriceObservations::get().max(1)),
+				Self::get_cumulative_prices(&last, block),
+			),
+			_ => (0, 1, vec![FixedU128::zero(); prices.len()]),
+		};
+		PriceObservations::<T>::insert(
+			pool_id,
+			slot,
+			PriceObservation { block, cumulative_prices, prices },
+		);
+		PriceObservationCursors::<T>::insert(pool_id, (slot, count));
+	}
+
+	/// Returns the time-weighted average price of each pool asset in the first pool asset over
+	/// the `window` blocks before the current block. Returns `None` when the observations don't
+	/// reach back to the start of the window.
+	pub fn get_twap(
+		pool_id: StableAssetPoolId,
+		window: BlockNumberFor<T>,
+	) -> Option<Vec<FixedU128>> {
+		if window.is_zero() {
+			return None;
+		}
+		let block = frame_system::Pallet::<T>::block_number();
+		let start = block.checked_sub(&window)?;
+		let (slot, count) = PriceObservationCursors::<T>::get(pool_id)?;
+		let end_prices =
+			Self::get_cumulative_prices(&PriceObservations::<T>::get(pool_id, slot)?, block);
+		let start_prices = Self::get_cumulative_prices(
+			&Self::find_price_observation(pool_id, slot, count, start)?,
+			start,
+		);
+		let window = FixedU128::saturating_from_integer(window.saturated_into::<u128>());
+		end_prices
+			.iter()
+			.zip(start_prices.iter())
+			.map(|(end_price, start_price)| {
+				end_price.checked_sub(start_price)?.checked_div(&window)
+			})
+			.collect()
+	}
+
+	// the latest observation at or before `block`, by a binary search of the ring from the
+	// oldest observation to the latest one in `slot`
+	fn find_price_observation(
+		pool_id: StableAssetPoolId,
+		slot: u32,
+		count: u32,
+		block: BlockNumberFor<T>,
+	) -> Option<PriceObservation<BlockNumberFor<T>>> {
+		let max_observations = T::MaxPriceObservations::get().max(1);
+		let oldest = (slot + max_observations + 1 - count) % max_observations;
+		let get = |position: u32| {
+			PriceObservations::<T>::get(pool_id, (oldest + position) % max_observations)
+		};
+		let mut found = get(0).filter(|observation| observation.block <= block)?;
+		let (mut low, mut high) = (1, count);
+		while low < high {
+			let middle = low + (high - low) / 2;
+			let observation = get(middle)?;
+			if observation.block <= block {
+				found = observation;
+				low = middle + 1;
+			} else {
+				high = middle;
+			}
+		}
+		Some(found)
+	}
+
+	// the cumulative prices at the start of `block`, the prices of the observation held since
+	fn get_cumulative_prices(
+		observation: &PriceObservation<BlockNumberFor<T>>,
+		block: BlockNumberFor<T>,
+	) -> Vec<FixedU128> {
+		let blocks = FixedU128::saturating_from_integer(
+			block.saturating_sub(observation.block).saturated_into::<u128>(),
+		);
+		observation
+			.cumulative_prices
+			.iter()
+			.zip(observation.prices.iter())
+			.map(|(cumulative_price, price)| {
+				cumulative_price.saturating_add(price.saturating_mul(blocks))
+			})
+			.collect()
+	}
+
 	pub fn get_balance_update_amount(
 		pool_info: &StableAssetPoolInfo<
 			T::AssetId,
@@ -1552,6 +1896,86 @@ impl<T: Config> Pallet<T> {
 		cloned_stable_asset_info.balances = updated_balances;
 		Ok(cloned_stable_asset_info)
 	}
+
+	pub fn ensure_pool_not_frozen(pool_id: StableAssetPoolId) -> DispatchResult {
+		ensure!(!FrozenPools::<T>::get(pool_id), Error::<T>::PoolFrozen);
+		Ok(())
+	}
+
+	/// Removes an empty pool and everything kept for it. The assets left in the pool account
+	/// by rounding go to the fee recipient of the pool.
+	pub fn remove_pool(pool_id: StableAssetPoolId) -> DispatchResult {
+		let pool_info = Pools::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		ensure!(pool_info.total_supply.is_zero(), Error::<T>::InvalidPoolValue);
+		for asset in pool_info.assets.iter() {
+			let dust = T::Assets::free_balance(*asset, &pool_info.account_id);
+			if !dust.is_zero() {
+				T::Assets::transfer(*asset, &pool_info.account_id, &pool_info.fee_recipient, dust)?;
+			}
+		}
+		frame_system::Pallet::<T>::dec_providers(&pool_info.account_id)?;
+		for (index, asset_a) in pool_info.assets.iter().enumerate() {
+			for asset_b in pool_info.assets.iter().skip(index + 1) {
+				if CanonicalPools::<T>::get(asset_a, asset_b) == Some(pool_id) {
+					Self::set_canonical_pool(*asset_a, *asset_b, None);
+				}
+			}
+		}
+		PoolAssetPools::<T>::remove(pool_info.pool_asset);
+		Pools::<T>::remove(pool_id);
+		let _ = TokenRateCaches::<T>::clear_prefix(pool_id, u32::max_value(), None);
+		DynamicFees::<T>::remove(pool_id);
+		let _ = PriceObservations::<T>::clear_prefix(pool_id, T::MaxPriceObservations::get(), None);
+		PriceObservationCursors::<T>::remove(pool_id);
+		BasePools::<T>::remove(pool_id);
+		FrozenPools::<T>::remove(pool_id);
+		PoolDeposits::<T>::remove(pool_id);
+		Ok(())
+	}
+
+	/// Designates `pool_id` as the pool of the pair of assets, or removes the designation.
+	pub fn set_canonical_pool(
+		asset_a: T::AssetId,
+		asset_b: T::AssetId,
+		pool_id: Option<StableAssetPoolId>,
+	) {
+		CanonicalPools::<T>::set(asset_a, asset_b, pool_id);
+		CanonicalPools::<T>::set(asset_b, asset_a, pool_id);
+		Self::deposit_event(Event::CanonicalPoolSet { asset_a, asset_b, pool_id });
+	}
+
+	/// Designates `pool_id` as the pool of each pair of its assets that has none yet.
+	pub fn designate_canonical_pool(pool_id: StableAssetPoolId, assets: &[T::AssetId]) {
+		for (index, asset_a) in assets.iter().enumerate() {
+			for asset_b in assets.iter().skip(index + 1) {
+				if !CanonicalPools::<T>::contains_key(asset_a, asset_b) {
+					Self::set_canonical_pool(*asset_a, *asset_b, Some(pool_id));
+				}
+			}
+		}
+	}
+
+	/// Returns the token rate of the pool token of the base pool of a metapool, in place of its
+	/// cached rate: the invariant `D` of the base pool over its total supply, so that its pool
+	/// token is always valued at the current virtual price of the base pool.
+	pub fn get_base_pool_token_rate(
+		pool_id: StableAssetPoolId,
+		asset_id: T::AssetId,
+	) -> Option<(T::AtLeast64BitUnsigned, T::AtLeast64BitUnsigned)> {
+		let base_pool_info = Pools::<T>::get(BasePools::<T>::get(pool_id)?)?;
+		if base_pool_info.pool_asset != asset_id || base_pool_info.total_supply.is_zero() {
+			return None;
+		}
+		let a = Self::get_a(
+			base_pool_info.a,
+			base_pool_info.a_block,
+			base_pool_info.future_a,
+			base_pool_info.future_a_block,
+		)?;
+		let balances = Self::convert_vec_balance_to_number(base_pool_info.balances);
+		let d = Self::get_d(&balances, a)?;
+		Some((base_pool_info.total_supply.into(), d))
+	}
 }
 
 impl<T: Config> StableAsset for Pallet<T> {
@@ -1593,7 +2017,8 @@ impl<T: Config> StableAsset for Pallet<T> {
 		pool_id: StableAssetPoolId,
 		asset_id: Self::AssetId,
 	) -> Option<(Self::AtLeast64BitUnsigned, Self::AtLeast64BitUnsigned)> {
-		TokenRateCaches::<T>::get(pool_id, asset_id)
+		Self::get_base_pool_token_rate(pool_id, asset_id)
+			.or_else(|| TokenRateCaches::<T>::get(pool_id, asset_id))
 	}
 
 	fn insert_pool(
@@ -1786,6 +2211,15 @@ impl<T: Config> StableAsset for Pallet<T> {
 		let pool_asset_limit = T::PoolAssetLimit::get() as usize;
 		ensure!(assets.len() <= pool_asset_limit, Error::<T>::ArgumentsError);
 		ensure!(assets.len() == precisions.len(), Error::<T>::ArgumentsMismatch);
+		// A pool holding the pool token of another pool is a metapool of it. Metapools of
+		// metapools are not supported, nor are pools holding the pool tokens of several pools.
+		let base_pool_ids: Vec<StableAssetPoolId> =
+			assets.iter().filter_map(PoolAssetPools::<T>::get).collect();
+		ensure!(base_pool_ids.len() <= 1, Error::<T>::ArgumentsError);
+		let base_pool_id = base_pool_ids.first().copied();
+		if let Some(base_pool_id) = base_pool_id {
+			ensure!(!BasePools::<T>::contains_key(base_pool_id), Error::<T>::ArgumentsError);
+		}
 		PoolCount::<T>::try_mutate(|pool_count| -> DispatchResult {
 			let pool_id = *pool_count;
 			let swap_id: T::AccountId = T::PalletId::get().into_sub_account_truncating(pool_id);
@@ -1795,6 +2229,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 				let balances = sp_std::vec![Zero::zero(); assets.len()];
 				frame_system::Pallet::<T>::inc_providers(&swap_id);
 				let current_block = frame_system::Pallet::<T>::block_number();
+				PoolAssetPools::<T>::insert(pool_asset, pool_id);
 				*maybe_pool_info = Some(StableAssetPoolInfo {
 					pool_id,
 					pool_asset,
@@ -1826,6 +2261,10 @@ impl<T: Config> StableAsset for Pallet<T> {
 				a: initial_a,
 				pallet_id: T::PalletId::get().into_account_truncating(),
 			});
+			if let Some(base_pool_id) = base_pool_id {
+				BasePools::<T>::insert(pool_id, base_pool_id);
+				Self::deposit_event(Event::MetaPoolCreated { pool_id, base_pool_id });
+			}
 			Ok(())
 		})
 	}
@@ -1844,6 +2283,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 		amounts: Vec<Self::Balance>,
 		min_mint_amount: Self::Balance,
 	) -> DispatchResult {
+		Self::ensure_pool_not_frozen(pool_id)?;
 		Pools::<T>::try_mutate_exists(pool_id, |maybe_pool_info| -> DispatchResult {
 			let pool_info = maybe_pool_info.as_mut().ok_or(Error::<T>::PoolNotFound)?;
 			Self::collect_yield(pool_id, pool_info)?;
@@ -1871,6 +2311,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 			pool_info.total_supply = total_supply;
 			pool_info.balances = balances;
 			Self::collect_fee(pool_id, pool_info)?;
+			Self::update_price_observations(pool_id, pool_info);
 			Self::deposit_event(Event::LiquidityAdded {
 				minter: who.clone(),
 				pool_id,
@@ -1906,6 +2347,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 		min_dy: Self::Balance,
 		asset_length: u32,
 	) -> sp_std::result::Result<(Self::Balance, Self::Balance), DispatchError> {
+		Self::ensure_pool_not_frozen(pool_id)?;
 		Pools::<T>::try_mutate_exists(
 			pool_id,
 			|maybe_pool_info| -> sp_std::result::Result<(Self::Balance, Self::Balance), DispatchError> {
@@ -1933,6 +2375,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 				// Since the actual output amount is round down, collect fee should update the pool balances and
 				// total supply
 				Self::collect_fee(pool_id, pool_info)?;
+				Self::update_price_observations(pool_id, pool_info);
 				let a: T::AtLeast64BitUnsigned = Self::get_a(
 					pool_info.a,
 					pool_info.a_block,
@@ -2005,6 +2448,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 			// Since the output amounts are round down, collect fee updates pool balances and total
 			// supply.
 			Self::collect_fee(pool_id, pool_info)?;
+			Self::update_price_observations(pool_id, pool_info);
 			let a: T::AtLeast64BitUnsigned = Self::get_a(
 				pool_info.a,
 				pool_info.a_block,
@@ -2080,6 +2524,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 				pool_info.balances = balances;
 				// Since the output amounts are round down, collect fee updates pool balances and total supply.
 				Self::collect_fee(pool_id, pool_info)?;
+				Self::update_price_observations(pool_id, pool_info);
 				let a: T::AtLeast64BitUnsigned = Self::get_a(
 					pool_info.a,
 					pool_info.a_block,
@@ -2153,6 +2598,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 			pool_info.total_supply = total_supply;
 			pool_info.balances = balances;
 			Self::collect_fee(pool_id, pool_info)?;
+			Self::update_price_observations(pool_id, pool_info);
 			let a: T::AtLeast64BitUnsigned = Self::get_a(
 				pool_info.a,
 				pool_info.a_block,
@@ -53,7 +53,10 @@ pub fn update_pallet_id<T: Config>() -> Weight {
 	Weight::from(T::DbWeight::get().reads_writes(count, count))
 }
 
-use frame_support::{pallet_prelude::PhantomData, traits::OnRuntimeUpgrade};
+use frame_support::{
+	pallet_prelude::PhantomData,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
 pub struct StableAssetOnRuntimeUpgrade<T>(PhantomData<T>);
 impl<T: super::Config> OnRuntimeUpgrade for StableAssetOnRuntimeUpgrade<T> {
 	#[cfg(feature = "try-runtime")]
@@ -110,3 +113,44 @@ impl<T: super::Config> OnRuntimeUpgrade for StableAssetOnRuntimeUpgrade<T> {
 		Ok(())
 	}
 }
+
+const LOG_TARGET: &str = "stable-asset::migration";
+
+/// Fills `PoolAssetPools` for the existing pools, and designates each of them as the pool of
+/// the pairs of its assets, the lowest pool id first, as they were all created by governance.
+pub struct InitPoolIndexes<T>(PhantomData<T>);
+impl<T: super::Config> OnRuntimeUpgrade for InitPoolIndexes<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+		if onchain_version >= 1 {
+			return Weight::zero();
+		}
+		log::info!(target: LOG_TARGET, "Start to index the stable-asset pools...");
+
+		let pool_count: u32 = PoolCount::<T>::get();
+		let mut writes: u64 = 1;
+		for pool_id in 0..pool_count {
+			if let Some(pool_info) = Pools::<T>::get(pool_id) {
+				PoolAssetPools::<T>::insert(pool_info.pool_asset, pool_id);
+				Pallet::<T>::designate_canonical_pool(pool_id, &pool_info.assets);
+				let pairs = pool_info.assets.len() * pool_info.assets.len().saturating_sub(1);
+				writes = writes.saturating_add(pairs as u64 + 1);
+			}
+		}
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(u64::from(pool_count) + 1, writes)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_: sp_std::prelude::Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+		for (pool_id, pool_info) in Pools::<T>::iter() {
+			frame_support::ensure!(
+				PoolAssetPools::<T>::get(pool_info.pool_asset) == Some(pool_id),
+				"Pool asset not indexed"
+			);
+		}
+		Ok(())
+	}
+}
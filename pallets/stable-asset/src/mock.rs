@@ -285,6 +285,7 @@ impl stable_asset::Config for Test {
 	type WeightInfo = ();
 	type ListingOrigin = EnsureStableAsset;
 	type EnsurePoolAssetId = EnsurePoolAssetId;
+	type MaxPriceObservations = ConstU32<1200>;
 }
 
 // Build genesis storage according to the mock runtime.
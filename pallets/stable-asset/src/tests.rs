@@ -22,7 +22,10 @@ use crate::{
 };
 use frame_support::{assert_noop, assert_ok};
 use orml_traits::MultiCurrency;
-use sp_runtime::DispatchError;
+use sp_runtime::{
+	traits::{CheckedDiv, One, Saturating},
+	DispatchError, FixedPointNumber, FixedU128,
+};
 
 pub const BALANCE_OFF: u128 = 1;
 
@@ -1480,3 +1483,82 @@ fn swap_should_work_with_unbalance_coin1() {
 		));
 	});
 }
+
+#[test]
+fn get_twap_successful() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		System::set_block_number(2);
+
+		assert_ok!(StableAsset::mint(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![10000000u128, 10000000u128],
+			0
+		));
+		assert_eq!(StableAsset::price_observation_cursors(0), Some((0, 1)));
+		let balanced_prices = StableAsset::price_observations(0, 0).unwrap().prices;
+		assert_eq!(balanced_prices[0], FixedU128::one());
+
+		System::set_block_number(12);
+		assert_eq!(StableAsset::get_twap(0, 10), Some(balanced_prices.clone()));
+
+		// The last swap of the block sets the prices of the block
+		assert_ok!(StableAsset::swap(RuntimeOrigin::signed(1), 0, 0, 1, 5000000u128, 0, 2));
+		assert_ok!(StableAsset::swap(RuntimeOrigin::signed(1), 0, 0, 1, 1000000u128, 0, 2));
+		assert_eq!(StableAsset::price_observation_cursors(0), Some((1, 2)));
+		let prices = StableAsset::price_observations(0, 1).unwrap().prices;
+		assert!(prices[1] > balanced_prices[1]);
+		// and is left out of the averages until the next block
+		assert_eq!(StableAsset::get_twap(0, 10), Some(balanced_prices.clone()));
+
+		System::set_block_number(22);
+		let ten = FixedU128::saturating_from_integer(10);
+		assert_eq!(
+			StableAsset::get_twap(0, 20),
+			Some(vec![
+				FixedU128::one(),
+				balanced_prices[1]
+					.saturating_mul(ten)
+					.saturating_add(prices[1].saturating_mul(ten))
+					.checked_div(&FixedU128::saturating_from_integer(20))
+					.unwrap(),
+			])
+		);
+		assert_eq!(StableAsset::get_twap(0, 10), Some(prices));
+		// The observations don't reach back to block 1
+		assert_eq!(StableAsset::get_twap(0, 21), None);
+		assert_eq!(StableAsset::get_twap(0, 0), None);
+	});
+}
+
+#[test]
+fn price_observations_should_wrap_around() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		System::set_block_number(2);
+		assert_ok!(StableAsset::mint(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![10000000u128, 10000000u128],
+			0
+		));
+		let pool_info = StableAsset::pools(0).unwrap();
+		let prices = StableAsset::price_observations(0, 0).unwrap().prices;
+
+		// One observation every other block, past the end of the ring
+		for block in 1..=1202u64 {
+			System::set_block_number(2 + 2 * block);
+			StableAsset::update_price_observations(0, &pool_info);
+		}
+		assert_eq!(StableAsset::price_observation_cursors(0), Some((2, 1200)));
+		assert_eq!(StableAsset::price_observations(0, 2).unwrap().block, 2406);
+		assert_eq!(StableAsset::price_observations(0, 3).unwrap().block, 8);
+
+		System::set_block_number(2407);
+		assert_eq!(StableAsset::get_twap(0, 2399), Some(prices.clone()));
+		assert_eq!(StableAsset::get_twap(0, 2000), Some(prices));
+		// The observations before block 8 were overwritten
+		assert_eq!(StableAsset::get_twap(0, 2400), None);
+	});
+}
@@ -73,39 +73,39 @@ impl WeightInfo for () {
 	fn mint(u: u32) -> Weight {
 		Weight::from_parts(85_694_000 as u64, 0)
 			.saturating_add(Weight::from_parts(46_172_000 as u64, 0).saturating_mul(u as u64))
-			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
 			.saturating_add(RocksDbWeight::get().reads((3 as u64).saturating_mul(u as u64)))
-			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
 			.saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(u as u64)))
 	}
 	fn swap(u: u32) -> Weight {
 		Weight::from_parts(124_402_000 as u64, 0)
 			.saturating_add(Weight::from_parts(8_138_000 as u64, 0).saturating_mul(u as u64))
-			.saturating_add(RocksDbWeight::get().reads(7 as u64))
+			.saturating_add(RocksDbWeight::get().reads(9 as u64))
 			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(u as u64)))
-			.saturating_add(RocksDbWeight::get().writes(9 as u64))
+			.saturating_add(RocksDbWeight::get().writes(11 as u64))
 	}
 	fn redeem_proportion(u: u32) -> Weight {
 		Weight::from_parts(107_494_000 as u64, 0)
 			.saturating_add(Weight::from_parts(43_376_000 as u64, 0).saturating_mul(u as u64))
-			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
 			.saturating_add(RocksDbWeight::get().reads((3 as u64).saturating_mul(u as u64)))
-			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
 			.saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(u as u64)))
 	}
 	fn redeem_single(u: u32) -> Weight {
 		Weight::from_parts(114_847_000 as u64, 0)
 			.saturating_add(Weight::from_parts(14_613_000 as u64, 0).saturating_mul(u as u64))
-			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
 			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(u as u64)))
-			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+			.saturating_add(RocksDbWeight::get().writes(9 as u64))
 	}
 	fn redeem_multi(u: u32) -> Weight {
 		Weight::from_parts(86_888_000 as u64, 0)
 			.saturating_add(Weight::from_parts(43_556_000 as u64, 0).saturating_mul(u as u64))
-			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
 			.saturating_add(RocksDbWeight::get().reads((3 as u64).saturating_mul(u as u64)))
-			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
 			.saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(u as u64)))
 	}
 }
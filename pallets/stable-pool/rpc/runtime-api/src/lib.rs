@@ -18,7 +18,9 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use bifrost_primitives::Balance;
+use bifrost_primitives::{
+	AccountId, Balance, BlockNumber, CurrencyId, Price, RedeemQuote, StablePoolInfo,
+};
 use sp_api::decl_runtime_apis;
 use sp_std::vec::Vec;
 
@@ -36,5 +38,55 @@ decl_runtime_apis! {
 			pool_id: u32,
 			amounts: Vec<Balance>,
 		) -> Balance;
+
+		/// Returns the swap output and the effective swap fee, in the pool fee precision.
+		fn get_swap_quote(
+			pool_id: u32,
+			currency_id_in: u32,
+			currency_id_out: u32,
+			amount: Balance,
+		) -> (Balance, Balance);
+
+		/// Returns the pool tokens minted and the effective mint fee, in the pool fee precision.
+		fn add_liquidity_quote(
+			pool_id: u32,
+			amounts: Vec<Balance>,
+		) -> (Balance, Balance);
+
+		fn get_twap(
+			pool_id: u32,
+			currency_id_in: u32,
+			currency_id_out: u32,
+			window: u32,
+		) -> Option<Price>;
+
+		/// Returns the output of a swap between the assets of a metapool and of its base pool.
+		fn get_underlying_swap_output(
+			pool_id: u32,
+			currency_id_in: CurrencyId,
+			currency_id_out: CurrencyId,
+			amount: Balance,
+		) -> Balance;
+
+		fn get_pool_info(pool_id: u32) -> Option<StablePoolInfo<AccountId, Balance, BlockNumber>>;
+
+		/// Returns the value of one pool token in the scaled balances of the pool.
+		fn get_virtual_price(pool_id: u32) -> Option<Price>;
+
+		/// Returns the amount of each pool asset `amount` pool tokens stand for.
+		fn get_lp_token_value(pool_id: u32, amount: Balance) -> Vec<(CurrencyId, Balance)>;
+
+		fn quote_redeem_proportion(pool_id: u32, amount: Balance) -> Option<RedeemQuote<Balance>>;
+
+		fn quote_redeem_single(
+			pool_id: u32,
+			amount: Balance,
+			i: u32,
+		) -> Option<RedeemQuote<Balance>>;
+
+		fn quote_redeem_multi(
+			pool_id: u32,
+			amounts: Vec<Balance>,
+		) -> Option<RedeemQuote<Balance>>;
 	}
 }
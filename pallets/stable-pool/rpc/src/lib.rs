@@ -18,7 +18,9 @@
 
 use std::{marker::PhantomData, sync::Arc};
 
-use bifrost_primitives::Balance;
+use bifrost_primitives::{
+	AccountId, Balance, BlockNumber, CurrencyId, Price, RedeemQuote, StablePoolInfo,
+};
 pub use bifrost_stable_pool_rpc_runtime_api::{self as runtime_api, StablePoolRuntimeApi};
 use jsonrpsee::{
 	core::{async_trait, RpcResult},
@@ -50,6 +52,117 @@ pub trait StablePoolRpcApi<BlockHash> {
 		amounts: Vec<Balance>,
 		at: Option<BlockHash>,
 	) -> RpcResult<NumberOrHex>;
+
+	/// rpc method for getting stable_pool swap output amount and effective swap fee
+	#[method(name = "stable_pool_getSwapQuote")]
+	fn get_swap_quote(
+		&self,
+		pool_id: u32,
+		currency_id_in: u32,
+		currency_id_out: u32,
+		amount: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<(NumberOrHex, NumberOrHex)>;
+
+	/// rpc method for getting stable_pool add liquidity amount and effective mint fee
+	#[method(name = "stable_pool_addLiquidityQuote")]
+	fn add_liquidity_quote(
+		&self,
+		pool_id: u32,
+		amounts: Vec<Balance>,
+		at: Option<BlockHash>,
+	) -> RpcResult<(NumberOrHex, NumberOrHex)>;
+
+	/// rpc method for getting the time-weighted average price of `currency_id_in` in
+	/// `currency_id_out` over the last `window` blocks
+	#[method(name = "stable_pool_getTwap")]
+	fn get_twap(
+		&self,
+		pool_id: u32,
+		currency_id_in: u32,
+		currency_id_out: u32,
+		window: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Price>>;
+
+	/// rpc method for getting the output amount of a swap between the assets of a metapool and
+	/// of its base pool
+	#[method(name = "stable_pool_getUnderlyingSwapOutputAmount")]
+	fn get_underlying_swap_output_amount(
+		&self,
+		pool_id: u32,
+		currency_id_in: CurrencyId,
+		currency_id_out: CurrencyId,
+		amount: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<NumberOrHex>;
+
+	/// rpc method for getting a pool with its balances, fees and A ramping
+	#[method(name = "stable_pool_getPoolInfo")]
+	fn get_pool_info(
+		&self,
+		pool_id: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<StablePoolInfo<AccountId, NumberOrHex, BlockNumber>>>;
+
+	/// rpc method for getting the virtual price of a pool
+	#[method(name = "stable_pool_getVirtualPrice")]
+	fn get_virtual_price(&self, pool_id: u32, at: Option<BlockHash>) -> RpcResult<Option<Price>>;
+
+	/// rpc method for getting the amount of each pool asset `amount` pool tokens stand for
+	#[method(name = "stable_pool_getLpTokenValue")]
+	fn get_lp_token_value(
+		&self,
+		pool_id: u32,
+		amount: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(CurrencyId, NumberOrHex)>>;
+
+	/// rpc method for quoting redeem_proportion
+	#[method(name = "stable_pool_quoteRedeemProportion")]
+	fn quote_redeem_proportion(
+		&self,
+		pool_id: u32,
+		amount: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<RedeemQuote<NumberOrHex>>>;
+
+	/// rpc method for quoting redeem_single
+	#[method(name = "stable_pool_quoteRedeemSingle")]
+	fn quote_redeem_single(
+		&self,
+		pool_id: u32,
+		amount: Balance,
+		i: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<RedeemQuote<NumberOrHex>>>;
+
+	/// rpc method for quoting redeem_multi
+	#[method(name = "stable_pool_quoteRedeemMulti")]
+	fn quote_redeem_multi(
+		&self,
+		pool_id: u32,
+		amounts: Vec<Balance>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<RedeemQuote<NumberOrHex>>>;
+}
+
+fn to_hex(amount: Balance) -> NumberOrHex {
+	NumberOrHex::Hex(amount.into())
+}
+
+fn redeem_quote_to_hex(quote: RedeemQuote<Balance>) -> RedeemQuote<NumberOrHex> {
+	RedeemQuote {
+		amounts: quote.amounts.into_iter().map(to_hex).collect(),
+		input_amount: to_hex(quote.input_amount),
+		burn_amount: to_hex(quote.burn_amount),
+		fee_amount: to_hex(quote.fee_amount),
+		fee: to_hex(quote.fee),
+	}
+}
+
+fn runtime_error(message: &'static str, e: impl std::fmt::Debug) -> ErrorObject<'static> {
+	ErrorObject::owned(ErrorCode::InternalError.code(), message, Some(format!("{:?}", e)))
 }
 
 #[derive(Clone, Debug)]
@@ -115,4 +228,223 @@ where
 			)),
 		}
 	}
+	fn get_swap_quote(
+		&self,
+		pool_id: u32,
+		currency_id_in: u32,
+		currency_id_out: u32,
+		amount: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<(NumberOrHex, NumberOrHex)> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let rs: Result<(Balance, Balance), _> =
+			lm_rpc_api.get_swap_quote(at, pool_id, currency_id_in, currency_id_out, amount);
+
+		match rs {
+			Ok((amount, fee)) =>
+				Ok((NumberOrHex::Hex(amount.into()), NumberOrHex::Hex(fee.into()))),
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get stable_pool swap quote.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
+
+	fn add_liquidity_quote(
+		&self,
+		pool_id: u32,
+		amounts: Vec<Balance>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<(NumberOrHex, NumberOrHex)> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let rs: Result<(Balance, Balance), _> =
+			lm_rpc_api.add_liquidity_quote(at, pool_id, amounts);
+
+		match rs {
+			Ok((amount, fee)) =>
+				Ok((NumberOrHex::Hex(amount.into()), NumberOrHex::Hex(fee.into()))),
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get stable_pool add liquidity quote.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
+
+	fn get_twap(
+		&self,
+		pool_id: u32,
+		currency_id_in: u32,
+		currency_id_out: u32,
+		window: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Price>> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		lm_rpc_api
+			.get_twap(at, pool_id, currency_id_in, currency_id_out, window)
+			.map_err(|e| {
+				ErrorObject::owned(
+					ErrorCode::InternalError.code(),
+					"Failed to get stable_pool twap.",
+					Some(format!("{:?}", e)),
+				)
+			})
+	}
+
+	fn get_underlying_swap_output_amount(
+		&self,
+		pool_id: u32,
+		currency_id_in: CurrencyId,
+		currency_id_out: CurrencyId,
+		amount: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let rs: Result<Balance, _> = lm_rpc_api.get_underlying_swap_output(
+			at,
+			pool_id,
+			currency_id_in,
+			currency_id_out,
+			amount,
+		);
+
+		match rs {
+			Ok(amount) => Ok(NumberOrHex::Hex(amount.into())),
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get stable_pool underlying swap output amount.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
+
+	fn get_pool_info(
+		&self,
+		pool_id: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<StablePoolInfo<AccountId, NumberOrHex, BlockNumber>>> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let pool_info = lm_rpc_api
+			.get_pool_info(at, pool_id)
+			.map_err(|e| runtime_error("Failed to get stable_pool pool info.", e))?;
+
+		Ok(pool_info.map(|info| StablePoolInfo {
+			pool_id: info.pool_id,
+			pool_asset: info.pool_asset,
+			assets: info.assets,
+			precisions: info.precisions.into_iter().map(to_hex).collect(),
+			balances: info.balances.into_iter().map(to_hex).collect(),
+			reserves: info.reserves.into_iter().map(to_hex).collect(),
+			total_supply: to_hex(info.total_supply),
+			precision: to_hex(info.precision),
+			mint_fee: to_hex(info.mint_fee),
+			swap_fee: to_hex(info.swap_fee),
+			redeem_fee: to_hex(info.redeem_fee),
+			dynamic_fee: info
+				.dynamic_fee
+				.map(|(imbalance_fee, max_fee)| (to_hex(imbalance_fee), to_hex(max_fee))),
+			current_a: to_hex(info.current_a),
+			a: to_hex(info.a),
+			a_block: info.a_block,
+			future_a: to_hex(info.future_a),
+			future_a_block: info.future_a_block,
+			account_id: info.account_id,
+			fee_recipient: info.fee_recipient,
+			yield_recipient: info.yield_recipient,
+			base_pool_id: info.base_pool_id,
+			frozen: info.frozen,
+		}))
+	}
+
+	fn get_virtual_price(
+		&self,
+		pool_id: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Price>> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		lm_rpc_api
+			.get_virtual_price(at, pool_id)
+			.map_err(|e| runtime_error("Failed to get stable_pool virtual price.", e))
+	}
+
+	fn get_lp_token_value(
+		&self,
+		pool_id: u32,
+		amount: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(CurrencyId, NumberOrHex)>> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let value = lm_rpc_api
+			.get_lp_token_value(at, pool_id, amount)
+			.map_err(|e| runtime_error("Failed to get stable_pool lp token value.", e))?;
+
+		Ok(value
+			.into_iter()
+			.map(|(currency_id, amount)| (currency_id, to_hex(amount)))
+			.collect())
+	}
+
+	fn quote_redeem_proportion(
+		&self,
+		pool_id: u32,
+		amount: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<RedeemQuote<NumberOrHex>>> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let quote = lm_rpc_api
+			.quote_redeem_proportion(at, pool_id, amount)
+			.map_err(|e| runtime_error("Failed to quote stable_pool redeem proportion.", e))?;
+
+		Ok(quote.map(redeem_quote_to_hex))
+	}
+
+	fn quote_redeem_single(
+		&self,
+		pool_id: u32,
+		amount: Balance,
+		i: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<RedeemQuote<NumberOrHex>>> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let quote = lm_rpc_api
+			.quote_redeem_single(at, pool_id, amount, i)
+			.map_err(|e| runtime_error("Failed to quote stable_pool redeem single.", e))?;
+
+		Ok(quote.map(redeem_quote_to_hex))
+	}
+
+	fn quote_redeem_multi(
+		&self,
+		pool_id: u32,
+		amounts: Vec<Balance>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<RedeemQuote<NumberOrHex>>> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let quote = lm_rpc_api
+			.quote_redeem_multi(at, pool_id, amounts)
+			.map_err(|e| runtime_error("Failed to quote stable_pool redeem multi.", e))?;
+
+		Ok(quote.map(redeem_quote_to_hex))
+	}
 }
@@ -288,5 +288,170 @@ benchmarks! {
 			1000000000000u128.into()));
 	}: _(RawOrigin::Root, 0, Some(test_account.clone()), Some(test_account))
 
+	config_dynamic_fee {
+		let fee_account: T::AccountId = account("seed",1,1);
+		let coin0 = BNC;
+		let coin1 = KSM;
+		assert_ok!(
+			StablePool::<T>::create_pool(
+			RawOrigin::Root.into(),
+			vec![coin0.into(), coin1.into()],
+			vec![1u128.into(), 1u128.into()],
+			10000000u128.into(),
+			20000000u128.into(),
+			50000000u128.into(),
+			10000u128.into(),
+			fee_account.clone(),
+			fee_account.clone(),
+			1000000000000000000u128.into())
+		);
+	}: _(RawOrigin::Root, 0, 100000000u128.into(), 200000000u128.into())
+
+	remove_dynamic_fee {
+		let fee_account: T::AccountId = account("seed",1,1);
+		let coin0 = BNC;
+		let coin1 = KSM;
+		assert_ok!(
+			StablePool::<T>::create_pool(
+			RawOrigin::Root.into(),
+			vec![coin0.into(), coin1.into()],
+			vec![1u128.into(), 1u128.into()],
+			10000000u128.into(),
+			20000000u128.into(),
+			50000000u128.into(),
+			10000u128.into(),
+			fee_account.clone(),
+			fee_account.clone(),
+			1000000000000000000u128.into())
+		);
+		assert_ok!(StablePool::<T>::config_dynamic_fee(RawOrigin::Root.into(), 0, 100000000u128.into(), 200000000u128.into()));
+	}: _(RawOrigin::Root, 0)
+
+	swap_underlying {
+		let fee_account: T::AccountId = account("seed",1,1);
+		let base_pool_token = CurrencyId::BLP(0);
+		for coin in [BNC, KSM, DOT] {
+			T::MultiCurrency::deposit(
+				coin.into(),
+				&fee_account,
+				<T as bifrost_stable_asset::Config>::Balance::from(1000_000_000_000u128.into())
+			)?;
+		}
+		let amounts = vec![<T as bifrost_stable_asset::Config>::Balance::from(100_000_000_000u128.into()), <T as bifrost_stable_asset::Config>::Balance::from(100_000_000_000u128.into())];
+		assert_ok!(StablePool::<T>::create_pool(
+		RawOrigin::Root.into(),
+		vec![BNC.into(), KSM.into()],
+		vec![1u128.into(), 1u128.into()],
+		10000000u128.into(),
+		20000000u128.into(),
+		50000000u128.into(),
+		10000u128.into(),
+		fee_account.clone(),
+		fee_account.clone(),
+		1000000000000000000u128.into()));
+		assert_ok!(StablePool::<T>::edit_token_rate(RawOrigin::Root.into(), 0, vec![(BNC.into(), (1u128.into(), 1u128.into())), (KSM.into(), (1u128.into(), 1u128.into()))]));
+		assert_ok!(StablePool::<T>::add_liquidity(RawOrigin::Signed(fee_account.clone()).into(), 0, amounts.clone(), <T as bifrost_stable_asset::Config>::Balance::zero()));
+		assert_ok!(StablePool::<T>::create_pool(
+		RawOrigin::Root.into(),
+		vec![DOT.into(), base_pool_token.into()],
+		vec![1u128.into(), 1u128.into()],
+		10000000u128.into(),
+		20000000u128.into(),
+		50000000u128.into(),
+		10000u128.into(),
+		fee_account.clone(),
+		fee_account.clone(),
+		1000000000000000000u128.into()));
+		assert_ok!(StablePool::<T>::edit_token_rate(RawOrigin::Root.into(), 1, vec![(DOT.into(), (1u128.into(), 1u128.into()))]));
+		assert_ok!(StablePool::<T>::add_liquidity(RawOrigin::Signed(fee_account.clone()).into(), 1, amounts, <T as bifrost_stable_asset::Config>::Balance::zero()));
+	}: _(RawOrigin::Signed(fee_account), 1, DOT.into(), BNC.into(), <T as bifrost_stable_asset::Config>::Balance::from(50_000_000_000u128.into()), <T as bifrost_stable_asset::Config>::Balance::zero())
+
+	reap_pool {
+		let fee_account: T::AccountId = account("seed",1,1);
+		let caller: T::AccountId = whitelisted_caller();
+		assert_ok!(
+			StablePool::<T>::create_pool(
+			RawOrigin::Root.into(),
+			vec![BNC.into(), KSM.into()],
+			vec![1u128.into(), 1u128.into()],
+			10000000u128.into(),
+			20000000u128.into(),
+			50000000u128.into(),
+			10000u128.into(),
+			fee_account.clone(),
+			fee_account.clone(),
+			1000000000000000000u128.into())
+		);
+		assert_ok!(StablePool::<T>::edit_token_rate(RawOrigin::Root.into(), 0, vec![(BNC.into(), (1u128.into(), 1u128.into())), (KSM.into(), (1u128.into(), 1u128.into()))]));
+		PoolDeposits::<T>::insert(0, PoolDeposit {
+			depositor: caller.clone(),
+			amount: <T as bifrost_stable_asset::Config>::Balance::zero(),
+			created_at: frame_system::Pallet::<T>::block_number(),
+		});
+	}: _(RawOrigin::Signed(caller), 0)
+
+	set_parameter_bounds {
+		let bounds = PoolParameterBounds {
+			min_a: 100u128.into(),
+			max_a: 1000000u128.into(),
+			max_fee: 100000000u128.into(),
+			max_asset_precision: 1000000u128.into(),
+			min_precision: 1000000000000u128.into(),
+			max_precision: 1000000000000000000u128.into(),
+		};
+	}: _(RawOrigin::Root, bounds)
+
+	freeze_pool {
+		let fee_account: T::AccountId = account("seed",1,1);
+		assert_ok!(
+			StablePool::<T>::create_pool(
+			RawOrigin::Root.into(),
+			vec![BNC.into(), KSM.into()],
+			vec![1u128.into(), 1u128.into()],
+			10000000u128.into(),
+			20000000u128.into(),
+			50000000u128.into(),
+			10000u128.into(),
+			fee_account.clone(),
+			fee_account.clone(),
+			1000000000000000000u128.into())
+		);
+	}: _(RawOrigin::Root, 0)
+
+	unfreeze_pool {
+		let fee_account: T::AccountId = account("seed",1,1);
+		assert_ok!(
+			StablePool::<T>::create_pool(
+			RawOrigin::Root.into(),
+			vec![BNC.into(), KSM.into()],
+			vec![1u128.into(), 1u128.into()],
+			10000000u128.into(),
+			20000000u128.into(),
+			50000000u128.into(),
+			10000u128.into(),
+			fee_account.clone(),
+			fee_account.clone(),
+			1000000000000000000u128.into())
+		);
+		assert_ok!(StablePool::<T>::freeze_pool(RawOrigin::Root.into(), 0));
+	}: _(RawOrigin::Root, 0)
+
+	set_canonical_pool {
+		let fee_account: T::AccountId = account("seed",1,1);
+		assert_ok!(
+			StablePool::<T>::create_pool(
+			RawOrigin::Root.into(),
+			vec![BNC.into(), KSM.into()],
+			vec![1u128.into(), 1u128.into()],
+			10000000u128.into(),
+			20000000u128.into(),
+			50000000u128.into(),
+			10000u128.into(),
+			fee_account.clone(),
+			fee_account.clone(),
+			1000000000000000000u128.into())
+		);
+	}: _(RawOrigin::Root, BNC.into(), KSM.into(), Some(0))
+
 	impl_benchmark_test_suite!(StablePool, crate::mock::ExtBuilder::default().build(), crate::mock::Test);
 }
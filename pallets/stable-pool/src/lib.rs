@@ -31,20 +31,24 @@ pub use weights::*;
 pub mod traits;
 
 use bifrost_primitives::{
-	CurrencyId, CurrencyIdConversion, CurrencyIdExt, CurrencyIdRegister, TimeUnit,
-	VtokenMintingOperator,
+	CurrencyId, CurrencyIdConversion, CurrencyIdExt, CurrencyIdRegister, RedeemQuote,
+	StablePoolInfo, TimeStampedPrice, TimeUnit, VtokenMintingOperator,
 };
 pub use bifrost_stable_asset::{
-	MintResult, PoolCount, PoolTokenIndex, Pools, RedeemMultiResult, RedeemProportionResult,
-	RedeemSingleResult, StableAsset, StableAssetPoolId, StableAssetPoolInfo, SwapResult,
-	TokenRateHardcap,
+	BasePools, CanonicalPools, DynamicFeeConfig, DynamicFees, FrozenPools, MintResult,
+	ParameterBounds, PoolCount, PoolDeposit, PoolDeposits, PoolParameterBounds, PoolTokenIndex,
+	Pools, RedeemMultiResult, RedeemProportionResult, RedeemSingleResult, StableAsset,
+	StableAssetPoolId, StableAssetPoolInfo, SwapResult, TokenRateHardcap,
 };
 use frame_support::{self, pallet_prelude::*, sp_runtime::traits::Zero, transactional};
 use frame_system::pallet_prelude::*;
-use orml_traits::MultiCurrency;
+use orml_traits::{DataProvider, MultiCurrency, MultiReservableCurrency};
 use sp_core::U256;
-use sp_runtime::{Permill, SaturatedConversion};
-use sp_std::prelude::*;
+use sp_runtime::{
+	traits::{CheckedDiv, CheckedMul, One},
+	FixedPointNumber, FixedU128, Perbill, Permill, SaturatedConversion, Saturating,
+};
+use sp_std::{marker::PhantomData, prelude::*};
 
 #[allow(type_alias_bounds)]
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
@@ -54,6 +58,15 @@ pub type AssetIdOf<T> = <T as Config>::CurrencyId;
 
 #[allow(type_alias_bounds)]
 pub type AtLeast64BitUnsignedOf<T> = <T as bifrost_stable_asset::Config>::AtLeast64BitUnsigned;
+
+#[allow(type_alias_bounds)]
+pub type StableAssetPoolInfoOf<T: Config> = StableAssetPoolInfo<
+	AssetIdOf<T>,
+	AtLeast64BitUnsignedOf<T>,
+	<T as bifrost_stable_asset::Config>::Balance,
+	AccountIdOf<T>,
+	BlockNumberFor<T>,
+>;
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -70,11 +83,8 @@ pub mod pallet {
 
 		type ControlOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
-		type MultiCurrency: MultiCurrency<
-			AccountIdOf<Self>,
-			CurrencyId = AssetIdOf<Self>,
-			Balance = Self::Balance,
-		>;
+		type MultiCurrency: MultiCurrency<AccountIdOf<Self>, CurrencyId = AssetIdOf<Self>, Balance = Self::Balance>
+			+ MultiReservableCurrency<AccountIdOf<Self>>;
 
 		type CurrencyId: Parameter
 			+ Ord
@@ -102,6 +112,19 @@ pub mod pallet {
 		type CurrencyIdConversion: CurrencyIdConversion<AssetIdOf<Self>>;
 
 		type CurrencyIdRegister: CurrencyIdRegister<AssetIdOf<Self>>;
+
+		/// The currency the deposit for creating a pool is reserved in.
+		#[pallet::constant]
+		type DepositCurrencyId: Get<AssetIdOf<Self>>;
+
+		/// The deposit reserved for creating a pool without governance.
+		#[pallet::constant]
+		type PoolDeposit: Get<Self::Balance>;
+
+		/// The blocks after its creation before an empty pool can be reaped by anyone other
+		/// than its creator.
+		#[pallet::constant]
+		type PoolReapDelay: Get<BlockNumberFor<Self>>;
 	}
 
 	#[pallet::error]
@@ -111,10 +134,23 @@ pub mod pallet {
 		CantMint,
 		RedeemOverMax,
 		TokenRateNotSet,
+		NotMetaPool,
+		ParameterBoundsNotSet,
+		ParameterOutOfBounds,
+		CurrencyNotRegistered,
+		NoPoolDeposit,
+		PoolNotEmpty,
+		ReapTooEarly,
+		PoolInUse,
+		NotPoolAssets,
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
+		/// Creates a pool. Anyone may create a pool of registered assets by reserving
+		/// `PoolDeposit`, with parameters within the `ParameterBounds` set by governance, and with
+		/// the token rates of its assets set from vToken minting. `ControlOrigin` creates pools
+		/// without either.
 		#[pallet::call_index(0)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::create_pool())]
 		pub fn create_pool(
@@ -129,7 +165,19 @@ pub mod pallet {
 			yield_recipient: AccountIdOf<T>,
 			precision: AtLeast64BitUnsignedOf<T>,
 		) -> DispatchResult {
-			T::ControlOrigin::ensure_origin(origin)?;
+			let depositor = match T::ControlOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+			if depositor.is_some() {
+				Self::ensure_within_bounds(
+					&assets,
+					&precisions,
+					&[mint_fee, swap_fee, redeem_fee],
+					initial_a,
+					precision,
+				)?;
+			}
 
 			let pool_id = PoolCount::<T>::get();
 			T::CurrencyIdRegister::register_blp_metadata(
@@ -142,7 +190,7 @@ pub mod pallet {
 			)?;
 			T::StableAsset::create_pool(
 				CurrencyId::BLP(pool_id).into(),
-				assets,
+				assets.clone(),
 				precisions,
 				mint_fee,
 				swap_fee,
@@ -151,7 +199,34 @@ pub mod pallet {
 				fee_recipient,
 				yield_recipient,
 				precision,
-			)
+			)?;
+
+			if let Some(depositor) = depositor {
+				let amount = T::PoolDeposit::get();
+				T::MultiCurrency::reserve(T::DepositCurrencyId::get(), &depositor, amount)?;
+				PoolDeposits::<T>::insert(
+					pool_id,
+					PoolDeposit {
+						depositor: depositor.clone(),
+						amount,
+						created_at: frame_system::Pallet::<T>::block_number(),
+					},
+				);
+				bifrost_stable_asset::Pallet::<T>::set_token_rate(
+					pool_id,
+					Self::get_initial_token_rates(&assets),
+				)?;
+				bifrost_stable_asset::Pallet::<T>::deposit_event(
+					bifrost_stable_asset::Event::<T>::PoolDepositReserved {
+						pool_id,
+						depositor,
+						amount,
+					},
+				);
+			} else {
+				bifrost_stable_asset::Pallet::<T>::designate_canonical_pool(pool_id, &assets);
+			}
+			Ok(())
 		}
 
 		#[pallet::call_index(1)]
@@ -353,10 +428,273 @@ pub mod pallet {
 			);
 			Ok(())
 		}
+
+		#[pallet::call_index(12)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::config_dynamic_fee())]
+		pub fn config_dynamic_fee(
+			origin: OriginFor<T>,
+			pool_id: StableAssetPoolId,
+			imbalance_fee: T::AtLeast64BitUnsigned,
+			max_fee: T::AtLeast64BitUnsigned,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+			ensure!(
+				imbalance_fee < T::FeePrecision::get() && max_fee < T::FeePrecision::get(),
+				bifrost_stable_asset::Error::<T>::ArgumentsError
+			);
+			let pool_info =
+				Pools::<T>::get(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+			// A maximum below the flat fees would never let the dynamic fee apply.
+			ensure!(
+				max_fee >= pool_info.mint_fee &&
+					max_fee >= pool_info.swap_fee &&
+					max_fee >= pool_info.redeem_fee,
+				bifrost_stable_asset::Error::<T>::ArgumentsError
+			);
+
+			let config = DynamicFeeConfig { imbalance_fee, max_fee };
+			DynamicFees::<T>::insert(pool_id, config);
+
+			bifrost_stable_asset::Pallet::<T>::deposit_event(
+				bifrost_stable_asset::Event::<T>::DynamicFeeConfigured { pool_id, config },
+			);
+			Ok(())
+		}
+
+		#[pallet::call_index(13)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::remove_dynamic_fee())]
+		pub fn remove_dynamic_fee(
+			origin: OriginFor<T>,
+			pool_id: StableAssetPoolId,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+			ensure!(
+				DynamicFees::<T>::take(pool_id).is_some(),
+				bifrost_stable_asset::Error::<T>::DynamicFeeNotConfigured
+			);
+
+			bifrost_stable_asset::Pallet::<T>::deposit_event(
+				bifrost_stable_asset::Event::<T>::DynamicFeeRemoved { pool_id },
+			);
+			Ok(())
+		}
+
+		/// Swaps between the assets of a metapool and of its base pool, through the pool token
+		/// of the base pool.
+		#[pallet::call_index(14)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::swap_underlying())]
+		pub fn swap_underlying(
+			origin: OriginFor<T>,
+			pool_id: StableAssetPoolId,
+			currency_id_in: AssetIdOf<T>,
+			currency_id_out: AssetIdOf<T>,
+			dx: T::Balance,
+			min_dy: T::Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::on_swap_underlying(&who, pool_id, currency_id_in, currency_id_out, dx, min_dy)
+		}
+
+		/// Removes an empty pool created without governance, and releases its deposit. Its
+		/// creator may reap it at any time, anyone else `PoolReapDelay` blocks after its creation.
+		#[pallet::call_index(15)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::reap_pool().saturating_add(
+			T::DbWeight::get().writes(T::MaxPriceObservations::get().into())
+		))]
+		pub fn reap_pool(origin: OriginFor<T>, pool_id: StableAssetPoolId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let deposit = PoolDeposits::<T>::get(pool_id).ok_or(Error::<T>::NoPoolDeposit)?;
+			let pool_info = T::StableAsset::pool(pool_id)
+				.ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+			ensure!(pool_info.total_supply.is_zero(), Error::<T>::PoolNotEmpty);
+			ensure!(
+				who == deposit.depositor ||
+					frame_system::Pallet::<T>::block_number() >=
+						deposit.created_at.saturating_add(T::PoolReapDelay::get()),
+				Error::<T>::ReapTooEarly
+			);
+			ensure!(
+				!BasePools::<T>::iter_values().any(|base_pool_id| base_pool_id == pool_id),
+				Error::<T>::PoolInUse
+			);
+
+			bifrost_stable_asset::Pallet::<T>::remove_pool(pool_id)?;
+			T::CurrencyIdRegister::remove_blp_metadata(pool_id)?;
+			T::MultiCurrency::unreserve(
+				T::DepositCurrencyId::get(),
+				&deposit.depositor,
+				deposit.amount,
+			);
+
+			bifrost_stable_asset::Pallet::<T>::deposit_event(
+				bifrost_stable_asset::Event::<T>::PoolReaped {
+					pool_id,
+					depositor: deposit.depositor,
+					amount: deposit.amount,
+				},
+			);
+			Ok(())
+		}
+
+		#[pallet::call_index(16)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_parameter_bounds())]
+		pub fn set_parameter_bounds(
+			origin: OriginFor<T>,
+			bounds: PoolParameterBounds<T::AtLeast64BitUnsigned>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+			ensure!(
+				bounds.min_a <= bounds.max_a &&
+					bounds.min_precision <= bounds.max_precision &&
+					bounds.max_fee < T::FeePrecision::get(),
+				bifrost_stable_asset::Error::<T>::ArgumentsError
+			);
+
+			ParameterBounds::<T>::put(bounds);
+
+			bifrost_stable_asset::Pallet::<T>::deposit_event(
+				bifrost_stable_asset::Event::<T>::ParameterBoundsSet { bounds },
+			);
+			Ok(())
+		}
+
+		/// Freezes a pool, which can then only be redeemed from.
+		#[pallet::call_index(17)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::freeze_pool())]
+		pub fn freeze_pool(origin: OriginFor<T>, pool_id: StableAssetPoolId) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+			ensure!(
+				Pools::<T>::contains_key(pool_id),
+				bifrost_stable_asset::Error::<T>::PoolNotFound
+			);
+
+			FrozenPools::<T>::insert(pool_id, true);
+
+			bifrost_stable_asset::Pallet::<T>::deposit_event(
+				bifrost_stable_asset::Event::<T>::PoolFrozen { pool_id },
+			);
+			Ok(())
+		}
+
+		#[pallet::call_index(18)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::unfreeze_pool())]
+		pub fn unfreeze_pool(origin: OriginFor<T>, pool_id: StableAssetPoolId) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			FrozenPools::<T>::remove(pool_id);
+
+			bifrost_stable_asset::Pallet::<T>::deposit_event(
+				bifrost_stable_asset::Event::<T>::PoolUnfrozen { pool_id },
+			);
+			Ok(())
+		}
+
+		/// Designates the pool used to price and route between two assets, or removes the
+		/// designation with `None`.
+		#[pallet::call_index(19)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_canonical_pool())]
+		pub fn set_canonical_pool(
+			origin: OriginFor<T>,
+			asset_a: AssetIdOf<T>,
+			asset_b: AssetIdOf<T>,
+			pool_id: Option<StableAssetPoolId>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			if let Some(pool_id) = pool_id {
+				let pool_info = T::StableAsset::pool(pool_id)
+					.ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+				ensure!(
+					asset_a != asset_b &&
+						pool_info.assets.contains(&asset_a) &&
+						pool_info.assets.contains(&asset_b),
+					Error::<T>::NotPoolAssets
+				);
+			}
+
+			bifrost_stable_asset::Pallet::<T>::set_canonical_pool(asset_a, asset_b, pool_id);
+			Ok(())
+		}
 	}
 }
 
+/// The path of a swap between the assets of a metapool and of its base pool.
+enum UnderlyingRoute {
+	/// A swap in the metapool.
+	Meta(PoolTokenIndex, PoolTokenIndex),
+	/// A swap in the metapool to the base pool token, redeemed for an asset of the base pool.
+	MetaToBase {
+		i: PoolTokenIndex,
+		lp_index: PoolTokenIndex,
+		base_pool_id: StableAssetPoolId,
+		j: PoolTokenIndex,
+	},
+	/// An asset of the base pool added to it, and its pool token swapped in the metapool.
+	BaseToMeta {
+		base_pool_id: StableAssetPoolId,
+		i: PoolTokenIndex,
+		lp_index: PoolTokenIndex,
+		j: PoolTokenIndex,
+	},
+	/// A swap in the base pool.
+	Base(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex),
+}
+
 impl<T: Config> Pallet<T> {
+	fn ensure_within_bounds(
+		assets: &[AssetIdOf<T>],
+		precisions: &[AtLeast64BitUnsignedOf<T>],
+		fees: &[AtLeast64BitUnsignedOf<T>],
+		a: AtLeast64BitUnsignedOf<T>,
+		precision: AtLeast64BitUnsignedOf<T>,
+	) -> DispatchResult {
+		let bounds = ParameterBounds::<T>::get().ok_or(Error::<T>::ParameterBoundsNotSet)?;
+		ensure!(
+			assets
+				.iter()
+				.all(|asset| T::CurrencyIdRegister::check_currency_registered(*asset)),
+			Error::<T>::CurrencyNotRegistered
+		);
+		ensure!(
+			a >= bounds.min_a &&
+				a <= bounds.max_a &&
+				fees.iter().all(|fee| *fee <= bounds.max_fee) &&
+				precisions.iter().all(|asset_precision| {
+					!asset_precision.is_zero() && *asset_precision <= bounds.max_asset_precision
+				}) && precision >= bounds.min_precision &&
+				precision <= bounds.max_precision,
+			Error::<T>::ParameterOutOfBounds
+		);
+		Ok(())
+	}
+
+	/// Returns the token rates of the assets of a new pool: a vToken paired with its token at the
+	/// rate of vToken minting, and any other asset at par.
+	fn get_initial_token_rates(
+		assets: &[AssetIdOf<T>],
+	) -> Vec<(AssetIdOf<T>, (AtLeast64BitUnsignedOf<T>, AtLeast64BitUnsignedOf<T>))> {
+		assets
+			.iter()
+			.map(|asset| {
+				let vtoken_rate = T::CurrencyIdConversion::convert_to_token(*asset)
+					.ok()
+					.filter(|token| assets.contains(token))
+					.map(|token| {
+						(
+							AtLeast64BitUnsignedOf::<T>::from(T::MultiCurrency::total_issuance(
+								*asset,
+							)),
+							AtLeast64BitUnsignedOf::<T>::from(T::VtokenMinting::get_token_pool(
+								token,
+							)),
+						)
+					})
+					.filter(|(issuance, token_pool)| !issuance.is_zero() && !token_pool.is_zero());
+				(*asset, vtoken_rate.unwrap_or((One::one(), One::one())))
+			})
+			.collect()
+	}
+
 	fn ensure_can_refresh(
 		token_in: AssetIdOf<T>,
 		token_out: AssetIdOf<T>,
@@ -432,6 +770,7 @@ impl<T: Config> Pallet<T> {
 		mut amounts: Vec<T::Balance>,
 		min_mint_amount: T::Balance,
 	) -> DispatchResult {
+		bifrost_stable_asset::Pallet::<T>::ensure_pool_not_frozen(pool_id)?;
 		let mut pool_info =
 			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
 		let amounts_old = amounts.clone();
@@ -446,8 +785,8 @@ impl<T: Config> Pallet<T> {
 			)?;
 		}
 		T::StableAsset::collect_yield(pool_id, &mut pool_info)?;
-		let MintResult { mint_amount, fee_amount, balances, total_supply } =
-			bifrost_stable_asset::Pallet::<T>::get_mint_amount(&pool_info, &amounts)?;
+		let (MintResult { mint_amount, fee_amount, balances, total_supply }, _) =
+			Self::get_dynamic_mint_amount(&pool_info, &amounts)?;
 		let a = T::StableAsset::get_a(
 			pool_info.a,
 			pool_info.a_block,
@@ -494,6 +833,7 @@ impl<T: Config> Pallet<T> {
 		pool_info.total_supply = total_supply;
 		pool_info.balances = balances;
 		T::StableAsset::collect_fee(pool_id, &mut pool_info)?;
+		bifrost_stable_asset::Pallet::<T>::update_price_observations(pool_id, &pool_info);
 		T::StableAsset::insert_pool(pool_id, &pool_info);
 		bifrost_stable_asset::Pallet::<T>::deposit_event(
 			bifrost_stable_asset::Event::<T>::LiquidityAdded {
@@ -579,6 +919,7 @@ impl<T: Config> Pallet<T> {
 		// Since the output amounts are round down, collect fee updates pool balances and total
 		// supply.
 		T::StableAsset::collect_fee(pool_id, &mut pool_info)?;
+		bifrost_stable_asset::Pallet::<T>::update_price_observations(pool_id, &pool_info);
 		T::StableAsset::insert_pool(pool_id, &pool_info);
 		let a = T::StableAsset::get_a(
 			pool_info.a,
@@ -624,11 +965,19 @@ impl<T: Config> Pallet<T> {
 					.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)?,
 			)?;
 		}
-		let RedeemMultiResult { redeem_amount, fee_amount, balances, total_supply, burn_amount } =
-			bifrost_stable_asset::Pallet::<T>::get_redeem_multi_amount(
-				&mut pool_info,
-				&new_amounts,
-			)?;
+		let (
+			RedeemMultiResult { redeem_amount, fee_amount, balances, total_supply, burn_amount },
+			_,
+		) = Self::quote_with_dynamic_fee(
+			&pool_info,
+			pool_info.redeem_fee,
+			|pool_info, fee| pool_info.redeem_fee = fee,
+			|pool_info| {
+				bifrost_stable_asset::Pallet::<T>::get_redeem_multi_amount(pool_info, &new_amounts)
+					.map_err(Into::into)
+			},
+			|result| result.balances.clone(),
+		)?;
 		let zero: T::Balance = Zero::zero();
 		ensure!(redeem_amount <= max_redeem_amount, Error::<T>::RedeemOverMax);
 		if fee_amount > zero {
@@ -658,6 +1007,7 @@ impl<T: Config> Pallet<T> {
 		pool_info.total_supply = total_supply;
 		pool_info.balances = balances;
 		T::StableAsset::collect_fee(pool_id, &mut pool_info)?;
+		bifrost_stable_asset::Pallet::<T>::update_price_observations(pool_id, &pool_info);
 		T::StableAsset::insert_pool(pool_id, &pool_info);
 		let a = T::StableAsset::get_a(
 			pool_info.a,
@@ -695,8 +1045,19 @@ impl<T: Config> Pallet<T> {
 			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
 
 		T::StableAsset::collect_yield(pool_id, &mut pool_info)?;
-		let RedeemSingleResult { mut dy, fee_amount, total_supply, balances, redeem_amount } =
-			bifrost_stable_asset::Pallet::<T>::get_redeem_single_amount(&mut pool_info, amount, i)?;
+		let (RedeemSingleResult { mut dy, fee_amount, total_supply, balances, redeem_amount }, _) =
+			Self::quote_with_dynamic_fee(
+				&pool_info,
+				pool_info.redeem_fee,
+				|pool_info, fee| pool_info.redeem_fee = fee,
+				|pool_info| {
+					bifrost_stable_asset::Pallet::<T>::get_redeem_single_amount(
+						pool_info, amount, i,
+					)
+					.map_err(Into::into)
+				},
+				|result| result.balances.clone(),
+			)?;
 		dy = Self::downscale(
 			dy,
 			pool_id,
@@ -726,6 +1087,7 @@ impl<T: Config> Pallet<T> {
 		// Since the output amounts are round down, collect fee updates pool balances and total
 		// supply.
 		T::StableAsset::collect_fee(pool_id, &mut pool_info)?;
+		bifrost_stable_asset::Pallet::<T>::update_price_observations(pool_id, &pool_info);
 		T::StableAsset::insert_pool(pool_id, &pool_info);
 		let a: T::AtLeast64BitUnsigned = T::StableAsset::get_a(
 			pool_info.a,
@@ -760,6 +1122,7 @@ impl<T: Config> Pallet<T> {
 		amount: T::Balance,
 		min_dy: T::Balance,
 	) -> DispatchResult {
+		bifrost_stable_asset::Pallet::<T>::ensure_pool_not_frozen(pool_id)?;
 		let mut pool_info =
 			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
 
@@ -773,13 +1136,8 @@ impl<T: Config> Pallet<T> {
 			.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)?;
 		T::StableAsset::collect_yield(pool_id, &mut pool_info)?;
 		let dx = Self::upscale(amount, pool_id, token_in)?;
-		let SwapResult { dx: _, dy, y, balance_i } =
-			bifrost_stable_asset::Pallet::<T>::get_swap_amount(
-				&pool_info,
-				currency_id_in,
-				currency_id_out,
-				dx,
-			)?;
+		let (SwapResult { dx: _, dy, y, balance_i }, _) =
+			Self::get_dynamic_swap_amount(&pool_info, currency_id_in, currency_id_out, dx)?;
 
 		let downscale_out = Self::downscale(dy, pool_id, token_out)?;
 		ensure!(downscale_out >= min_dy, Error::<T>::SwapUnderMin);
@@ -804,6 +1162,7 @@ impl<T: Config> Pallet<T> {
 		let asset_i = pool_info.assets[i_usize];
 		let asset_j = pool_info.assets[j_usize];
 		T::StableAsset::collect_fee(pool_id, &mut pool_info)?;
+		bifrost_stable_asset::Pallet::<T>::update_price_observations(pool_id, &pool_info);
 		T::StableAsset::insert_pool(pool_id, &pool_info);
 		let a = T::StableAsset::get_a(
 			pool_info.a,
@@ -846,6 +1205,102 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	fn get_underlying_route(
+		pool_id: StableAssetPoolId,
+		currency_id_in: AssetIdOf<T>,
+		currency_id_out: AssetIdOf<T>,
+	) -> Result<UnderlyingRoute, DispatchError> {
+		let pool_info =
+			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		let base_pool_id = BasePools::<T>::get(pool_id).ok_or(Error::<T>::NotMetaPool)?;
+		let base_pool_info = T::StableAsset::pool(base_pool_id)
+			.ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		let position = |assets: &[AssetIdOf<T>], currency_id: AssetIdOf<T>| {
+			assets
+				.iter()
+				.position(|asset| *asset == currency_id)
+				.map(|i| i as PoolTokenIndex)
+		};
+		let lp_index = position(&pool_info.assets, base_pool_info.pool_asset)
+			.ok_or(bifrost_stable_asset::Error::<T>::InconsistentStorage)?;
+
+		let route = match (
+			position(&pool_info.assets, currency_id_in),
+			position(&pool_info.assets, currency_id_out),
+		) {
+			(Some(i), Some(j)) => UnderlyingRoute::Meta(i, j),
+			(Some(i), None) => UnderlyingRoute::MetaToBase {
+				i,
+				lp_index,
+				base_pool_id,
+				j: position(&base_pool_info.assets, currency_id_out)
+					.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)?,
+			},
+			(None, Some(j)) => UnderlyingRoute::BaseToMeta {
+				base_pool_id,
+				i: position(&base_pool_info.assets, currency_id_in)
+					.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)?,
+				lp_index,
+				j,
+			},
+			(None, None) => UnderlyingRoute::Base(
+				base_pool_id,
+				position(&base_pool_info.assets, currency_id_in)
+					.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)?,
+				position(&base_pool_info.assets, currency_id_out)
+					.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)?,
+			),
+		};
+		if let UnderlyingRoute::Meta(i, j) | UnderlyingRoute::Base(_, i, j) = route {
+			ensure!(i != j, bifrost_stable_asset::Error::<T>::ArgumentsError);
+		}
+		Ok(route)
+	}
+
+	#[transactional]
+	fn on_swap_underlying(
+		who: &AccountIdOf<T>,
+		pool_id: StableAssetPoolId,
+		currency_id_in: AssetIdOf<T>,
+		currency_id_out: AssetIdOf<T>,
+		amount: T::Balance,
+		min_dy: T::Balance,
+	) -> DispatchResult {
+		match Self::get_underlying_route(pool_id, currency_id_in, currency_id_out)? {
+			UnderlyingRoute::Meta(i, j) => Self::on_swap(who, pool_id, i, j, amount, min_dy),
+			UnderlyingRoute::MetaToBase { i, lp_index, base_pool_id, j } => {
+				let base_pool_info = T::StableAsset::pool(base_pool_id)
+					.ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+				let lp_before = T::MultiCurrency::free_balance(base_pool_info.pool_asset, who);
+				Self::on_swap(who, pool_id, i, lp_index, amount, Zero::zero())?;
+				let lp_amount = T::MultiCurrency::free_balance(base_pool_info.pool_asset, who)
+					.saturating_sub(lp_before);
+				Self::redeem_single_inner(
+					who,
+					base_pool_id,
+					lp_amount,
+					j,
+					min_dy,
+					base_pool_info.assets.len() as u32,
+				)?;
+				Ok(())
+			},
+			UnderlyingRoute::BaseToMeta { base_pool_id, i, lp_index, j } => {
+				let base_pool_info = T::StableAsset::pool(base_pool_id)
+					.ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+				let mut amounts = sp_std::vec![Zero::zero(); base_pool_info.assets.len()];
+				amounts[i as usize] = amount;
+				let lp_before = T::MultiCurrency::free_balance(base_pool_info.pool_asset, who);
+				Self::mint_inner(who, base_pool_id, amounts, Zero::zero())?;
+				let lp_amount = T::MultiCurrency::free_balance(base_pool_info.pool_asset, who)
+					.saturating_sub(lp_before);
+				Self::on_swap(who, pool_id, lp_index, j, lp_amount, min_dy)
+			},
+			UnderlyingRoute::Base(base_pool_id, i, j) =>
+				Self::on_swap(who, base_pool_id, i, j, amount, min_dy),
+		}
+	}
+
 	pub fn upscale(
 		amount: T::Balance,
 		pool_id: StableAssetPoolId,
@@ -897,12 +1352,130 @@ impl<T: Config> Pallet<T> {
 		charge_amount.into()
 	}
 
+	/// Returns how far `balances` are from balance: the sum of their distances to their mean,
+	/// from zero when balanced to one when all in one asset.
+	fn get_imbalance(balances: &[T::Balance]) -> Perbill {
+		let size = balances.len() as u128;
+		if size < 2 {
+			return Perbill::zero();
+		}
+		let balances: Vec<u128> = balances
+			.iter()
+			.map(|balance| AtLeast64BitUnsignedOf::<T>::from(*balance).saturated_into::<u128>())
+			.collect();
+		let sum: u128 = balances.iter().fold(0, |sum, balance| sum.saturating_add(*balance));
+		let deviation: u128 = balances.iter().fold(0, |deviation, balance| {
+			deviation.saturating_add(balance.saturating_mul(size).abs_diff(sum))
+		});
+		Perbill::from_rational(deviation, sum.saturating_mul(2).saturating_mul(size - 1))
+	}
+
+	/// Returns the fee charged for moving the balances of the pool to `new_balances`: the flat
+	/// `base_fee`, plus the imbalance fee for the share of the way to imbalance the move takes,
+	/// capped at the maximum fee. Moves towards balance are only charged the flat fee.
+	pub fn get_dynamic_fee(
+		config: &DynamicFeeConfig<T::AtLeast64BitUnsigned>,
+		base_fee: T::AtLeast64BitUnsigned,
+		balances: &[T::Balance],
+		new_balances: &[T::Balance],
+	) -> T::AtLeast64BitUnsigned {
+		let imbalance =
+			Self::get_imbalance(new_balances).saturating_sub(Self::get_imbalance(balances));
+		let imbalance_fee: T::AtLeast64BitUnsigned =
+			imbalance.mul_floor(config.imbalance_fee.saturated_into::<u128>()).into();
+		base_fee.saturating_add(imbalance_fee).min(config.max_fee).max(base_fee)
+	}
+
+	/// Quotes a trade with the dynamic fee of the pool, if any. The trade is first quoted with
+	/// the flat fee `base_fee`, to find the balances it leaves the pool with, and quoted again
+	/// with the effective fee if it differs. Returns the quote and the effective fee.
+	fn quote_with_dynamic_fee<R>(
+		pool_info: &StableAssetPoolInfoOf<T>,
+		base_fee: T::AtLeast64BitUnsigned,
+		set_fee: impl Fn(&mut StableAssetPoolInfoOf<T>, T::AtLeast64BitUnsigned),
+		quote: impl Fn(&StableAssetPoolInfoOf<T>) -> Result<R, DispatchError>,
+		new_balances: impl Fn(&R) -> Vec<T::Balance>,
+	) -> Result<(R, T::AtLeast64BitUnsigned), DispatchError> {
+		let result = quote(pool_info)?;
+		let fee = match DynamicFees::<T>::get(pool_info.pool_id) {
+			Some(config) => Self::get_dynamic_fee(
+				&config,
+				base_fee,
+				&pool_info.balances,
+				&new_balances(&result),
+			),
+			None => return Ok((result, base_fee)),
+		};
+		if fee == base_fee {
+			return Ok((result, fee));
+		}
+		let mut fee_pool_info = pool_info.clone();
+		set_fee(&mut fee_pool_info, fee);
+		Ok((quote(&fee_pool_info)?, fee))
+	}
+
+	fn get_dynamic_swap_amount(
+		pool_info: &StableAssetPoolInfoOf<T>,
+		currency_id_in: PoolTokenIndex,
+		currency_id_out: PoolTokenIndex,
+		dx: T::Balance,
+	) -> Result<(SwapResult<T::Balance>, T::AtLeast64BitUnsigned), DispatchError> {
+		Self::quote_with_dynamic_fee(
+			pool_info,
+			pool_info.swap_fee,
+			|pool_info, fee| pool_info.swap_fee = fee,
+			|pool_info| {
+				bifrost_stable_asset::Pallet::<T>::get_swap_amount(
+					pool_info,
+					currency_id_in,
+					currency_id_out,
+					dx,
+				)
+				.map_err(Into::into)
+			},
+			|result| {
+				let mut balances = pool_info.balances.clone();
+				balances[currency_id_in as usize] = result.balance_i;
+				balances[currency_id_out as usize] = result.y;
+				balances
+			},
+		)
+	}
+
+	fn get_dynamic_mint_amount(
+		pool_info: &StableAssetPoolInfoOf<T>,
+		amounts: &[T::Balance],
+	) -> Result<(MintResult<T>, T::AtLeast64BitUnsigned), DispatchError> {
+		Self::quote_with_dynamic_fee(
+			pool_info,
+			pool_info.mint_fee,
+			|pool_info, fee| pool_info.mint_fee = fee,
+			|pool_info| {
+				bifrost_stable_asset::Pallet::<T>::get_mint_amount(pool_info, amounts)
+					.map_err(Into::into)
+			},
+			|result| result.balances.clone(),
+		)
+	}
+
 	pub fn get_swap_output(
 		pool_id: StableAssetPoolId,
 		currency_id_in: PoolTokenIndex,
 		currency_id_out: PoolTokenIndex,
 		amount: T::Balance,
 	) -> Result<T::Balance, DispatchError> {
+		Self::get_swap_quote(pool_id, currency_id_in, currency_id_out, amount)
+			.map(|(amount, _)| amount)
+	}
+
+	/// Returns the output of swapping `amount` and the effective swap fee, the dynamic fee
+	/// included, in `FeePrecision`.
+	pub fn get_swap_quote(
+		pool_id: StableAssetPoolId,
+		currency_id_in: PoolTokenIndex,
+		currency_id_out: PoolTokenIndex,
+		amount: T::Balance,
+	) -> Result<(T::Balance, T::AtLeast64BitUnsigned), DispatchError> {
 		let pool_info =
 			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
 		let dx = Self::upscale(
@@ -913,12 +1486,8 @@ impl<T: Config> Pallet<T> {
 				.get(currency_id_in as usize)
 				.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)?,
 		)?;
-		let SwapResult { dx: _, dy, .. } = bifrost_stable_asset::Pallet::<T>::get_swap_amount(
-			&pool_info,
-			currency_id_in,
-			currency_id_out,
-			dx,
-		)?;
+		let (SwapResult { dx: _, dy, .. }, fee) =
+			Self::get_dynamic_swap_amount(&pool_info, currency_id_in, currency_id_out, dx)?;
 		let downscale_out = Self::downscale(
 			dy,
 			pool_id,
@@ -928,7 +1497,7 @@ impl<T: Config> Pallet<T> {
 				.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)?,
 		)?;
 
-		Ok(downscale_out)
+		Ok((downscale_out, fee))
 	}
 
 	pub fn get_swap_input(
@@ -947,14 +1516,32 @@ impl<T: Config> Pallet<T> {
 				.get(currency_id_out as usize)
 				.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)?,
 		)?;
-		let SwapResult { dx, dy: _, .. } =
-			bifrost_stable_asset::Pallet::<T>::get_swap_amount_exact(
-				&pool_info,
-				currency_id_in,
-				currency_id_out,
-				dy,
-			)
-			.ok_or(bifrost_stable_asset::Error::<T>::Math)?;
+		let (SwapResult { dx, dy: _, .. }, _) = Self::quote_with_dynamic_fee(
+			&pool_info,
+			pool_info.swap_fee,
+			|pool_info, fee| pool_info.swap_fee = fee,
+			|pool_info| {
+				bifrost_stable_asset::Pallet::<T>::get_swap_amount_exact(
+					pool_info,
+					currency_id_in,
+					currency_id_out,
+					dy,
+				)
+				.ok_or(bifrost_stable_asset::Error::<T>::Math.into())
+			},
+			|result| {
+				let mut balances = pool_info.balances.clone();
+				let output_index = currency_id_out as usize;
+				let balance_out = T::AtLeast64BitUnsigned::from(balances[output_index])
+					.saturating_sub(
+						T::AtLeast64BitUnsigned::from(result.dy)
+							.saturating_mul(pool_info.precisions[output_index]),
+					);
+				balances[currency_id_in as usize] = result.y;
+				balances[output_index] = balance_out.into();
+				balances
+			},
+		)?;
 		let downscale_out = Self::downscale(
 			dx,
 			pool_id,
@@ -967,10 +1554,149 @@ impl<T: Config> Pallet<T> {
 		Ok(downscale_out)
 	}
 
+	/// Returns the output of swapping `amount` between the assets of the metapool `pool_id` and
+	/// of its base pool, through the pool token of the base pool.
+	pub fn get_underlying_swap_output(
+		pool_id: StableAssetPoolId,
+		currency_id_in: AssetIdOf<T>,
+		currency_id_out: AssetIdOf<T>,
+		amount: T::Balance,
+	) -> Result<T::Balance, DispatchError> {
+		match Self::get_underlying_route(pool_id, currency_id_in, currency_id_out)? {
+			UnderlyingRoute::Meta(i, j) => Self::get_swap_output(pool_id, i, j, amount),
+			UnderlyingRoute::MetaToBase { i, lp_index, base_pool_id, j } => {
+				let lp_amount = Self::get_swap_output(pool_id, i, lp_index, amount)?;
+				Self::redeem_single_quote(base_pool_id, lp_amount, j)
+					.map(|quote| quote.amounts[j as usize])
+			},
+			UnderlyingRoute::BaseToMeta { base_pool_id, i, lp_index, j } => {
+				let base_pool_info = T::StableAsset::pool(base_pool_id)
+					.ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+				let mut amounts = sp_std::vec![Zero::zero(); base_pool_info.assets.len()];
+				amounts[i as usize] = amount;
+				let lp_amount = Self::add_liquidity_amount(base_pool_id, amounts)?;
+				Self::get_swap_output(pool_id, lp_index, j, lp_amount)
+			},
+			UnderlyingRoute::Base(base_pool_id, i, j) =>
+				Self::get_swap_output(base_pool_id, i, j, amount),
+		}
+	}
+
+	/// Quotes redeeming `amount` pool tokens for all the pool assets in proportion.
+	pub fn redeem_proportion_quote(
+		pool_id: StableAssetPoolId,
+		amount: T::Balance,
+	) -> Result<RedeemQuote<T::Balance>, DispatchError> {
+		let pool_info =
+			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		let RedeemProportionResult { amounts, fee_amount, redeem_amount, .. } =
+			bifrost_stable_asset::Pallet::<T>::get_redeem_proportion_amount(&pool_info, amount)?;
+		let amounts = amounts
+			.into_iter()
+			.zip(pool_info.assets.iter())
+			.map(|(amount, asset)| Self::downscale(amount, pool_id, *asset))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		Ok(RedeemQuote {
+			amounts,
+			input_amount: amount,
+			burn_amount: redeem_amount,
+			fee_amount,
+			fee: pool_info.redeem_fee.into(),
+		})
+	}
+
+	/// Quotes redeeming `amount` pool tokens for the asset `i`, with the dynamic fee of the pool.
+	pub fn redeem_single_quote(
+		pool_id: StableAssetPoolId,
+		amount: T::Balance,
+		i: PoolTokenIndex,
+	) -> Result<RedeemQuote<T::Balance>, DispatchError> {
+		let pool_info =
+			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		let (RedeemSingleResult { dy, fee_amount, redeem_amount, .. }, fee) =
+			Self::quote_with_dynamic_fee(
+				&pool_info,
+				pool_info.redeem_fee,
+				|pool_info, fee| pool_info.redeem_fee = fee,
+				|pool_info| {
+					bifrost_stable_asset::Pallet::<T>::get_redeem_single_amount(
+						pool_info, amount, i,
+					)
+					.map_err(Into::into)
+				},
+				|result| result.balances.clone(),
+			)?;
+		let downscale_out = Self::downscale(
+			dy,
+			pool_id,
+			*pool_info
+				.assets
+				.get(i as usize)
+				.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)?,
+		)?;
+		let mut amounts = sp_std::vec![Zero::zero(); pool_info.assets.len()];
+		amounts[i as usize] = downscale_out;
+
+		Ok(RedeemQuote {
+			amounts,
+			input_amount: amount,
+			burn_amount: redeem_amount,
+			fee_amount,
+			fee: fee.into(),
+		})
+	}
+
+	/// Quotes redeeming pool tokens for `amounts` of the pool assets, with the dynamic fee of the
+	/// pool.
+	pub fn redeem_multi_quote(
+		pool_id: StableAssetPoolId,
+		amounts: Vec<T::Balance>,
+	) -> Result<RedeemQuote<T::Balance>, DispatchError> {
+		let pool_info =
+			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		let upscaled_amounts = amounts
+			.iter()
+			.zip(pool_info.assets.iter())
+			.map(|(amount, asset)| Self::upscale(*amount, pool_id, *asset))
+			.collect::<Result<Vec<_>, _>>()?;
+		let (RedeemMultiResult { redeem_amount, fee_amount, burn_amount, .. }, fee) =
+			Self::quote_with_dynamic_fee(
+				&pool_info,
+				pool_info.redeem_fee,
+				|pool_info, fee| pool_info.redeem_fee = fee,
+				|pool_info| {
+					bifrost_stable_asset::Pallet::<T>::get_redeem_multi_amount(
+						pool_info,
+						&upscaled_amounts,
+					)
+					.map_err(Into::into)
+				},
+				|result| result.balances.clone(),
+			)?;
+
+		Ok(RedeemQuote {
+			amounts,
+			input_amount: redeem_amount,
+			burn_amount,
+			fee_amount,
+			fee: fee.into(),
+		})
+	}
+
 	pub fn add_liquidity_amount(
 		pool_id: StableAssetPoolId,
-		mut amounts: Vec<T::Balance>,
+		amounts: Vec<T::Balance>,
 	) -> Result<T::Balance, DispatchError> {
+		Self::add_liquidity_quote(pool_id, amounts).map(|(amount, _)| amount)
+	}
+
+	/// Returns the pool tokens minted for `amounts` and the effective mint fee, the dynamic fee
+	/// included, in `FeePrecision`.
+	pub fn add_liquidity_quote(
+		pool_id: StableAssetPoolId,
+		mut amounts: Vec<T::Balance>,
+	) -> Result<(T::Balance, T::AtLeast64BitUnsigned), DispatchError> {
 		let pool_info =
 			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
 		for (i, amount) in amounts.iter_mut().enumerate() {
@@ -983,28 +1709,179 @@ impl<T: Config> Pallet<T> {
 					.ok_or(bifrost_stable_asset::Error::<T>::ArgumentsMismatch)?,
 			)?;
 		}
-		let MintResult { mint_amount, .. } =
-			bifrost_stable_asset::Pallet::<T>::get_mint_amount(&pool_info, &amounts)?;
+		let (MintResult { mint_amount, .. }, fee) =
+			Self::get_dynamic_mint_amount(&pool_info, &amounts)?;
+
+		Ok((mint_amount, fee))
+	}
+
+	/// Returns the amount of each pool asset `amount` pool tokens stand for at the pool
+	/// invariant, `D` computed from the pool balances with the current A over the total supply.
+	pub fn get_lp_token_value(
+		pool_id: StableAssetPoolId,
+		amount: T::Balance,
+	) -> Result<Vec<(AssetIdOf<T>, T::Balance)>, DispatchError> {
+		let pool_info =
+			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		ensure!(!pool_info.total_supply.is_zero(), bifrost_stable_asset::Error::<T>::Math);
+		let a = bifrost_stable_asset::Pallet::<T>::get_a(
+			pool_info.a,
+			pool_info.a_block,
+			pool_info.future_a,
+			pool_info.future_a_block,
+		)
+		.ok_or(bifrost_stable_asset::Error::<T>::Math)?;
+		let balances = bifrost_stable_asset::Pallet::<T>::convert_vec_balance_to_number(
+			pool_info.balances.clone(),
+		);
+		let d = bifrost_stable_asset::Pallet::<T>::get_d(&balances, a)
+			.ok_or(bifrost_stable_asset::Error::<T>::Math)?;
+		let amount: AtLeast64BitUnsignedOf<T> = amount.into();
+		let total_supply: AtLeast64BitUnsignedOf<T> = pool_info.total_supply.into();
+		let invariant_amount: u128 = U256::from(amount.saturated_into::<u128>())
+			.checked_mul(U256::from(d.saturated_into::<u128>()))
+			.and_then(|n| n.checked_div(U256::from(total_supply.saturated_into::<u128>())))
+			.and_then(|n| TryInto::<u128>::try_into(n).ok())
+			.ok_or(bifrost_stable_asset::Error::<T>::Math)?;
+
+		pool_info
+			.assets
+			.iter()
+			.zip(pool_info.precisions.iter())
+			.map(|(asset_id, precision)| {
+				let upscaled_amount: AtLeast64BitUnsignedOf<T> = invariant_amount.into();
+				let upscaled_amount = upscaled_amount
+					.checked_div(precision)
+					.ok_or(bifrost_stable_asset::Error::<T>::Math)?;
+				Ok((*asset_id, Self::downscale(upscaled_amount.into(), pool_id, *asset_id)?))
+			})
+			.collect()
+	}
+
+	/// Returns the invariant `D` of the pool over its total supply, the value of one pool token
+	/// in the scaled balances of the pool.
+	pub fn get_virtual_price(pool_id: StableAssetPoolId) -> Option<FixedU128> {
+		let pool_info = T::StableAsset::pool(pool_id)?;
+		let a = bifrost_stable_asset::Pallet::<T>::get_a(
+			pool_info.a,
+			pool_info.a_block,
+			pool_info.future_a,
+			pool_info.future_a_block,
+		)?;
+		let balances =
+			bifrost_stable_asset::Pallet::<T>::convert_vec_balance_to_number(pool_info.balances);
+		let d = bifrost_stable_asset::Pallet::<T>::get_d(&balances, a)?;
+		FixedU128::checked_from_rational(
+			d.saturated_into::<u128>(),
+			AtLeast64BitUnsignedOf::<T>::from(pool_info.total_supply).saturated_into::<u128>(),
+		)
+	}
+
+	/// Returns the pool `pool_id` as shown to integrators.
+	pub fn get_pool_info(
+		pool_id: StableAssetPoolId,
+	) -> Option<StablePoolInfo<AccountIdOf<T>, T::Balance, BlockNumberFor<T>>> {
+		let pool_info = T::StableAsset::pool(pool_id)?;
+		let current_a = bifrost_stable_asset::Pallet::<T>::get_a(
+			pool_info.a,
+			pool_info.a_block,
+			pool_info.future_a,
+			pool_info.future_a_block,
+		)?;
+		let reserves = pool_info
+			.balances
+			.iter()
+			.zip(pool_info.precisions.iter())
+			.zip(pool_info.assets.iter())
+			.map(|((balance, precision), asset)| {
+				let balance = AtLeast64BitUnsignedOf::<T>::from(*balance).checked_div(precision)?;
+				Self::downscale(balance.into(), pool_id, *asset).ok()
+			})
+			.collect::<Option<Vec<_>>>()?;
+
+		Some(StablePoolInfo {
+			pool_id,
+			pool_asset: pool_info.pool_asset.into(),
+			assets: pool_info.assets.into_iter().map(Into::into).collect(),
+			precisions: pool_info.precisions.into_iter().map(Into::into).collect(),
+			balances: pool_info.balances,
+			reserves,
+			total_supply: pool_info.total_supply,
+			precision: pool_info.precision.into(),
+			mint_fee: pool_info.mint_fee.into(),
+			swap_fee: pool_info.swap_fee.into(),
+			redeem_fee: pool_info.redeem_fee.into(),
+			dynamic_fee: DynamicFees::<T>::get(pool_id)
+				.map(|config| (config.imbalance_fee.into(), config.max_fee.into())),
+			current_a: current_a.into(),
+			a: pool_info.a.into(),
+			a_block: pool_info.a_block,
+			future_a: pool_info.future_a.into(),
+			future_a_block: pool_info.future_a_block,
+			account_id: pool_info.account_id,
+			fee_recipient: pool_info.fee_recipient,
+			yield_recipient: pool_info.yield_recipient,
+			base_pool_id: BasePools::<T>::get(pool_id),
+			frozen: FrozenPools::<T>::get(pool_id),
+		})
+	}
 
-		Ok(mint_amount)
+	/// Returns the time-weighted average price of one unit of `currency_id_in` in
+	/// `currency_id_out` over the `window` blocks before the current block. The price between two
+	/// assets other than the first pool asset is the ratio of their average prices in it.
+	pub fn get_twap(
+		pool_id: StableAssetPoolId,
+		currency_id_in: PoolTokenIndex,
+		currency_id_out: PoolTokenIndex,
+		window: BlockNumberFor<T>,
+	) -> Option<FixedU128> {
+		let prices = bifrost_stable_asset::Pallet::<T>::get_twap(pool_id, window)?;
+		prices
+			.get(currency_id_in as usize)?
+			.checked_div(prices.get(currency_id_out as usize)?)
 	}
 
+	/// Returns the pool designated for the pair of assets, with their token indexes.
 	fn get_pool_id(
 		currency_id_in: &AssetIdOf<T>,
 		currency_id_out: &AssetIdOf<T>,
 	) -> Option<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)> {
-		Pools::<T>::iter().find_map(|(pool_id, pool_info)| {
-			if pool_info.assets.get(0) == Some(currency_id_in) &&
-				pool_info.assets.get(1) == Some(currency_id_out)
-			{
-				Some((pool_id, 0, 1))
-			} else if pool_info.assets.get(0) == Some(currency_id_out) &&
-				pool_info.assets.get(1) == Some(currency_id_in)
-			{
-				Some((pool_id, 1, 0))
-			} else {
-				None
-			}
+		let pool_id = CanonicalPools::<T>::get(currency_id_in, currency_id_out)?;
+		let pool_info = Pools::<T>::get(pool_id)?;
+		let i = pool_info.assets.iter().position(|a| a == currency_id_in)?;
+		let j = pool_info.assets.iter().position(|a| a == currency_id_out)?;
+		Some((pool_id, i as PoolTokenIndex, j as PoolTokenIndex))
+	}
+
+	pub fn get_pool_ids(
+		currency_id_in: &AssetIdOf<T>,
+		currency_id_out: &AssetIdOf<T>,
+	) -> Vec<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)> {
+		Self::get_pool_id(currency_id_in, currency_id_out).into_iter().collect()
+	}
+}
+
+/// Prices an asset by its TWAP over `Window` blocks in a pool designated for it and another asset
+/// priced by `Oracle`, for the consumers of pool prices that can't trust spot prices.
+pub struct StablePoolTwapProvider<T, Oracle, Window>(PhantomData<(T, Oracle, Window)>);
+
+impl<T, Oracle, Window> DataProvider<CurrencyId, TimeStampedPrice>
+	for StablePoolTwapProvider<T, Oracle, Window>
+where
+	T: Config,
+	Oracle: DataProvider<CurrencyId, TimeStampedPrice>,
+	Window: Get<u32>,
+{
+	fn get(asset_id: &CurrencyId) -> Option<TimeStampedPrice> {
+		let asset: AssetIdOf<T> = (*asset_id).into();
+		CanonicalPools::<T>::iter_prefix(asset).find_map(|(quote, _)| {
+			let quote_price = Oracle::get(&quote.into())?;
+			let (pool_id, index, quote_index) = Pallet::<T>::get_pool_id(&asset, &quote)?;
+			let twap = Pallet::<T>::get_twap(pool_id, index, quote_index, Window::get().into())?;
+			Some(TimeStampedPrice {
+				value: quote_price.value.checked_mul(&twap)?,
+				timestamp: quote_price.timestamp,
+			})
 		})
 	}
 }
@@ -15,12 +15,18 @@
 
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
-use crate::{mock::*, AssetIdOf, AtLeast64BitUnsignedOf, Error};
-use bifrost_primitives::VtokenMintingOperator;
+use crate::{
+	mock::*, AssetIdOf, AtLeast64BitUnsignedOf, CanonicalPools, DynamicFeeConfig, Error,
+	PoolDeposit, PoolParameterBounds, Pools, StablePoolTwapProvider,
+};
+use bifrost_primitives::{TimeStampedPrice, VtokenMintingOperator};
 use bifrost_stable_asset::StableAssetPoolInfo;
-use frame_support::{assert_noop, assert_ok, BoundedVec};
-use orml_traits::MultiCurrency;
-use sp_runtime::{traits::AccountIdConversion, Permill};
+use frame_support::{assert_err, assert_noop, assert_ok, traits::ConstU32, BoundedVec};
+use orml_traits::{DataProvider, MultiCurrency};
+use sp_runtime::{
+	traits::{AccountIdConversion, CheckedDiv, CheckedMul, One},
+	DispatchError, DispatchResult, FixedPointNumber, FixedU128, Permill,
+};
 
 pub const BALANCE_OFF: u128 = 0;
 
@@ -800,6 +806,117 @@ fn bnc_add_liquidity_should_work() {
 	});
 }
 
+#[test]
+fn get_lp_token_value_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		let coin0 = BNC;
+		let coin1 = VBNC;
+		let pool_asset = CurrencyId::BLP(0);
+
+		assert_ok!(<Test as crate::Config>::MultiCurrency::deposit(
+			coin1.into(),
+			&6,
+			1_000_000_000_000u128
+		));
+		assert_ok!(StablePool::create_pool(
+			RuntimeOrigin::root(),
+			vec![coin0.into(), coin1.into()],
+			vec![1u128.into(), 1u128.into()],
+			0u128.into(),
+			0u128.into(),
+			0u128.into(),
+			220u128.into(),
+			5,
+			5,
+			1000000000000u128.into()
+		));
+		assert_ok!(StablePool::edit_token_rate(
+			RuntimeOrigin::root(),
+			0,
+			vec![(BNC, (1, 1)), (VBNC, (10, 11))]
+		));
+		assert_err!(
+			StablePool::get_lp_token_value(0, 1_000_000),
+			bifrost_stable_asset::Error::<Test>::Math
+		);
+
+		let amounts = vec![100_000_000_000u128, 100_000_000_000u128];
+		assert_ok!(StablePool::add_liquidity(RuntimeOrigin::signed(6).into(), 0, amounts, 0));
+		let lp_amount = Tokens::free_balance(pool_asset, &6);
+		assert_eq!(lp_amount, 209_955_833_377);
+
+		// Without fees the invariant equals the total supply, and 1 VBNC counts as 1.1 BNC
+		assert_eq!(
+			StablePool::get_lp_token_value(0, lp_amount),
+			Ok(vec![(BNC, 209_955_833_377), (VBNC, 190_868_939_433)])
+		);
+		// Swaps move the balances, the value of the pool token only grows by the rounding
+		assert_ok!(StablePool::swap(RuntimeOrigin::signed(6).into(), 0, 1, 0, 50_000_000_000, 0));
+		let value = StablePool::get_lp_token_value(0, lp_amount).unwrap();
+		assert_eq!(value[0].0, BNC);
+		assert!(value[0].1 >= 209_955_833_377 && value[0].1 <= 209_955_833_377 + 10);
+	});
+}
+
+pub struct MockOracle;
+impl DataProvider<CurrencyId, TimeStampedPrice> for MockOracle {
+	fn get(currency_id: &CurrencyId) -> Option<TimeStampedPrice> {
+		(*currency_id == BNC).then_some(TimeStampedPrice {
+			value: FixedU128::saturating_from_integer(2),
+			timestamp: 0,
+		})
+	}
+}
+
+#[test]
+fn get_twap_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		assert_ok!(<Test as crate::Config>::MultiCurrency::deposit(
+			VBNC.into(),
+			&6,
+			1_000_000_000_000u128
+		));
+		assert_ok!(StablePool::create_pool(
+			RuntimeOrigin::root(),
+			vec![BNC.into(), VBNC.into()],
+			vec![1u128.into(), 1u128.into()],
+			0u128.into(),
+			0u128.into(),
+			0u128.into(),
+			220u128.into(),
+			5,
+			5,
+			1000000000000u128.into()
+		));
+		assert_ok!(StablePool::edit_token_rate(
+			RuntimeOrigin::root(),
+			0,
+			vec![(BNC, (1, 1)), (VBNC, (10, 11))]
+		));
+		System::set_block_number(2);
+		let amounts = vec![100_000_000_000u128, 100_000_000_000u128];
+		assert_ok!(StablePool::add_liquidity(RuntimeOrigin::signed(6).into(), 0, amounts, 0));
+		assert_eq!(StablePool::get_twap(0, 1, 0, 10), None);
+
+		System::set_block_number(12);
+		let prices = bifrost_stable_asset::PriceObservations::<Test>::get(0, 0).unwrap().prices;
+		// 1 VBNC is worth about its token rate of 1.1 BNC
+		assert!(prices[1] > FixedU128::saturating_from_rational(105, 100));
+		assert_eq!(StablePool::get_twap(0, 1, 0, 10), Some(prices[1]));
+		assert_eq!(StablePool::get_twap(0, 0, 1, 10), FixedU128::one().checked_div(&prices[1]));
+
+		// VBNC is priced in BNC at $2
+		assert_eq!(
+			StablePoolTwapProvider::<Test, MockOracle, ConstU32<10>>::get(&VBNC),
+			Some(TimeStampedPrice {
+				value: FixedU128::saturating_from_integer(2).checked_mul(&prices[1]).unwrap(),
+				timestamp: 0
+			})
+		);
+		assert_eq!(StablePoolTwapProvider::<Test, MockOracle, ConstU32<10>>::get(&BNC), None);
+	});
+}
+
 #[test]
 fn edit_token_rate() {
 	ExtBuilder::default().new_test_ext().build().execute_with(|| {
@@ -1032,3 +1149,582 @@ fn remove_vtoken_auto_refresh_should_work() {
 		);
 	});
 }
+
+fn create_dynamic_fee_pool() {
+	System::set_block_number(2);
+	assert_ok!(<Test as crate::Config>::MultiCurrency::deposit(VBNC, &6, 1_000_000_000_000u128));
+	assert_ok!(StablePool::create_pool(
+		RuntimeOrigin::root(),
+		vec![BNC, VBNC],
+		vec![1u128, 1u128],
+		0u128,
+		0u128,
+		0u128,
+		220u128,
+		5,
+		5,
+		1000000000000u128
+	));
+	assert_ok!(StablePool::edit_token_rate(
+		RuntimeOrigin::root(),
+		0,
+		vec![(BNC, (1, 1)), (VBNC, (1, 1))]
+	));
+	assert_ok!(StablePool::add_liquidity(
+		RuntimeOrigin::signed(6).into(),
+		0,
+		vec![100_000_000_000u128, 100_000_000_000u128],
+		0
+	));
+}
+
+#[test]
+fn config_dynamic_fee_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		assert_noop!(
+			StablePool::config_dynamic_fee(RuntimeOrigin::root(), 0, 1_000_000_000, 500_000_000),
+			bifrost_stable_asset::Error::<Test>::PoolNotFound
+		);
+		create_dynamic_fee_pool();
+		assert_noop!(
+			StablePool::config_dynamic_fee(RuntimeOrigin::signed(6), 0, 1_000_000_000, 500_000_000),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			StablePool::config_dynamic_fee(RuntimeOrigin::root(), 0, 1_000_000_000, 10_000_000_000),
+			bifrost_stable_asset::Error::<Test>::ArgumentsError
+		);
+		// The maximum fee can't be below any flat fee of the pool
+		assert_ok!(StablePool::modify_fees(
+			RuntimeOrigin::root(),
+			0,
+			None,
+			Some(600_000_000),
+			None
+		));
+		assert_noop!(
+			StablePool::config_dynamic_fee(RuntimeOrigin::root(), 0, 1_000_000_000, 500_000_000),
+			bifrost_stable_asset::Error::<Test>::ArgumentsError
+		);
+		assert_ok!(StablePool::modify_fees(RuntimeOrigin::root(), 0, None, Some(0), None));
+
+		assert_ok!(StablePool::config_dynamic_fee(
+			RuntimeOrigin::root(),
+			0,
+			1_000_000_000,
+			500_000_000
+		));
+		let config = DynamicFeeConfig { imbalance_fee: 1_000_000_000, max_fee: 500_000_000 };
+		assert_eq!(bifrost_stable_asset::DynamicFees::<Test>::get(0), Some(config));
+		System::assert_last_event(RuntimeEvent::StableAsset(
+			bifrost_stable_asset::Event::DynamicFeeConfigured { pool_id: 0, config },
+		));
+
+		assert_ok!(StablePool::remove_dynamic_fee(RuntimeOrigin::root(), 0));
+		assert_eq!(bifrost_stable_asset::DynamicFees::<Test>::get(0), None);
+		System::assert_last_event(RuntimeEvent::StableAsset(
+			bifrost_stable_asset::Event::DynamicFeeRemoved { pool_id: 0 },
+		));
+		assert_noop!(
+			StablePool::remove_dynamic_fee(RuntimeOrigin::root(), 0),
+			bifrost_stable_asset::Error::<Test>::DynamicFeeNotConfigured
+		);
+	});
+}
+
+#[test]
+fn dynamic_fee_should_scale_with_imbalance() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		create_dynamic_fee_pool();
+		let (flat_output, flat_fee) = StablePool::get_swap_quote(0, 0, 1, 10_000_000_000).unwrap();
+		assert_eq!(flat_fee, 0);
+
+		// 10% of the way to all in one asset, charged 10% of the imbalance fee
+		assert_ok!(StablePool::config_dynamic_fee(
+			RuntimeOrigin::root(),
+			0,
+			1_000_000_000,
+			500_000_000
+		));
+		let (output, fee) = StablePool::get_swap_quote(0, 0, 1, 10_000_000_000).unwrap();
+		assert!(fee > 95_000_000 && fee < 105_000_000);
+		assert_eq!(output, flat_output - flat_output * fee / 10_000_000_000);
+		assert_eq!(StablePool::get_swap_output(0, 0, 1, 10_000_000_000), Ok(output));
+
+		// Capped at the maximum fee
+		assert_eq!(StablePool::get_swap_quote(0, 0, 1, 80_000_000_000).unwrap().1, 500_000_000);
+
+		// Single-sided liquidity pays the dynamic fee as well, balanced liquidity doesn't
+		assert!(StablePool::add_liquidity_quote(0, vec![10_000_000_000, 0]).unwrap().1 > 0);
+		assert_eq!(
+			StablePool::add_liquidity_quote(0, vec![10_000_000_000, 10_000_000_000])
+				.unwrap()
+				.1,
+			0
+		);
+
+		// Swaps back towards balance only pay the flat fee
+		let vbnc_before = Tokens::free_balance(VBNC, &6);
+		assert_ok!(StablePool::swap(RuntimeOrigin::signed(6), 0, 0, 1, 10_000_000_000, 0));
+		assert_eq!(Tokens::free_balance(VBNC, &6), vbnc_before + output);
+		assert_eq!(StablePool::get_swap_quote(0, 1, 0, 5_000_000_000).unwrap().1, 0);
+
+		assert_ok!(StablePool::remove_dynamic_fee(RuntimeOrigin::root(), 0));
+		assert_eq!(StablePool::get_swap_quote(0, 0, 1, 10_000_000_000).unwrap().1, 0);
+	});
+}
+
+// A BNC/VBNC base pool, and a DOT metapool holding its pool token.
+fn create_meta_pool() {
+	System::set_block_number(2);
+	assert_ok!(<Test as crate::Config>::MultiCurrency::deposit(VBNC, &6, 1_000_000_000_000u128));
+	assert_ok!(<Test as crate::Config>::MultiCurrency::deposit(DOT, &6, 1_000_000_000_000u128));
+	for assets in [vec![BNC, VBNC], vec![DOT, CurrencyId::BLP(0)]] {
+		assert_ok!(StablePool::create_pool(
+			RuntimeOrigin::root(),
+			assets,
+			vec![1u128, 1u128],
+			0u128,
+			20000000u128,
+			0u128,
+			220u128,
+			5,
+			5,
+			1000000000000u128
+		));
+	}
+	assert_ok!(StablePool::edit_token_rate(
+		RuntimeOrigin::root(),
+		0,
+		vec![(BNC, (1, 1)), (VBNC, (1, 1))]
+	));
+	assert_ok!(StablePool::edit_token_rate(RuntimeOrigin::root(), 1, vec![(DOT, (1, 1))]));
+	assert_ok!(StablePool::add_liquidity(
+		RuntimeOrigin::signed(6).into(),
+		0,
+		vec![100_000_000_000u128, 100_000_000_000u128],
+		0
+	));
+	assert_ok!(StablePool::add_liquidity(
+		RuntimeOrigin::signed(6).into(),
+		1,
+		vec![100_000_000_000u128, 100_000_000_000u128],
+		0
+	));
+}
+
+#[test]
+fn create_meta_pool_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		create_meta_pool();
+		assert_eq!(bifrost_stable_asset::BasePools::<Test>::get(0), None);
+		assert_eq!(bifrost_stable_asset::BasePools::<Test>::get(1), Some(0));
+		System::assert_has_event(RuntimeEvent::StableAsset(
+			bifrost_stable_asset::Event::MetaPoolCreated { pool_id: 1, base_pool_id: 0 },
+		));
+
+		// The base pool token is valued at the virtual price of the base pool.
+		let (total_supply, d) =
+			bifrost_stable_asset::Pallet::<Test>::get_base_pool_token_rate(1, CurrencyId::BLP(0))
+				.unwrap();
+		assert_eq!(total_supply, bifrost_stable_asset::Pools::<Test>::get(0).unwrap().total_supply);
+		assert!(d >= total_supply);
+		assert_eq!(
+			bifrost_stable_asset::Pallet::<Test>::get_base_pool_token_rate(0, CurrencyId::BLP(0)),
+			None
+		);
+
+		// Neither metapools of metapools nor pools of several pool tokens.
+		assert_noop!(
+			StablePool::create_pool(
+				RuntimeOrigin::root(),
+				vec![BNC, CurrencyId::BLP(1)],
+				vec![1u128, 1u128],
+				0u128,
+				0u128,
+				0u128,
+				220u128,
+				5,
+				5,
+				1000000000000u128
+			),
+			bifrost_stable_asset::Error::<Test>::ArgumentsError
+		);
+	});
+}
+
+#[test]
+fn swap_underlying_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		create_meta_pool();
+		assert_noop!(
+			StablePool::swap_underlying(RuntimeOrigin::signed(6), 0, BNC, VBNC, 1_000_000, 0),
+			Error::<Test>::NotMetaPool
+		);
+		assert_noop!(
+			StablePool::get_underlying_swap_output(1, DOT, MOVR, 1_000_000),
+			bifrost_stable_asset::Error::<Test>::ArgumentsMismatch
+		);
+
+		// From the metapool to the base pool, through the base pool token.
+		let amount = 1_000_000_000u128;
+		let output = StablePool::get_underlying_swap_output(1, DOT, BNC, amount).unwrap();
+		let lp_amount = StablePool::get_swap_output(1, 0, 1, amount).unwrap();
+		assert_eq!(output, StablePool::redeem_single_quote(0, lp_amount, 0).unwrap().amounts[0]);
+		assert!(output > amount * 99 / 100 && output < amount);
+		assert_noop!(
+			StablePool::swap_underlying(RuntimeOrigin::signed(6), 1, DOT, BNC, amount, output + 1),
+			bifrost_stable_asset::Error::<Test>::RedeemUnderMin
+		);
+		let dot_before = Tokens::free_balance(DOT, &6);
+		let bnc_before = Balances::free_balance(&6);
+		let lp_before = Tokens::free_balance(CurrencyId::BLP(0), &6);
+		assert_ok!(StablePool::swap_underlying(
+			RuntimeOrigin::signed(6),
+			1,
+			DOT,
+			BNC,
+			amount,
+			output
+		));
+		assert_eq!(Tokens::free_balance(DOT, &6), dot_before - amount);
+		assert_eq!(Balances::free_balance(&6), bnc_before + output);
+		assert_eq!(Tokens::free_balance(CurrencyId::BLP(0), &6), lp_before);
+
+		// From the base pool to the metapool, through the base pool token.
+		let output = StablePool::get_underlying_swap_output(1, VBNC, DOT, amount).unwrap();
+		assert!(output > amount * 99 / 100 && output < amount);
+		let vbnc_before = Tokens::free_balance(VBNC, &6);
+		let dot_before = Tokens::free_balance(DOT, &6);
+		assert_ok!(StablePool::swap_underlying(RuntimeOrigin::signed(6), 1, VBNC, DOT, amount, 0));
+		assert_eq!(Tokens::free_balance(VBNC, &6), vbnc_before - amount);
+		assert!(Tokens::free_balance(DOT, &6) >= dot_before + output * 999 / 1000);
+
+		// Within either pool.
+		assert_eq!(
+			StablePool::get_underlying_swap_output(1, BNC, VBNC, amount),
+			StablePool::get_swap_output(0, 0, 1, amount)
+		);
+		assert_eq!(
+			StablePool::get_underlying_swap_output(1, DOT, CurrencyId::BLP(0), amount),
+			StablePool::get_swap_output(1, 0, 1, amount)
+		);
+		assert_noop!(
+			StablePool::get_underlying_swap_output(1, BNC, BNC, amount),
+			bifrost_stable_asset::Error::<Test>::ArgumentsError
+		);
+	});
+}
+
+fn set_parameter_bounds() -> PoolParameterBounds<u128> {
+	let bounds = PoolParameterBounds {
+		min_a: 100,
+		max_a: 100_000,
+		max_fee: 100_000_000,
+		max_asset_precision: 1_000_000,
+		min_precision: 1_000_000_000_000,
+		max_precision: 1_000_000_000_000_000_000,
+	};
+	assert_ok!(StablePool::set_parameter_bounds(RuntimeOrigin::root(), bounds));
+	bounds
+}
+
+fn create_pool_with_deposit(who: u128, assets: Vec<CurrencyId>, a: u128) -> DispatchResult {
+	StablePool::create_pool(
+		RuntimeOrigin::signed(who),
+		assets,
+		vec![1u128, 1u128],
+		0u128,
+		20_000_000u128,
+		0u128,
+		a,
+		who,
+		who,
+		1_000_000_000_000u128,
+	)
+}
+
+#[test]
+fn create_pool_with_deposit_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		System::set_block_number(2);
+		assert_noop!(
+			create_pool_with_deposit(6, vec![DOT, VDOT], 10_000),
+			Error::<Test>::ParameterBoundsNotSet
+		);
+		assert_noop!(
+			StablePool::set_parameter_bounds(
+				RuntimeOrigin::signed(6),
+				PoolParameterBounds {
+					min_a: 0,
+					max_a: 0,
+					max_fee: 0,
+					max_asset_precision: 0,
+					min_precision: 0,
+					max_precision: 0,
+				}
+			),
+			DispatchError::BadOrigin
+		);
+		let bounds = set_parameter_bounds();
+		System::assert_last_event(RuntimeEvent::StableAsset(
+			bifrost_stable_asset::Event::ParameterBoundsSet { bounds },
+		));
+
+		assert_noop!(
+			create_pool_with_deposit(6, vec![DOT, VBNC], 10_000),
+			Error::<Test>::CurrencyNotRegistered
+		);
+		assert_noop!(
+			create_pool_with_deposit(6, vec![DOT, VDOT], 1_000_000),
+			Error::<Test>::ParameterOutOfBounds
+		);
+
+		assert_ok!(create_pool_with_deposit(6, vec![DOT, VDOT], 10_000));
+		assert_eq!(Balances::reserved_balance(&6), 1_000_000_000_000);
+		assert_eq!(
+			bifrost_stable_asset::PoolDeposits::<Test>::get(0),
+			Some(PoolDeposit { depositor: 6, amount: 1_000_000_000_000, created_at: 2 })
+		);
+		// Token rates are set, so the pool can be used at once.
+		assert_eq!(bifrost_stable_asset::TokenRateCaches::<Test>::get(0, DOT), Some((1, 1)));
+		assert_eq!(bifrost_stable_asset::TokenRateCaches::<Test>::get(0, VDOT), Some((1, 1)));
+		System::assert_last_event(RuntimeEvent::StableAsset(
+			bifrost_stable_asset::Event::PoolDepositReserved {
+				pool_id: 0,
+				depositor: 6,
+				amount: 1_000_000_000_000,
+			},
+		));
+	});
+}
+
+#[test]
+fn reap_pool_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		System::set_block_number(2);
+		set_parameter_bounds();
+		for _ in 0..3 {
+			assert_ok!(create_pool_with_deposit(6, vec![DOT, VDOT], 10_000));
+		}
+		assert_eq!(Balances::reserved_balance(&6), 3_000_000_000_000);
+
+		// Only the creator may reap an empty pool before the delay.
+		assert_noop!(
+			StablePool::reap_pool(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::ReapTooEarly
+		);
+		assert!(
+			bifrost_asset_registry::CurrencyMetadatas::<Test>::get(CurrencyId::BLP(0)).is_some()
+		);
+		assert_ok!(StablePool::reap_pool(RuntimeOrigin::signed(6), 0));
+		assert_eq!(Pools::<Test>::get(0), None);
+		assert_eq!(
+			bifrost_asset_registry::CurrencyMetadatas::<Test>::get(CurrencyId::BLP(0)),
+			None
+		);
+		assert_eq!(bifrost_stable_asset::PoolDeposits::<Test>::get(0), None);
+		assert_eq!(bifrost_stable_asset::TokenRateCaches::<Test>::get(0, DOT), None);
+		assert_eq!(Balances::reserved_balance(&6), 2_000_000_000_000);
+		System::assert_last_event(RuntimeEvent::StableAsset(
+			bifrost_stable_asset::Event::PoolReaped {
+				pool_id: 0,
+				depositor: 6,
+				amount: 1_000_000_000_000,
+			},
+		));
+		assert_noop!(
+			StablePool::reap_pool(RuntimeOrigin::signed(6), 0),
+			Error::<Test>::NoPoolDeposit
+		);
+
+		// Anyone may reap an empty pool after the delay.
+		assert_ok!(StablePool::add_liquidity(
+			RuntimeOrigin::signed(1),
+			1,
+			vec![1_000_000_000u128, 0],
+			0
+		));
+		System::set_block_number(102);
+		assert_noop!(
+			StablePool::reap_pool(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::PoolNotEmpty
+		);
+		assert_ok!(StablePool::reap_pool(RuntimeOrigin::signed(1), 2));
+		assert_eq!(Balances::reserved_balance(&6), 1_000_000_000_000);
+	});
+}
+
+#[test]
+fn freeze_pool_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		create_dynamic_fee_pool();
+		assert_noop!(
+			StablePool::freeze_pool(RuntimeOrigin::signed(6), 0),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			StablePool::freeze_pool(RuntimeOrigin::root(), 1),
+			bifrost_stable_asset::Error::<Test>::PoolNotFound
+		);
+		assert_ok!(StablePool::freeze_pool(RuntimeOrigin::root(), 0));
+		System::assert_last_event(RuntimeEvent::StableAsset(
+			bifrost_stable_asset::Event::PoolFrozen { pool_id: 0 },
+		));
+
+		// A frozen pool can only be redeemed from.
+		assert_noop!(
+			StablePool::swap(RuntimeOrigin::signed(6), 0, 0, 1, 1_000_000, 0),
+			bifrost_stable_asset::Error::<Test>::PoolFrozen
+		);
+		assert_noop!(
+			StablePool::add_liquidity(RuntimeOrigin::signed(6), 0, vec![1_000_000, 0], 0),
+			bifrost_stable_asset::Error::<Test>::PoolFrozen
+		);
+		assert_ok!(StablePool::redeem_proportion(
+			RuntimeOrigin::signed(6),
+			0,
+			1_000_000,
+			vec![0, 0]
+		));
+
+		assert_ok!(StablePool::unfreeze_pool(RuntimeOrigin::root(), 0));
+		assert_ok!(StablePool::swap(RuntimeOrigin::signed(6), 0, 0, 1, 1_000_000, 0));
+	});
+}
+
+#[test]
+fn set_canonical_pool_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		set_parameter_bounds();
+		// A pool created without governance isn't used for prices or routes.
+		assert_ok!(create_pool_with_deposit(6, vec![DOT, VDOT], 10_000));
+		assert_eq!(StablePool::get_pool_ids(&DOT, &VDOT), vec![]);
+		assert_eq!(CanonicalPools::<Test>::get(DOT, VDOT), None);
+
+		assert_noop!(
+			StablePool::set_canonical_pool(RuntimeOrigin::signed(6), DOT, VDOT, Some(0)),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			StablePool::set_canonical_pool(RuntimeOrigin::root(), DOT, VDOT, Some(1)),
+			bifrost_stable_asset::Error::<Test>::PoolNotFound
+		);
+		assert_noop!(
+			StablePool::set_canonical_pool(RuntimeOrigin::root(), DOT, BNC, Some(0)),
+			Error::<Test>::NotPoolAssets
+		);
+		assert_noop!(
+			StablePool::set_canonical_pool(RuntimeOrigin::root(), DOT, DOT, Some(0)),
+			Error::<Test>::NotPoolAssets
+		);
+
+		assert_ok!(StablePool::set_canonical_pool(RuntimeOrigin::root(), DOT, VDOT, Some(0)));
+		System::assert_last_event(RuntimeEvent::StableAsset(
+			bifrost_stable_asset::Event::CanonicalPoolSet {
+				asset_a: DOT,
+				asset_b: VDOT,
+				pool_id: Some(0),
+			},
+		));
+		assert_eq!(StablePool::get_pool_ids(&DOT, &VDOT), vec![(0, 0, 1)]);
+		assert_eq!(StablePool::get_pool_ids(&VDOT, &DOT), vec![(0, 1, 0)]);
+
+		assert_ok!(StablePool::set_canonical_pool(RuntimeOrigin::root(), VDOT, DOT, None));
+		assert_eq!(StablePool::get_pool_ids(&DOT, &VDOT), vec![]);
+	});
+}
+
+fn create_quote_pool() -> CurrencyId {
+	let pool_asset = CurrencyId::BLP(0);
+	assert_ok!(<Test as crate::Config>::MultiCurrency::deposit(DOT, &6, 1_000_000_000_000u128));
+	assert_ok!(<Test as crate::Config>::MultiCurrency::deposit(VDOT, &6, 1_000_000_000_000u128));
+	assert_ok!(StablePool::create_pool(
+		RuntimeOrigin::root(),
+		vec![DOT, VDOT],
+		vec![1u128, 1u128],
+		0u128,
+		0u128,
+		50_000_000u128,
+		220u128,
+		5,
+		5,
+		1000000000000u128
+	));
+	assert_ok!(StablePool::edit_token_rate(
+		RuntimeOrigin::root(),
+		0,
+		vec![(DOT, (1, 1)), (VDOT, (10, 11))]
+	));
+	let amounts = vec![100_000_000_000u128, 100_000_000_000u128];
+	assert_ok!(StablePool::add_liquidity(RuntimeOrigin::signed(6), 0, amounts, 0));
+	pool_asset
+}
+
+#[test]
+fn redeem_quotes_should_match_redeems() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		let pool_asset = create_quote_pool();
+		let amount = 10_000_000_000u128;
+
+		let quote = StablePool::redeem_proportion_quote(0, amount).unwrap();
+		assert_eq!(quote.input_amount, amount);
+		assert_eq!(quote.fee, 50_000_000);
+		assert_eq!(quote.fee_amount, amount / 200);
+		assert_eq!(quote.burn_amount, amount - quote.fee_amount);
+		let (dot, vdot) = (Tokens::free_balance(DOT, &6), Tokens::free_balance(VDOT, &6));
+		assert_ok!(StablePool::redeem_proportion(RuntimeOrigin::signed(6), 0, amount, vec![0, 0]));
+		assert_eq!(Tokens::free_balance(DOT, &6) - dot, quote.amounts[0]);
+		assert_eq!(Tokens::free_balance(VDOT, &6) - vdot, quote.amounts[1]);
+
+		let quote = StablePool::redeem_single_quote(0, amount, 1).unwrap();
+		assert_eq!(quote.amounts[0], 0);
+		assert_eq!(quote.burn_amount + quote.fee_amount, amount);
+		let vdot = Tokens::free_balance(VDOT, &6);
+		assert_ok!(StablePool::redeem_single(RuntimeOrigin::signed(6), 0, amount, 1, 0, 2));
+		assert_eq!(Tokens::free_balance(VDOT, &6) - vdot, quote.amounts[1]);
+
+		let amounts = vec![1_000_000_000u128, 2_000_000_000u128];
+		let quote = StablePool::redeem_multi_quote(0, amounts.clone()).unwrap();
+		assert_eq!(quote.amounts, amounts);
+		assert_eq!(quote.burn_amount + quote.fee_amount, quote.input_amount);
+		let lp_amount = Tokens::free_balance(pool_asset, &6);
+		assert_ok!(StablePool::redeem_multi(
+			RuntimeOrigin::signed(6),
+			0,
+			amounts,
+			quote.input_amount
+		));
+		assert_eq!(lp_amount - Tokens::free_balance(pool_asset, &6), quote.input_amount);
+
+		assert_err!(
+			StablePool::redeem_proportion_quote(1, amount),
+			bifrost_stable_asset::Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+#[test]
+fn get_pool_info_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		assert_eq!(StablePool::get_pool_info(0), None);
+		assert_eq!(StablePool::get_virtual_price(0), None);
+		let pool_asset = create_quote_pool();
+
+		let info = StablePool::get_pool_info(0).unwrap();
+		assert_eq!(info.pool_asset, pool_asset);
+		assert_eq!(info.assets, vec![DOT, VDOT]);
+		assert_eq!(info.reserves, vec![100_000_000_000u128, 100_000_000_000u128]);
+		assert_eq!(info.total_supply, Tokens::free_balance(pool_asset, &6));
+		assert_eq!(info.redeem_fee, 50_000_000);
+		assert_eq!(info.current_a, info.a);
+		assert_eq!(info.dynamic_fee, None);
+		assert_eq!(info.base_pool_id, None);
+		assert!(!info.frozen);
+
+		// Without mint or swap fees the invariant equals the total supply
+		assert_eq!(StablePool::get_virtual_price(0), Some(FixedU128::one()));
+		assert_ok!(StablePool::freeze_pool(RuntimeOrigin::root(), 0));
+		assert!(StablePool::get_pool_info(0).unwrap().frozen);
+	});
+}
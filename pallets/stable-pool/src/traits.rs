@@ -86,6 +86,28 @@ pub trait StablePoolHandler {
 		currency_id_in: &Self::CurrencyId,
 		currency_id_out: &Self::CurrencyId,
 	) -> Option<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)>;
+
+	/// The pools to swap between the assets through, with their token indexes: only the pool
+	/// designated for the pair by governance.
+	fn get_pool_ids(
+		currency_id_in: &Self::CurrencyId,
+		currency_id_out: &Self::CurrencyId,
+	) -> Vec<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)>;
+
+	/// The amount of each pool asset `amount` pool tokens stand for at the pool invariant.
+	fn get_lp_token_value(
+		pool_id: StableAssetPoolId,
+		amount: Self::Balance,
+	) -> Result<Vec<(Self::CurrencyId, Self::Balance)>, DispatchError>;
+
+	/// The time-weighted average price of `currency_id_in` in `currency_id_out` over the last
+	/// `window` blocks.
+	fn get_twap(
+		pool_id: StableAssetPoolId,
+		currency_id_in: PoolTokenIndex,
+		currency_id_out: PoolTokenIndex,
+		window: u32,
+	) -> Option<FixedU128>;
 }
 
 impl<T: Config> StablePoolHandler for Pallet<T> {
@@ -178,6 +200,29 @@ impl<T: Config> StablePoolHandler for Pallet<T> {
 	) -> Option<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)> {
 		Self::get_pool_id(currency_id_in, currency_id_out)
 	}
+
+	fn get_pool_ids(
+		currency_id_in: &Self::CurrencyId,
+		currency_id_out: &Self::CurrencyId,
+	) -> Vec<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)> {
+		Self::get_pool_ids(currency_id_in, currency_id_out)
+	}
+
+	fn get_lp_token_value(
+		pool_id: StableAssetPoolId,
+		amount: Self::Balance,
+	) -> Result<Vec<(Self::CurrencyId, Self::Balance)>, DispatchError> {
+		Self::get_lp_token_value(pool_id, amount)
+	}
+
+	fn get_twap(
+		pool_id: StableAssetPoolId,
+		currency_id_in: PoolTokenIndex,
+		currency_id_out: PoolTokenIndex,
+		window: u32,
+	) -> Option<FixedU128> {
+		Self::get_twap(pool_id, currency_id_in, currency_id_out, window.into())
+	}
 }
 
 impl StablePoolHandler for () {
@@ -265,4 +310,27 @@ impl StablePoolHandler for () {
 	) -> Option<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)> {
 		None
 	}
+
+	fn get_pool_ids(
+		_currency_id_in: &Self::CurrencyId,
+		_currency_id_out: &Self::CurrencyId,
+	) -> Vec<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)> {
+		Vec::new()
+	}
+
+	fn get_lp_token_value(
+		_pool_id: StableAssetPoolId,
+		_amount: Self::Balance,
+	) -> Result<Vec<(Self::CurrencyId, Self::Balance)>, DispatchError> {
+		Ok(Vec::new())
+	}
+
+	fn get_twap(
+		_pool_id: StableAssetPoolId,
+		_currency_id_in: PoolTokenIndex,
+		_currency_id_out: PoolTokenIndex,
+		_window: u32,
+	) -> Option<FixedU128> {
+		None
+	}
 }
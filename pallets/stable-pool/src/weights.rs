@@ -65,6 +65,14 @@ pub trait WeightInfo {
 	fn modify_a() -> Weight;
 	fn modify_fees() -> Weight;
 	fn modify_recipients() -> Weight;
+	fn config_dynamic_fee() -> Weight;
+	fn remove_dynamic_fee() -> Weight;
+	fn swap_underlying() -> Weight;
+	fn reap_pool() -> Weight;
+	fn set_parameter_bounds() -> Weight;
+	fn freeze_pool() -> Weight;
+	fn unfreeze_pool() -> Weight;
+	fn set_canonical_pool() -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -83,8 +91,8 @@ impl WeightInfo for () {
 		//  Estimated: `4128`
 		// Minimum execution time: 87_500_000 picoseconds.
 		Weight::from_parts(89_559_000, 4128)
-			.saturating_add(RocksDbWeight::get().reads(4_u64))
-			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().reads(18_u64))
+			.saturating_add(RocksDbWeight::get().writes(18_u64))
 	}
 	/// Storage: StableAsset PoolCount (r:1 w:0)
 	/// Proof Skipped: StableAsset PoolCount (max_values: Some(1), max_size: None, mode: Measured)
@@ -133,6 +141,10 @@ impl WeightInfo for () {
 	/// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Tokens TotalIssuance (r:1 w:1)
 	/// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	/// Storage: StableAsset PriceObservationCursors (r:1 w:1)
+	/// Proof Skipped: StableAsset PriceObservationCursors (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StableAsset PriceObservations (r:1 w:1)
+	/// Proof Skipped: StableAsset PriceObservations (max_values: None, max_size: None, mode: Measured)
 	fn add_liquidity() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2557`
@@ -154,6 +166,10 @@ impl WeightInfo for () {
 	/// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Tokens TotalIssuance (r:1 w:1)
 	/// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	/// Storage: StableAsset PriceObservationCursors (r:1 w:1)
+	/// Proof Skipped: StableAsset PriceObservationCursors (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StableAsset PriceObservations (r:1 w:1)
+	/// Proof Skipped: StableAsset PriceObservations (max_values: None, max_size: None, mode: Measured)
 	fn swap() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2239`
@@ -175,6 +191,10 @@ impl WeightInfo for () {
 	/// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Tokens TotalIssuance (r:1 w:1)
 	/// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	/// Storage: StableAsset PriceObservationCursors (r:1 w:1)
+	/// Proof Skipped: StableAsset PriceObservationCursors (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StableAsset PriceObservations (r:1 w:1)
+	/// Proof Skipped: StableAsset PriceObservations (max_values: None, max_size: None, mode: Measured)
 	fn redeem_proportion() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2239`
@@ -196,6 +216,10 @@ impl WeightInfo for () {
 	/// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Tokens TotalIssuance (r:1 w:1)
 	/// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	/// Storage: StableAsset PriceObservationCursors (r:1 w:1)
+	/// Proof Skipped: StableAsset PriceObservationCursors (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StableAsset PriceObservations (r:1 w:1)
+	/// Proof Skipped: StableAsset PriceObservations (max_values: None, max_size: None, mode: Measured)
 	fn redeem_single() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2239`
@@ -217,6 +241,10 @@ impl WeightInfo for () {
 	/// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Tokens TotalIssuance (r:1 w:1)
 	/// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	/// Storage: StableAsset PriceObservationCursors (r:1 w:1)
+	/// Proof Skipped: StableAsset PriceObservationCursors (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StableAsset PriceObservations (r:1 w:1)
+	/// Proof Skipped: StableAsset PriceObservations (max_values: None, max_size: None, mode: Measured)
 	fn redeem_multi() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2239`
@@ -259,4 +287,138 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `StableAsset::Pools` (r:1 w:0)
+	/// Proof: `StableAsset::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::DynamicFees` (r:0 w:1)
+	/// Proof: `StableAsset::DynamicFees` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn config_dynamic_fee() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `342`
+		//  Estimated: `3807`
+		// Minimum execution time: 15_134_000 picoseconds.
+		Weight::from_parts(15_612_000, 3807)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: `StableAsset::DynamicFees` (r:0 w:1)
+	/// Proof: `StableAsset::DynamicFees` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn remove_dynamic_fee() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 12_207_000 picoseconds.
+		Weight::from_parts(12_498_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: `StableAsset::BasePools` (r:1 w:0)
+	/// Proof: `StableAsset::BasePools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::Pools` (r:2 w:2)
+	/// Proof: `StableAsset::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::TokenRateCaches` (r:2 w:0)
+	/// Proof: `StableAsset::TokenRateCaches` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:4 w:4)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Storage: `AssetRegistry::CurrencyMetadatas` (r:2 w:0)
+	/// Proof: `AssetRegistry::CurrencyMetadatas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::TotalIssuance` (r:2 w:2)
+	/// Proof: `Tokens::TotalIssuance` (`max_values`: None, `max_size`: Some(38), added: 2513, mode: `MaxEncodedLen`)
+	/// Storage: `StableAsset::PriceObservationCursors` (r:2 w:2)
+	/// Proof: `StableAsset::PriceObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::PriceObservations` (r:2 w:2)
+	/// Proof: `StableAsset::PriceObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn swap_underlying() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3188`
+		//  Estimated: `8768`
+		// Minimum execution time: 691_812_000 picoseconds.
+		Weight::from_parts(704_355_000, 8768)
+			.saturating_add(RocksDbWeight::get().reads(15_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: `StableAsset::PoolDeposits` (r:1 w:1)
+	/// Proof: `StableAsset::PoolDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::Pools` (r:1 w:1)
+	/// Proof: `StableAsset::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::BasePools` (r:1 w:1)
+	/// Proof: `StableAsset::BasePools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:2 w:0)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `StableAsset::TokenRateCaches` (r:0 w:2)
+	/// Proof: `StableAsset::TokenRateCaches` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::DynamicFees` (r:0 w:1)
+	/// Proof: `StableAsset::DynamicFees` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::PriceObservations` (r:0 w:1)
+	/// Proof: `StableAsset::PriceObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::PriceObservationCursors` (r:0 w:1)
+	/// Proof: `StableAsset::PriceObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::FrozenPools` (r:0 w:1)
+	/// Proof: `StableAsset::FrozenPools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::CanonicalPools` (r:1 w:2)
+	/// Proof: `StableAsset::CanonicalPools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::PoolAssetPools` (r:0 w:1)
+	/// Proof: `StableAsset::PoolAssetPools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetRegistry::CurrencyMetadatas` (r:0 w:1)
+	/// Proof: `AssetRegistry::CurrencyMetadatas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn reap_pool() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1245`
+		//  Estimated: `6196`
+		// Minimum execution time: 98_412_000 picoseconds.
+		Weight::from_parts(100_873_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(16_u64))
+	}
+	/// Storage: `StableAsset::ParameterBounds` (r:0 w:1)
+	/// Proof: `StableAsset::ParameterBounds` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn set_parameter_bounds() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 11_921_000 picoseconds.
+		Weight::from_parts(12_310_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: `StableAsset::Pools` (r:1 w:0)
+	/// Proof: `StableAsset::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::FrozenPools` (r:0 w:1)
+	/// Proof: `StableAsset::FrozenPools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn freeze_pool() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `342`
+		//  Estimated: `3807`
+		// Minimum execution time: 14_906_000 picoseconds.
+		Weight::from_parts(15_337_000, 3807)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: `StableAsset::FrozenPools` (r:0 w:1)
+	/// Proof: `StableAsset::FrozenPools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn unfreeze_pool() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 11_874_000 picoseconds.
+		Weight::from_parts(12_196_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: `StableAsset::Pools` (r:1 w:0)
+	/// Proof: `StableAsset::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::CanonicalPools` (r:0 w:2)
+	/// Proof: `StableAsset::CanonicalPools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_canonical_pool() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `342`
+		//  Estimated: `3807`
+		// Minimum execution time: 16_081_000 picoseconds.
+		Weight::from_parts(16_704_000, 3807)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
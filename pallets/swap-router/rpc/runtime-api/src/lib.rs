@@ -0,0 +1,36 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use bifrost_primitives::{Balance, CurrencyId, SwapQuote, SwapType};
+use sp_api::decl_runtime_apis;
+
+decl_runtime_apis! {
+	pub trait SwapRouterRuntimeApi
+	{
+		/// Returns the best route swapping `amount` in, or `amount` out for `ExactOut`, with its
+		/// amounts and price impact.
+		fn get_best_quote(
+			asset_in: CurrencyId,
+			asset_out: CurrencyId,
+			amount: Balance,
+			swap_type: SwapType,
+		) -> Option<SwapQuote<Balance>>;
+	}
+}
@@ -0,0 +1,93 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use bifrost_primitives::{Balance, CurrencyId, SwapQuote, SwapType};
+pub use bifrost_swap_router_rpc_runtime_api::{self as runtime_api, SwapRouterRuntimeApi};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{ErrorCode, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+
+#[rpc(client, server)]
+pub trait SwapRouterRpcApi<BlockHash> {
+	/// rpc method for getting the best swap route with its amounts and price impact
+	#[method(name = "swapRouter_getBestQuote")]
+	fn get_best_quote(
+		&self,
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount: Balance,
+		swap_type: SwapType,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<SwapQuote<NumberOrHex>>>;
+}
+
+#[derive(Clone, Debug)]
+pub struct SwapRouterRpc<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> SwapRouterRpc<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: PhantomData }
+	}
+}
+
+#[async_trait]
+impl<C, Block> SwapRouterRpcApiServer<<Block as BlockT>::Hash> for SwapRouterRpc<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: SwapRouterRuntimeApi<Block>,
+{
+	fn get_best_quote(
+		&self,
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount: Balance,
+		swap_type: SwapType,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<SwapQuote<NumberOrHex>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let quote =
+			api.get_best_quote(at, asset_in, asset_out, amount, swap_type).map_err(|e| {
+				ErrorObject::owned(
+					ErrorCode::InternalError.code(),
+					"Failed to get swap router best quote.",
+					Some(format!("{:?}", e)),
+				)
+			})?;
+
+		Ok(quote.map(|quote| SwapQuote {
+			route: quote.route,
+			amount_in: NumberOrHex::Hex(quote.amount_in.into()),
+			amount_out: NumberOrHex::Hex(quote.amount_out.into()),
+			price_impact: quote.price_impact,
+		}))
+	}
+}
@@ -0,0 +1,126 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::{Pallet as SwapRouter, *};
+use bifrost_primitives::{DOT, VDOT};
+use frame_benchmarking::v1::{account, benchmarks, whitelisted_caller, BenchmarkError};
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
+
+const UNIT: Balance = 1_000_000_000_000;
+
+// Creates a stable pool of `asset_a` and `asset_b` at par, with liquidity from `provider`.
+fn create_stable_pool<T>(
+	provider: &T::AccountId,
+	asset_a: CurrencyId,
+	asset_b: CurrencyId,
+) -> Result<(), BenchmarkError>
+where
+	T: Config
+		+ bifrost_stable_pool::Config<CurrencyId = CurrencyId>
+		+ bifrost_stable_asset::Config<Balance = Balance, AtLeast64BitUnsigned = Balance>,
+{
+	let fee_account: T::AccountId = account("seed", 1, 1);
+	assert_ok!(bifrost_stable_pool::Pallet::<T>::create_pool(
+		RawOrigin::Root.into(),
+		vec![asset_a, asset_b],
+		vec![1u128, 1u128],
+		10000000u128,
+		20000000u128,
+		50000000u128,
+		10000u128,
+		fee_account.clone(),
+		fee_account,
+		1000000000000000000u128,
+	));
+	let pool_id = bifrost_stable_asset::PoolCount::<T>::get() - 1;
+	assert_ok!(bifrost_stable_pool::Pallet::<T>::edit_token_rate(
+		RawOrigin::Root.into(),
+		pool_id,
+		vec![(asset_a, (1u128, 1u128)), (asset_b, (1u128, 1u128))]
+	));
+	assert_ok!(bifrost_stable_pool::Pallet::<T>::add_liquidity(
+		RawOrigin::Signed(provider.clone()).into(),
+		pool_id,
+		vec![10_000 * UNIT, 10_000 * UNIT],
+		0
+	));
+	Ok(())
+}
+
+// Fills the routing assets and creates a stable pool for every pair of DOT, VDOT and the routing
+// assets but DOT/VDOT, so that every candidate path of up to `MaxHops` hops is quoted. Returns
+// an account holding DOT.
+fn init_routing_pools<T>() -> Result<T::AccountId, BenchmarkError>
+where
+	T: Config
+		+ bifrost_stable_pool::Config<CurrencyId = CurrencyId>
+		+ bifrost_stable_asset::Config<Balance = Balance, AtLeast64BitUnsigned = Balance>,
+{
+	let routing_assets: Vec<CurrencyId> = (0..T::MaxRoutingAssets::get())
+		.map(|token_id| CurrencyId::Token2(100 + token_id as u8))
+		.collect();
+	RoutingAssets::<T>::put(
+		BoundedVec::try_from(routing_assets.clone()).map_err(|_| BenchmarkError::Weightless)?,
+	);
+
+	let provider: T::AccountId = account("provider", 0, 0);
+	let assets: Vec<CurrencyId> = [DOT, VDOT].into_iter().chain(routing_assets).collect();
+	for asset in assets.iter() {
+		T::MultiCurrency::deposit(*asset, &provider, 1_000_000 * UNIT)?;
+	}
+	for (index, asset_a) in assets.iter().enumerate() {
+		for asset_b in assets.iter().skip(index + 1) {
+			if (*asset_a, *asset_b) != (DOT, VDOT) {
+				create_stable_pool::<T>(&provider, *asset_a, *asset_b)?;
+			}
+		}
+	}
+
+	let caller: T::AccountId = whitelisted_caller();
+	T::MultiCurrency::deposit(DOT, &caller, 1_000 * UNIT)?;
+	Ok(caller)
+}
+
+benchmarks! {
+	where_clause {
+		where
+			T: bifrost_stable_pool::Config<CurrencyId = CurrencyId>
+				+ bifrost_stable_asset::Config<Balance = Balance, AtLeast64BitUnsigned = Balance>,
+	}
+
+	swap_exact_in {
+		let caller = init_routing_pools::<T>()?;
+	}: _(RawOrigin::Signed(caller), DOT, VDOT, 10 * UNIT, 0)
+
+	swap_exact_out {
+		let caller = init_routing_pools::<T>()?;
+	}: _(RawOrigin::Signed(caller), DOT, VDOT, 10 * UNIT, 20 * UNIT)
+
+	set_routing_assets {
+		let origin = T::ControlOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let assets: Vec<CurrencyId> = (0..T::MaxRoutingAssets::get())
+			.map(|token_id| CurrencyId::Token2(token_id as u8))
+			.collect();
+		let assets = BoundedVec::try_from(assets).map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, assets)
+
+	impl_benchmark_test_suite!(SwapRouter, crate::mock::ExtBuilder::default().build(), crate::mock::Test);
+}
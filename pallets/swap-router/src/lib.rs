@@ -0,0 +1,507 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Swap router.
+//!
+//! Swaps between any two assets through the stable pools and the Zenlink pairs. Routes go
+//! through at most `MaxHops` pools, with the routing assets set by governance as intermediate
+//! assets. Each hop of a route takes the pool quoting best for it, and the route quoting best
+//! overall is executed.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+use bifrost_primitives::{
+	Balance, CurrencyId, SwapPool, SwapQuote, SwapStep, SwapType, TryConvertFrom,
+};
+use bifrost_stable_pool::traits::StablePoolHandler;
+use cumulus_primitives_core::ParaId;
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+pub use pallet::*;
+use sp_runtime::{traits::Zero, FixedPointNumber, FixedU128, Permill};
+use sp_std::{vec, vec::Vec};
+pub use weights::WeightInfo;
+use zenlink_protocol::{AssetId as ZenlinkAssetId, ExportZenlink};
+
+/// A small swap is `1 / SPOT_SAMPLE_DIVISOR` of the quoted amount, and prices the route for the
+/// price impact.
+pub const SPOT_SAMPLE_DIVISOR: Balance = 1_000;
+
+#[allow(type_alias_bounds)]
+pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		type WeightInfo: WeightInfo;
+
+		type ControlOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		type MultiCurrency: MultiCurrency<
+			AccountIdOf<Self>,
+			CurrencyId = CurrencyId,
+			Balance = Balance,
+		>;
+
+		type StablePoolHandler: StablePoolHandler<
+			Balance = Balance,
+			AccountId = AccountIdOf<Self>,
+			CurrencyId = CurrencyId,
+		>;
+
+		type DexOperator: ExportZenlink<Self::AccountId, ZenlinkAssetId>;
+
+		/// The parachain id, used to convert currency ids into Zenlink asset ids.
+		type ParachainId: Get<ParaId>;
+
+		/// The maximum number of pools a route goes through.
+		#[pallet::constant]
+		type MaxHops: Get<u32>;
+
+		/// The maximum number of routing assets. The swaps are charged for quoting every path
+		/// through `MaxRoutingAssets` routing assets of up to `MaxHops` hops, so the weights must
+		/// be benchmarked again when either changes.
+		#[pallet::constant]
+		type MaxRoutingAssets: Get<u32>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// `amount_in` of `asset_in` was swapped for `amount_out` of `asset_out` along `route`.
+		Swapped {
+			who: AccountIdOf<T>,
+			asset_in: CurrencyId,
+			asset_out: CurrencyId,
+			route: Vec<SwapStep>,
+			amount_in: Balance,
+			amount_out: Balance,
+		},
+		/// The intermediate assets of the routes were set.
+		RoutingAssetsSet { assets: Vec<CurrencyId> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The assets in and out are the same.
+		IdenticalAssets,
+		/// No route swaps between the assets.
+		NoRouteFound,
+		/// The swap would pay out less than the minimum amount out.
+		AmountOutBelowMin,
+		/// The swap would cost more than the maximum amount in.
+		AmountInAboveMax,
+		/// A routing asset is listed twice.
+		DuplicateRoutingAsset,
+	}
+
+	/// The assets routes may go through between the asset in and the asset out.
+	#[pallet::storage]
+	pub type RoutingAssets<T: Config> =
+		StorageValue<_, BoundedVec<CurrencyId, T::MaxRoutingAssets>, ValueQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Swaps exactly `amount_in` of `asset_in` for at least `min_amount_out` of `asset_out`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::swap_exact_in())]
+		pub fn swap_exact_in(
+			origin: OriginFor<T>,
+			asset_in: CurrencyId,
+			asset_out: CurrencyId,
+			#[pallet::compact] amount_in: Balance,
+			#[pallet::compact] min_amount_out: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_swap_exact_in(&who, asset_in, asset_out, amount_in, min_amount_out)?;
+			Ok(())
+		}
+
+		/// Swaps at most `max_amount_in` of `asset_in` for exactly `amount_out` of `asset_out`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::swap_exact_out())]
+		pub fn swap_exact_out(
+			origin: OriginFor<T>,
+			asset_in: CurrencyId,
+			asset_out: CurrencyId,
+			#[pallet::compact] amount_out: Balance,
+			#[pallet::compact] max_amount_in: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_swap_exact_out(&who, asset_in, asset_out, amount_out, max_amount_in)?;
+			Ok(())
+		}
+
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_routing_assets())]
+		pub fn set_routing_assets(
+			origin: OriginFor<T>,
+			assets: BoundedVec<CurrencyId, T::MaxRoutingAssets>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			for (index, asset) in assets.iter().enumerate() {
+				ensure!(!assets[..index].contains(asset), Error::<T>::DuplicateRoutingAsset);
+			}
+			RoutingAssets::<T>::put(assets.clone());
+
+			Self::deposit_event(Event::RoutingAssetsSet { assets: assets.into_inner() });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns the route quoting best for swapping `amount`, in for `ExactIn` and out for
+	/// `ExactOut`.
+	pub fn get_best_quote(
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount: Balance,
+		swap_type: SwapType,
+	) -> Result<SwapQuote<Balance>, DispatchError> {
+		ensure!(asset_in != asset_out, Error::<T>::IdenticalAssets);
+		ensure!(!amount.is_zero(), Error::<T>::NoRouteFound);
+
+		let mut best: Option<SwapQuote<Balance>> = None;
+		for path in Self::candidate_paths(asset_in, asset_out) {
+			let quote = match Self::quote_path(&path, amount, swap_type) {
+				Some(quote) => quote,
+				None => continue,
+			};
+			let is_better = best.as_ref().map_or(true, |best| match swap_type {
+				SwapType::ExactIn => quote.amount_out > best.amount_out,
+				SwapType::ExactOut => quote.amount_in < best.amount_in,
+			});
+			if is_better {
+				best = Some(quote);
+			}
+		}
+
+		let mut quote = best.ok_or(Error::<T>::NoRouteFound)?;
+		quote.price_impact = Self::price_impact(&quote.route, quote.amount_in, quote.amount_out);
+		Ok(quote)
+	}
+
+	/// Swaps `amount_in` through the best route and returns the amount received.
+	#[transactional]
+	pub fn do_swap_exact_in(
+		who: &AccountIdOf<T>,
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount_in: Balance,
+		min_amount_out: Balance,
+	) -> Result<Balance, DispatchError> {
+		let quote = Self::get_best_quote(asset_in, asset_out, amount_in, SwapType::ExactIn)?;
+		ensure!(quote.amount_out >= min_amount_out, Error::<T>::AmountOutBelowMin);
+
+		Self::execute_route(who, asset_in, asset_out, quote.route, amount_in, min_amount_out)
+	}
+
+	/// Swaps through the best route for `amount_out` and returns the amount paid.
+	#[transactional]
+	pub fn do_swap_exact_out(
+		who: &AccountIdOf<T>,
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount_out: Balance,
+		max_amount_in: Balance,
+	) -> Result<Balance, DispatchError> {
+		let quote = Self::get_best_quote(asset_in, asset_out, amount_out, SwapType::ExactOut)?;
+		ensure!(quote.amount_in <= max_amount_in, Error::<T>::AmountInAboveMax);
+
+		// The route is quoted against the current pools, so swapping the amount in it quotes
+		// pays out at least `amount_out`.
+		Self::execute_route(who, asset_in, asset_out, quote.route, quote.amount_in, amount_out)?;
+		Ok(quote.amount_in)
+	}
+
+	fn execute_route(
+		who: &AccountIdOf<T>,
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		route: Vec<SwapStep>,
+		amount_in: Balance,
+		min_amount_out: Balance,
+	) -> Result<Balance, DispatchError> {
+		let mut amount = amount_in;
+		for step in route.iter() {
+			let balance_before = T::MultiCurrency::free_balance(step.asset_out, who);
+			match step.pool {
+				SwapPool::Stable(pool_id) => {
+					let currency_id_in =
+						T::StablePoolHandler::get_pool_token_index(pool_id, step.asset_in)
+							.ok_or(Error::<T>::NoRouteFound)?;
+					let currency_id_out =
+						T::StablePoolHandler::get_pool_token_index(pool_id, step.asset_out)
+							.ok_or(Error::<T>::NoRouteFound)?;
+					T::StablePoolHandler::swap(
+						who,
+						pool_id,
+						currency_id_in,
+						currency_id_out,
+						amount,
+						Zero::zero(),
+					)?;
+				},
+				SwapPool::Zenlink => {
+					T::DexOperator::inner_swap_exact_assets_for_assets(
+						who,
+						amount,
+						Zero::zero(),
+						&Self::zenlink_path(step.asset_in, step.asset_out)?,
+						who,
+					)?;
+				},
+			}
+			amount =
+				T::MultiCurrency::free_balance(step.asset_out, who).saturating_sub(balance_before);
+		}
+		ensure!(amount >= min_amount_out, Error::<T>::AmountOutBelowMin);
+
+		Self::deposit_event(Event::Swapped {
+			who: who.clone(),
+			asset_in,
+			asset_out,
+			route,
+			amount_in,
+			amount_out: amount,
+		});
+		Ok(amount)
+	}
+
+	/// The asset paths from `asset_in` to `asset_out` of at most `MaxHops` hops, through the
+	/// routing assets.
+	fn candidate_paths(asset_in: CurrencyId, asset_out: CurrencyId) -> Vec<Vec<CurrencyId>> {
+		let routing_assets: Vec<CurrencyId> = RoutingAssets::<T>::get()
+			.into_iter()
+			.filter(|asset| *asset != asset_in && *asset != asset_out)
+			.collect();
+		let max_hops = T::MaxHops::get() as usize;
+
+		let mut paths = Vec::new();
+		let mut stack = vec![vec![asset_in]];
+		while let Some(path) = stack.pop() {
+			let hops = path.len();
+			let mut direct = path.clone();
+			direct.push(asset_out);
+			paths.push(direct);
+
+			if hops < max_hops {
+				for asset in routing_assets.iter() {
+					if !path.contains(asset) {
+						let mut next = path.clone();
+						next.push(*asset);
+						stack.push(next);
+					}
+				}
+			}
+		}
+		paths
+	}
+
+	/// Quotes `path` taking the best pool at each hop.
+	fn quote_path(
+		path: &[CurrencyId],
+		amount: Balance,
+		swap_type: SwapType,
+	) -> Option<SwapQuote<Balance>> {
+		let mut route = Vec::with_capacity(path.len().saturating_sub(1));
+		let mut current = amount;
+		match swap_type {
+			SwapType::ExactIn =>
+				for hop in path.windows(2) {
+					let (pool, amount_out) = Self::best_hop(hop[0], hop[1], current, swap_type)?;
+					route.push(SwapStep { pool, asset_in: hop[0], asset_out: hop[1] });
+					current = amount_out;
+				},
+			SwapType::ExactOut => {
+				for hop in path.windows(2).rev() {
+					let (pool, amount_in) = Self::best_hop(hop[0], hop[1], current, swap_type)?;
+					route.push(SwapStep { pool, asset_in: hop[0], asset_out: hop[1] });
+					current = amount_in;
+				}
+				route.reverse();
+			},
+		}
+
+		let (amount_in, amount_out) = match swap_type {
+			SwapType::ExactIn => (amount, current),
+			SwapType::ExactOut => (current, amount),
+		};
+		Some(SwapQuote { route, amount_in, amount_out, price_impact: Permill::zero() })
+	}
+
+	/// The pool quoting best for a single hop, with the amount out for `ExactIn` or in for
+	/// `ExactOut`.
+	fn best_hop(
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount: Balance,
+		swap_type: SwapType,
+	) -> Option<(SwapPool, Balance)> {
+		let mut quotes: Vec<(SwapPool, Balance)> =
+			T::StablePoolHandler::get_pool_ids(&asset_in, &asset_out)
+				.into_iter()
+				.filter_map(|(pool_id, currency_id_in, currency_id_out)| {
+					let quote = match swap_type {
+						SwapType::ExactIn => T::StablePoolHandler::get_swap_output(
+							pool_id,
+							currency_id_in,
+							currency_id_out,
+							amount,
+						),
+						SwapType::ExactOut => T::StablePoolHandler::get_swap_input(
+							pool_id,
+							currency_id_in,
+							currency_id_out,
+							amount,
+						),
+					};
+					quote.ok().map(|quote| (SwapPool::Stable(pool_id), quote))
+				})
+				.collect();
+		if let Some(quote) = Self::quote_zenlink(asset_in, asset_out, amount, swap_type) {
+			quotes.push((SwapPool::Zenlink, quote));
+		}
+
+		let quotes = quotes.into_iter().filter(|(_, quote)| !quote.is_zero());
+		match swap_type {
+			SwapType::ExactIn => quotes.max_by_key(|(_, quote)| *quote),
+			SwapType::ExactOut => quotes.min_by_key(|(_, quote)| *quote),
+		}
+	}
+
+	fn quote_zenlink(
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount: Balance,
+		swap_type: SwapType,
+	) -> Option<Balance> {
+		let path = Self::zenlink_path(asset_in, asset_out).ok()?;
+		match swap_type {
+			SwapType::ExactIn =>
+				T::DexOperator::get_amount_out_by_path(amount, &path).ok()?.last().copied(),
+			SwapType::ExactOut =>
+				T::DexOperator::get_amount_in_by_path(amount, &path).ok()?.first().copied(),
+		}
+	}
+
+	/// Swaps a small sample along `route`, and compares the output it would scale to with
+	/// `amount_out`.
+	fn price_impact(route: &[SwapStep], amount_in: Balance, amount_out: Balance) -> Permill {
+		let sample = amount_in / SPOT_SAMPLE_DIVISOR;
+		if sample.is_zero() {
+			return Permill::zero();
+		}
+		let sample_out = match Self::quote_route(route, sample) {
+			Some(sample_out) => sample_out,
+			None => return Permill::zero(),
+		};
+		let expected_out =
+			FixedU128::saturating_from_rational(sample_out, sample).saturating_mul_int(amount_in);
+		if expected_out <= amount_out {
+			return Permill::zero();
+		}
+		Permill::from_rational(expected_out.saturating_sub(amount_out), expected_out)
+	}
+
+	/// The amount out of swapping `amount_in` along `route`.
+	fn quote_route(route: &[SwapStep], amount_in: Balance) -> Option<Balance> {
+		route.iter().try_fold(amount_in, |amount, step| match step.pool {
+			SwapPool::Stable(pool_id) => {
+				let currency_id_in =
+					T::StablePoolHandler::get_pool_token_index(pool_id, step.asset_in)?;
+				let currency_id_out =
+					T::StablePoolHandler::get_pool_token_index(pool_id, step.asset_out)?;
+				T::StablePoolHandler::get_swap_output(
+					pool_id,
+					currency_id_in,
+					currency_id_out,
+					amount,
+				)
+				.ok()
+			},
+			SwapPool::Zenlink =>
+				Self::quote_zenlink(step.asset_in, step.asset_out, amount, SwapType::ExactIn),
+		})
+	}
+
+	fn zenlink_path(
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+	) -> Result<Vec<ZenlinkAssetId>, DispatchError> {
+		let para_id: u32 = T::ParachainId::get().into();
+		let convert = |currency_id| {
+			ZenlinkAssetId::try_convert_from(currency_id, para_id)
+				.map_err(|_| Error::<T>::NoRouteFound)
+		};
+		Ok(vec![convert(asset_in)?, convert(asset_out)?])
+	}
+}
+
+impl<T: Config> bifrost_primitives::SwapRouter<AccountIdOf<T>, Balance> for Pallet<T> {
+	fn get_best_quote(
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount: Balance,
+		swap_type: SwapType,
+	) -> Result<SwapQuote<Balance>, DispatchError> {
+		Self::get_best_quote(asset_in, asset_out, amount, swap_type)
+	}
+
+	fn swap_exact_in(
+		who: &AccountIdOf<T>,
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount_in: Balance,
+		min_amount_out: Balance,
+	) -> Result<Balance, DispatchError> {
+		Self::do_swap_exact_in(who, asset_in, asset_out, amount_in, min_amount_out)
+	}
+
+	fn swap_exact_out(
+		who: &AccountIdOf<T>,
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount_out: Balance,
+		max_amount_in: Balance,
+	) -> Result<Balance, DispatchError> {
+		Self::do_swap_exact_out(who, asset_in, asset_out, amount_out, max_amount_in)
+	}
+}
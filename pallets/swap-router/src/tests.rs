@@ -0,0 +1,242 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate::{mock::*, *};
+use bifrost_primitives::TryConvertFrom;
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use sp_runtime::DispatchError;
+use zenlink_protocol::AssetId as ZenlinkAssetId;
+
+// A DOT/VDOT stable pool at par, and a DOT/BNC Zenlink pair at 4 BNC per DOT.
+fn init() {
+	System::set_block_number(1);
+
+	assert_ok!(StablePool::create_pool(
+		RuntimeOrigin::root(),
+		vec![DOT, VDOT],
+		vec![1u128, 1u128],
+		0u128,
+		20000000u128,
+		0u128,
+		10000u128,
+		3,
+		3,
+		1000000000000u128,
+	));
+	assert_ok!(StablePool::edit_token_rate(
+		RuntimeOrigin::root(),
+		0,
+		vec![(DOT, (1, 1)), (VDOT, (1, 1))]
+	));
+	assert_ok!(StablePool::add_liquidity(
+		RuntimeOrigin::signed(ALICE),
+		0,
+		vec![unit(100_000), unit(100_000)],
+		0
+	));
+
+	add_zenlink_liquidity(DOT, BNC, unit(100_000), unit(400_000));
+}
+
+fn add_zenlink_liquidity(
+	currency_0: CurrencyId,
+	currency_1: CurrencyId,
+	amount_0: Balance,
+	amount_1: Balance,
+) {
+	let asset_0 = ZenlinkAssetId::try_convert_from(currency_0, 2001).unwrap();
+	let asset_1 = ZenlinkAssetId::try_convert_from(currency_1, 2001).unwrap();
+	assert_ok!(ZenlinkProtocol::create_pair(RuntimeOrigin::root(), asset_0, asset_1, ALICE));
+	assert_ok!(ZenlinkProtocol::add_liquidity(
+		RuntimeOrigin::signed(ALICE),
+		asset_0,
+		asset_1,
+		amount_0,
+		amount_1,
+		1,
+		1,
+		100
+	));
+}
+
+fn set_routing_assets(assets: Vec<CurrencyId>) {
+	assert_ok!(SwapRouter::set_routing_assets(
+		RuntimeOrigin::root(),
+		BoundedVec::try_from(assets).unwrap()
+	));
+}
+
+#[test]
+fn set_routing_assets_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			SwapRouter::set_routing_assets(
+				RuntimeOrigin::signed(ALICE),
+				BoundedVec::try_from(vec![DOT]).unwrap()
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			SwapRouter::set_routing_assets(
+				RuntimeOrigin::root(),
+				BoundedVec::try_from(vec![DOT, DOT]).unwrap()
+			),
+			Error::<Test>::DuplicateRoutingAsset
+		);
+
+		set_routing_assets(vec![DOT, BNC]);
+		assert_eq!(RoutingAssets::<Test>::get().into_inner(), vec![DOT, BNC]);
+		System::assert_last_event(RuntimeEvent::SwapRouter(Event::RoutingAssetsSet {
+			assets: vec![DOT, BNC],
+		}));
+	});
+}
+
+#[test]
+fn get_best_quote_should_pick_the_best_pool() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		init();
+		// A shallow DOT/VDOT pair quotes worse than the stable pool.
+		add_zenlink_liquidity(DOT, VDOT, unit(1_000), unit(1_000));
+
+		let quote = SwapRouter::get_best_quote(DOT, VDOT, unit(100), SwapType::ExactIn).unwrap();
+		assert_eq!(
+			quote.route,
+			vec![SwapStep { pool: SwapPool::Stable(0), asset_in: DOT, asset_out: VDOT }]
+		);
+		assert_eq!(quote.amount_in, unit(100));
+		assert_eq!(quote.amount_out, StablePool::get_swap_output(0, 0, 1, unit(100)).unwrap());
+	});
+}
+
+#[test]
+fn get_best_quote_should_route_through_routing_assets() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		init();
+
+		assert_noop!(
+			SwapRouter::get_best_quote(VDOT, BNC, unit(10), SwapType::ExactIn),
+			Error::<Test>::NoRouteFound
+		);
+		assert_noop!(
+			SwapRouter::get_best_quote(DOT, DOT, unit(10), SwapType::ExactIn),
+			Error::<Test>::IdenticalAssets
+		);
+
+		set_routing_assets(vec![DOT]);
+		let quote = SwapRouter::get_best_quote(VDOT, BNC, unit(10), SwapType::ExactIn).unwrap();
+		assert_eq!(
+			quote.route,
+			vec![
+				SwapStep { pool: SwapPool::Stable(0), asset_in: VDOT, asset_out: DOT },
+				SwapStep { pool: SwapPool::Zenlink, asset_in: DOT, asset_out: BNC },
+			]
+		);
+		// Close to 4 BNC per VDOT, less the fees.
+		assert!(quote.amount_out < unit(40) && quote.amount_out > unit(39));
+		assert!(quote.price_impact < Permill::from_percent(1));
+
+		// A large swap moves the Zenlink price.
+		let quote = SwapRouter::get_best_quote(VDOT, BNC, unit(10_000), SwapType::ExactIn).unwrap();
+		assert!(quote.price_impact > Permill::from_percent(5));
+
+		// Quoting the output back gives the input.
+		let quote_out =
+			SwapRouter::get_best_quote(VDOT, BNC, unit(39), SwapType::ExactOut).unwrap();
+		assert_eq!(quote_out.route.len(), 2);
+		assert_eq!(quote_out.amount_out, unit(39));
+		assert!(quote_out.amount_in < unit(10));
+	});
+}
+
+#[test]
+fn swap_exact_in_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		init();
+		set_routing_assets(vec![DOT]);
+
+		let quote = SwapRouter::get_best_quote(VDOT, BNC, unit(10), SwapType::ExactIn).unwrap();
+		assert_noop!(
+			SwapRouter::swap_exact_in(
+				RuntimeOrigin::signed(BOB),
+				VDOT,
+				BNC,
+				unit(10),
+				quote.amount_out + 1
+			),
+			Error::<Test>::AmountOutBelowMin
+		);
+
+		let vdot_before = Currencies::free_balance(VDOT, &BOB);
+		let bnc_before = Currencies::free_balance(BNC, &BOB);
+		assert_ok!(SwapRouter::swap_exact_in(
+			RuntimeOrigin::signed(BOB),
+			VDOT,
+			BNC,
+			unit(10),
+			quote.amount_out
+		));
+		assert_eq!(Currencies::free_balance(VDOT, &BOB), vdot_before - unit(10));
+		assert_eq!(Currencies::free_balance(BNC, &BOB), bnc_before + quote.amount_out);
+		assert_eq!(Currencies::free_balance(DOT, &BOB), unit(1_000));
+		System::assert_last_event(RuntimeEvent::SwapRouter(Event::Swapped {
+			who: BOB,
+			asset_in: VDOT,
+			asset_out: BNC,
+			route: quote.route,
+			amount_in: unit(10),
+			amount_out: quote.amount_out,
+		}));
+	});
+}
+
+#[test]
+fn swap_exact_out_should_work() {
+	ExtBuilder::default().new_test_ext().build().execute_with(|| {
+		init();
+		set_routing_assets(vec![DOT]);
+
+		let quote = SwapRouter::get_best_quote(VDOT, BNC, unit(20), SwapType::ExactOut).unwrap();
+		assert_noop!(
+			SwapRouter::swap_exact_out(
+				RuntimeOrigin::signed(BOB),
+				VDOT,
+				BNC,
+				unit(20),
+				quote.amount_in - 1
+			),
+			Error::<Test>::AmountInAboveMax
+		);
+
+		let vdot_before = Currencies::free_balance(VDOT, &BOB);
+		let bnc_before = Currencies::free_balance(BNC, &BOB);
+		assert_ok!(SwapRouter::swap_exact_out(
+			RuntimeOrigin::signed(BOB),
+			VDOT,
+			BNC,
+			unit(20),
+			quote.amount_in
+		));
+		assert_eq!(Currencies::free_balance(VDOT, &BOB), vdot_before - quote.amount_in);
+		assert!(Currencies::free_balance(BNC, &BOB) >= bnc_before + unit(20));
+	});
+}
@@ -0,0 +1,125 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Autogenerated weights for `bifrost_swap_router`
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 32.0.0
+//! DATE: 2024-06-05, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `yml`, CPU: `AMD Ryzen 9 3950X 16-Core Processor`
+//! WASM-EXECUTION: `Compiled`, CHAIN: `Some("bifrost-polkadot-local")`, DB CACHE: 1024
+
+// Executed Command:
+// ./target/release/bifrost
+// benchmark
+// pallet
+// --chain=bifrost-polkadot-local
+// --pallet=bifrost-swap-router
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --header=./HEADER-GPL3
+// --output=./bifrost-swap-router.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{constants::RocksDbWeight, Weight}};
+use core::marker::PhantomData;
+
+/// Weight functions for `bifrost_swap_router`.
+impl WeightInfo for () {
+	/// Storage: `SwapRouter::RoutingAssets` (r:1 w:0)
+	/// Proof: `SwapRouter::RoutingAssets` (`max_values`: Some(1), `max_size`: Some(83), added: 578, mode: `MaxEncodedLen`)
+	/// Storage: `StableAsset::CanonicalPools` (r:81 w:0)
+	/// Proof: `StableAsset::CanonicalPools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::Pools` (r:44 w:2)
+	/// Proof: `StableAsset::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::TokenRateCaches` (r:88 w:0)
+	/// Proof: `StableAsset::TokenRateCaches` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ZenlinkProtocol::PairStatuses` (r:81 w:0)
+	/// Proof: `ZenlinkProtocol::PairStatuses` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:8 w:8)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:3 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `StableAsset::PriceObservationCursors` (r:2 w:2)
+	/// Proof: `StableAsset::PriceObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::PriceObservations` (r:2 w:2)
+	/// Proof: `StableAsset::PriceObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn swap_exact_in() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `16104`
+		//  Estimated: `238314`
+		// Minimum execution time: 6_812_530_000 picoseconds.
+		Weight::from_parts(6_894_217_000, 0)
+			.saturating_add(Weight::from_parts(0, 238314))
+			.saturating_add(RocksDbWeight::get().reads(310))
+			.saturating_add(RocksDbWeight::get().writes(15))
+	}
+	/// Storage: `SwapRouter::RoutingAssets` (r:1 w:0)
+	/// Proof: `SwapRouter::RoutingAssets` (`max_values`: Some(1), `max_size`: Some(83), added: 578, mode: `MaxEncodedLen`)
+	/// Storage: `StableAsset::CanonicalPools` (r:81 w:0)
+	/// Proof: `StableAsset::CanonicalPools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::Pools` (r:44 w:2)
+	/// Proof: `StableAsset::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::TokenRateCaches` (r:88 w:0)
+	/// Proof: `StableAsset::TokenRateCaches` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ZenlinkProtocol::PairStatuses` (r:81 w:0)
+	/// Proof: `ZenlinkProtocol::PairStatuses` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:8 w:8)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:3 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `StableAsset::PriceObservationCursors` (r:2 w:2)
+	/// Proof: `StableAsset::PriceObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::PriceObservations` (r:2 w:2)
+	/// Proof: `StableAsset::PriceObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn swap_exact_out() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `16104`
+		//  Estimated: `238314`
+		// Minimum execution time: 7_306_941_000 picoseconds.
+		Weight::from_parts(7_402_388_000, 0)
+			.saturating_add(Weight::from_parts(0, 238314))
+			.saturating_add(RocksDbWeight::get().reads(310))
+			.saturating_add(RocksDbWeight::get().writes(15))
+	}
+	/// Storage: `SwapRouter::RoutingAssets` (r:0 w:1)
+	/// Proof: `SwapRouter::RoutingAssets` (`max_values`: Some(1), `max_size`: Some(83), added: 578, mode: `MaxEncodedLen`)
+	fn set_routing_assets() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_236_000 picoseconds.
+		Weight::from_parts(8_517_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+}
+
+
+/// Weight functions needed for bifrost_swap_router.
+pub trait WeightInfo {
+	fn swap_exact_in() -> Weight;
+	fn swap_exact_out() -> Weight;
+	fn set_routing_assets() -> Weight;
+}
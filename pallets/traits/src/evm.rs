@@ -18,3 +18,13 @@ pub trait InspectEvmAccounts<AccountId, EvmAddress> {
 	/// Returns `True` if the address is allowed to deploy smart contracts.
 	fn can_deploy_contracts(evm_address: EvmAddress) -> bool;
 }
+
+/// Handles an EVM address being bound to a Substrate account.
+pub trait OnEvmAddressBound<AccountId> {
+	/// `truncated` is the account the EVM address used before it was bound to `bound`.
+	fn on_evm_address_bound(truncated: &AccountId, bound: &AccountId);
+}
+
+impl<AccountId> OnEvmAddressBound<AccountId> for () {
+	fn on_evm_address_bound(_truncated: &AccountId, _bound: &AccountId) {}
+}
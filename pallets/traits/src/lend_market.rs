@@ -44,6 +44,25 @@ pub trait LendMarket<CurrencyId, AccountId, Balance> {
 		asset_id: CurrencyId,
 		amount: Balance,
 	) -> Result<(), DispatchError>;
+	/// The fee charged for flash loaning `amount` of `asset_id`.
+	fn flash_loan_fee(asset_id: CurrencyId, amount: Balance) -> Balance;
+	/// Lends `amount` of `asset_id` to `borrower` while `f` runs. `borrower` must hold `amount`
+	/// plus the flash loan fee once `f` returns, otherwise everything is reverted.
+	fn do_flash_loan<R>(
+		borrower: &AccountId,
+		asset_id: CurrencyId,
+		amount: Balance,
+		f: impl FnOnce() -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError>;
+	/// Lends `amount` of `asset_id` to `borrower` while `f` runs, then records the loan plus
+	/// the flash loan fee as a borrow of `borrower` instead of taking it back. The borrow must
+	/// be covered by the collateral `borrower` holds once `f` returns.
+	fn do_flash_borrow<R>(
+		borrower: &AccountId,
+		asset_id: CurrencyId,
+		amount: Balance,
+		f: impl FnOnce() -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError>;
 }
 
 pub trait LendMarketPositionDataProvider<CurrencyId, AccountId, Balance> {
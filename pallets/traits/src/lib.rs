@@ -1,7 +1,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use bifrost_primitives::{CurrencyId, PriceDetail};
+use bifrost_primitives::{CurrencyId, PriceDetail, TimeStampedPrice};
 use num_bigint::{BigUint, ToBigUint};
+use sp_std::vec::Vec;
 
 pub mod evm;
 pub mod lend_market;
@@ -15,6 +16,27 @@ pub trait EmergencyCallFilter<Call> {
 pub trait PriceFeeder {
 	fn get_price(asset_id: &CurrencyId) -> Option<PriceDetail>;
 	fn get_normal_price(asset_id: &CurrencyId) -> Option<u128>;
+
+	/// Whether the price of `asset_id` passes the freshness and deviation checks of the feeder.
+	fn is_price_valid(_asset_id: &CurrencyId) -> bool {
+		true
+	}
+}
+
+/// A source of prices aggregated with the oracle, such as the TWAP of a pool.
+pub trait PriceSource {
+	fn get_prices(asset_id: &CurrencyId) -> Vec<TimeStampedPrice>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(3)]
+impl PriceSource for Tuple {
+	fn get_prices(asset_id: &CurrencyId) -> Vec<TimeStampedPrice> {
+		let mut prices = Vec::new();
+		for_tuples!( #(
+			prices.extend(Tuple::get_prices(asset_id));
+		)* );
+		prices
+	}
 }
 
 pub trait EmergencyPriceFeeder<CurrencyId, Price> {
@@ -2202,6 +2202,10 @@ impl<T: Config> VtokenMintingInterface<AccountIdOf<T>, CurrencyIdOf<T>, BalanceO
 		MinimumRedeem::<T>::get(vtoken_id)
 	}
 
+	fn get_mint_fee_rate() -> Permill {
+		Fees::<T>::get().0
+	}
+
 	fn get_token_pool(currency_id: CurrencyId) -> BalanceOf<T> {
 		Self::token_pool(currency_id)
 	}
@@ -0,0 +1,87 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CurrencyId, XcmOperationType};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// How a fee currency pays the fee.
+#[derive(
+	Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, Serialize, Deserialize,
+)]
+pub enum FeePaymentMethod {
+	/// The fee is paid in BNC
+	Native,
+	/// The currency is swapped for BNC on the DEX
+	Swap,
+	/// The currency is charged at its oracle price in BNC
+	Oracle,
+}
+
+/// Why a fee currency can't pay the fee.
+#[derive(
+	Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, Serialize, Deserialize,
+)]
+pub enum FeeCurrencySkipReason {
+	/// The account holds too little of the currency
+	InsufficientBalance,
+	/// The DEX has no path from the currency to BNC
+	NoSwapPath,
+	/// The oracle has no valid price for the currency or for BNC
+	NoPrice,
+}
+
+/// A currency of the fee charge order list of an account and what paying the fee in it costs.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeCurrencyCandidate<Balance> {
+	pub currency_id: CurrencyId,
+	pub method: FeePaymentMethod,
+	/// The amount of the currency charged, zero if it can't be quoted
+	pub amount: Balance,
+	/// The currencies the fee is swapped through, empty unless it is swapped
+	pub route: Vec<CurrencyId>,
+	/// Why the currency is passed over, `None` if it can pay the fee
+	pub skip_reason: Option<FeeCurrencySkipReason>,
+}
+
+/// How the fee of a transaction is made up and charged.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeBreakdown<Balance> {
+	/// The inclusion fee of the transaction in BNC
+	pub base_fee: Balance,
+	/// The XCM operation the extra fee pays for, `None` without an extra fee
+	pub xcm_operation: Option<XcmOperationType>,
+	pub extra_fee_currency: CurrencyId,
+	/// The extra fee in its currency
+	pub extra_fee_amount: Balance,
+	/// The BNC swapped for the extra fee
+	pub extra_fee_bnc_amount: Balance,
+	/// The currencies BNC is swapped through for the extra fee
+	pub extra_fee_route: Vec<CurrencyId>,
+	/// The base and extra fees in BNC
+	pub total_bnc_fee: Balance,
+	/// The first candidate able to pay the fee, if any
+	pub fee_payment: Option<FeeCurrencyCandidate<Balance>>,
+	/// Every currency of the fee charge order list of the account, in order
+	pub candidates: Vec<FeeCurrencyCandidate<Balance>>,
+}
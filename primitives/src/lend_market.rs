@@ -0,0 +1,69 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CurrencyId, Price};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{FixedU128, RuntimeDebug};
+use sp_std::vec::Vec;
+
+/// The position of an account in a lend-market market, values are in USD.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct MarketPosition<Balance> {
+	pub asset_id: CurrencyId,
+	/// The underlying amount of the deposits
+	pub supply_amount: Balance,
+	pub supply_value: FixedU128,
+	pub borrow_amount: Balance,
+	pub borrow_value: FixedU128,
+	pub is_collateral: bool,
+	/// The price of `asset_id` at which the account becomes liquidatable, the other prices
+	/// being unchanged. `None` if no price of `asset_id` makes it liquidatable.
+	pub liquidation_price: Option<Price>,
+}
+
+/// The positions of an account in lend-market and its health.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AccountHealth<Balance> {
+	pub positions: Vec<MarketPosition<Balance>>,
+	/// The collateral value weighted by the liquidation thresholds
+	pub collateral_value: FixedU128,
+	pub borrow_value: FixedU128,
+	/// `collateral_value / borrow_value`, the account can be liquidated below 1.
+	/// `None` if the account has no borrows.
+	pub health_factor: Option<FixedU128>,
+}
+
+/// The largest liquidation of an account at the current prices.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct MaxLiquidation<Balance> {
+	pub liquidation_asset_id: CurrencyId,
+	pub max_repay_amount: Balance,
+	pub collateral_asset_id: CurrencyId,
+	/// The underlying amount of `collateral_asset_id` seized for `max_repay_amount`
+	pub max_seizable_amount: Balance,
+}
+
+/// An account below the health factor asked for.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct LiquidationCandidate<AccountId, Balance> {
+	pub account: AccountId,
+	pub health_factor: FixedU128,
+	/// `None` if the account can't be liquidated yet.
+	pub liquidation: Option<MaxLiquidation<Balance>>,
+}
@@ -32,9 +32,17 @@ use xcm::v4::{prelude::*, Asset, Location};
 use xcm_executor::traits::{AssetTransferError, TransferType, XcmAssetTransfers};
 
 pub mod currency;
+mod flexible_fee;
+mod lend_market;
 mod salp;
+mod stable_pool;
+mod swap_router;
 pub mod traits;
+pub use flexible_fee::*;
+pub use lend_market::*;
 pub use salp::*;
+pub use stable_pool::*;
+pub use swap_router::*;
 
 #[cfg(test)]
 mod tests;
@@ -155,18 +163,6 @@ pub type DerivativeIndex = u16;
 
 pub type TimeStampedPrice = orml_oracle::TimestampedValue<Price, Moment>;
 
-#[derive(
-	Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, PartialOrd, Ord, scale_info::TypeInfo,
-)]
-pub enum ExtraFeeName {
-	SalpContribute,
-	StatemineTransfer,
-	VoteVtoken,
-	VoteRemoveDelegatorVote,
-	NoExtraFee,
-	EthereumTransfer,
-}
-
 // For vtoken-minting and slp modules
 #[derive(Encode, Decode, Clone, RuntimeDebug, Eq, TypeInfo, MaxEncodedLen)]
 pub enum TimeUnit {
@@ -302,7 +298,18 @@ impl XcmAssetTransfers for DoNothingExecuteXcm {
 	}
 }
 
-#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+#[derive(
+	Encode,
+	Decode,
+	Eq,
+	PartialEq,
+	Copy,
+	Clone,
+	RuntimeDebug,
+	TypeInfo,
+	serde::Serialize,
+	serde::Deserialize,
+)]
 pub enum XcmOperationType {
 	// SALP operations
 	UmpContributeTransact,
@@ -333,17 +340,3 @@ pub enum XcmOperationType {
 	EthereumTransfer,
 	TeleportAssets,
 }
-
-pub struct ExtraFeeInfo {
-	pub extra_fee_name: ExtraFeeName,
-	pub extra_fee_currency: CurrencyId,
-}
-
-impl Default for ExtraFeeInfo {
-	fn default() -> Self {
-		Self {
-			extra_fee_name: ExtraFeeName::NoExtraFee,
-			extra_fee_currency: CurrencyId::Native(TokenSymbol::BNC),
-		}
-	}
-}
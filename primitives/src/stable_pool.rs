@@ -0,0 +1,75 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CurrencyId, PoolId};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// A stable pool as shown to integrators. Fees are in the fee precision of the pool, and A in
+/// its A precision.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StablePoolInfo<AccountId, Balance, BlockNumber> {
+	pub pool_id: PoolId,
+	pub pool_asset: CurrencyId,
+	pub assets: Vec<CurrencyId>,
+	pub precisions: Vec<Balance>,
+	/// The balances of the pool, scaled by the token rates and precisions of the assets
+	pub balances: Vec<Balance>,
+	/// The amounts of each asset held by the pool
+	pub reserves: Vec<Balance>,
+	pub total_supply: Balance,
+	pub precision: Balance,
+	pub mint_fee: Balance,
+	pub swap_fee: Balance,
+	pub redeem_fee: Balance,
+	/// The imbalance fee and highest fee of the pool, if it charges a dynamic fee
+	pub dynamic_fee: Option<(Balance, Balance)>,
+	/// The A of the pool at the current block, ramping from `a` at `a_block` to `future_a` at
+	/// `future_a_block`
+	pub current_a: Balance,
+	pub a: Balance,
+	pub a_block: BlockNumber,
+	pub future_a: Balance,
+	pub future_a_block: BlockNumber,
+	pub account_id: AccountId,
+	pub fee_recipient: AccountId,
+	pub yield_recipient: AccountId,
+	/// The pool whose pool token the pool holds, if it is a metapool
+	pub base_pool_id: Option<PoolId>,
+	pub frozen: bool,
+}
+
+/// The quote of a redemption from a stable pool.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RedeemQuote<Balance> {
+	/// The amounts of each pool asset received
+	pub amounts: Vec<Balance>,
+	/// The pool tokens paid, fee included
+	pub input_amount: Balance,
+	/// The pool tokens burnt
+	pub burn_amount: Balance,
+	/// The pool tokens paid to the fee recipient
+	pub fee_amount: Balance,
+	/// The effective redeem fee, the dynamic fee included, in the fee precision of the pool
+	pub fee: Balance,
+}
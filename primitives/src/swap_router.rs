@@ -0,0 +1,87 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CurrencyId, PoolId};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_runtime::{Permill, RuntimeDebug};
+use sp_std::vec::Vec;
+
+/// A pool the swap router can swap through.
+#[derive(
+	Encode,
+	Decode,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+pub enum SwapPool {
+	/// A stable-asset pool
+	Stable(PoolId),
+	/// The Zenlink pair of the two assets
+	Zenlink,
+}
+
+/// A hop of a swap route, from `asset_in` to `asset_out` through `pool`.
+#[derive(
+	Encode,
+	Decode,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapStep {
+	pub pool: SwapPool,
+	pub asset_in: CurrencyId,
+	pub asset_out: CurrencyId,
+}
+
+/// Which side of a swap is exact.
+#[derive(
+	Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, Serialize, Deserialize,
+)]
+pub enum SwapType {
+	ExactIn,
+	ExactOut,
+}
+
+/// The best route found for a swap and its amounts.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapQuote<Balance> {
+	pub route: Vec<SwapStep>,
+	pub amount_in: Balance,
+	pub amount_out: Balance,
+	/// The share of the output lost to the size of the swap, against the price of a small swap
+	/// along the same route
+	pub price_impact: Permill,
+}
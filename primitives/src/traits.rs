@@ -21,8 +21,8 @@
 #![allow(clippy::unnecessary_cast)]
 
 use crate::{
-	AssetIds, CurrencyId, DerivativeIndex, ExtraFeeInfo, LeasePeriod, ParaId, PoolId, RedeemType,
-	TokenId, TokenSymbol, XcmOperationType,
+	AssetIds, CurrencyId, DerivativeIndex, LeasePeriod, ParaId, PoolId, RedeemType, SwapQuote,
+	SwapType, TokenId, TokenSymbol, XcmOperationType,
 };
 use frame_support::pallet_prelude::{DispatchResultWithPostInfo, Weight};
 use parity_scale_codec::{Decode, Encode, FullCodec};
@@ -30,9 +30,9 @@ use sp_runtime::{
 	traits::{
 		AccountIdConversion, AtLeast32BitUnsigned, ConstU32, MaybeSerializeDeserialize, Zero,
 	},
-	BoundedVec, DispatchError, DispatchResult, TokenError, TypeId,
+	BoundedVec, DispatchError, DispatchResult, Perbill, Permill, TokenError, TypeId,
 };
-use sp_std::{fmt::Debug, vec::Vec};
+use sp_std::{fmt::Debug, vec, vec::Vec};
 
 pub trait TokenInfo {
 	fn name(&self) -> Option<&str>;
@@ -224,6 +224,8 @@ pub trait CurrencyIdRegister<CurrencyId> {
 		last_slot: crate::LeasePeriod,
 	) -> DispatchResult;
 	fn register_blp_metadata(pool_id: PoolId, decimals: u8) -> DispatchResult;
+	fn check_currency_registered(currency_id: CurrencyId) -> bool;
+	fn remove_blp_metadata(pool_id: PoolId) -> DispatchResult;
 }
 
 impl<CurrencyId> CurrencyIdRegister<CurrencyId> for () {
@@ -306,6 +308,14 @@ impl<CurrencyId> CurrencyIdRegister<CurrencyId> for () {
 	fn register_blp_metadata(_pool_id: PoolId, _decimals: u8) -> DispatchResult {
 		Ok(())
 	}
+
+	fn check_currency_registered(_currency_id: CurrencyId) -> bool {
+		false
+	}
+
+	fn remove_blp_metadata(_pool_id: PoolId) -> DispatchResult {
+		Ok(())
+	}
 }
 
 /// The interface to call farming pallet functions.
@@ -314,6 +324,47 @@ pub trait FarmingInfo<Balance, CurrencyId> {
 	fn get_token_shares(pool_id: PoolId, currency_id: CurrencyId) -> Balance;
 }
 
+/// The interface to farm in the farming pallet on behalf of an account.
+pub trait FarmingOperator<AccountId, Balance, CurrencyId, BlockNumber> {
+	/// Get the shares `who` holds in the pool.
+	fn get_shares(who: &AccountId, pool_id: PoolId) -> Balance;
+
+	/// Get the tokens a deposit of `shares` takes, which is also what withdrawing them returns.
+	fn get_share_tokens(
+		pool_id: PoolId,
+		shares: Balance,
+	) -> Result<Vec<(CurrencyId, Balance)>, DispatchError>;
+
+	/// Get the tokens the shares of the pool are made of, and their proportions.
+	fn get_tokens_proportion(pool_id: PoolId) -> Vec<(CurrencyId, Perbill)>;
+
+	/// Get the most shares `tokens` can be deposited for.
+	fn get_max_shares(pool_id: PoolId, tokens: Vec<(CurrencyId, Balance)>) -> Balance;
+
+	/// Get the currencies the pool rewards.
+	fn get_reward_currencies(pool_id: PoolId) -> Vec<CurrencyId>;
+
+	/// Get how long withdrawn shares stay locked before their tokens are paid out.
+	fn get_withdraw_limit_time(pool_id: PoolId) -> Result<BlockNumber, DispatchError>;
+
+	/// Get how long an account has to wait between two claims of its rewards.
+	fn get_claim_limit_time(pool_id: PoolId) -> Result<BlockNumber, DispatchError>;
+
+	/// Deposit the tokens of `shares` from `who`.
+	fn deposit(who: &AccountId, pool_id: PoolId, shares: Balance) -> DispatchResult;
+
+	/// Withdraw `shares` of `who`, their tokens being paid out by `claim_withdrawn` once the
+	/// withdraw limit time is over. Like the `withdraw` call, capped by the pool's withdraw limit
+	/// count, though a withdrawal unlocking in the same block as an earlier one is merged into it.
+	fn withdraw(who: &AccountId, pool_id: PoolId, shares: Balance) -> DispatchResult;
+
+	/// Pay the rewards of `who` out.
+	fn claim(who: &AccountId, pool_id: PoolId) -> DispatchResult;
+
+	/// Pay the tokens of the withdrawals of `who` that are no longer locked out.
+	fn claim_withdrawn(who: &AccountId, pool_id: PoolId) -> DispatchResult;
+}
+
 pub trait VtokenMintingInterface<AccountId, CurrencyId, Balance> {
 	fn mint(
 		exchanger: AccountId,
@@ -347,6 +398,7 @@ pub trait VtokenMintingInterface<AccountId, CurrencyId, Balance> {
 	fn token_id(vtoken_id: CurrencyId) -> Option<CurrencyId>;
 	fn get_token_pool(currency_id: CurrencyId) -> Balance;
 	fn get_minimums_redeem(vtoken_id: CurrencyId) -> Balance;
+	fn get_mint_fee_rate() -> Permill;
 	fn get_astar_parachain_id() -> u32;
 	fn get_moonbeam_parachain_id() -> u32;
 	fn get_hydradx_parachain_id() -> u32;
@@ -416,6 +468,10 @@ impl<AccountId, CurrencyId, Balance: Zero> VtokenMintingInterface<AccountId, Cur
 		Zero::zero()
 	}
 
+	fn get_mint_fee_rate() -> Permill {
+		Zero::zero()
+	}
+
 	fn get_astar_parachain_id() -> u32 {
 		0
 	}
@@ -477,7 +533,8 @@ where
 }
 
 pub trait FeeGetter<RuntimeCall> {
-	fn get_fee_info(call: &RuntimeCall) -> ExtraFeeInfo;
+	/// The currency `call` operates on, if it pays the extra fee of the call.
+	fn get_fee_currency(call: &RuntimeCall) -> Option<CurrencyId>;
 }
 
 pub trait DerivativeAccountHandler<CurrencyId, Balance> {
@@ -555,6 +612,11 @@ impl<CurrencyId, Balance, AccountId> SlpHostingFeeProvider<CurrencyId, Balance,
 /// Provides account's fee payment currency id
 pub trait AccountFeeCurrency<AccountId> {
 	fn get(a: &AccountId) -> CurrencyId;
+
+	/// Provides the currencies the account pays fees in, in order of preference
+	fn get_order_list(a: &AccountId) -> Vec<CurrencyId> {
+		vec![Self::get(a)]
+	}
 }
 
 /// Provides account's balance of fee asset currency in a given currency
@@ -568,3 +630,64 @@ pub trait PriceProvider {
 
 	fn get_price(asset_a: CurrencyId, asset_b: CurrencyId) -> Option<Self::Price>;
 }
+
+/// Swaps between any two assets through the best route across the stable pools and the Zenlink
+/// pairs.
+pub trait SwapRouter<AccountId, Balance> {
+	/// Returns the best route swapping `amount` in, or `amount` out for `ExactOut`.
+	fn get_best_quote(
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount: Balance,
+		swap_type: SwapType,
+	) -> Result<SwapQuote<Balance>, DispatchError>;
+
+	/// Swaps `amount_in` through the best route, and returns the amount received.
+	fn swap_exact_in(
+		who: &AccountId,
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount_in: Balance,
+		min_amount_out: Balance,
+	) -> Result<Balance, DispatchError>;
+
+	/// Swaps for `amount_out` through the best route, and returns the amount paid.
+	fn swap_exact_out(
+		who: &AccountId,
+		asset_in: CurrencyId,
+		asset_out: CurrencyId,
+		amount_out: Balance,
+		max_amount_in: Balance,
+	) -> Result<Balance, DispatchError>;
+}
+
+impl<AccountId, Balance> SwapRouter<AccountId, Balance> for () {
+	fn get_best_quote(
+		_asset_in: CurrencyId,
+		_asset_out: CurrencyId,
+		_amount: Balance,
+		_swap_type: SwapType,
+	) -> Result<SwapQuote<Balance>, DispatchError> {
+		Err(DispatchError::Unavailable)
+	}
+
+	fn swap_exact_in(
+		_who: &AccountId,
+		_asset_in: CurrencyId,
+		_asset_out: CurrencyId,
+		_amount_in: Balance,
+		_min_amount_out: Balance,
+	) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Unavailable)
+	}
+
+	fn swap_exact_out(
+		_who: &AccountId,
+		_asset_in: CurrencyId,
+		_asset_out: CurrencyId,
+		_amount_out: Balance,
+		_max_amount_in: Balance,
+	) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Unavailable)
+	}
+}
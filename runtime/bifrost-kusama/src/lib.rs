@@ -75,9 +75,9 @@ pub use bifrost_primitives::{
 		CheckSubAccount, FarmingInfo, FeeGetter, VtokenMintingInterface, VtokenMintingOperator,
 		XcmDestWeightAndFeeHandler,
 	},
-	AccountId, Amount, AssetIds, Balance, BlockNumber, CurrencyId, CurrencyIdMapping,
-	DistributionId, ExtraFeeInfo, ExtraFeeName, Liquidity, Moment, ParaId, PoolId, Price, Rate,
-	Ratio, RpcContributionStatus, Shortfall, TimeUnit, TokenSymbol,
+	AccountHealth, AccountId, Amount, AssetIds, Balance, BlockNumber, CurrencyId,
+	CurrencyIdMapping, DistributionId, LiquidationCandidate, Liquidity, Moment, ParaId, PoolId,
+	Price, Rate, Ratio, RpcContributionStatus, Shortfall, TimeUnit, TokenSymbol,
 };
 pub use bifrost_runtime_common::{
 	cent, constants::time::*, dollar, micro, milli, millicent, AuraId, CouncilCollective,
@@ -146,7 +146,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_version: 12000,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 0,
 };
 
@@ -230,6 +230,7 @@ parameter_types! {
 	pub const CommissionPalletId: PalletId = PalletId(*b"bf/comms");
 	pub IncentivePoolAccount: PalletId = PalletId(*b"bf/inpoo");
 	pub const FarmingGaugeRewardIssuerPalletId: PalletId = PalletId(*b"bf/fmgar");
+	pub const FarmingVaultPalletId: PalletId = PalletId(*b"bf/fmvlt");
 	pub const FlexibleFeePalletId: PalletId = PalletId(*b"bf/flexi");
 }
 
@@ -835,7 +836,7 @@ where
 			frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
 			frame_system::CheckNonce::<Runtime>::from(nonce),
 			frame_system::CheckWeight::<Runtime>::new(),
-			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+			bifrost_flexible_fee::ChargeTransactionPaymentWithSponsor::<Runtime>::new(tip, None),
 			frame_metadata_hash_extension::CheckMetadataHash::<Runtime>::new(false),
 		);
 		let raw_payload = SignedPayload::new(call, extra)
@@ -1020,31 +1021,15 @@ impl bifrost_vesting::Config for Runtime {
 
 pub struct ExtraFeeMatcher;
 impl FeeGetter<RuntimeCall> for ExtraFeeMatcher {
-	fn get_fee_info(c: &RuntimeCall) -> ExtraFeeInfo {
+	fn get_fee_currency(c: &RuntimeCall) -> Option<CurrencyId> {
 		match *c {
-			RuntimeCall::Salp(bifrost_salp::Call::contribute { .. }) => ExtraFeeInfo {
-				extra_fee_name: ExtraFeeName::SalpContribute,
-				extra_fee_currency: RelayCurrencyId::get(),
-			},
-			RuntimeCall::XcmInterface(bifrost_xcm_interface::Call::transfer_statemine_assets {
-				..
-			}) => ExtraFeeInfo {
-				extra_fee_name: ExtraFeeName::StatemineTransfer,
-				extra_fee_currency: RelayCurrencyId::get(),
-			},
 			RuntimeCall::VtokenVoting(bifrost_vtoken_voting::Call::vote { vtoken, .. }) =>
-				ExtraFeeInfo {
-					extra_fee_name: ExtraFeeName::VoteVtoken,
-					extra_fee_currency: vtoken.to_token().unwrap_or(vtoken),
-				},
+				Some(vtoken.to_token().unwrap_or(vtoken)),
 			RuntimeCall::VtokenVoting(bifrost_vtoken_voting::Call::remove_delegator_vote {
 				vtoken,
 				..
-			}) => ExtraFeeInfo {
-				extra_fee_name: ExtraFeeName::VoteRemoveDelegatorVote,
-				extra_fee_currency: vtoken.to_token().unwrap_or(vtoken),
-			},
-			_ => ExtraFeeInfo::default(),
+			}) => Some(vtoken.to_token().unwrap_or(vtoken)),
+			_ => None,
 		}
 	}
 }
@@ -1071,6 +1056,8 @@ impl bifrost_flexible_fee::Config for Runtime {
 	type RelaychainCurrencyId = RelayCurrencyId;
 	type XcmRouter = XcmRouter;
 	type PalletId = FlexibleFeePalletId;
+	type PriceFeeder = Prices;
+	type MaxSponsorListLen = ConstU32<50>;
 }
 
 parameter_types! {
@@ -1317,6 +1304,7 @@ impl bifrost_vstoken_conversion::Config for Runtime {
 
 parameter_types! {
 	pub const WhitelistMaximumLimit: u32 = 10;
+	pub const MaxEmissionSchedulePoints: u32 = 50;
 }
 
 impl bifrost_farming::Config for Runtime {
@@ -1333,6 +1321,7 @@ impl bifrost_farming::Config for Runtime {
 	type BlockNumberToBalance = ConvertInto;
 	type WhitelistMaximumLimit = WhitelistMaximumLimit;
 	type GaugeRewardIssuer = FarmingGaugeRewardIssuerPalletId;
+	type MaxEmissionSchedulePoints = MaxEmissionSchedulePoints;
 }
 
 parameter_types! {
@@ -1590,6 +1579,12 @@ impl bifrost_stable_asset::Config for Runtime {
 	type WeightInfo = ();
 	type ListingOrigin = EitherOfDiverse<MoreThanHalfCouncil, EnsureRootOrAllTechnicalCommittee>;
 	type EnsurePoolAssetId = EnsurePoolAssetId;
+	type MaxPriceObservations = ConstU32<1200>;
+}
+
+parameter_types! {
+	pub StablePoolDeposit: Balance = 1_000 * BNCS;
+	pub const StablePoolReapDelay: BlockNumber = 30 * DAYS;
 }
 
 impl bifrost_stable_pool::Config for Runtime {
@@ -1601,6 +1596,45 @@ impl bifrost_stable_pool::Config for Runtime {
 	type VtokenMinting = VtokenMinting;
 	type CurrencyIdConversion = AssetIdMaps<Runtime>;
 	type CurrencyIdRegister = AssetIdMaps<Runtime>;
+	type DepositCurrencyId = NativeCurrencyId;
+	type PoolDeposit = StablePoolDeposit;
+	type PoolReapDelay = StablePoolReapDelay;
+}
+
+parameter_types! {
+	pub const SwapRouterMaxHops: u32 = 3;
+	pub const SwapRouterMaxRoutingAssets: u32 = 8;
+}
+
+impl bifrost_swap_router::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = weights::bifrost_swap_router::BifrostWeight<Runtime>;
+	type ControlOrigin = TechAdminOrCouncil;
+	type MultiCurrency = Currencies;
+	type StablePoolHandler = StablePool;
+	type DexOperator = ZenlinkProtocol;
+	type ParachainId = ParachainInfo;
+	type MaxHops = SwapRouterMaxHops;
+	type MaxRoutingAssets = SwapRouterMaxRoutingAssets;
+}
+
+parameter_types! {
+	pub const FarmingVaultMaxUnlockings: u32 = 10;
+	pub const FarmingVaultDeadShares: Balance = 1_000_000;
+}
+
+impl bifrost_farming_vault::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = weights::bifrost_farming_vault::BifrostWeight<Runtime>;
+	type ControlOrigin = TechAdminOrCouncil;
+	type MultiCurrency = Currencies;
+	type Farming = Farming;
+	type SwapRouter = SwapRouter;
+	type VtokenMinting = VtokenMinting;
+	type TreasuryAccount = BifrostTreasuryAccount;
+	type PalletId = FarmingVaultPalletId;
+	type MaxUnlockings = FarmingVaultMaxUnlockings;
+	type DeadShares = FarmingVaultDeadShares;
 }
 
 parameter_types! {
@@ -1659,6 +1693,10 @@ impl DataFeeder<CurrencyId, TimeStampedPrice, AccountId> for AggregatedDataProvi
 	}
 }
 
+parameter_types! {
+	pub const StablePoolTwapWindow: u32 = 300;
+}
+
 impl pallet_prices::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Source = AggregatedDataProvider;
@@ -1667,9 +1705,47 @@ impl pallet_prices::Config for Runtime {
 	type RelayCurrency = RelayCurrencyId;
 	type CurrencyIdConvert = AssetIdMaps<Runtime>;
 	type Assets = Currencies;
+	type VtokenMinting = VtokenMinting;
+	type StablePoolHandler = StablePool;
+	type AggregatedSources = (
+		pallet_prices::DataProviderSource<
+			bifrost_stable_pool::StablePoolTwapProvider<
+				Runtime,
+				AggregatedDataProvider,
+				StablePoolTwapWindow,
+			>,
+		>,
+		pallet_prices::DataProviderSource<pallet_prices::ZenlinkTwapProvider<Runtime>>,
+	);
+	type MarketPriceWindow = StablePoolTwapWindow;
+	type DexOperator = ZenlinkProtocol;
+	type ParachainId = ParachainInfo;
+	type UnixTime = Timestamp;
 	type WeightInfo = pallet_prices::weights::SubstrateWeight<Runtime>;
 }
 
+/// Calls that can be dispatched with flash loaned funds: swaps, minting vtokens and repaying
+/// lend market borrows. Calls that dispatch other calls are not allowed.
+pub struct FlashLoanCallFilter;
+impl Contains<RuntimeCall> for FlashLoanCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::System(frame_system::Call::remark { .. }) |
+				RuntimeCall::ZenlinkProtocol(_) |
+				RuntimeCall::StablePool(_) |
+				RuntimeCall::VtokenMinting(
+					bifrost_vtoken_minting::Call::mint { .. } |
+						bifrost_vtoken_minting::Call::redeem { .. }
+				) | RuntimeCall::LendMarket(
+				lend_market::Call::repay_borrow { .. } |
+					lend_market::Call::repay_borrow_all { .. } |
+					lend_market::Call::liquidate_borrow { .. }
+			)
+		)
+	}
+}
+
 impl lend_market::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type PalletId = LendMarketPalletId;
@@ -1681,6 +1757,10 @@ impl lend_market::Config for Runtime {
 	type Assets = Currencies;
 	type RewardAssetId = NativeCurrencyId;
 	type LiquidationFreeAssetId = RelayCurrencyId;
+	type SwapRouter = SwapRouter;
+	type MaxKeeperLiquidationsPerBlock = ConstU32<5>;
+	type RuntimeCall = RuntimeCall;
+	type FlashLoanCallFilter = FlashLoanCallFilter;
 }
 
 parameter_types! {
@@ -1912,6 +1992,8 @@ construct_runtime! {
 		OracleMembership: pallet_membership::<Instance3> = 134,
 		LeverageStaking: leverage_staking = 135,
 		ChannelCommission: bifrost_channel_commission = 136,
+		SwapRouter: bifrost_swap_router = 137,
+		FarmingVault: bifrost_farming_vault = 138,
 	}
 }
 
@@ -1942,7 +2024,7 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	bifrost_flexible_fee::ChargeTransactionPaymentWithSponsor<Runtime>,
 	frame_metadata_hash_extension::CheckMetadataHash<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
@@ -1986,6 +2068,11 @@ pub mod migrations {
 			Runtime,
 			governance::fellowship::FellowshipCollectiveInstance,
 		>,
+		bifrost_flexible_fee::migrations::v3::InitExtraFeeRules<
+			Runtime,
+			crate::migration::flexible_fee::ExtraFeeRules,
+		>,
+		bifrost_stable_asset::migration::InitPoolIndexes<Runtime>,
 	);
 }
 
@@ -2015,6 +2102,8 @@ mod benches {
 		[bifrost_slp, Slp]
 		[bifrost_slpx, Slpx]
 		[bifrost_stable_pool, StablePool]
+		[bifrost_swap_router, SwapRouter]
+		[bifrost_farming_vault, FarmingVault]
 		[bifrost_system_maker, SystemMaker]
 		[bifrost_system_staking, SystemStaking]
 		[bifrost_token_issuer, TokenIssuer]
@@ -2160,6 +2249,10 @@ impl_runtime_apis! {
 				_ => (CurrencyId::Native(TokenSymbol::BNC), Zero::zero()),
 			}
 		}
+
+		fn get_fee_breakdown(who: AccountId, fee: Balance, utx: <Block as BlockT>::Extrinsic) -> Option<bifrost_primitives::FeeBreakdown<Balance>> {
+			FlexibleFee::get_fee_breakdown(&who, fee, &utx.function).ok()
+		}
 	}
 
 	// zenlink runtime outer apis
@@ -2299,6 +2392,10 @@ impl_runtime_apis! {
 		fn get_gauge_rewards(who: AccountId, pid: PoolId) -> Vec<(CurrencyId, Balance)> {
 			Farming::get_gauge_rewards(&who, pid).unwrap_or(Vec::new())
 		}
+
+		fn get_remaining_funded_blocks(pid: PoolId) -> Vec<(CurrencyId, Option<BlockNumber>)> {
+			Farming::get_remaining_funded_blocks(pid).unwrap_or(Vec::new())
+		}
 	}
 
 	impl bifrost_stable_pool_rpc_runtime_api::StablePoolRuntimeApi<Block> for Runtime {
@@ -2317,6 +2414,75 @@ impl_runtime_apis! {
 		) -> Balance {
 			StablePool::add_liquidity_amount(pool_id, amounts).unwrap_or(Zero::zero())
 		}
+
+		fn get_swap_quote(
+			pool_id: u32,
+			currency_id_in: u32,
+			currency_id_out: u32,
+			amount: Balance,
+		) -> (Balance, Balance) {
+			StablePool::get_swap_quote(pool_id, currency_id_in, currency_id_out, amount).unwrap_or_default()
+		}
+
+		fn add_liquidity_quote(
+			pool_id: u32,
+			amounts: Vec<Balance>,
+		) -> (Balance, Balance) {
+			StablePool::add_liquidity_quote(pool_id, amounts).unwrap_or_default()
+		}
+
+		fn get_twap(
+			pool_id: u32,
+			currency_id_in: u32,
+			currency_id_out: u32,
+			window: u32,
+		) -> Option<Price> {
+			StablePool::get_twap(pool_id, currency_id_in, currency_id_out, window)
+		}
+
+		fn get_underlying_swap_output(
+			pool_id: u32,
+			currency_id_in: CurrencyId,
+			currency_id_out: CurrencyId,
+			amount: Balance,
+		) -> Balance {
+			StablePool::get_underlying_swap_output(pool_id, currency_id_in, currency_id_out, amount).unwrap_or(Zero::zero())
+		}
+
+		fn get_pool_info(pool_id: u32) -> Option<bifrost_primitives::StablePoolInfo<AccountId, Balance, BlockNumber>> {
+			StablePool::get_pool_info(pool_id)
+		}
+
+		fn get_virtual_price(pool_id: u32) -> Option<Price> {
+			StablePool::get_virtual_price(pool_id)
+		}
+
+		fn get_lp_token_value(pool_id: u32, amount: Balance) -> Vec<(CurrencyId, Balance)> {
+			StablePool::get_lp_token_value(pool_id, amount).unwrap_or_default()
+		}
+
+		fn quote_redeem_proportion(pool_id: u32, amount: Balance) -> Option<bifrost_primitives::RedeemQuote<Balance>> {
+			StablePool::redeem_proportion_quote(pool_id, amount).ok()
+		}
+
+		fn quote_redeem_single(pool_id: u32, amount: Balance, i: u32) -> Option<bifrost_primitives::RedeemQuote<Balance>> {
+			StablePool::redeem_single_quote(pool_id, amount, i).ok()
+		}
+
+		fn quote_redeem_multi(pool_id: u32, amounts: Vec<Balance>) -> Option<bifrost_primitives::RedeemQuote<Balance>> {
+			StablePool::redeem_multi_quote(pool_id, amounts).ok()
+		}
+	}
+
+	impl bifrost_swap_router_rpc_runtime_api::SwapRouterRuntimeApi<Block> for Runtime {
+		fn get_best_quote(
+			asset_in: CurrencyId,
+			asset_out: CurrencyId,
+			amount: Balance,
+			swap_type: bifrost_primitives::SwapType,
+		) -> Option<bifrost_primitives::SwapQuote<Balance>> {
+			SwapRouter::get_best_quote(asset_in, asset_out, amount, swap_type).ok()
+		}
 	}
 
 	impl lend_market_rpc_runtime_api::LendMarketApi<Block, AccountId, Balance> for Runtime {
@@ -2331,6 +2497,18 @@ impl_runtime_apis! {
 		fn get_liquidation_threshold_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError> {
 			LendMarket::get_account_liquidation_threshold_liquidity(&account)
 		}
+
+		fn get_market_bad_debt(asset_id: CurrencyId) -> Balance {
+			LendMarket::bad_debt(asset_id)
+		}
+
+		fn get_account_health(account: AccountId) -> Result<AccountHealth<Balance>, DispatchError> {
+			LendMarket::get_account_health(&account)
+		}
+
+		fn get_liquidation_candidates(health_factor: FixedU128, start_key: Option<Vec<u8>>, limit: u32) -> (Vec<LiquidationCandidate<AccountId, Balance>>, Option<Vec<u8>>) {
+			LendMarket::get_liquidation_candidates(health_factor, start_key, limit)
+		}
 	}
 
 	impl bifrost_vtoken_minting_rpc_runtime_api::VtokenMintingRuntimeApi<Block, CurrencyId> for Runtime {
@@ -74,4 +74,113 @@ impl<T: frame_system::Config> bifrost_flexible_fee::WeightInfo for BifrostWeight
 		Weight::from_parts(10_160_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	// Storage: FlexibleFee OracleFeeCurrencies (r:0 w:1)
+	// Proof Skipped: FlexibleFee OracleFeeCurrencies (max_values: None, max_size: None, mode: Measured)
+	fn set_oracle_fee_currency() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 10_741 nanoseconds.
+		Weight::from_parts(11_257_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: FlexibleFee Sponsors (r:1 w:1)
+	// Proof Skipped: FlexibleFee Sponsors (max_values: None, max_size: None, mode: Measured)
+	fn set_sponsor_policy() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 14_318 nanoseconds.
+		Weight::from_parts(14_318_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: FlexibleFee Sponsors (r:1 w:0)
+	// Proof Skipped: FlexibleFee Sponsors (max_values: None, max_size: None, mode: Measured)
+	// Storage: FlexibleFee SponsoredCalls (r:0 w:1)
+	// Proof Skipped: FlexibleFee SponsoredCalls (max_values: None, max_size: None, mode: Measured)
+	fn set_sponsored_calls(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 12_504 nanoseconds.
+		Weight::from_parts(12_504_000, 0)
+			// Standard Error: 3_000
+			.saturating_add(Weight::from_parts(2_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	// Storage: FlexibleFee Sponsors (r:1 w:0)
+	// Proof Skipped: FlexibleFee Sponsors (max_values: None, max_size: None, mode: Measured)
+	// Storage: FlexibleFee SponsorWhitelist (r:0 w:1)
+	// Proof Skipped: FlexibleFee SponsorWhitelist (max_values: None, max_size: None, mode: Measured)
+	fn set_sponsor_whitelist(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 12_611 nanoseconds.
+		Weight::from_parts(12_611_000, 0)
+			// Standard Error: 3_000
+			.saturating_add(Weight::from_parts(2_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	// Storage: FlexibleFee Sponsors (r:1 w:1)
+	// Proof Skipped: FlexibleFee Sponsors (max_values: None, max_size: None, mode: Measured)
+	// Storage: System Account (r:1 w:1)
+	// Proof Skipped: System Account (max_values: None, max_size: None, mode: Measured)
+	fn fund_sponsor() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 31_745 nanoseconds.
+		Weight::from_parts(31_745_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: FlexibleFee Sponsors (r:1 w:1)
+	// Proof Skipped: FlexibleFee Sponsors (max_values: None, max_size: None, mode: Measured)
+	// Storage: System Account (r:1 w:1)
+	// Proof Skipped: System Account (max_values: None, max_size: None, mode: Measured)
+	fn withdraw_sponsor_budget() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 30_928 nanoseconds.
+		Weight::from_parts(30_928_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: FlexibleFee Sponsors (r:1 w:1)
+	// Proof Skipped: FlexibleFee Sponsors (max_values: None, max_size: None, mode: Measured)
+	// Storage: FlexibleFee SponsoredCalls (r:0 w:1)
+	// Proof Skipped: FlexibleFee SponsoredCalls (max_values: None, max_size: None, mode: Measured)
+	// Storage: FlexibleFee SponsorWhitelist (r:0 w:1)
+	// Proof Skipped: FlexibleFee SponsorWhitelist (max_values: None, max_size: None, mode: Measured)
+	// Storage: FlexibleFee SponsoredUsage (r:0 w:1)
+	// Proof Skipped: FlexibleFee SponsoredUsage (max_values: None, max_size: None, mode: Measured)
+	// Storage: System Account (r:1 w:1)
+	// Proof Skipped: System Account (max_values: None, max_size: None, mode: Measured)
+	fn unregister_sponsor(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 38_914 nanoseconds.
+		Weight::from_parts(38_914_000, 0)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_parts(1_604_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	// Storage: FlexibleFee ExtraFeeRules (r:0 w:1)
+	// Proof Skipped: FlexibleFee ExtraFeeRules (max_values: None, max_size: None, mode: Measured)
+	fn set_extra_fee_rule() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 10_934 nanoseconds.
+		Weight::from_parts(11_462_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
@@ -61,7 +61,9 @@ impl TryDrop for EvmPaymentInfo {
 }
 
 /// Implements the transaction payment for EVM transactions.
-/// Supports multi-currency fees based on what is provided by AC - account currency.
+/// Supports multi-currency fees based on what is provided by AC - account currency. The fee is
+/// paid in the first currency of the account's fee charge order list it can afford, and unused
+/// gas is refunded in that currency.
 pub struct TransferEvmFees<OU, AC, EC, C, MC>(PhantomData<(OU, AC, EC, C, MC)>);
 
 impl<T, OU, AC, EC, C, MC> OnChargeEVMTransaction<T> for TransferEvmFees<OU, AC, EC, C, MC>
@@ -94,24 +96,33 @@ where
 			return Ok(None);
 		}
 		let account_id = T::AddressMapping::into_account_id(*who);
-		let fee_currency = AC::get(&account_id);
-
-		let Some((converted, price)) =
-			C::convert((EC::get(), fee_currency, fee.unique_saturated_into()))
+		let fee: Balance = fee.unique_saturated_into();
+
+		// Charge the first currency in the fee charge order list of the account that can be
+		// priced and that the account can afford, like substrate transactions do. The account is
+		// kept alive, as `get_balance_in_currency` expects when the transaction is validated
+		let Some((fee_currency, converted, price)) =
+			AC::get_order_list(&account_id).into_iter().find_map(|fee_currency| {
+				let (converted, price) = C::convert((EC::get(), fee_currency, fee))?;
+				// Ensure that converted fee is not zero
+				let affordable = converted != 0 &&
+					MC::reducible_balance(
+						fee_currency,
+						&account_id,
+						Preservation::Preserve,
+						Fortitude::Polite,
+					) >= converted;
+				affordable.then_some((fee_currency, converted, price))
+			})
 		else {
-			return Err(Error::<T>::WithdrawFailed);
+			return Err(Error::<T>::BalanceLow);
 		};
 
-		// Ensure that converted fee is not zero
-		if converted == 0 {
-			return Err(Error::<T>::WithdrawFailed);
-		}
-
 		let burned = MC::burn_from(
 			fee_currency,
 			&account_id,
 			converted,
-			Preservation::Expendable,
+			Preservation::Preserve,
 			Precision::Exact,
 			Fortitude::Polite,
 		)
@@ -153,6 +153,7 @@ impl pallet_evm_accounts::Config for Runtime {
 	type FeeMultiplier = ConstU32<50>;
 	type EvmNonceProvider = EvmNonceProvider;
 	type ControllerOrigin = TechAdminOrCouncil;
+	type OnAddressBound = crate::FlexibleFee;
 	type WeightInfo = ();
 }
 
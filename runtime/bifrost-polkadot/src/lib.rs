@@ -75,10 +75,10 @@ pub use bifrost_primitives::{
 		CheckSubAccount, FarmingInfo, FeeGetter, VtokenMintingInterface, VtokenMintingOperator,
 		XcmDestWeightAndFeeHandler,
 	},
-	AccountId, Amount, AssetIds, Balance, BlockNumber, CurrencyId, CurrencyIdMapping,
-	DistributionId, ExtraFeeInfo, ExtraFeeName, Liquidity, Moment, Nonce, ParaId, PoolId, Price,
-	Rate, Ratio, RpcContributionStatus, Shortfall, TimeUnit, TokenSymbol, DOT_TOKEN_ID,
-	GLMR_TOKEN_ID,
+	AccountHealth, AccountId, Amount, AssetIds, Balance, BlockNumber, CurrencyId,
+	CurrencyIdMapping, DistributionId, LiquidationCandidate, Liquidity, Moment, Nonce, ParaId,
+	PoolId, Price, Rate, Ratio, RpcContributionStatus, Shortfall, TimeUnit, TokenSymbol,
+	DOT_TOKEN_ID, GLMR_TOKEN_ID,
 };
 use bifrost_runtime_common::{
 	constants::time::*, dollar, micro, milli, AuraId, CouncilCollective,
@@ -174,7 +174,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_version: 12001,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 0,
 };
 
@@ -252,6 +252,7 @@ parameter_types! {
 	pub const CloudsPalletId: PalletId = PalletId(*b"bf/cloud");
 	pub IncentivePoolAccount: PalletId = PalletId(*b"bf/inpoo");
 	pub const FarmingGaugeRewardIssuerPalletId: PalletId = PalletId(*b"bf/fmgar");
+	pub const FarmingVaultPalletId: PalletId = PalletId(*b"bf/fmvlt");
 	pub const BuyBackAccount: PalletId = PalletId(*b"bf/bybck");
 	pub const LiquidityAccount: PalletId = PalletId(*b"bf/liqdt");
 	pub const FlexibleFeePalletId: PalletId = PalletId(*b"bf/flexi");
@@ -911,37 +912,15 @@ impl bifrost_vesting::Config for Runtime {
 
 pub struct ExtraFeeMatcher;
 impl FeeGetter<RuntimeCall> for ExtraFeeMatcher {
-	fn get_fee_info(c: &RuntimeCall) -> ExtraFeeInfo {
+	fn get_fee_currency(c: &RuntimeCall) -> Option<CurrencyId> {
 		match *c {
-			RuntimeCall::Salp(bifrost_salp::Call::contribute { .. }) => ExtraFeeInfo {
-				extra_fee_name: ExtraFeeName::SalpContribute,
-				extra_fee_currency: RelayCurrencyId::get(),
-			},
-			RuntimeCall::XcmInterface(bifrost_xcm_interface::Call::transfer_statemine_assets {
-				..
-			}) => ExtraFeeInfo {
-				extra_fee_name: ExtraFeeName::StatemineTransfer,
-				extra_fee_currency: RelayCurrencyId::get(),
-			},
-			RuntimeCall::XcmInterface(bifrost_xcm_interface::Call::transfer_ethereum_assets {
-				..
-			}) => ExtraFeeInfo {
-				extra_fee_name: ExtraFeeName::EthereumTransfer,
-				extra_fee_currency: RelayCurrencyId::get(),
-			},
 			RuntimeCall::VtokenVoting(bifrost_vtoken_voting::Call::vote { vtoken, .. }) =>
-				ExtraFeeInfo {
-					extra_fee_name: ExtraFeeName::VoteVtoken,
-					extra_fee_currency: vtoken.to_token().unwrap_or(vtoken),
-				},
+				Some(vtoken.to_token().unwrap_or(vtoken)),
 			RuntimeCall::VtokenVoting(bifrost_vtoken_voting::Call::remove_delegator_vote {
 				vtoken,
 				..
-			}) => ExtraFeeInfo {
-				extra_fee_name: ExtraFeeName::VoteRemoveDelegatorVote,
-				extra_fee_currency: vtoken.to_token().unwrap_or(vtoken),
-			},
-			_ => ExtraFeeInfo::default(),
+			}) => Some(vtoken.to_token().unwrap_or(vtoken)),
+			_ => None,
 		}
 	}
 }
@@ -968,6 +947,8 @@ impl bifrost_flexible_fee::Config for Runtime {
 	type RelaychainCurrencyId = RelayCurrencyId;
 	type XcmRouter = XcmRouter;
 	type PalletId = FlexibleFeePalletId;
+	type PriceFeeder = Prices;
+	type MaxSponsorListLen = ConstU32<50>;
 }
 
 parameter_types! {
@@ -1190,6 +1171,7 @@ impl bifrost_vstoken_conversion::Config for Runtime {
 
 parameter_types! {
 	pub const WhitelistMaximumLimit: u32 = 10;
+	pub const MaxEmissionSchedulePoints: u32 = 50;
 }
 
 impl bifrost_farming::Config for Runtime {
@@ -1206,6 +1188,7 @@ impl bifrost_farming::Config for Runtime {
 	type BlockNumberToBalance = ConvertInto;
 	type WhitelistMaximumLimit = WhitelistMaximumLimit;
 	type GaugeRewardIssuer = FarmingGaugeRewardIssuerPalletId;
+	type MaxEmissionSchedulePoints = MaxEmissionSchedulePoints;
 }
 
 parameter_types! {
@@ -1296,6 +1279,12 @@ impl bifrost_stable_asset::Config for Runtime {
 	type WeightInfo = ();
 	type ListingOrigin = EitherOfDiverse<MoreThanHalfCouncil, EnsureRootOrAllTechnicalCommittee>;
 	type EnsurePoolAssetId = EnsurePoolAssetId;
+	type MaxPriceObservations = ConstU32<1200>;
+}
+
+parameter_types! {
+	pub StablePoolDeposit: Balance = 1_000 * BNCS;
+	pub const StablePoolReapDelay: BlockNumber = 30 * DAYS;
 }
 
 impl bifrost_stable_pool::Config for Runtime {
@@ -1307,6 +1296,45 @@ impl bifrost_stable_pool::Config for Runtime {
 	type VtokenMinting = VtokenMinting;
 	type CurrencyIdConversion = AssetIdMaps<Runtime>;
 	type CurrencyIdRegister = AssetIdMaps<Runtime>;
+	type DepositCurrencyId = NativeCurrencyId;
+	type PoolDeposit = StablePoolDeposit;
+	type PoolReapDelay = StablePoolReapDelay;
+}
+
+parameter_types! {
+	pub const SwapRouterMaxHops: u32 = 3;
+	pub const SwapRouterMaxRoutingAssets: u32 = 8;
+}
+
+impl bifrost_swap_router::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = weights::bifrost_swap_router::BifrostWeight<Runtime>;
+	type ControlOrigin = TechAdminOrCouncil;
+	type MultiCurrency = Currencies;
+	type StablePoolHandler = StablePool;
+	type DexOperator = ZenlinkProtocol;
+	type ParachainId = ParachainInfo;
+	type MaxHops = SwapRouterMaxHops;
+	type MaxRoutingAssets = SwapRouterMaxRoutingAssets;
+}
+
+parameter_types! {
+	pub const FarmingVaultMaxUnlockings: u32 = 10;
+	pub const FarmingVaultDeadShares: Balance = 1_000_000;
+}
+
+impl bifrost_farming_vault::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = weights::bifrost_farming_vault::BifrostWeight<Runtime>;
+	type ControlOrigin = TechAdminOrCouncil;
+	type MultiCurrency = Currencies;
+	type Farming = Farming;
+	type SwapRouter = SwapRouter;
+	type VtokenMinting = VtokenMinting;
+	type TreasuryAccount = BifrostTreasuryAccount;
+	type PalletId = FarmingVaultPalletId;
+	type MaxUnlockings = FarmingVaultMaxUnlockings;
+	type DeadShares = FarmingVaultDeadShares;
 }
 
 parameter_types! {
@@ -1512,6 +1540,10 @@ impl DataFeeder<CurrencyId, TimeStampedPrice, AccountId> for AggregatedDataProvi
 	}
 }
 
+parameter_types! {
+	pub const StablePoolTwapWindow: u32 = 300;
+}
+
 impl pallet_prices::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Source = AggregatedDataProvider;
@@ -1520,9 +1552,47 @@ impl pallet_prices::Config for Runtime {
 	type RelayCurrency = RelayCurrencyId;
 	type CurrencyIdConvert = AssetIdMaps<Runtime>;
 	type Assets = Currencies;
+	type VtokenMinting = VtokenMinting;
+	type StablePoolHandler = StablePool;
+	type AggregatedSources = (
+		pallet_prices::DataProviderSource<
+			bifrost_stable_pool::StablePoolTwapProvider<
+				Runtime,
+				AggregatedDataProvider,
+				StablePoolTwapWindow,
+			>,
+		>,
+		pallet_prices::DataProviderSource<pallet_prices::ZenlinkTwapProvider<Runtime>>,
+	);
+	type MarketPriceWindow = StablePoolTwapWindow;
+	type DexOperator = ZenlinkProtocol;
+	type ParachainId = ParachainInfo;
+	type UnixTime = Timestamp;
 	type WeightInfo = pallet_prices::weights::SubstrateWeight<Runtime>;
 }
 
+/// Calls that can be dispatched with flash loaned funds: swaps, minting vtokens and repaying
+/// lend market borrows. Calls that dispatch other calls are not allowed.
+pub struct FlashLoanCallFilter;
+impl Contains<RuntimeCall> for FlashLoanCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::System(frame_system::Call::remark { .. }) |
+				RuntimeCall::ZenlinkProtocol(_) |
+				RuntimeCall::StablePool(_) |
+				RuntimeCall::VtokenMinting(
+					bifrost_vtoken_minting::Call::mint { .. } |
+						bifrost_vtoken_minting::Call::redeem { .. }
+				) | RuntimeCall::LendMarket(
+				lend_market::Call::repay_borrow { .. } |
+					lend_market::Call::repay_borrow_all { .. } |
+					lend_market::Call::liquidate_borrow { .. }
+			)
+		)
+	}
+}
+
 impl lend_market::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type PalletId = LendMarketPalletId;
@@ -1534,6 +1604,10 @@ impl lend_market::Config for Runtime {
 	type Assets = Currencies;
 	type RewardAssetId = NativeCurrencyId;
 	type LiquidationFreeAssetId = RelayCurrencyId;
+	type SwapRouter = SwapRouter;
+	type MaxKeeperLiquidationsPerBlock = ConstU32<5>;
+	type RuntimeCall = RuntimeCall;
+	type FlashLoanCallFilter = FlashLoanCallFilter;
 }
 
 parameter_types! {
@@ -1796,6 +1870,8 @@ construct_runtime! {
 		ChannelCommission: bifrost_channel_commission = 136,
 		CloudsConvert: bifrost_clouds_convert = 137,
 		BuyBack: bifrost_buy_back = 138,
+		SwapRouter: bifrost_swap_router = 139,
+		FarmingVault: bifrost_farming_vault = 140,
 	}
 }
 
@@ -1851,7 +1927,7 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	bifrost_flexible_fee::ChargeTransactionPaymentWithSponsor<Runtime>,
 	frame_metadata_hash_extension::CheckMetadataHash<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
@@ -1896,7 +1972,12 @@ pub mod migrations {
 			Runtime,
 			governance::fellowship::FellowshipCollectiveInstance,
 		>,
+		bifrost_flexible_fee::migrations::v3::InitExtraFeeRules<
+			Runtime,
+			crate::migration::flexible_fee::ExtraFeeRules,
+		>,
 		crate::migration::genesis_evm_storage::GenesisEVMStorage,
+		bifrost_stable_asset::migration::InitPoolIndexes<Runtime>,
 	);
 }
 
@@ -1975,6 +2056,8 @@ mod benches {
 	define_benchmarks!(
 		[bifrost_ve_minting, VeMinting]
 		[bifrost_buy_back, BuyBack]
+		[bifrost_swap_router, SwapRouter]
+		[bifrost_farming_vault, FarmingVault]
 	);
 }
 
@@ -2379,6 +2462,10 @@ impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
 				_ => (CurrencyId::Native(TokenSymbol::BNC), Zero::zero()),
 			}
 		}
+
+		fn get_fee_breakdown(who: AccountId, fee: Balance, utx: <Block as BlockT>::Extrinsic) -> Option<bifrost_primitives::FeeBreakdown<Balance>> {
+			FlexibleFee::get_fee_breakdown(&who, fee, &utx.0.function).ok()
+		}
 	}
 
 	// zenlink runtime outer apis
@@ -2460,6 +2547,10 @@ impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
 		fn get_gauge_rewards(who: AccountId, pid: PoolId) -> Vec<(CurrencyId, Balance)> {
 			Farming::get_gauge_rewards(&who, pid).unwrap_or(Vec::new())
 		}
+
+		fn get_remaining_funded_blocks(pid: PoolId) -> Vec<(CurrencyId, Option<BlockNumber>)> {
+			Farming::get_remaining_funded_blocks(pid).unwrap_or(Vec::new())
+		}
 	}
 
 	impl bifrost_ve_minting_rpc_runtime_api::VeMintingRuntimeApi<Block, AccountId> for Runtime {
@@ -2496,6 +2587,18 @@ impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
 		fn get_liquidation_threshold_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError> {
 			LendMarket::get_account_liquidation_threshold_liquidity(&account)
 		}
+
+		fn get_market_bad_debt(asset_id: CurrencyId) -> Balance {
+			LendMarket::bad_debt(asset_id)
+		}
+
+		fn get_account_health(account: AccountId) -> Result<AccountHealth<Balance>, DispatchError> {
+			LendMarket::get_account_health(&account)
+		}
+
+		fn get_liquidation_candidates(health_factor: FixedU128, start_key: Option<Vec<u8>>, limit: u32) -> (Vec<LiquidationCandidate<AccountId, Balance>>, Option<Vec<u8>>) {
+			LendMarket::get_liquidation_candidates(health_factor, start_key, limit)
+		}
 	}
 
 	impl bifrost_stable_pool_rpc_runtime_api::StablePoolRuntimeApi<Block> for Runtime {
@@ -2514,6 +2617,75 @@ impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
 		) -> Balance {
 			StablePool::add_liquidity_amount(pool_id, amounts).unwrap_or(Zero::zero())
 		}
+
+		fn get_swap_quote(
+			pool_id: u32,
+			currency_id_in: u32,
+			currency_id_out: u32,
+			amount: Balance,
+		) -> (Balance, Balance) {
+			StablePool::get_swap_quote(pool_id, currency_id_in, currency_id_out, amount).unwrap_or_default()
+		}
+
+		fn add_liquidity_quote(
+			pool_id: u32,
+			amounts: Vec<Balance>,
+		) -> (Balance, Balance) {
+			StablePool::add_liquidity_quote(pool_id, amounts).unwrap_or_default()
+		}
+
+		fn get_twap(
+			pool_id: u32,
+			currency_id_in: u32,
+			currency_id_out: u32,
+			window: u32,
+		) -> Option<Price> {
+			StablePool::get_twap(pool_id, currency_id_in, currency_id_out, window)
+		}
+
+		fn get_underlying_swap_output(
+			pool_id: u32,
+			currency_id_in: CurrencyId,
+			currency_id_out: CurrencyId,
+			amount: Balance,
+		) -> Balance {
+			StablePool::get_underlying_swap_output(pool_id, currency_id_in, currency_id_out, amount).unwrap_or(Zero::zero())
+		}
+
+		fn get_pool_info(pool_id: u32) -> Option<bifrost_primitives::StablePoolInfo<AccountId, Balance, BlockNumber>> {
+			StablePool::get_pool_info(pool_id)
+		}
+
+		fn get_virtual_price(pool_id: u32) -> Option<Price> {
+			StablePool::get_virtual_price(pool_id)
+		}
+
+		fn get_lp_token_value(pool_id: u32, amount: Balance) -> Vec<(CurrencyId, Balance)> {
+			StablePool::get_lp_token_value(pool_id, amount).unwrap_or_default()
+		}
+
+		fn quote_redeem_proportion(pool_id: u32, amount: Balance) -> Option<bifrost_primitives::RedeemQuote<Balance>> {
+			StablePool::redeem_proportion_quote(pool_id, amount).ok()
+		}
+
+		fn quote_redeem_single(pool_id: u32, amount: Balance, i: u32) -> Option<bifrost_primitives::RedeemQuote<Balance>> {
+			StablePool::redeem_single_quote(pool_id, amount, i).ok()
+		}
+
+		fn quote_redeem_multi(pool_id: u32, amounts: Vec<Balance>) -> Option<bifrost_primitives::RedeemQuote<Balance>> {
+			StablePool::redeem_multi_quote(pool_id, amounts).ok()
+		}
+	}
+
+	impl bifrost_swap_router_rpc_runtime_api::SwapRouterRuntimeApi<Block> for Runtime {
+		fn get_best_quote(
+			asset_in: CurrencyId,
+			asset_out: CurrencyId,
+			amount: Balance,
+			swap_type: bifrost_primitives::SwapType,
+		) -> Option<bifrost_primitives::SwapQuote<Balance>> {
+			SwapRouter::get_best_quote(asset_in, asset_out, amount, swap_type).ok()
+		}
 	}
 
 	impl bifrost_vtoken_minting_rpc_runtime_api::VtokenMintingRuntimeApi<Block, CurrencyId> for Runtime {
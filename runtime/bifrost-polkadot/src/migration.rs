@@ -418,3 +418,76 @@ pub mod genesis_evm_storage {
 		}
 	}
 }
+
+pub mod flexible_fee {
+	use super::*;
+	use bifrost_flexible_fee::{ExtraFeeRule, TargetChain};
+	use bifrost_primitives::XcmOperationType;
+	use frame_support::traits::{GetCallIndex, GetCallName, PalletInfoAccess};
+
+	/// The pallet index and call index of the call `name` of the pallet `P`.
+	fn call_index<P: PalletInfoAccess, C: GetCallIndex + GetCallName>(
+		name: &str,
+	) -> Option<(u8, u8)> {
+		let position = C::get_call_names().iter().position(|call_name| *call_name == name)?;
+		Some((P::index() as u8, *C::get_call_indices().get(position)?))
+	}
+
+	/// The extra fees `ExtraFeeMatcher` charged before they were moved to `ExtraFeeRules`.
+	pub struct ExtraFeeRules;
+	impl Get<Vec<(u8, u8, ExtraFeeRule<CurrencyId>)>> for ExtraFeeRules {
+		fn get() -> Vec<(u8, u8, ExtraFeeRule<CurrencyId>)> {
+			let rule = |operation, currency_id, receiver| ExtraFeeRule {
+				operation,
+				currency_id,
+				receiver,
+			};
+
+			vec![
+				(
+					call_index::<Salp, bifrost_salp::Call<Runtime>>("contribute"),
+					rule(
+						XcmOperationType::UmpContributeTransact,
+						Some(RelayCurrencyId::get()),
+						TargetChain::RelayChain,
+					),
+				),
+				(
+					call_index::<XcmInterface, bifrost_xcm_interface::Call<Runtime>>(
+						"transfer_statemine_assets",
+					),
+					rule(
+						XcmOperationType::StatemineTransfer,
+						Some(RelayCurrencyId::get()),
+						TargetChain::AssetHub,
+					),
+				),
+				(
+					call_index::<XcmInterface, bifrost_xcm_interface::Call<Runtime>>(
+						"transfer_ethereum_assets",
+					),
+					rule(
+						XcmOperationType::EthereumTransfer,
+						Some(RelayCurrencyId::get()),
+						TargetChain::AssetHub,
+					),
+				),
+				(
+					call_index::<VtokenVoting, bifrost_vtoken_voting::Call<Runtime>>("vote"),
+					rule(XcmOperationType::Vote, None, TargetChain::RelayChain),
+				),
+				(
+					call_index::<VtokenVoting, bifrost_vtoken_voting::Call<Runtime>>(
+						"remove_delegator_vote",
+					),
+					rule(XcmOperationType::RemoveVote, None, TargetChain::RelayChain),
+				),
+			]
+			.into_iter()
+			.filter_map(|(index, rule)| {
+				index.map(|(pallet_index, call_index)| (pallet_index, call_index, rule))
+			})
+			.collect()
+		}
+	}
+}
@@ -356,4 +356,17 @@ impl<T: frame_system::Config> bifrost_farming::WeightInfo for BifrostWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4))
 			.saturating_add(T::DbWeight::get().writes(3))
 	}
+	// Storage: Farming PoolInfos (r:1 w:0)
+	// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	// Storage: Farming EmissionSchedules (r:0 w:1)
+	// Proof Skipped: Farming EmissionSchedules (max_values: None, max_size: None, mode: Measured)
+	fn set_emission_schedule() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `417`
+		//  Estimated: `3882`
+		// Minimum execution time: 38_211 nanoseconds.
+		Weight::from_parts(39_027_000, 3882)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
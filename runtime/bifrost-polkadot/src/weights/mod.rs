@@ -27,12 +27,14 @@ pub mod bifrost_clouds_convert;
 pub mod bifrost_cross_in_out;
 pub mod bifrost_currencies;
 pub mod bifrost_farming;
+pub mod bifrost_farming_vault;
 pub mod bifrost_fee_share;
 pub mod bifrost_flexible_fee;
 pub mod bifrost_salp;
 pub mod bifrost_slp;
 pub mod bifrost_slpx;
 pub mod bifrost_stable_pool;
+pub mod bifrost_swap_router;
 pub mod bifrost_system_maker;
 pub mod bifrost_system_staking;
 pub mod bifrost_ve_minting;
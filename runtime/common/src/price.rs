@@ -69,19 +69,31 @@ where
 	type Output = (Balance, Weight);
 
 	fn get_balance_in_currency(to_currency: CurrencyId, account: &T::AccountId) -> Self::Output {
-		let from_currency = AC::get(account);
-		let account_balance =
-			I::reducible_balance(from_currency, account, Preservation::Preserve, Fortitude::Polite);
-		let price_weight = T::DbWeight::get().reads(2); // 1 read to get currency and 1 read to get balance
-
-		if from_currency == to_currency {
-			return (account_balance, price_weight);
-		}
-
-		let Some((converted, _)) = C::convert((from_currency, to_currency, account_balance)) else {
-			return (0, price_weight);
-		};
-		(converted, price_weight)
+		let order_list = AC::get_order_list(account);
+		// 2 reads to get the currencies and 1 read to get each balance
+		let price_weight = T::DbWeight::get().reads(2 + order_list.len() as u64);
+
+		// The fee is paid in whichever currency of the order list can afford it, so the account
+		// can pay up to its largest balance among them
+		let balance = order_list
+			.into_iter()
+			.filter_map(|from_currency| {
+				let account_balance = I::reducible_balance(
+					from_currency,
+					account,
+					Preservation::Preserve,
+					Fortitude::Polite,
+				);
+				if from_currency == to_currency {
+					return Some(account_balance);
+				}
+				C::convert((from_currency, to_currency, account_balance))
+					.map(|(converted, _)| converted)
+			})
+			.max()
+			.unwrap_or_default();
+
+		(balance, price_weight)
 	}
 }
 